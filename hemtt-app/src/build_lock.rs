@@ -0,0 +1,124 @@
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use hemtt::{HEMTTError, Project};
+
+use crate::interrupt::Partial;
+
+/// Held by the running build, in the project root
+pub const BUILD_LOCK: &str = ".hemtt-build.lock";
+
+/// Time between checks while waiting for another build
+const WAIT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Keeps other builds of the project from running until it is dropped
+///
+/// The lock file contains the process ID of the build, so the lock of a build
+/// that was killed is removed by the next one. With `--dry-run` the project is not locked
+pub struct BuildLock {
+    _file: Option<Partial>,
+}
+
+impl BuildLock {
+    /// Locks the project, waiting for a running build to finish when `wait` is set
+    pub fn acquire(wait: bool) -> Result<Self, HEMTTError> {
+        let path = Project::find_root()?.join(BUILD_LOCK);
+        if crate::dry_run("lock", &path) {
+            return Ok(Self { _file: None });
+        }
+        let mut waiting = false;
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    // Removed when the lock is dropped, or when the build is stopped with Ctrl-C
+                    let partial = Partial::new(&path);
+                    write!(file, "{}", std::process::id())?;
+                    return Ok(Self {
+                        _file: Some(partial),
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    // The file is empty for a moment after another build creates it
+                    let owner = std::fs::read_to_string(&path)
+                        .ok()
+                        .and_then(|pid| pid.trim().parse::<u32>().ok());
+                    if let Some(pid) = owner {
+                        if !is_running(pid) {
+                            if remove_stale(&path, pid)? {
+                                warn!("Removed the lock of build {}, it is no longer running", pid);
+                            }
+                            continue;
+                        }
+                    }
+                    let owner = owner.map_or_else(String::new, |pid| format!(" ({})", pid));
+                    if !wait {
+                        return Err(HEMTTError::UserHint(
+                            format!("Another build{} is running in this project", owner),
+                            String::from("wait for it to finish, or use `--wait`"),
+                        ));
+                    }
+                    if !waiting {
+                        info!("Waiting for the build{} to finish", owner);
+                        waiting = true;
+                    }
+                    std::thread::sleep(WAIT_INTERVAL);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+/// Removes the lock of a build that is no longer running, returns false if the
+/// lock was not the stale one
+///
+/// The lock is moved aside before it is removed, so when builds find the same
+/// stale lock only one of them removes it. A lock taken by a new build after the
+/// stale lock was read is put back
+fn remove_stale(path: &Path, pid: u32) -> Result<bool, HEMTTError> {
+    let mut claimed = path.as_os_str().to_owned();
+    claimed.push(format!(".{}", std::process::id()));
+    let claimed = PathBuf::from(claimed);
+    match std::fs::rename(path, &claimed) {
+        Ok(()) => {}
+        // Removed by another build, or still open by one on Windows
+        Err(e) if matches!(e.kind(), ErrorKind::NotFound | ErrorKind::PermissionDenied) => {
+            return Ok(false)
+        }
+        Err(e) => return Err(e.into()),
+    }
+    let owner = std::fs::read_to_string(&claimed)
+        .ok()
+        .and_then(|owner| owner.trim().parse::<u32>().ok());
+    if owner != Some(pid) {
+        // Fails if yet another build has locked the project since
+        let _ = std::fs::hard_link(&claimed, path);
+        std::fs::remove_file(&claimed)?;
+        return Ok(false);
+    }
+    std::fs::remove_file(&claimed)?;
+    Ok(true)
+}
+
+/// Whether a process is running, a process that can not be checked is assumed to be
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(&["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_or(true, |status| status.success())
+}
+
+/// Whether a process is running, a process that can not be checked is assumed to be
+#[cfg(windows)]
+fn is_running(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(&["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .map_or(true, |output| {
+            String::from_utf8_lossy(&output.stdout).contains(&pid.to_string())
+        })
+}
@@ -0,0 +1,100 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::{HEMTTError, UserConfig};
+
+use crate::Command;
+
+pub struct Auth {}
+impl Command for Auth {
+    fn register(&self) -> App {
+        SubCommand::with_name("auth")
+            .version(*crate::VERSION)
+            .about("Manage the accounts used to publish")
+            .subcommand(
+                SubCommand::with_name("steam")
+                    .about("Steam account used by steamcmd")
+                    .subcommand(
+                        SubCommand::with_name("login")
+                            .about("Log in once, steamcmd caches the session for later runs")
+                            .arg(
+                                Arg::with_name("username")
+                                    .help("Steam account, defaults to the one logged in last"),
+                            ),
+                    )
+                    .subcommand(
+                        SubCommand::with_name("logout")
+                            .about("Log out and clear the cached session of the account"),
+                    ),
+            )
+    }
+
+    fn require_project(&self) -> bool {
+        false
+    }
+
+    fn can_announce(&self) -> bool {
+        false
+    }
+
+    fn run_no_project(&self, a: &ArgMatches) -> Result<(), HEMTTError> {
+        match a.subcommand() {
+            ("steam", Some(b)) => match b.subcommand() {
+                ("login", Some(c)) => login(c),
+                ("logout", Some(_)) => logout(),
+                _ => Err(HEMTTError::User(String::from(
+                    "No command was provided, use `auth steam help` to see all commands and options",
+                ))),
+            },
+            _ => Err(HEMTTError::User(String::from(
+                "No command was provided, use `auth help` to see all commands and options",
+            ))),
+        }
+    }
+}
+
+/// Logs steamcmd in, it prompts for the password and Steam Guard code and
+/// caches the session, the username is saved to the user config
+fn login(a: &ArgMatches) -> Result<(), HEMTTError> {
+    let mut config = UserConfig::read()?;
+    let username = a
+        .value_of("username")
+        .map(str::to_string)
+        .or_else(|| config.steam_username())
+        .ok_or_else(|| {
+            HEMTTError::UserHint(
+                String::from("No Steam account was given"),
+                String::from("run `hemtt auth steam login <username>`"),
+            )
+        })?;
+    info!("Logging in to Steam as `{}`", username);
+    if !crate::steam::steamcmd(&[crate::steam::login_as(username.clone())])? {
+        return Err(HEMTTError::UserHint(
+            format!("steamcmd was unable to log in as `{}`", username),
+            String::from("check the password and Steam Guard code"),
+        ));
+    }
+    if config.steam_username.as_deref() != Some(&username) {
+        config.steam_username = Some(username);
+        config.write()?;
+    }
+    info!("Logged in, the session is reused by `hemtt publish workshop`");
+    Ok(())
+}
+
+/// Logs steamcmd out, clearing its cached session, and forgets the account
+fn logout() -> Result<(), HEMTTError> {
+    let mut config = UserConfig::read()?;
+    match config.steam_username.take() {
+        Some(username) => {
+            if !crate::steam::logout(username.clone())? {
+                warn!(
+                    "steamcmd was unable to log out `{}`, the session may have already expired",
+                    username
+                );
+            }
+            config.write()?;
+            info!("Logged out `{}`", username);
+        }
+        None => info!("No Steam account is logged in"),
+    }
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use serde_json::json;
+
 use crate::{Command, Flow, HEMTTError, Project, Task};
 
 pub struct Build {}
@@ -6,88 +8,199 @@ impl Command for Build {
         clap::SubCommand::with_name("build")
             .version(*crate::VERSION)
             .about("Build the Project")
-        // .args(&super::building_args())
+            .arg(
+                clap::Arg::with_name("release")
+                    .long("release")
+                    .help("Build a release"),
+            )
+            .arg(
+                clap::Arg::with_name("kit")
+                    .long("kit")
+                    .help("Release only the named kits, each as its own mod")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .requires("release"),
+            )
+            .arg(
+                clap::Arg::with_name("channel")
+                    .long("channel")
+                    .help("Release channel, available to the release naming templates")
+                    .takes_value(true)
+                    .requires("release"),
+            )
+            .arg(
+                clap::Arg::with_name("jobs")
+                    .long("jobs")
+                    .help("Files copied into the release at the same time, defaults to 4")
+                    .takes_value(true)
+                    .requires("release"),
+            )
+            .arg(
+                clap::Arg::with_name("dump-vfs")
+                    .long("dump-vfs")
+                    .help(
+                        "Write each addon's files as they are packed into a folder, for debugging",
+                    )
+                    .takes_value(true)
+                    .value_name("dir"),
+            )
+            .arg(
+                clap::Arg::with_name("wait")
+                    .long("wait")
+                    .help("Wait for another build of the project to finish instead of failing"),
+            )
+            .args(&super::building_args())
     }
 
     fn run(&self, args: &clap::ArgMatches, p: Project) -> Result<(), HEMTTError> {
-        let addons = crate::get_addons_from_args(args)?;
+        let _lock = crate::build_lock::BuildLock::acquire(args.is_present("wait"))?;
+        if p.game.is_enfusion() {
+            return crate::enfusion::build(&p, args);
+        }
+        let release = args.is_present("release");
+        let mut opts = crate::tasks::ReleaseOptions::from_args(args, &p)?;
+        // Downstream tools only ever see complete releases
+        opts.staged = release;
+        let kits = &opts.kits;
+        let mut addons = if opts.main {
+            crate::get_addons_from_args(args)?
+        } else {
+            Vec::new()
+        };
+        // The addons of the kits are built even when they are not selected for the mod
+        for addon in hemtt::get_addon_from_locations(&hemtt::AddonLocation::first_class())? {
+            if kits.iter().any(|(_, kit)| kit.contains(&addon)) && !addons.contains(&addon) {
+                addons.push(addon);
+            }
+        }
+        if release {
+            crate::tasks::stage_release(&p, &opts)?;
+        }
         let flow = Flow {
             tasks: {
-                let mut tasks: Vec<Box<dyn Task>> = vec![
-                    Box::new(crate::tasks::Clear {}),
-                    Box::new(crate::tasks::NotEmpty {}),
-                    Box::new(crate::tasks::ValidName {}),
-                    Box::new(crate::tasks::ModTime {}),
-                    Box::new(crate::tasks::Populate {}),
-                    Box::new(crate::tasks::Prefix::new()),
-                    Box::new(crate::tasks::Preprocess {}),
-                    Box::new(crate::tasks::Rapify {}),
-                    Box::new(crate::tasks::Pack {}),
-                    // Step::single(
-                    //     "",
-                    //     vec![Box::new(crate::flow::Script {
-                    //         release: args.is_present("release"),
-                    //     })],
-                    // ),
-                    // Step::parallel(
-                    //     "Prebuild",
-                    //     vec![
-                    //         // Box::new(crate::build::prebuild::preprocess::Preprocess {}),
-                    //     ],
-                    // ),
-                    // Step::single(
-                    //     "",
-                    //     vec![Box::new(crate::flow::Script {
-                    //         release: args.is_present("release"),
-                    //     })],
-                    // ),
-                    // Step::parallel(
-                    //     "Build",
-                    //     Stage::Build,
-                    //     vec![Box::new(crate::build::build::Build::new(true))],
-                    // ),
-                    // Step::single(
-                    //     "",
-                    //     vec![Box::new(crate::flow::Script {
-                    //         release: args.is_present("release"),
-                    //     })],
-                    // ),
-                    // if args.is_present("release") {
-                    //     Step::single(
-                    //         "Release",
-                    //         vec![Box::new(crate::build::postbuild::release::Release {
-                    //             force_release: args.is_present("force-release"),
-                    //         })],
-                    //     )
-                    // } else {
-                    //     Step::none()
-                    // },
-                    // if args.is_present("release") {
-                    //     Step::single(
-                    //         "Sign",
-                    //         vec![Box::new(crate::build::postbuild::sign::Sign {})],
-                    //     )
-                    // } else {
-                    //     Step::none()
-                    // },
-                    // if args.is_present("release") {
-                    //     Step::single(
-                    //         "",
-                    //         vec![Box::new(crate::flow::Script {
-                    //             release: args.is_present("release"),
-                    //         })],
-                    //     )
-                    // } else {
-                    //     Step::none()
-                    // },
-                ];
+                let mut tasks = build_tasks(args);
+                if release {
+                    tasks.push(Box::new(crate::tasks::Release::new(opts.clone())));
+                    tasks.push(Box::new(crate::tasks::Extensions::new(opts.clone())));
+                    tasks.push(Box::new(crate::tasks::Sign::new(opts.clone())));
+                    tasks.push(Box::new(crate::tasks::Preset::new(opts.clone())));
+                    tasks.push(Box::new(crate::tasks::Links::new(opts.clone())));
+                    tasks.push(Box::new(crate::tasks::Workshop::new(opts.clone())));
+                    tasks.push(Box::new(crate::tasks::Archive::new(opts)));
+                }
+                tasks.extend(crate::plugin::Plugin::load()?);
                 if args.is_present("force") {
                     tasks.push(Box::new(crate::tasks::Clean {}));
                 }
                 tasks
             },
         };
-        flow.execute(addons, &p)?;
+        crate::notify::event(&p, "build.started", json!({ "release": release }));
+        let start = std::time::Instant::now();
+        let dashboard = crate::dashboard::start();
+        let mut result = flow.execute(addons, &p);
+        drop(dashboard);
+        if release {
+            result = result.and_then(|()| crate::tasks::promote_release());
+            if result.is_err() {
+                warn!("The incomplete release is kept in `releases/.staging` and resumed by the next release");
+            }
+        }
+        crate::notify::finished(&p, "build", start, &result, json!({ "release": release }));
+        if release {
+            crate::notify::notify(&p, "release", "Release", start, &result, None);
+        }
+        result?;
         Ok(())
     }
 }
+
+/// Tasks that check and build the addons into `.hemttout`, before the release
+pub fn build_tasks(args: &clap::ArgMatches) -> Vec<Box<dyn Task>> {
+    vec![
+        Box::new(crate::tasks::Clear {}),
+        Box::new(crate::tasks::NotEmpty {}),
+        Box::new(crate::tasks::ValidName {}),
+        Box::new(crate::tasks::Encoding { fix: false }),
+        Box::new(crate::tasks::LineEndings { fix: false }),
+        Box::new(crate::tasks::IncludeCase::new()),
+        Box::new(crate::tasks::Separators { fix: false }),
+        Box::new(crate::tasks::Languages {}),
+        Box::new(crate::tasks::Textures {}),
+        Box::new(crate::tasks::Models::new()),
+        Box::new(crate::tasks::Populate {}),
+        Box::new(crate::tasks::Cache::new()),
+        Box::new(crate::tasks::Prefix::new()),
+        Box::new(crate::tasks::Preprocess {}),
+        Box::new(crate::tasks::Rapify {}),
+        Box::new(crate::tasks::Duplicates {}),
+        Box::new(crate::tasks::PatchesMetadata {}),
+        Box::new(crate::tasks::Campaigns {}),
+        Box::new(crate::tasks::Strings {}),
+        Box::new(crate::tasks::Binarize {}),
+        Box::new(crate::tasks::DumpVfs::new(
+            args.value_of("dump-vfs").map(std::path::PathBuf::from),
+        )),
+        Box::new(crate::tasks::Pack {}),
+        Box::new(crate::tasks::Size {}),
+        Box::new(crate::tasks::PackMissions {}),
+        // Step::single(
+        //     "",
+        //     vec![Box::new(crate::flow::Script {
+        //         release: args.is_present("release"),
+        //     })],
+        // ),
+        // Step::parallel(
+        //     "Prebuild",
+        //     vec![
+        //         // Box::new(crate::build::prebuild::preprocess::Preprocess {}),
+        //     ],
+        // ),
+        // Step::single(
+        //     "",
+        //     vec![Box::new(crate::flow::Script {
+        //         release: args.is_present("release"),
+        //     })],
+        // ),
+        // Step::parallel(
+        //     "Build",
+        //     Stage::Build,
+        //     vec![Box::new(crate::build::build::Build::new(true))],
+        // ),
+        // Step::single(
+        //     "",
+        //     vec![Box::new(crate::flow::Script {
+        //         release: args.is_present("release"),
+        //     })],
+        // ),
+        // if args.is_present("release") {
+        //     Step::single(
+        //         "Release",
+        //         vec![Box::new(crate::build::postbuild::release::Release {
+        //             force_release: args.is_present("force-release"),
+        //         })],
+        //     )
+        // } else {
+        //     Step::none()
+        // },
+        // if args.is_present("release") {
+        //     Step::single(
+        //         "Sign",
+        //         vec![Box::new(crate::build::postbuild::sign::Sign {})],
+        //     )
+        // } else {
+        //     Step::none()
+        // },
+        // if args.is_present("release") {
+        //     Step::single(
+        //         "",
+        //         vec![Box::new(crate::flow::Script {
+        //             release: args.is_present("release"),
+        //         })],
+        //     )
+        // } else {
+        //     Step::none()
+        // },
+    ]
+}
@@ -0,0 +1,39 @@
+use crate::{Command, Flow, HEMTTError, Project};
+
+pub struct Check {}
+impl Command for Check {
+    fn register(&self) -> clap::App {
+        clap::SubCommand::with_name("check")
+            .version(*crate::VERSION)
+            .about("Check the addons' files without building")
+            .arg(
+                clap::Arg::with_name("fix")
+                    .long("fix")
+                    .help("Fix the problems that can be fixed automatically"),
+            )
+            .args(&super::building_args())
+    }
+
+    fn run(&self, args: &clap::ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        if p.game.is_enfusion() {
+            return crate::enfusion::check(&p);
+        }
+        let fix = args.is_present("fix");
+        let addons = crate::get_addons_from_args(args)?;
+        let flow = Flow {
+            tasks: vec![
+                Box::new(crate::tasks::NotEmpty {}),
+                Box::new(crate::tasks::ValidName {}),
+                Box::new(crate::tasks::Encoding { fix }),
+                Box::new(crate::tasks::LineEndings { fix }),
+                Box::new(crate::tasks::IncludeCase::new()),
+                Box::new(crate::tasks::Separators { fix }),
+                Box::new(crate::tasks::Languages {}),
+                Box::new(crate::tasks::Textures {}),
+                Box::new(crate::tasks::Models::new()),
+            ],
+        };
+        flow.execute(addons, &p)?;
+        Ok(())
+    }
+}
@@ -6,9 +6,21 @@ impl Command for Clean {
         clap::SubCommand::with_name("clean")
             .version(*crate::VERSION)
             .about("Clean built files")
+            .arg(
+                clap::Arg::with_name("cache")
+                    .long("cache")
+                    .help("Also remove all cached artifacts"),
+            )
     }
 
-    fn run(&self, _: &clap::ArgMatches, p: Project) -> Result<(), HEMTTError> {
+    fn run(&self, args: &clap::ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        if args.is_present("cache") {
+            let cache = Project::find_root()?.join(crate::tasks::CACHE_DIR);
+            if cache.exists() && !crate::dry_run("delete", &cache) {
+                info!("Removing cached artifacts");
+                std::fs::remove_dir_all(&cache)?;
+            }
+        }
         let addons = hemtt::get_all_addons()?;
         let flow = Flow {
             tasks: vec![
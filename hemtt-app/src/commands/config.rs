@@ -0,0 +1,46 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::{HEMTTError, Project};
+
+use crate::Command;
+
+pub struct Config;
+impl Command for Config {
+    fn register(&self) -> App {
+        SubCommand::with_name("config")
+            .version(*crate::VERSION)
+            .about("Read the resolved project configuration")
+            .subcommand(
+                SubCommand::with_name("get")
+                    .about("Print a single value, nested keys are separated by `.`")
+                    .arg(
+                        Arg::with_name("key")
+                            .help("Key of the value, ex: version, kits.server.addons")
+                            .required(true),
+                    ),
+            )
+    }
+
+    fn can_announce(&self) -> bool {
+        false
+    }
+
+    fn run(&self, a: &ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        match a.subcommand() {
+            ("get", Some(b)) => {
+                let key = b.value_of("key").unwrap();
+                let value = p.get(key).ok_or_else(|| {
+                    HEMTTError::User(format!("The project has no value for `{}`", key))
+                })?;
+                match value {
+                    // Strings are printed without quotes so scripts can use them directly
+                    serde_json::Value::String(s) => println!("{}", s),
+                    value => println!("{}", value),
+                }
+                Ok(())
+            }
+            _ => Err(HEMTTError::User(String::from(
+                "No command was provided, use `config help` to see all commands and options",
+            ))),
+        }
+    }
+}
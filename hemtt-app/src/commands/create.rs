@@ -0,0 +1,163 @@
+use std::convert::TryFrom;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use hemtt::templates::Templates;
+use strum::IntoEnumIterator;
+
+use crate::{Command, HEMTTError};
+
+pub struct Create {}
+impl Command for Create {
+    fn register(&self) -> clap::App {
+        clap::SubCommand::with_name("create")
+            .version(*crate::VERSION)
+            .about("Create a new project from a template")
+            .arg(
+                clap::Arg::with_name("name")
+                    .help("Name of the project, also the name of its folder")
+                    .required(true),
+            )
+            .arg(
+                clap::Arg::with_name("template")
+                    .long("template")
+                    .short("t")
+                    .help(
+                        "Template to create the project from, chosen interactively when not given",
+                    )
+                    .takes_value(true)
+                    .validator(Templates::validate),
+            )
+            .arg(
+                clap::Arg::with_name("prefix")
+                    .long("prefix")
+                    .help("Prefix of the project, defaults to the name in lowercase")
+                    .takes_value(true),
+            )
+            .arg(
+                clap::Arg::with_name("author")
+                    .long("author")
+                    .help("Author of the project, defaults to the git user")
+                    .takes_value(true),
+            )
+    }
+
+    fn require_project(&self) -> bool {
+        false
+    }
+
+    fn run_no_project(&self, a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+        let name = a.value_of("name").unwrap();
+        let folder = PathBuf::from(name);
+        if folder.exists() && folder.read_dir()?.next().is_some() {
+            return Err(HEMTTError::UserHint(
+                format!("{:?} already exists and is not empty", folder),
+                String::from("choose another name, or use `hemtt template init` inside it"),
+            ));
+        }
+        let template = match a.value_of("template") {
+            Some(template) => Templates::try_from(template.to_string())
+                .map_err(|_| HEMTTError::TemplateUnknown(template.to_string()))?,
+            None if *crate::CI => {
+                return Err(HEMTTError::UserHint(
+                    String::from("No template was chosen"),
+                    format!("use `--template`, {}", Templates::options()),
+                ))
+            }
+            None => choose()?,
+        };
+        let prefix = a
+            .value_of("prefix")
+            .map_or_else(|| default_prefix(name), str::to_string);
+        let author = a
+            .value_of("author")
+            .map(str::to_string)
+            .or_else(git_user)
+            .unwrap_or_else(|| name.to_string());
+        if crate::dry_run("create", &folder) {
+            return Ok(());
+        }
+        hemtt::templates::init(template, folder.clone())?;
+        configure(
+            &folder.join("hemtt.toml"),
+            &[("name", name), ("prefix", &prefix), ("author", &author)],
+        )?;
+        info!(
+            "Created `{}` from the `{}` template in {:?}",
+            name, template, folder
+        );
+        Ok(())
+    }
+}
+
+/// Asks which template to use, the first one when nothing is entered
+fn choose() -> Result<Templates, HEMTTError> {
+    let templates: Vec<Templates> = Templates::iter().collect();
+    println!("Templates:");
+    for (i, template) in templates.iter().enumerate() {
+        // Padded as a string, the template's `Display` does not pad
+        let name = template.to_string();
+        println!("  {}. {:<9} {}", i + 1, name, template.description());
+    }
+    print!("Template [1]: ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer)? == 0 {
+        return Err(HEMTTError::UserHint(
+            String::from("No template was chosen"),
+            format!("use `--template`, {}", Templates::options()),
+        ));
+    }
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Ok(templates[0]);
+    }
+    answer
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| templates.get(number.checked_sub(1)?).copied())
+        .or_else(|| Templates::try_from(answer.to_lowercase()).ok())
+        .ok_or_else(|| HEMTTError::TemplateUnknown(answer.to_string()))
+}
+
+/// Ex: "My Mod" => "my_mod"
+fn default_prefix(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn git_user() -> Option<String> {
+    git2::Config::open_default()
+        .ok()?
+        .get_string("user.name")
+        .ok()
+}
+
+/// Replaces the values of top level keys in the template's `hemtt.toml`
+fn configure(path: &Path, values: &[(&str, &str)]) -> Result<(), HEMTTError> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let text = std::fs::read_to_string(path)?;
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    for line in &mut lines {
+        let key = line
+            .split('=')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        if let Some((_, value)) = values.iter().find(|(name, _)| *name == key) {
+            *line = format!("{} = {:?}", key, value);
+        }
+    }
+    create_file!(path)?.write_all(format!("{}\n", lines.join("\n")).as_bytes())?;
+    Ok(())
+}
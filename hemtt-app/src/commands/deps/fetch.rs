@@ -0,0 +1,264 @@
+use std::io::{Cursor, Read};
+use std::path::{Component, Path, PathBuf};
+
+use hemtt::{Dependency, Game, HEMTTError, Lock, Project};
+use hemtt_pbo::ReadablePbo;
+
+/// Files extracted from dependency PBOs to be used as includes
+const INCLUDE_EXTS: [&str; 3] = ["hpp", "h", "inc"];
+
+pub fn run(p: &Project, _: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    if p.dependencies.is_empty() {
+        info!("No dependencies are declared");
+        return Ok(());
+    }
+    let mut lock = Lock::read()?;
+    let mut changed = false;
+    create_dir!(hemtt::DEPS_DIR)?;
+    for (name, dep) in &p.dependencies {
+        let locked = lock
+            .dependencies
+            .get(name)
+            .filter(|locked| locked.matches(dep))
+            .cloned();
+        if let Some(locked) = &locked {
+            // The locked version is kept, even when a newer one has been published
+            let folder = Dependency::folder(name);
+            if folder.is_dir() && super::lock::hash_folder(&folder)? == locked.hash {
+                info!("`{}` is already fetched", name);
+                extract_includes(&folder)?;
+                continue;
+            }
+            // Only the latest version of a workshop item can be downloaded
+            if let Some(id) = dep.workshop {
+                if locked.time_updated != Some(super::lock::workshop_time_updated(id)?) {
+                    return Err(outdated(name));
+                }
+            }
+        }
+        info!("Fetching `{}`", name);
+        let download = fetch(name, dep, p.game)?;
+        if let Some(locked) = &locked {
+            if locked.hash != super::lock::hash_folder(&download)? {
+                std::fs::remove_dir_all(&download)?;
+                return Err(outdated(name));
+            }
+        }
+        let folder = install(name, &download)?;
+        if locked.is_none() {
+            debug!("Adding `{}` to {}", name, hemtt::LOCK_FILE);
+            lock.dependencies
+                .insert(name.to_string(), super::lock::lock(name, dep)?);
+            changed = true;
+        }
+        extract_includes(&folder)?;
+    }
+    if changed {
+        lock.dependencies
+            .retain(|name, _| p.dependencies.contains_key(name));
+        lock.write()?;
+    }
+    Ok(())
+}
+
+fn outdated(name: &str) -> HEMTTError {
+    HEMTTError::UserHint(
+        format!(
+            "The version of dependency `{}` in {} can no longer be downloaded",
+            name,
+            hemtt::LOCK_FILE
+        ),
+        format!("`hemtt deps update {}` to lock the new version", name),
+    )
+}
+
+/// Download a dependency into a temporary folder next to its folder, workshop items
+/// are downloaded for the project's game
+///
+/// The dependency's folder is left as it is, `install` replaces it with the download
+pub fn fetch(name: &str, dep: &Dependency, game: Game) -> Result<PathBuf, HEMTTError> {
+    let mut target = Dependency::folder(name).into_os_string();
+    target.push(".download");
+    let target = PathBuf::from(target);
+    if target.exists() {
+        std::fs::remove_dir_all(&target)?;
+    }
+    if let Err(e) = download_into(name, dep, game, &target) {
+        if target.exists() {
+            std::fs::remove_dir_all(&target)?;
+        }
+        return Err(e);
+    }
+    Ok(target)
+}
+
+/// Replaces the dependency's folder with a download
+pub fn install(name: &str, download: &Path) -> Result<PathBuf, HEMTTError> {
+    let target = Dependency::folder(name);
+    if target.exists() {
+        std::fs::remove_dir_all(&target)?;
+    }
+    rename_file!(download, &target)?;
+    Ok(target)
+}
+
+fn download_into(
+    name: &str,
+    dep: &Dependency,
+    game: Game,
+    target: &Path,
+) -> Result<(), HEMTTError> {
+    match (dep.workshop, &dep.url) {
+        (Some(id), _) => workshop(
+            id,
+            game.appid().ok_or_else(|| {
+                HEMTTError::User(format!(
+                    "Dependency `{}` is a workshop item, {} projects can only use urls",
+                    name, game
+                ))
+            })?,
+            target,
+        )?,
+        (None, Some(url)) => download(url, target)?,
+        (None, None) => {
+            return Err(HEMTTError::User(format!(
+                "Dependency `{}` requires a `workshop` id or a `url`",
+                name
+            )))
+        }
+    }
+    Ok(())
+}
+
+fn workshop(id: u64, appid: u32, target: &Path) -> Result<(), HEMTTError> {
+    crate::http::online("download workshop items")?;
+    let mut cache = std::env::current_dir()?;
+    cache.push(hemtt::DEPS_DIR);
+    cache.push(".steamcmd");
+    create_dir!(cache)?;
+    debug!("Downloading workshop item {} with steamcmd", id);
+    let commands = vec![
+        vec![
+            String::from("force_install_dir"),
+            cache.display().to_string(),
+        ],
+        crate::steam::login(false)?,
+        vec![
+            String::from("workshop_download_item"),
+            appid.to_string(),
+            id.to_string(),
+        ],
+    ];
+    if !crate::steam::steamcmd(&commands)? {
+        return Err(HEMTTError::Network(format!(
+            "steamcmd was unable to download workshop item {}",
+            id
+        )));
+    }
+    let mut content = cache;
+    content.push("steamapps/workshop/content");
+    content.push(appid.to_string());
+    content.push(id.to_string());
+    copy_dir(&content, target)
+}
+
+fn download(url: &str, target: &Path) -> Result<(), HEMTTError> {
+    debug!("Downloading {}", url);
+    let response = crate::http::agent()?
+        .get(url)
+        .call()
+        .map_err(|e| HEMTTError::Network(format!("Unable to download `{}`: {}", url, e)))?;
+    let mut data = Vec::new();
+    response.into_reader().read_to_end(&mut data)?;
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| HEMTTError::Generic(format!("`{}` is not a valid zip: {}", url, e)))?;
+    archive
+        .extract(target)
+        .map_err(|e| HEMTTError::Generic(format!("Unable to extract `{}`: {}", url, e)))
+}
+
+fn copy_dir(source: &Path, target: &Path) -> Result<(), HEMTTError> {
+    for entry in walkdir::WalkDir::new(source) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        let dest = target.join(entry.path().strip_prefix(source).unwrap());
+        if entry.file_type().is_dir() {
+            create_dir!(dest)?;
+        } else {
+            std::fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Extract the headers from all PBOs in the folder into `deps/include` using their prefix
+pub fn extract_includes(folder: &Path) -> Result<(), HEMTTError> {
+    for entry in walkdir::WalkDir::new(folder) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        if entry.path().extension().and_then(|e| e.to_str()) != Some("pbo") {
+            continue;
+        }
+        let mut pbo = ReadablePbo::from(open_file!(entry.path())?)?;
+        let prefix = match pbo.extension("prefix") {
+            Some(prefix) => prefix.replace("\\", "/"),
+            None => {
+                debug!("{:?} has no prefix, skipping includes", entry.path());
+                continue;
+            }
+        };
+        for header in pbo.files() {
+            let filename = header.filename().replace("\\", "/");
+            let ext = filename.rsplit('.').next().unwrap_or("").to_lowercase();
+            if !INCLUDE_EXTS.contains(&ext.as_str()) {
+                continue;
+            }
+            let target = match include_path(&prefix, &filename) {
+                Some(target) => target,
+                None => {
+                    warn!(
+                        "{:?} contains `{}/{}` which is outside of {}, skipping",
+                        entry.path(),
+                        prefix,
+                        filename,
+                        hemtt::deps_include().display()
+                    );
+                    continue;
+                }
+            };
+            trace!("extracting include {:?}", target);
+            create_dir!(target.parent().unwrap())?;
+            let mut data = pbo
+                .retrieve_decompressed(header.filename())?
+                .ok_or_else(|| {
+                    HEMTTError::Generic(format!(
+                        "Unable to read `{}` from {:?}",
+                        header.filename(),
+                        entry.path()
+                    ))
+                })?;
+            std::io::copy(&mut data, &mut create_file!(&target)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// The location of an include in `deps/include`, `None` if it would be outside of it
+///
+/// Leading separators are ignored, so prefixes like `\x\cba\addons\main` stay relative
+fn include_path(prefix: &str, filename: &str) -> Option<PathBuf> {
+    let root = hemtt::deps_include();
+    let mut target = root.clone();
+    for component in Path::new(prefix)
+        .components()
+        .chain(Path::new(filename).components())
+    {
+        match component {
+            Component::Normal(part) => target.push(part),
+            Component::RootDir | Component::CurDir => {}
+            Component::ParentDir | Component::Prefix(_) => return None,
+        }
+    }
+    if target == root || !target.starts_with(&root) {
+        return None;
+    }
+    Some(target)
+}
@@ -0,0 +1,57 @@
+use std::io::Read;
+use std::path::Path;
+
+use hemtt::{Dependency, HEMTTError, LockedDependency};
+use sha1::{Digest, Sha1};
+
+const WORKSHOP_DETAILS: &str =
+    "https://api.steampowered.com/ISteamRemoteStorage/GetPublishedFileDetails/v1/";
+
+/// SHA-1 of all files in a folder, including their relative paths
+pub fn hash_folder(folder: &Path) -> Result<String, HEMTTError> {
+    let mut hasher = Sha1::new();
+    for entry in walkdir::WalkDir::new(folder).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(folder)
+            .unwrap()
+            .to_string_lossy()
+            .replace("\\", "/");
+        hasher.update(relative.as_bytes());
+        let mut data = Vec::new();
+        open_file!(entry.path())?.read_to_end(&mut data)?;
+        hasher.update(&data);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// The last time a Workshop item was updated, as reported by Steam
+pub fn workshop_time_updated(id: u64) -> Result<u64, HEMTTError> {
+    let response = crate::http::agent()?
+        .post(WORKSHOP_DETAILS)
+        .send_form(&[("itemcount", "1"), ("publishedfileids[0]", &id.to_string())])
+        .map_err(|e| HEMTTError::Network(format!("Unable to query workshop item {}: {}", id, e)))?;
+    let json: serde_json::Value = serde_json::from_str(&response.into_string()?)
+        .map_err(|e| HEMTTError::Generic(e.to_string()))?;
+    json["response"]["publishedfiledetails"][0]["time_updated"]
+        .as_u64()
+        .ok_or_else(|| HEMTTError::Generic(format!("Workshop item {} was not found", id)))
+}
+
+/// Create a lock entry for a fetched dependency
+pub fn lock(name: &str, dep: &Dependency) -> Result<LockedDependency, HEMTTError> {
+    Ok(LockedDependency {
+        workshop: dep.workshop,
+        url: dep.url.clone(),
+        time_updated: if let Some(id) = dep.workshop {
+            Some(workshop_time_updated(id)?)
+        } else {
+            None
+        },
+        hash: hash_folder(&Dependency::folder(name))?,
+    })
+}
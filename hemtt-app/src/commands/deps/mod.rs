@@ -0,0 +1,40 @@
+use crate::Command;
+use hemtt::HEMTTError;
+
+mod fetch;
+mod lock;
+mod update;
+
+pub struct Deps;
+impl Command for Deps {
+    fn register(&self) -> clap::App {
+        clap::SubCommand::with_name("deps")
+            .version(*crate::VERSION)
+            .about("Manage the project dependencies")
+            .subcommand(
+                clap::SubCommand::with_name("fetch")
+                    .about("Download the dependencies into the deps folder"),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("update")
+                    .about(
+                        "Download the latest version of the dependencies and update the lockfile",
+                    )
+                    .arg(
+                        clap::Arg::with_name("dependency")
+                            .help("Only update the given dependencies")
+                            .multiple(true),
+                    ),
+            )
+    }
+
+    fn run(&self, a: &clap::ArgMatches, p: hemtt::Project) -> Result<(), HEMTTError> {
+        match a.subcommand() {
+            ("fetch", Some(b)) => fetch::run(&p, b),
+            ("update", Some(b)) => update::run(&p, b),
+            _ => Err(HEMTTError::User(String::from(
+                "No command was provided, use `deps help` to see all commands and options",
+            ))),
+        }
+    }
+}
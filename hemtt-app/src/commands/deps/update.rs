@@ -0,0 +1,24 @@
+use hemtt::{HEMTTError, Lock, Project};
+
+pub fn run(p: &Project, a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let mut lock = Lock::read()?;
+    create_dir!(hemtt::DEPS_DIR)?;
+    for (name, dep) in &p.dependencies {
+        if let Some(mut only) = a.values_of("dependency") {
+            if !only.any(|x| x == name.as_str()) {
+                continue;
+            }
+        }
+        info!("Updating `{}`", name);
+        let download = super::fetch::fetch(name, dep, p.game)?;
+        let folder = super::fetch::install(name, &download)?;
+        super::fetch::extract_includes(&folder)?;
+        lock.dependencies
+            .insert(name.to_string(), super::lock::lock(name, dep)?);
+    }
+    lock.dependencies
+        .retain(|name, _| p.dependencies.contains_key(name));
+    lock.write()?;
+    info!("Updated {}", hemtt::LOCK_FILE);
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use hemtt::Addon;
+use serde_json::json;
+
+use crate::{Command, Flow, HEMTTError, Project};
+
+/// Time between checks for changed files
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time for editors to finish writing files before the addon is rebuilt
+const SETTLE_TIME: Duration = Duration::from_millis(300);
+
+/// Config of the project, every addon is rebuilt when it changes
+const CONFIG_FILES: [&str; 4] = [
+    "hemtt.toml",
+    "hemtt.local.toml",
+    "hemtt.local.json",
+    ".hemtt",
+];
+
+pub struct Dev {}
+impl Command for Dev {
+    fn register(&self) -> clap::App {
+        clap::SubCommand::with_name("dev")
+            .version(*crate::VERSION)
+            .about("Build the addons and rebuild each addon when its files change")
+            .long_about(
+                "Build the addons into `.hemttout` and rebuild each addon when its files change, \
+                every addon is rebuilt when the project's config or an include folder changes\n\n\
+                Load `.hemttout` as a mod with `-filePatching` to test the changes in the game, \
+                the `dev.rebuilt` hook event is triggered after every rebuild",
+            )
+            .args(&super::building_args())
+    }
+
+    fn run(&self, args: &clap::ArgMatches, mut p: Project) -> Result<(), HEMTTError> {
+        if p.game.is_enfusion() {
+            return Err(HEMTTError::User(String::from(
+                "`hemtt dev` is not available for Enfusion projects",
+            )));
+        }
+        let addons = crate::get_addons_from_args(args)?;
+        if let Err(e) = build(args, &p, addons.clone(), true) {
+            error!("{}", e);
+        }
+        info!(
+            "Load {:?} with `-mod` and `-filePatching` to test the addons",
+            Project::output_dir()?
+        );
+
+        let mut states: Vec<(Addon, State)> = addons
+            .into_iter()
+            .map(|addon| {
+                let state = State::read(Path::new(addon.source()));
+                (addon, state)
+            })
+            .collect();
+        let mut config = State::config();
+        let mut includes = State::includes(&p);
+        info!(
+            "Watching {} addons for changes, Ctrl-C to stop",
+            states.len()
+        );
+        while !crate::interrupt::interrupted() {
+            std::thread::sleep(POLL_INTERVAL);
+            if config == State::config()
+                && includes == State::includes(&p)
+                && !states
+                    .iter()
+                    .any(|(addon, state)| *state != State::read(Path::new(addon.source())))
+            {
+                continue;
+            }
+            std::thread::sleep(SETTLE_TIME);
+            // Every addon can use the project's config and the included files
+            let mut all = false;
+            let current = State::config();
+            if config != current {
+                config = current;
+                all = true;
+                match Project::read() {
+                    Ok(project) => p = project,
+                    Err(e) => {
+                        error!("{}", e);
+                        continue;
+                    }
+                }
+            }
+            let current = State::includes(&p);
+            if includes != current {
+                includes = current;
+                all = true;
+            }
+            let mut changed = Vec::new();
+            for (addon, state) in &mut states {
+                let current = State::read(Path::new(addon.source()));
+                if all || *state != current {
+                    *state = current;
+                    changed.push(addon.clone());
+                }
+            }
+            let names = changed
+                .iter()
+                .map(|addon| addon.name().to_string())
+                .collect::<Vec<_>>();
+            info!("Rebuilding {}", names.join(", "));
+            let start = Instant::now();
+            match build(args, &p, changed, false) {
+                Ok(()) => {
+                    info!(
+                        "Rebuilt in {} ms, restart the mission to load script changes \
+                        or the game to load config changes",
+                        start.elapsed().as_millis()
+                    );
+                    crate::notify::event(&p, "dev.rebuilt", json!({ "addons": names }));
+                }
+                Err(e) => error!("{}", e),
+            }
+        }
+        info!("Stopped watching");
+        Ok(())
+    }
+}
+
+/// Builds the addons into `.hemttout`, the other addons are only removed from it
+/// by the first build
+fn build(
+    args: &clap::ArgMatches,
+    p: &Project,
+    addons: Vec<Addon>,
+    first: bool,
+) -> Result<(), HEMTTError> {
+    let _lock = crate::build_lock::BuildLock::acquire(true)?;
+    let mut tasks = super::build::build_tasks(args);
+    if !first {
+        tasks.retain(|task| task.name() != "clear");
+    }
+    tasks.extend(crate::plugin::Plugin::load()?);
+    Flow { tasks }.execute(addons, p)
+}
+
+/// Number of files in an addon's folder and when the last one was changed
+#[derive(PartialEq)]
+struct State {
+    files: usize,
+    modified: Option<SystemTime>,
+}
+
+impl State {
+    fn read(folder: &Path) -> Self {
+        Self::read_all(std::iter::once(folder.to_path_buf()))
+    }
+
+    /// State of the project's config files
+    fn config() -> Self {
+        Self::read_all(CONFIG_FILES.iter().map(PathBuf::from))
+    }
+
+    /// State of the folders searched for absolute includes
+    fn includes(p: &Project) -> Self {
+        Self::read_all(crate::tasks::includes(p).iter().map(PathBuf::from))
+    }
+
+    /// State of the files in all of the folders, a file is its own folder
+    fn read_all<I: IntoIterator<Item = PathBuf>>(folders: I) -> Self {
+        let mut state = Self {
+            files: 0,
+            modified: None,
+        };
+        for entry in folders
+            .into_iter()
+            .flat_map(walkdir::WalkDir::new)
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+        {
+            state.files += 1;
+            let modified = entry.metadata().ok().and_then(|meta| meta.modified().ok());
+            if modified > state.modified {
+                state.modified = modified;
+            }
+        }
+        state
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::{HEMTTError, Project};
+use hemtt_arma_config::simplify::{Class, Config, Entry};
+use hemtt_pbo::ReadablePbo;
+use serde_json::json;
+
+use crate::Command;
+
+/// Root classes that are documented
+const DOCUMENTED: [&str; 3] = ["CfgVehicles", "CfgWeapons", "CfgMagazines"];
+
+struct DocClass {
+    name: String,
+    parent: String,
+    display_name: Option<String>,
+    scope: Option<i32>,
+    addon: String,
+}
+
+pub struct Docs;
+impl Command for Docs {
+    fn register(&self) -> App {
+        SubCommand::with_name("docs")
+            .version(*crate::VERSION)
+            .about("Generate documentation of the classes in the built configs")
+            .arg(
+                Arg::with_name("format")
+                    .long("format")
+                    .help("Format of the documentation")
+                    .possible_values(&["markdown", "json"])
+                    .default_value("markdown"),
+            )
+            .arg(
+                Arg::with_name("output")
+                    .long("output")
+                    .help("Folder to write the documentation to")
+                    .default_value("docs"),
+            )
+    }
+
+    fn run(&self, a: &ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        let built = Project::output_dir()?;
+        let mut documented: BTreeMap<&str, Vec<DocClass>> =
+            DOCUMENTED.iter().map(|r| (*r, Vec::new())).collect();
+        for addon in hemtt::get_all_addons()? {
+            let path = addon.destination(&built, Some(p.prefix()), None);
+            if !path.exists() {
+                warn!("`{}` has not been built, skipping", addon.name());
+                continue;
+            }
+            let config = match read_config(&path)? {
+                Some(config) => config,
+                None => continue,
+            };
+            for (name, classes) in documented.iter_mut() {
+                if let Some(root) = config.root.class(name) {
+                    collect(root, addon.name(), classes);
+                }
+            }
+        }
+
+        let output = PathBuf::from(a.value_of("output").unwrap());
+        create_dir!(output)?;
+        match a.value_of("format").unwrap() {
+            "json" => {
+                let mut map = serde_json::Map::new();
+                for (name, classes) in &documented {
+                    map.insert(
+                        name.to_string(),
+                        classes
+                            .iter()
+                            .map(|c| {
+                                json!({
+                                    "class": c.name,
+                                    "displayName": c.display_name,
+                                    "scope": c.scope,
+                                    "inheritance": inheritance(c, classes),
+                                    "addon": c.addon,
+                                })
+                            })
+                            .collect(),
+                    );
+                }
+                let path = output.join("classes.json");
+                create_file!(&path)?.write_all(
+                    serde_json::to_string_pretty(&map)
+                        .map_err(|e| HEMTTError::Generic(e.to_string()))?
+                        .as_bytes(),
+                )?;
+                info!("Documentation written to {}", path.display());
+            }
+            _ => {
+                for (name, classes) in &documented {
+                    if classes.is_empty() {
+                        continue;
+                    }
+                    let path = output.join(format!("{}.md", name));
+                    create_file!(&path)?.write_all(markdown(name, classes).as_bytes())?;
+                    info!("Documentation written to {}", path.display());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read the `config.bin` from a built PBO
+pub fn read_config(path: &std::path::Path) -> Result<Option<Config>, HEMTTError> {
+    let mut pbo = ReadablePbo::from(open_file!(path)?)?;
+    let config = pbo
+        .files()
+        .into_iter()
+        .map(|h| h.filename().to_string())
+        .filter(|f| {
+            let f = f.to_lowercase();
+            f == "config.bin" || f.ends_with("\\config.bin")
+        })
+        .min_by_key(|f| f.matches('\\').count());
+    if let Some(config) = config {
+        let mut cursor = pbo.retrieve_decompressed(&config)?.unwrap();
+        Ok(Some(Config::read_rapified(&mut cursor).map_err(|e| {
+            HEMTTError::Generic(format!("{}: {}", path.display(), e))
+        })?))
+    } else {
+        debug!("{} has no config.bin", path.display());
+        Ok(None)
+    }
+}
+
+fn collect(root: &Class, addon: &str, classes: &mut Vec<DocClass>) {
+    for (name, entry) in &root.entries {
+        if let Entry::Class(c) = entry {
+            if c.external || c.deletion {
+                continue;
+            }
+            classes.push(DocClass {
+                name: name.to_string(),
+                parent: c.parent.clone(),
+                display_name: match c.get("displayName") {
+                    Some(Entry::Str(s)) => Some(s.to_string()),
+                    _ => None,
+                },
+                scope: match c.get("scope") {
+                    Some(Entry::Int(i)) => Some(*i),
+                    _ => None,
+                },
+                addon: addon.to_string(),
+            });
+        }
+    }
+}
+
+/// Parents of the class, as far as they can be found in the project
+fn inheritance(class: &DocClass, classes: &[DocClass]) -> Vec<String> {
+    let lookup: HashMap<String, &DocClass> =
+        classes.iter().map(|c| (c.name.to_lowercase(), c)).collect();
+    let mut chain = Vec::new();
+    let mut parent = class.parent.clone();
+    while !parent.is_empty() && !chain.contains(&parent) {
+        chain.push(parent.clone());
+        parent = match lookup.get(&parent.to_lowercase()) {
+            Some(c) => c.parent.clone(),
+            None => String::new(),
+        };
+    }
+    chain
+}
+
+fn markdown(name: &str, classes: &[DocClass]) -> String {
+    let mut out = format!(
+        "# {}\n\n| Class | Display Name | Scope | Inheritance | Addon |\n|-------|--------------|-------|-------------|-------|\n",
+        name
+    );
+    for class in classes {
+        out.push_str(&format!(
+            "| `{}` | {} | {} | {} | {} |\n",
+            class.name,
+            class.display_name.as_deref().unwrap_or(""),
+            class.scope.map(|s| s.to_string()).unwrap_or_default(),
+            inheritance(class, classes)
+                .iter()
+                .map(|c| format!("`{}`", c))
+                .collect::<Vec<String>>()
+                .join(" > "),
+            class.addon,
+        ));
+    }
+    out
+}
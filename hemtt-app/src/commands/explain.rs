@@ -0,0 +1,56 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::HEMTTError;
+
+use crate::Command;
+
+pub struct Explain;
+impl Command for Explain {
+    fn register(&self) -> App {
+        SubCommand::with_name("explain")
+            .version(*crate::VERSION)
+            .about("Explain an error code or an exit code, or list all of them")
+            .arg(Arg::with_name("code").help("Error code, ex: E0301, or exit code, ex: 3"))
+    }
+
+    fn require_project(&self) -> bool {
+        false
+    }
+
+    fn can_announce(&self) -> bool {
+        false
+    }
+
+    fn run_no_project(&self, a: &ArgMatches) -> Result<(), HEMTTError> {
+        if let Some(code) = a.value_of("code") {
+            if let Ok(exit) = code.parse::<i32>() {
+                let exit = hemtt::explain_exit(exit).ok_or_else(|| {
+                    HEMTTError::UserHint(
+                        format!("Unknown exit code `{}`", code),
+                        String::from("hemtt explain"),
+                    )
+                })?;
+                println!(
+                    "Exit code {} {}\n\n{}",
+                    exit.code, exit.name, exit.explanation
+                );
+                return Ok(());
+            }
+            let error = hemtt::explain(code).ok_or_else(|| {
+                HEMTTError::UserHint(
+                    format!("Unknown error code `{}`", code),
+                    String::from("hemtt explain"),
+                )
+            })?;
+            println!("{} {}\n\n{}", error.code, error.name, error.explanation);
+        } else {
+            for error in hemtt::ERROR_CODES {
+                println!("{} {}", error.code, error.name);
+            }
+            println!("\nExit codes:");
+            for exit in hemtt::EXIT_CODES {
+                println!("{:>5} {}", exit.code, exit.name);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use hemtt::{HEMTTError, Project};
+
+/// Runs `hemtt-<name>` from the PATH for an unknown subcommand
+///
+/// The remaining arguments are passed through. When run inside a project the
+/// working directory is the project root and the resolved project is available
+/// as JSON in `HEMTT_PROJECT`.
+pub fn run(name: &str, args: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let binary = format!("hemtt-{}", name);
+    let mut command = std::process::Command::new(&binary);
+    if let Some(values) = args.values_of_os("") {
+        command.args(values);
+    }
+    command.env("HEMTT", std::env::current_exe()?);
+    command.env("HEMTT_VERSION", *crate::VERSION);
+    match Project::read() {
+        Ok(p) => {
+            command.env("HEMTT_PROJECT_ROOT", std::env::current_dir()?);
+            if let Some(json) = p.to_json() {
+                command.env("HEMTT_PROJECT", json.to_string());
+            }
+        }
+        Err(HEMTTError::NoProjectFound) => {}
+        Err(e) => return Err(e),
+    }
+    if *crate::OFFLINE {
+        command.env("HEMTT_OFFLINE", "1");
+    }
+    debug!("Running external command `{}`", binary);
+    let status = command.status().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            HEMTTError::UserHint(
+                format!("Unknown command `{}`", name),
+                format!(
+                    "`hemtt --help` lists the commands, or install `{}` in your PATH",
+                    binary
+                ),
+            )
+        } else {
+            HEMTTError::Generic(format!("Unable to run `{}`: {}", binary, e))
+        }
+    })?;
+    if !status.success() {
+        return Err(HEMTTError::Generic(format!(
+            "`{}` failed with {}",
+            binary, status
+        )));
+    }
+    Ok(())
+}
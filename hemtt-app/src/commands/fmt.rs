@@ -0,0 +1,84 @@
+use crate::{Command, HEMTTError, Project};
+
+pub struct Fmt {}
+impl Command for Fmt {
+    fn register(&self) -> clap::App {
+        clap::SubCommand::with_name("fmt")
+            .version(*crate::VERSION)
+            .about("Format the addons' SQF files and configs")
+            .arg(
+                clap::Arg::with_name("check")
+                    .long("check")
+                    .help("Fail if any file is not formatted instead of formatting it"),
+            )
+            .args(&super::building_args())
+    }
+
+    fn can_announce(&self) -> bool {
+        false
+    }
+
+    fn run(&self, args: &clap::ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        let check = args.is_present("check");
+        let mut unformatted = Vec::new();
+        let mut unreadable = 0;
+        let mut checked = 0;
+        for addon in crate::get_addons_from_args(args)? {
+            for entry in walkdir::WalkDir::new(addon.source())
+                .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+            {
+                let entry = entry.map_err(std::io::Error::from)?;
+                let path = entry.path();
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let format = match path
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().to_lowercase())
+                    .as_deref()
+                {
+                    Some("sqf") => hemtt_sqf::format,
+                    Some("cpp") | Some("hpp") => hemtt_sqf::format_config,
+                    _ => continue,
+                };
+                let source = match std::fs::read_to_string(path) {
+                    Ok(source) => source,
+                    Err(e) => {
+                        error!("Unable to read {}: {}", path.display(), e);
+                        unreadable += 1;
+                        continue;
+                    }
+                };
+                checked += 1;
+                let formatted = format(&source, &p.format);
+                if formatted == source {
+                    continue;
+                }
+                if check {
+                    println!("{}", path.display());
+                } else if !crate::dry_run("format", path) {
+                    std::fs::write(path, formatted)?;
+                }
+                unformatted.push(path.to_path_buf());
+            }
+        }
+        if check && !unformatted.is_empty() {
+            return Err(HEMTTError::UserHint(
+                format!("{} files are not formatted", unformatted.len()),
+                String::from("run `hemtt fmt` to format them"),
+            ));
+        }
+        if check {
+            info!("Checked {} files, all are formatted", checked);
+        } else {
+            info!("Formatted {} files", unformatted.len());
+        }
+        if unreadable > 0 {
+            return Err(HEMTTError::User(format!(
+                "{} files could not be read, SQF files and configs must be UTF-8",
+                unreadable
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use std::io::Write;
+use std::path::Path;
+
+use hemtt::{Dependency, Game, HEMTTError, Project};
+use serde_json::{json, Value as Json};
+
+use crate::Command;
+
+pub struct Gen {}
+impl Command for Gen {
+    fn register(&self) -> clap::App {
+        clap::SubCommand::with_name("gen")
+            .version(*crate::VERSION)
+            .about("Generate files for working on the project")
+            .subcommand(
+                clap::SubCommand::with_name("tasks")
+                    .about("Write editor tasks to build, release, launch and test the project")
+                    .arg(
+                        clap::Arg::with_name("editor")
+                            .help("Editor to write the tasks for")
+                            .possible_values(&["vscode"])
+                            .required(true),
+                    )
+                    .arg(
+                        clap::Arg::with_name("force")
+                            .long("force")
+                            .help("Replace existing task files"),
+                    ),
+            )
+    }
+
+    fn run(&self, a: &clap::ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        match a.subcommand() {
+            ("tasks", Some(b)) => match b.value_of("editor").unwrap() {
+                "vscode" => vscode(&p, b.is_present("force")),
+                editor => Err(HEMTTError::User(format!("Unknown editor `{}`", editor))),
+            },
+            _ => Err(HEMTTError::User(String::from(
+                "No command was provided, use `gen help` to see all commands and options",
+            ))),
+        }
+    }
+}
+
+/// Writes `.vscode/tasks.json`
+fn vscode(p: &Project, force: bool) -> Result<(), HEMTTError> {
+    let path = Project::find_root()?.join(".vscode").join("tasks.json");
+    let mut tasks = json!({
+        "version": "2.0.0",
+        "tasks": [
+            hemtt_task("build", &["build"], Some(json!({ "kind": "build", "isDefault": true }))),
+            hemtt_task("release", &["build", "--release"], Some(json!("build"))),
+            {
+                "label": "hemtt: dev",
+                "detail": "Rebuild addons into .hemttout as their files change",
+                "type": "process",
+                "command": "hemtt",
+                "args": ["dev"],
+                "isBackground": true,
+                "problemMatcher": [],
+            },
+            hemtt_task(
+                "test",
+                &["run", "loadtest"],
+                Some(json!({ "kind": "test", "isDefault": true })),
+            ),
+        ],
+    });
+    if let Some(launch) = launch_task(p) {
+        tasks["tasks"].as_array_mut().unwrap().push(launch);
+    }
+    write(&path, &tasks, force)
+}
+
+fn hemtt_task(name: &str, args: &[&str], group: Option<Json>) -> Json {
+    let mut task = json!({
+        "label": format!("hemtt: {}", name),
+        "type": "process",
+        "command": "hemtt",
+        "args": args,
+        "problemMatcher": [],
+    });
+    if let Some(group) = group {
+        task["group"] = group;
+    }
+    task
+}
+
+/// Launches the game with the built addons and the project's dependencies, with
+/// file patching for `hemtt dev`
+///
+/// The game is read from the `hemtt.executable` setting, so the task file can be
+/// shared without a path that only exists on one machine. Enfusion games are not
+/// launched with `-mod` and get no task
+fn launch_task(p: &Project) -> Option<Json> {
+    let executable = match p.game {
+        Game::Arma3 => "arma3_x64.exe",
+        Game::DayZ => "DayZ_x64.exe",
+        Game::Reforger => return None,
+    };
+    let found = match p.game {
+        Game::Arma3 => hemtt::tools::find_arma3()
+            .ok()
+            .map(|folder| folder.join(executable)),
+        _ => None,
+    };
+    match found {
+        Some(found) => info!(
+            "Set `\"hemtt.executable\": {:?}` in the VS Code user settings to use the `hemtt: launch` task",
+            found.to_string_lossy().replace('\\', "/")
+        ),
+        None => info!(
+            "Set `hemtt.executable` to the path of {} in the VS Code user settings to use the `hemtt: launch` task",
+            executable
+        ),
+    }
+    let mut mods = vec![String::from("${workspaceFolder}/.hemttout")];
+    for name in p.dependencies.keys() {
+        mods.push(format!(
+            "${{workspaceFolder}}/{}",
+            Dependency::folder(name)
+                .to_string_lossy()
+                .replace('\\', "/")
+        ));
+    }
+    Some(json!({
+        "label": "hemtt: launch",
+        "type": "process",
+        "command": "${config:hemtt.executable}",
+        "args": [
+            "-noSplash",
+            "-skipIntro",
+            "-window",
+            "-filePatching",
+            format!("-mod={}", mods.join(";")),
+        ],
+        "dependsOn": "hemtt: build",
+        "problemMatcher": [],
+    }))
+}
+
+fn write(path: &Path, json: &Json, force: bool) -> Result<(), HEMTTError> {
+    if path.exists() && !force {
+        return Err(HEMTTError::UserHint(
+            format!("{:?} already exists", path),
+            String::from("use `--force` to replace it"),
+        ));
+    }
+    if crate::dry_run("create", path) {
+        return Ok(());
+    }
+    create_dir!(path.parent().unwrap())?;
+    create_file!(path)?.write_all(
+        serde_json::to_string_pretty(json)
+            .map_err(|e| HEMTTError::Generic(e.to_string()))?
+            .as_bytes(),
+    )?;
+    info!("Tasks written to {:?}", path);
+    Ok(())
+}
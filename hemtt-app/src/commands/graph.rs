@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Write;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::{HEMTTError, Project};
+use hemtt_arma_config::simplify::{ArrayElement, Entry};
+
+use crate::Command;
+
+pub struct Graph;
+impl Command for Graph {
+    fn register(&self) -> App {
+        SubCommand::with_name("graph")
+            .version(*crate::VERSION)
+            .about("Output the dependency graph of the built addons")
+            .arg(
+                Arg::with_name("format")
+                    .long("format")
+                    .help("Format of the graph")
+                    .possible_values(&["dot", "mermaid"])
+                    .default_value("dot"),
+            )
+            .arg(
+                Arg::with_name("external")
+                    .long("external")
+                    .help("Include required addons from outside the project"),
+            )
+            .arg(
+                Arg::with_name("output")
+                    .long("output")
+                    .help("File to write the graph to, defaults to stdout")
+                    .takes_value(true),
+            )
+    }
+
+    fn can_announce(&self) -> bool {
+        false
+    }
+
+    fn run(&self, a: &ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        let built = Project::output_dir()?;
+        // CfgPatches class => addon
+        let mut patches: HashMap<String, String> = HashMap::new();
+        let mut required: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for addon in hemtt::get_all_addons()? {
+            let path = addon.destination(&built, Some(p.prefix()), None);
+            if !path.exists() {
+                warn!("`{}` has not been built, skipping", addon.name());
+                continue;
+            }
+            let config = match super::docs::read_config(&path)? {
+                Some(config) => config,
+                None => continue,
+            };
+            let mut requires = Vec::new();
+            if let Some(cfg_patches) = config.root.class("CfgPatches") {
+                for (name, entry) in &cfg_patches.entries {
+                    if let Entry::Class(patch) = entry {
+                        patches.insert(name.to_lowercase(), addon.name().to_string());
+                        if let Some(Entry::Array(array)) = patch.get("requiredAddons") {
+                            requires.extend(array.elements.iter().filter_map(|e| {
+                                if let ArrayElement::Str(s) = e {
+                                    Some(s.to_string())
+                                } else {
+                                    None
+                                }
+                            }));
+                        }
+                    }
+                }
+            }
+            required.insert(addon.name().to_string(), requires);
+        }
+
+        let external = a.is_present("external");
+        let mut edges: BTreeSet<(String, String, bool)> = BTreeSet::new();
+        for (addon, requires) in &required {
+            for patch in requires {
+                match patches.get(&patch.to_lowercase()) {
+                    Some(target) if target != addon => {
+                        edges.insert((addon.clone(), target.clone(), false));
+                    }
+                    Some(_) => {}
+                    None if external => {
+                        edges.insert((addon.clone(), patch.clone(), true));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        let out = match a.value_of("format").unwrap() {
+            "mermaid" => mermaid(required.keys(), &edges),
+            _ => dot(required.keys(), &edges),
+        };
+        if let Some(output) = a.value_of("output") {
+            create_file!(output)?.write_all(out.as_bytes())?;
+            info!("Graph written to {}", output);
+        } else {
+            print!("{}", out);
+        }
+        Ok(())
+    }
+}
+
+fn dot<'a, I: Iterator<Item = &'a String>>(
+    addons: I,
+    edges: &BTreeSet<(String, String, bool)>,
+) -> String {
+    let mut out = String::from("digraph addons {\n");
+    for addon in addons {
+        out.push_str(&format!("    \"{}\";\n", addon));
+    }
+    for (from, to, external) in edges {
+        out.push_str(&format!(
+            "    \"{}\" -> \"{}\"{};\n",
+            from,
+            to,
+            if *external { " [style=dashed]" } else { "" }
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn mermaid<'a, I: Iterator<Item = &'a String>>(
+    addons: I,
+    edges: &BTreeSet<(String, String, bool)>,
+) -> String {
+    let mut out = String::from("graph LR\n");
+    for addon in addons {
+        out.push_str(&format!("    {}\n", addon));
+    }
+    for (from, to, external) in edges {
+        if *external {
+            out.push_str(&format!("    {} -.-> {}([{}])\n", from, to, to));
+        } else {
+            out.push_str(&format!("    {} --> {}\n", from, to));
+        }
+    }
+    out
+}
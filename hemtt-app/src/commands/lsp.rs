@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+
+use hemtt::{AddonLocation, Diagnostic, Severity, Span};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use serde_json::{json, Value as Json};
+
+use crate::{Command, Flow, HEMTTError, Project};
+
+/// Characters escaped in the path of a file URI
+const PATH: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+pub struct Lsp {}
+impl Command for Lsp {
+    fn register(&self) -> clap::App {
+        clap::SubCommand::with_name("lsp")
+            .version(*crate::VERSION)
+            .about("Run a language server for editors on the standard input and output")
+            .long_about(
+                "Run a language server for editors on the standard input and output\n\n\
+                The addon of a file is checked as it is opened, changed and saved, with the \
+                same checks as `hemtt build`, and the problems are shown in the editor",
+            )
+    }
+
+    fn run(&self, _: &clap::ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        let mut server = Server {
+            p,
+            root: Project::find_root()?,
+            documents: BTreeMap::new(),
+            published: BTreeMap::new(),
+        };
+        let stdin = std::io::stdin();
+        server.serve(&mut stdin.lock())
+    }
+}
+
+struct Server {
+    p: Project,
+    root: PathBuf,
+    /// Text of the open documents, by path relative to the project root
+    documents: BTreeMap<String, String>,
+    /// Documents with problems, by the folder of the addon they were found in
+    published: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Server {
+    fn serve(&mut self, input: &mut impl BufRead) -> Result<(), HEMTTError> {
+        while let Some(message) = read_message(input)? {
+            let method = message["method"].as_str().unwrap_or_default();
+            let id = message.get("id").cloned();
+            let params = &message["params"];
+            let document = self.document(&params["textDocument"]["uri"]);
+            match (method, document) {
+                ("initialize", _) => respond(
+                    id,
+                    json!({
+                        "capabilities": {
+                            "textDocumentSync": {
+                                "openClose": true,
+                                // The whole document is sent with every change
+                                "change": 1,
+                                "save": true,
+                            },
+                        },
+                        "serverInfo": {
+                            "name": "hemtt",
+                            "version": *crate::VERSION,
+                        },
+                    }),
+                )?,
+                ("shutdown", _) => respond(id, Json::Null)?,
+                ("exit", _) => break,
+                ("textDocument/didOpen", Some(document)) => {
+                    let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                    self.documents.insert(document.clone(), text.to_string());
+                    self.check(&document)?;
+                }
+                ("textDocument/didChange", Some(document)) => {
+                    let text = params["contentChanges"]
+                        .as_array()
+                        .and_then(|changes| changes.last())
+                        .and_then(|change| change["text"].as_str());
+                    if let Some(text) = text {
+                        self.documents.insert(document.clone(), text.to_string());
+                        self.check(&document)?;
+                    }
+                }
+                ("textDocument/didSave", Some(document)) => self.check(&document)?,
+                ("textDocument/didClose", Some(document)) => {
+                    self.documents.remove(&document);
+                    self.check(&document)?;
+                }
+                // Responses from the editor have no method and are not answered
+                ("", _) => {}
+                (_, _) => {
+                    if let Some(id) = id {
+                        write_message(&json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": {
+                                "code": -32601,
+                                "message": format!("`{}` is not supported", method),
+                            },
+                        }))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Path of a document relative to the project root, documents outside
+    /// of the project are not checked
+    /// Ex: "file:///home/user/mod/addons/main/config.cpp" => "addons/main/config.cpp"
+    fn document(&self, uri: &Json) -> Option<String> {
+        let path = uri_path(uri.as_str()?)?;
+        Some(
+            path.strip_prefix(&self.root)
+                .ok()?
+                .to_string_lossy()
+                .replace('\\', "/"),
+        )
+    }
+
+    /// Checks the addon of a document and publishes the problems of its files
+    fn check(&mut self, document: &str) -> Result<(), HEMTTError> {
+        let addon = hemtt::get_addon_from_locations(&AddonLocation::first_class())?
+            .into_iter()
+            .find(|addon| document.starts_with(&format!("{}/", addon.source())));
+        let addon = match addon {
+            Some(addon) => addon,
+            None => return Ok(()),
+        };
+        let folder = format!("{}/", addon.source());
+        let files: Vec<(String, String)> = self
+            .documents
+            .iter()
+            .filter(|(path, _)| path.starts_with(&folder))
+            .map(|(path, text)| (path.clone(), text.clone()))
+            .collect();
+        let diagnostics = match tasks().diagnose(vec![addon.clone()], &self.p, &files) {
+            Ok(diagnostics) => diagnostics,
+            Err(e) => {
+                error!("Unable to check `{}`: {}", addon.name(), e);
+                return Ok(());
+            }
+        };
+
+        let mut problems: BTreeMap<String, Vec<Json>> = BTreeMap::new();
+        for diagnostic in &diagnostics {
+            // Problems with the whole addon are only shown by the build
+            if let Some(span) = diagnostic.span.as_ref().filter(|span| span.line > 0) {
+                problems
+                    .entry(
+                        span.path
+                            .replace('\\', "/")
+                            .trim_start_matches('/')
+                            .to_string(),
+                    )
+                    .or_default()
+                    .push(lsp_diagnostic(diagnostic, span));
+            }
+        }
+        let previous = self
+            .published
+            .insert(
+                addon.source().to_string(),
+                problems.keys().cloned().collect(),
+            )
+            .unwrap_or_default();
+        for path in previous.iter().filter(|path| !problems.contains_key(*path)) {
+            self.publish(path, Vec::new())?;
+        }
+        for (path, problems) in problems {
+            self.publish(&path, problems)?;
+        }
+        Ok(())
+    }
+
+    fn publish(&self, path: &str, diagnostics: Vec<Json>) -> Result<(), HEMTTError> {
+        write_message(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": {
+                "uri": path_uri(&self.root.join(path)),
+                "diagnostics": diagnostics,
+            },
+        }))
+    }
+}
+
+/// The checks of `hemtt check` and the build tasks that only change files in memory
+fn tasks() -> Flow {
+    Flow {
+        tasks: vec![
+            Box::new(crate::tasks::NotEmpty {}),
+            Box::new(crate::tasks::ValidName {}),
+            Box::new(crate::tasks::Encoding { fix: false }),
+            Box::new(crate::tasks::LineEndings { fix: false }),
+            Box::new(crate::tasks::IncludeCase::new()),
+            Box::new(crate::tasks::Separators { fix: false }),
+            Box::new(crate::tasks::Languages {}),
+            Box::new(crate::tasks::Populate {}),
+            Box::new(crate::tasks::Prefix::new()),
+            Box::new(crate::tasks::Preprocess {}),
+            Box::new(crate::tasks::Rapify {}),
+            Box::new(crate::tasks::Strings {}),
+        ],
+    }
+}
+
+fn lsp_diagnostic(diagnostic: &Diagnostic, span: &Span) -> Json {
+    let line = span.line - 1;
+    let column = span.column.saturating_sub(1);
+    json!({
+        "range": {
+            "start": { "line": line, "character": column },
+            "end": { "line": line, "character": column + span.length.max(1) },
+        },
+        "severity": match diagnostic.severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+            Severity::Note => 3,
+        },
+        "code": diagnostic.rule,
+        "source": "hemtt",
+        "message": diagnostic.message,
+    })
+}
+
+/// Reads a message, the body after its `Content-Length` header
+fn read_message(input: &mut impl BufRead) -> Result<Option<Json>, HEMTTError> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = length.ok_or_else(|| {
+        HEMTTError::Generic(String::from("Language server message without a length"))
+    })?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| HEMTTError::Generic(format!("Invalid language server message: {}", e)))
+}
+
+fn write_message(message: &Json) -> Result<(), HEMTTError> {
+    let body = message.to_string();
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    write!(out, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    out.flush()?;
+    Ok(())
+}
+
+fn respond(id: Option<Json>, result: Json) -> Result<(), HEMTTError> {
+    write_message(&json!({
+        "jsonrpc": "2.0",
+        "id": id.unwrap_or(Json::Null),
+        "result": result,
+    }))
+}
+
+/// Ex: "file:///c%3A/mod/config.cpp" => "c:/mod/config.cpp"
+fn uri_path(uri: &str) -> Option<PathBuf> {
+    let path = percent_decode_str(uri.strip_prefix("file://")?).decode_utf8_lossy();
+    // Windows paths start with the drive, after the slash of the URI
+    if path.get(2..3) == Some(":") {
+        return Some(PathBuf::from(&path[1..]));
+    }
+    Some(PathBuf::from(path.as_ref()))
+}
+
+fn path_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let path = utf8_percent_encode(&path, PATH).to_string();
+    if path.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        format!("file:///{}", path)
+    }
+}
@@ -1,11 +1,85 @@
+mod auth;
 mod bug;
 mod build;
+mod check;
 mod clean;
+mod config;
+mod create;
+mod deps;
+mod dev;
+mod docs;
+mod explain;
+pub mod external;
+mod fmt;
+mod gen;
+mod graph;
+mod lsp;
+mod patch;
+mod photoshoot;
 mod project;
+mod publish;
+mod run;
+mod scopes;
+mod sign;
+mod stats;
 mod template;
+mod update;
+mod verify;
 
+pub use auth::Auth;
 pub use bug::Bug;
 pub use build::Build;
+pub use check::Check;
 pub use clean::Clean;
+pub use config::Config;
+pub use create::Create;
+pub use deps::Deps;
+pub use dev::Dev;
+pub use docs::Docs;
+pub use explain::Explain;
+pub use fmt::Fmt;
+pub use gen::Gen;
+pub use graph::Graph;
+pub use lsp::Lsp;
+pub use patch::Patch;
+pub use photoshoot::Photoshoot;
 pub use project::Project;
+pub use publish::Publish;
+pub use run::Run;
+pub use scopes::Scopes;
+pub use sign::Sign;
+pub use stats::Stats;
 pub use template::Template;
+pub use update::{check_for_update, Update};
+pub use verify::Verify;
+
+/// Arguments used to select which addons a command works on
+pub fn building_args<'a, 'b>() -> Vec<clap::Arg<'a, 'b>> {
+    vec![
+        clap::Arg::with_name("addons")
+            .help("Addons and campaigns to build, supports glob patterns, `all` for all of them")
+            .multiple(true)
+            .use_delimiter(true),
+        clap::Arg::with_name("opts")
+            .long("opts")
+            .short("o")
+            .help("Optionals to build, supports glob patterns, `all` for every optional")
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true),
+        clap::Arg::with_name("compats")
+            .long("compats")
+            .short("c")
+            .help("Compats to build, supports glob patterns, `all` for every compat")
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true),
+        clap::Arg::with_name("filter")
+            .long("filter")
+            .short("f")
+            .help("Only build addons from any location matching the pattern")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1),
+    ]
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::delta::{Action, Change, Manifest};
+use hemtt::{HEMTTError, Project};
+
+use crate::tasks::ReleaseOptions;
+use crate::Command;
+
+pub struct Patch {}
+impl Command for Patch {
+    fn register(&self) -> App {
+        SubCommand::with_name("patch")
+            .version(*crate::VERSION)
+            .about("Create delta patches that update one release to another")
+            .arg(
+                Arg::with_name("from")
+                    .help("Version of the release being updated")
+                    .required(true),
+            )
+            .arg(
+                Arg::with_name("to")
+                    .help("Version of the updated release, defaults to the current version"),
+            )
+            .arg(
+                Arg::with_name("output")
+                    .long("output")
+                    .help("Folder to create the patches in, defaults to `releases/patches/{from}-{to}`")
+                    .takes_value(true),
+            )
+    }
+
+    fn run(&self, a: &ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        let from = a.value_of("from").unwrap().to_string();
+        let to = a
+            .value_of("to")
+            .map_or_else(|| p.version().to_string(), String::from);
+        let old = release(&p, &from)?;
+        let new = release(&p, &to)?;
+        let output = match a.value_of("output") {
+            Some(output) => PathBuf::from(output),
+            None => Project::find_root()?
+                .join("releases")
+                .join("patches")
+                .join(format!("{}-{}", from, to)),
+        };
+
+        let old_files = files(&old)?;
+        let new_files = files(&new)?;
+        let mut manifest = Manifest {
+            from,
+            to,
+            files: Vec::new(),
+        };
+        for path in &new_files {
+            let (source, target) = (old.join(path), new.join(path));
+            if !old_files.contains(path) {
+                manifest.files.push(Change {
+                    path: path.clone(),
+                    action: Action::Add,
+                    from_hash: None,
+                    to_hash: Some(crate::tasks::file_sha1(&target)?),
+                });
+            } else if !crate::tasks::is_unchanged(&target, &source)? {
+                let pbo = Path::new(path).extension().map_or(false, |e| e == "pbo");
+                manifest.files.push(Change {
+                    path: path.clone(),
+                    action: if pbo { Action::Patch } else { Action::Add },
+                    from_hash: if pbo {
+                        Some(crate::tasks::file_sha1(&source)?)
+                    } else {
+                        None
+                    },
+                    to_hash: Some(crate::tasks::file_sha1(&target)?),
+                });
+            }
+        }
+        for path in old_files.difference(&new_files) {
+            manifest.files.push(Change {
+                path: path.clone(),
+                action: Action::Remove,
+                from_hash: Some(crate::tasks::file_sha1(&old.join(path))?),
+                to_hash: None,
+            });
+        }
+
+        if crate::dry_run("create", &output) {
+            return Ok(());
+        }
+        if output.exists() {
+            // Only a folder of earlier patches is replaced
+            if std::fs::read_dir(&output)?.next().is_some()
+                && !output.join(hemtt::delta::MANIFEST).exists()
+            {
+                return Err(HEMTTError::UserHint(
+                    format!("{:?} is not empty and does not contain patches", output),
+                    String::from("choose a new or empty folder with `--output`"),
+                ));
+            }
+            std::fs::remove_dir_all(&output)?;
+        }
+        create_dir!(&output)?;
+        let mut size = 0;
+        for change in &manifest.files {
+            if change.action == Action::Remove {
+                continue;
+            }
+            let target = output.join(&change.path);
+            create_dir!(target.parent().unwrap())?;
+            match change.action {
+                Action::Patch => {
+                    debug!("Creating the patch of {}", change.path);
+                    let patch = hemtt::delta::diff(
+                        &std::fs::read(old.join(&change.path))?,
+                        &std::fs::read(new.join(&change.path))?,
+                    );
+                    size += patch.len() as u64;
+                    create_file!(hemtt::delta::patch_path(&target))?.write_all(&patch)?;
+                }
+                _ => size += copy_file!(new.join(&change.path), &target)?,
+            }
+        }
+        create_file!(output.join(hemtt::delta::MANIFEST))?.write_all(
+            serde_json::to_string_pretty(&manifest)
+                .map_err(|e| HEMTTError::Generic(e.to_string()))?
+                .as_bytes(),
+        )?;
+        info!(
+            "Created patches for {} changed files in {:?}, {} MB",
+            manifest.files.len(),
+            output,
+            size / 1024 / 1024
+        );
+        Ok(())
+    }
+}
+
+/// Release folder of a version
+fn release(p: &Project, version: &str) -> Result<PathBuf, HEMTTError> {
+    let root = crate::tasks::release_root(
+        p,
+        &ReleaseOptions {
+            version: Some(version.to_string()),
+            ..ReleaseOptions::default()
+        },
+    )?;
+    if !root.is_dir() {
+        return Err(HEMTTError::UserHint(
+            format!("The release of {} does not exist at {:?}", version, root),
+            String::from("patches are created between releases built with `hemtt build --release`"),
+        ));
+    }
+    Ok(root)
+}
+
+/// Files of a release, relative to it and separated by `/`
+fn files(root: &Path) -> Result<BTreeSet<String>, HEMTTError> {
+    let mut files = BTreeSet::new();
+    for entry in walkdir::WalkDir::new(root) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        if entry.file_type().is_file() {
+            files.insert(
+                entry
+                    .path()
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+            );
+        }
+    }
+    Ok(files)
+}
@@ -0,0 +1,77 @@
+/// Prefix of the generated photoshoot addon
+pub const PREFIX: &str = "hemtt_photoshoot";
+
+pub const CONFIG: &str = r#"class CfgPatches {
+    class hemtt_photoshoot {
+        units[] = {};
+        weapons[] = {};
+        requiredAddons[] = {};
+        author = "HEMTT";
+    };
+};
+"#;
+
+pub const MISSION: &str = r#"version=53;
+class Mission {
+    class Intel {
+        briefingName="HEMTT Photoshoot";
+    };
+    class Entities {
+        items=1;
+        class Item0 {
+            dataType="Group";
+            side="West";
+            class Entities {
+                items=1;
+                class Item0 {
+                    dataType="Object";
+                    class PositionInfo {
+                        position[]={0,5,0};
+                    };
+                    side="West";
+                    flags=7;
+                    class Attributes {
+                        isPlayer=1;
+                    };
+                    id=1;
+                    type="B_Soldier_F";
+                };
+            };
+            class Attributes {};
+            id=0;
+        };
+    };
+};
+"#;
+
+/// Script that photographs every class, then reports that it is done
+pub fn init(classes: &[String]) -> String {
+    format!(
+        r#"[] spawn {{
+    sleep 3;
+    private _classes = [{}];
+    private _cam = "camera" camCreate [0, 0, 0];
+    _cam cameraEffect ["internal", "back"];
+    showCinemaBorder false;
+    {{
+        private _vehicle = createVehicle [_x, [1000, 1000, 0], [], 0, "CAN_COLLIDE"];
+        private _size = (boundingBoxReal _vehicle) select 2;
+        _cam camSetTarget _vehicle;
+        _cam camSetRelPos [_size * 1.2, _size * 1.2, _size * 0.5];
+        _cam camCommit 0;
+        sleep 2;
+        screenshot format ["{}_%1.png", _x];
+        sleep 1;
+        deleteVehicle _vehicle;
+    }} forEach _classes;
+    diag_log "HEMTT_PHOTOSHOOT_DONE";
+}};
+"#,
+        classes
+            .iter()
+            .map(|c| format!("\"{}\"", c))
+            .collect::<Vec<String>>()
+            .join(", "),
+        PREFIX
+    )
+}
@@ -0,0 +1,222 @@
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::{Addon, Dependency, HEMTTError, Project};
+use hemtt_pbo::WritablePbo;
+
+use crate::Command;
+
+mod mission;
+
+/// Size of the images used by Eden for previews, PAAs must be a power of two
+const PREVIEW_SIZE: (u32, u32) = (512, 256);
+
+pub struct Photoshoot;
+impl Command for Photoshoot {
+    fn register(&self) -> App {
+        SubCommand::with_name("photoshoot")
+            .version(*crate::VERSION)
+            .about("Launch the game to generate editor previews")
+            .long_about(
+                "Launch the game to generate editor previews into `data/previews` of each addon\n\n\
+                The previews are converted to PAA with ImageToPAA from the Arma 3 Tools, \
+                without it they are saved as JPG and can be converted to PAA by hand",
+            )
+            .arg(
+                Arg::with_name("timeout")
+                    .long("timeout")
+                    .help("Seconds to wait for the game to take the screenshots")
+                    .takes_value(true)
+                    .default_value("300"),
+            )
+            .arg(
+                Arg::with_name("screenshots")
+                    .long("screenshots")
+                    .help("Folder the game saves screenshots to")
+                    .takes_value(true),
+            )
+    }
+
+    fn run(&self, a: &ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        if p.photoshoot.is_empty() {
+            return Err(HEMTTError::User(String::from(
+                "No classes are configured in `photoshoot.previews`",
+            )));
+        }
+        let timeout =
+            Duration::from_secs(a.value_of("timeout").unwrap().parse().map_err(|_| {
+                HEMTTError::User(String::from("timeout must be a number of seconds"))
+            })?);
+        let screenshots = match a.value_of("screenshots") {
+            Some(folder) => PathBuf::from(folder),
+            None => screenshots_folder()?,
+        };
+        let classes: Vec<String> = p.photoshoot.previews.values().flatten().cloned().collect();
+        for class in &classes {
+            let old = screenshot(&screenshots, class);
+            if old.exists() {
+                remove_file!(old)?;
+            }
+        }
+
+        let root = Project::find_root()?;
+        let mut mods = vec![
+            Project::output_dir()?.display().to_string(),
+            create_mod(&classes)?.display().to_string(),
+        ];
+        for name in p.dependencies.keys() {
+            mods.push(root.join(Dependency::folder(name)).display().to_string());
+        }
+        let arma3 = hemtt::tools::find_arma3()?;
+        info!("Launching Arma 3, {} classes to photograph", classes.len());
+        let mut child = std::process::Command::new(arma3.join("arma3_x64.exe"))
+            .arg("-nosplash")
+            .arg("-skipIntro")
+            .arg("-noPause")
+            .arg("-window")
+            .arg(format!("-mod={}", mods.join(";")))
+            .arg(format!(
+                "-init=playMission[\"\",\"\\{}\\photoshoot.VR\"]",
+                mission::PREFIX
+            ))
+            .spawn()?;
+        let start = Instant::now();
+        while !classes
+            .iter()
+            .all(|class| screenshot(&screenshots, class).exists())
+        {
+            if start.elapsed() > timeout {
+                child.kill()?;
+                return Err(HEMTTError::Generic(String::from(
+                    "Timed out waiting for the screenshots",
+                )));
+            }
+            std::thread::sleep(Duration::from_secs(1));
+        }
+        // Give the game a moment to finish writing the last screenshot
+        std::thread::sleep(Duration::from_secs(2));
+        child.kill()?;
+
+        let image_to_paa = image_to_paa();
+        for (addon, classes) in &p.photoshoot.previews {
+            let addon = Addon::locate(addon)?
+                .ok_or_else(|| HEMTTError::User(format!("Addon `{}` was not found", addon)))?;
+            for class in classes {
+                let previews = Path::new(addon.source()).join("data").join("previews");
+                let target = match &image_to_paa {
+                    Some(image_to_paa) => {
+                        let png = std::env::temp_dir().join(format!("{}.png", class));
+                        convert(&screenshot(&screenshots, class), &png)?;
+                        let target = previews.join(format!("{}.paa", class));
+                        to_paa(image_to_paa, &png, &target)?;
+                        remove_file!(png)?;
+                        target
+                    }
+                    None => {
+                        let target = previews.join(format!("{}.jpg", class));
+                        convert(&screenshot(&screenshots, class), &target)?;
+                        target
+                    }
+                };
+                info!("Created preview {}", target.display());
+            }
+        }
+        if image_to_paa.is_none() {
+            warn!(
+                "ImageToPAA from the Arma 3 Tools was not found, the previews are JPGs, \
+                convert them to PAA with ImageToPAA or TexView 2 before releasing"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Create a mod containing the photoshoot mission
+fn create_mod(classes: &[String]) -> Result<PathBuf, HEMTTError> {
+    let mut folder = std::env::temp_dir();
+    folder.push(format!("@{}", mission::PREFIX));
+    let addons = folder.join("addons");
+    create_dir!(addons)?;
+    let mut pbo = WritablePbo::<Cursor<Vec<u8>>>::new();
+    pbo.add_extension("prefix", mission::PREFIX);
+    pbo.add_file(
+        "config.cpp",
+        Cursor::new(mission::CONFIG.as_bytes().to_vec()),
+    )?;
+    pbo.add_file(
+        "photoshoot.VR\\mission.sqm",
+        Cursor::new(mission::MISSION.as_bytes().to_vec()),
+    )?;
+    pbo.add_file(
+        "photoshoot.VR\\init.sqf",
+        Cursor::new(mission::init(classes).into_bytes()),
+    )?;
+    pbo.write(&mut create_file!(
+        addons.join(format!("{}.pbo", mission::PREFIX))
+    )?)?;
+    Ok(folder)
+}
+
+fn screenshots_folder() -> Result<PathBuf, HEMTTError> {
+    std::env::var("USERPROFILE")
+        .map(|home| {
+            PathBuf::from(home)
+                .join("Documents")
+                .join("Arma 3")
+                .join("Screenshots")
+        })
+        .map_err(|_| {
+            HEMTTError::UserHint(
+                String::from("Unable to locate the Arma 3 screenshots folder"),
+                String::from("provide it with `--screenshots`"),
+            )
+        })
+}
+
+fn screenshot(folder: &Path, class: &str) -> PathBuf {
+    folder.join(format!("{}_{}.png", mission::PREFIX, class))
+}
+
+/// Crop and resize a screenshot into an editor preview
+fn convert(source: &Path, target: &Path) -> Result<(), HEMTTError> {
+    let image = image::open(source)
+        .map_err(|e| HEMTTError::Generic(format!("Unable to read {}: {}", source.display(), e)))?
+        .resize_to_fill(
+            PREVIEW_SIZE.0,
+            PREVIEW_SIZE.1,
+            image::imageops::FilterType::Lanczos3,
+        );
+    create_dir!(target.parent().unwrap())?;
+    image
+        .to_rgb8()
+        .save(target)
+        .map_err(|e| HEMTTError::Generic(format!("Unable to write {}: {}", target.display(), e)))
+}
+
+/// ImageToPAA from the Arma 3 Tools, when it is installed
+fn image_to_paa() -> Option<PathBuf> {
+    if !cfg!(windows) {
+        return None;
+    }
+    let tool = hemtt::tools::find_bi_tool("ImageToPAA").ok()?;
+    let tool = tool.with_file_name("ImageToPAA.exe");
+    Some(tool).filter(|tool| tool.exists())
+}
+
+/// Converts an image to PAA with ImageToPAA
+fn to_paa(image_to_paa: &Path, source: &Path, target: &Path) -> Result<(), HEMTTError> {
+    create_dir!(target.parent().unwrap())?;
+    let status = std::process::Command::new(image_to_paa)
+        .arg(source)
+        .arg(target)
+        .status()?;
+    if !status.success() || !target.exists() {
+        return Err(HEMTTError::Generic(format!(
+            "ImageToPAA was unable to convert {}",
+            source.display()
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,310 @@
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+
+use clap::ArgMatches;
+use hemtt::{HEMTTError, Mirror, MirrorProtocol, Project};
+use serde_json::{Map, Value as Json};
+use sha1::{Digest, Sha1};
+
+use crate::tasks::ReleaseOptions;
+
+/// Checksums of the released files, uploaded last so the mirror's users can
+/// verify their download
+const CHECKSUMS: &str = "SHA1SUMS";
+
+/// Uploads the release folder to a mirror
+///
+/// rsync only sends what changed, keeps partial files to resume from and checks
+/// every file afterwards, files that are not in the release are only deleted from
+/// the mirror when `delete` is set. Uploads with curl are resumed from the files
+/// recorded in `.hemttout/mirrors/{name}.json`, and each file is downloaded again
+/// after it is uploaded to check its SHA-1
+///
+/// Passwords are passed in the environment to rsync and on stdin to curl, never as arguments
+pub fn run(p: &Project, a: &ArgMatches) -> Result<(), HEMTTError> {
+    let name = a.value_of("name").unwrap();
+    let mirror = p.mirrors.get(name).ok_or_else(|| {
+        HEMTTError::UserHint(
+            format!("Unknown mirror `{}`", name),
+            if p.mirrors.is_empty() {
+                String::from("add the mirror to `mirrors` in hemtt.toml")
+            } else {
+                format!(
+                    "available mirrors: {}",
+                    p.mirrors.keys().cloned().collect::<Vec<_>>().join(", ")
+                )
+            },
+        )
+    })?;
+    let protocol = mirror
+        .protocol()
+        .map_err(|e| HEMTTError::User(format!("Mirror `{}` has an invalid url, {}", name, e)))?;
+    let release = crate::tasks::release_root(p, &ReleaseOptions::default())?;
+    if !release.is_dir() {
+        return Err(HEMTTError::UserHint(
+            format!("Folder {:?} does not exist", release),
+            String::from("build the release with `hemtt build --release` first"),
+        ));
+    }
+    crate::http::online("upload to a mirror")?;
+
+    let files = files(&release)?;
+    let output = Project::output_dir()?.join("mirrors");
+    let checksums = output.join(CHECKSUMS);
+    if crate::dry_run(
+        &format!("upload {:?} to {} from", release, mirror.url),
+        &output,
+    ) {
+        return Ok(());
+    }
+    create_dir!(&output)?;
+    let mut sums = String::new();
+    for path in &files {
+        sums.push_str(&format!(
+            "{}  {}\n",
+            crate::tasks::file_sha1(&release.join(path))?,
+            path
+        ));
+    }
+    create_file!(&checksums)?.write_all(sums.as_bytes())?;
+
+    info!("Uploading {:?} to mirror `{}`", release, name);
+    let password = std::env::var(Mirror::password_var(name))
+        .ok()
+        .filter(|password| !password.is_empty());
+    match protocol {
+        MirrorProtocol::Rsync => rsync(mirror, password, &release, &checksums)?,
+        MirrorProtocol::Ftp | MirrorProtocol::Sftp => {
+            let state = output.join(format!("{}.json", name));
+            let curl = Curl { mirror, password };
+            curl.upload_all(&release, &files, &state)?;
+            curl.upload(&checksums, CHECKSUMS, false)?;
+            curl.verify(&crate::tasks::file_sha1(&checksums)?, CHECKSUMS)?;
+        }
+    }
+    info!("Uploaded {} files to mirror `{}`", files.len(), name);
+    Ok(())
+}
+
+/// Files of the release, relative to it and separated by `/`
+fn files(release: &Path) -> Result<Vec<String>, HEMTTError> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(release).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        if entry.file_type().is_file() {
+            files.push(
+                entry
+                    .path()
+                    .strip_prefix(release)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+            );
+        }
+    }
+    Ok(files)
+}
+
+fn rsync(
+    mirror: &Mirror,
+    password: Option<String>,
+    release: &Path,
+    checksums: &Path,
+) -> Result<(), HEMTTError> {
+    let rsync = |extra: &[&str]| -> Result<std::process::Output, HEMTTError> {
+        let mut command = Command::new("rsync");
+        command
+            .args(&["--recursive", "--times", "--compress", "--checksum"])
+            .arg("--partial");
+        if mirror.delete {
+            command.arg("--delete");
+        }
+        command
+            .args(extra)
+            .arg(format!("{}/", release.display()))
+            .arg(checksums)
+            .arg(format!("{}/", mirror.url.trim_end_matches('/')));
+        if let Some(password) = &password {
+            command.env("RSYNC_PASSWORD", password);
+        }
+        command.output().map_err(|e| {
+            HEMTTError::UserHint(
+                format!("Unable to run rsync: {}", e),
+                String::from("install rsync and make sure it is in your PATH"),
+            )
+        })
+    };
+    let upload = rsync(&[])?;
+    if !upload.status.success() {
+        return Err(HEMTTError::Network(format!(
+            "rsync was unable to upload the release: {}",
+            String::from_utf8_lossy(&upload.stderr).trim()
+        )));
+    }
+    // A second run that would change nothing means every file arrived intact
+    let verify = rsync(&["--dry-run", "--itemize-changes"])?;
+    let changes = String::from_utf8_lossy(&verify.stdout);
+    if !verify.status.success() || !changes.trim().is_empty() {
+        return Err(HEMTTError::Network(format!(
+            "The mirror does not match the release after uploading:\n{}",
+            changes.trim()
+        )));
+    }
+    Ok(())
+}
+
+struct Curl<'a> {
+    mirror: &'a Mirror,
+    password: Option<String>,
+}
+impl<'a> Curl<'a> {
+    /// Uploads the files that were not uploaded yet, resuming a file that was
+    /// interrupted when it has not changed since
+    fn upload_all(&self, release: &Path, files: &[String], state: &Path) -> Result<(), HEMTTError> {
+        let mut uploaded: Map<String, Json> = if state.exists() {
+            serde_json::from_reader(open_file!(state)?).unwrap_or_default()
+        } else {
+            Map::new()
+        };
+        for path in files {
+            let source = release.join(path);
+            let sha1 = crate::tasks::file_sha1(&source)?;
+            let hash = Json::String(sha1.clone());
+            let previous = uploaded.get(path).cloned().unwrap_or(Json::Null);
+            if previous["sha1"] == hash && previous["complete"] == Json::Bool(true) {
+                debug!("{} is already uploaded", path);
+                continue;
+            }
+            let resume = previous["sha1"] == hash;
+            uploaded.insert(
+                path.clone(),
+                serde_json::json!({ "sha1": hash, "complete": false }),
+            );
+            save(state, &uploaded)?;
+            info!("Uploading {}", path);
+            self.upload(&source, path, resume)?;
+            self.verify(&sha1, path)?;
+            uploaded.insert(
+                path.clone(),
+                serde_json::json!({ "sha1": hash, "complete": true }),
+            );
+            save(state, &uploaded)?;
+        }
+        Ok(())
+    }
+
+    fn upload(&self, source: &Path, path: &str, resume: bool) -> Result<(), HEMTTError> {
+        let mut command = self.command();
+        command
+            .arg("--ftp-create-dirs")
+            .arg("--upload-file")
+            .arg(source);
+        if resume {
+            command.args(&["--continue-at", "-"]);
+        }
+        command.arg(self.mirror.file_url(path));
+        let output = self.spawn(command)?.wait_with_output()?;
+        if !output.status.success() {
+            return Err(HEMTTError::Network(format!(
+                "Unable to upload {}: {}",
+                path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Downloads the file from the server and checks that it has the SHA-1 of the uploaded file
+    fn verify(&self, expected: &str, path: &str) -> Result<(), HEMTTError> {
+        let mut command = self.command();
+        command.arg(self.mirror.file_url(path));
+        let mut child = self.spawn(command)?;
+        let mut stdout = child.stdout.take().unwrap();
+        let mut hasher = Sha1::new();
+        let mut buffer = [0; 64 * 1024];
+        loop {
+            let read = stdout.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(HEMTTError::Network(format!(
+                "Unable to download {} from the mirror to verify it: {}",
+                path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        let actual = format!("{:x}", hasher.finalize());
+        if actual != expected {
+            return Err(HEMTTError::Network(format!(
+                "{} on the mirror does not match the release, upload it again",
+                path
+            )));
+        }
+        Ok(())
+    }
+
+    /// curl reads its options from stdin, so the password is never an argument
+    fn command(&self) -> Command {
+        let mut command = Command::new("curl");
+        command.args(&["--silent", "--show-error", "--fail", "--config", "-"]);
+        command
+    }
+
+    /// Starts curl and writes the login to its stdin
+    fn spawn(&self, mut command: Command) -> Result<Child, HEMTTError> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(curl_error)?;
+        let mut stdin = child.stdin.take().unwrap();
+        let written = match &self.mirror.user {
+            Some(user) => stdin.write_all(
+                format!(
+                    "user = \"{}\"\n",
+                    config_escape(&format!(
+                        "{}:{}",
+                        user,
+                        self.password.as_deref().unwrap_or_default()
+                    ))
+                )
+                .as_bytes(),
+            ),
+            None => Ok(()),
+        };
+        drop(stdin);
+        if let Err(e) = written {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(e.into());
+        }
+        Ok(child)
+    }
+}
+
+/// Escapes a value for a quoted string in a curl config
+fn config_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn save(state: &Path, uploaded: &Map<String, Json>) -> Result<(), HEMTTError> {
+    create_file!(state)?.write_all(
+        serde_json::to_string_pretty(uploaded)
+            .map_err(|e| HEMTTError::Generic(e.to_string()))?
+            .as_bytes(),
+    )?;
+    Ok(())
+}
+
+fn curl_error(e: std::io::Error) -> HEMTTError {
+    HEMTTError::UserHint(
+        format!("Unable to run curl: {}", e),
+        String::from("install curl and make sure it is in your PATH"),
+    )
+}
@@ -0,0 +1,71 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::{HEMTTError, Project};
+
+use crate::Command;
+
+mod mirror;
+mod workshop;
+
+pub struct Publish {}
+impl Command for Publish {
+    fn register(&self) -> App {
+        SubCommand::with_name("publish")
+            .version(*crate::VERSION)
+            .about("Publish the release")
+            .subcommand(
+                SubCommand::with_name("mirror")
+                    .about("Upload the release to one of the project's `mirrors`")
+                    .arg(
+                        Arg::with_name("name")
+                            .help("Name of the mirror")
+                            .required(true),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("workshop")
+                    .about("Upload the release to the project's `workshop` item with steamcmd")
+                    .arg(
+                        Arg::with_name("changelog")
+                            .long("changelog")
+                            .help("Change note shown on the workshop")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("kit")
+                            .long("kit")
+                            .help("Upload the release of a kit to the kit's `workshop` item")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("platform")
+                            .long("platform")
+                            .help("Upload the release of a platform, when platforms are released separately")
+                            .takes_value(true)
+                            .possible_values(&["windows", "linux"]),
+                    ),
+            )
+    }
+
+    fn run(&self, a: &ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        let start = std::time::Instant::now();
+        let (action, result) =
+            match a.subcommand() {
+                ("mirror", Some(b)) => (
+                    format!("Publish to mirror `{}`", b.value_of("name").unwrap()),
+                    mirror::run(&p, b),
+                ),
+                ("workshop", Some(b)) => (
+                    String::from("Publish to the workshop"),
+                    workshop::run(&p, b),
+                ),
+                _ => return Err(HEMTTError::User(String::from(
+                    "No command was provided, use `publish help` to see all commands and options",
+                ))),
+            };
+        let changelog = a
+            .subcommand_matches("workshop")
+            .and_then(|b| b.value_of("changelog"));
+        crate::notify::notify(&p, "publish", &action, start, &result, changelog);
+        result
+    }
+}
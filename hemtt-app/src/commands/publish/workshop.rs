@@ -0,0 +1,129 @@
+use clap::ArgMatches;
+use hemtt::{HEMTTError, Platform, Project};
+
+use crate::tasks::ReleaseOptions;
+
+/// Uploads the release folder to the workshop item, after checking it against
+/// the workshop constraints
+///
+/// The release of a kit is uploaded to the kit's own `workshop` item, and the
+/// platform is chosen when platforms are released separately
+pub fn run(p: &Project, a: &ArgMatches) -> Result<(), HEMTTError> {
+    let kit = match a.value_of("kit") {
+        Some(name) => Some((
+            name,
+            p.kits.get(name).ok_or_else(|| {
+                HEMTTError::User(format!(
+                    "Unknown kit `{}`, available kits: {}",
+                    name,
+                    p.kits.keys().cloned().collect::<Vec<_>>().join(", ")
+                ))
+            })?,
+        )),
+        None => None,
+    };
+    let id = crate::tasks::workshop_id(p, kit.map(|(_, kit)| kit)).ok_or_else(|| {
+        HEMTTError::UserHint(
+            match kit {
+                Some((name, _)) => format!("Kit `{}` has no `workshop` ID", name),
+                None => String::from("The project has no `workshop` ID"),
+            },
+            String::from(
+                "create the item with the Arma 3 Publisher once and set its ID as `workshop`",
+            ),
+        )
+    })?;
+    let platform = a
+        .value_of("platform")
+        .and_then(|name| Platform::ALL.iter().copied().find(|p| p.as_str() == name));
+    if p.platforms.separate && platform.is_none() {
+        return Err(HEMTTError::UserHint(
+            String::from("The project releases each platform separately"),
+            String::from("choose the release with `--platform windows` or `--platform linux`"),
+        ));
+    }
+    if !p.platforms.separate && platform.is_some() {
+        return Err(HEMTTError::User(String::from(
+            "The project does not release platforms separately, remove `--platform`",
+        )));
+    }
+    let appid = p.game.appid().ok_or_else(|| {
+        HEMTTError::User(format!(
+            "{} mods can not be published with steamcmd",
+            p.game
+        ))
+    })?;
+    let opts = ReleaseOptions {
+        main: kit.is_none(),
+        kits: kit
+            .map(|(name, kit)| vec![(name.to_string(), kit.clone())])
+            .unwrap_or_default(),
+        ..ReleaseOptions::default()
+    };
+    let release = crate::tasks::roots(p, &opts)?
+        .into_iter()
+        .find(|root| root.platform == platform && root.kit.as_ref() == kit.map(|(_, kit)| kit))
+        .map(|root| root.path)
+        .unwrap();
+    if !release.is_dir() {
+        return Err(HEMTTError::UserHint(
+            format!("Folder {:?} does not exist", release),
+            match kit {
+                Some((name, _)) => format!(
+                    "build the release with `hemtt build --release --kit {}` first",
+                    name
+                ),
+                None => String::from("build the release with `hemtt build --release` first"),
+            },
+        ));
+    }
+    let problems = crate::tasks::workshop_problems(p, &[(release.clone(), id)])?;
+    if !problems.is_empty() {
+        for problem in &problems {
+            error!("{}", problem);
+        }
+        return Err(HEMTTError::User(format!(
+            "The release does not meet {} workshop constraints",
+            problems.len()
+        )));
+    }
+
+    let vdf = Project::output_dir()?.join("workshop.vdf");
+    let content = hemtt::long_path(std::fs::canonicalize(&release)?);
+    let source = format!(
+        "\"workshopitem\"\n{{\n\t\"appid\" \"{}\"\n\t\"publishedfileid\" \"{}\"\n\t\"contentfolder\" \"{}\"\n\t\"changenote\" \"{}\"\n}}\n",
+        appid,
+        id,
+        escape(&content.display().to_string()),
+        escape(a.value_of("changelog").unwrap_or_default()),
+    );
+    let commands = vec![
+        crate::steam::login(true)?,
+        vec![
+            String::from("workshop_build_item"),
+            vdf.display().to_string(),
+        ],
+    ];
+    if crate::dry_run(
+        &format!("publish {:?} to workshop item {} from", release, id),
+        &vdf,
+    ) {
+        return Ok(());
+    }
+    create_dir!(vdf.parent().unwrap())?;
+    std::fs::write(&vdf, source)?;
+    info!("Publishing {:?} to workshop item {}", release, id);
+    if !crate::steam::steamcmd(&commands)? {
+        return Err(HEMTTError::Network(format!(
+            "steamcmd was unable to publish workshop item {}, log in again with `hemtt auth steam login` if the session expired",
+            id
+        )));
+    }
+    info!("Published workshop item {}", id);
+    Ok(())
+}
+
+/// Escapes a value of a VDF file
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use hemtt::{HEMTTError, Project};
+use serde_json::json;
+
+use crate::{context::Context, tasks::PDrive};
+
+/// Describes how the build resolves includes and which macros it defines, for
+/// editors and their plugins to resolve them the same way
+///
+/// Ex: `hemtt run includes --output .hemtt/includes.json`
+pub fn run(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let p = Project::read()?;
+    let root = Project::find_root()?;
+    let ctx = Context::new(&p)?;
+    let mut addons = Vec::new();
+    let mut prefixes = HashMap::new();
+    for addon in hemtt::get_all_addons()? {
+        let prefix = crate::context::prefix(&ctx, &addon)?;
+        prefixes
+            .entry(prefix.to_lowercase())
+            .or_insert_with(|| addon.source().to_string());
+        addons.push(json!({
+            "name": addon.name(),
+            "location": addon.location().to_string(),
+            "folder": absolute(&root, Path::new(addon.source())),
+            "prefix": prefix,
+        }));
+    }
+    let mounts: Vec<_> = PDrive::new(&p, &prefixes)
+        .mounts()
+        .iter()
+        .map(|(prefix, folder)| {
+            json!({
+                "prefix": prefix,
+                "folder": absolute(&root, folder),
+            })
+        })
+        .collect();
+    let defines: Vec<_> = crate::tasks::predefined(&p)?
+        .into_iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect();
+    let includes: Vec<_> = crate::tasks::includes(&p)
+        .iter()
+        .map(|folder| absolute(&root, Path::new(folder)))
+        .collect();
+    let description = json!({
+        "version": 1,
+        "root": root.to_string_lossy().replace('\\', "/"),
+        "mainprefix": p.mainprefix(),
+        "prefix": p.prefix(),
+        "preprocessed": crate::tasks::PREPROCESSED,
+        "addons": addons,
+        // In the order absolute includes are searched, the longest matching prefix
+        // first and the folders without a prefix last
+        "mounts": mounts,
+        "includes": includes,
+        "defines": defines,
+    });
+    let text = serde_json::to_string_pretty(&description)
+        .map_err(|e| HEMTTError::Generic(e.to_string()))?;
+    match a.value_of("output") {
+        Some(output) => {
+            let output = PathBuf::from(output);
+            if crate::dry_run("create", &output) {
+                return Ok(());
+            }
+            if let Some(parent) = output
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+            {
+                create_dir!(parent)?;
+            }
+            create_file!(output)?.write_all(text.as_bytes())?;
+            info!("Include map written to {:?}", output);
+        }
+        None => println!("{}", text),
+    }
+    Ok(())
+}
+
+/// Ex: "addons/main" => "/home/user/mod/addons/main"
+fn absolute(root: &Path, path: &Path) -> String {
+    root.join(path).to_string_lossy().replace('\\', "/")
+}
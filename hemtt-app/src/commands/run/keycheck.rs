@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+
+use hemtt::HEMTTError;
+use hemtt_signing::{BIPublicKey, BISign};
+
+use crate::tasks::signing_error;
+
+/// Checks every PBO in a folder against a server's public keys
+pub fn run(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let folder = PathBuf::from(a.value_of("folder").unwrap());
+    let keys_folder = a
+        .value_of("keys")
+        .map_or_else(|| folder.join("keys"), PathBuf::from);
+    check(&folder, &keys_folder)
+}
+
+/// Checks every PBO in a folder against the public keys in `keys_folder`, an
+/// error is returned if any of them would be rejected
+pub fn check(folder: &Path, keys_folder: &Path) -> Result<(), HEMTTError> {
+    let keys = read_keys(keys_folder)?;
+    if keys.is_empty() {
+        warn!("No public keys were found in {:?}", keys_folder);
+    }
+
+    let mut rejected = 0;
+    let mut checked = 0;
+    for entry in walkdir::WalkDir::new(folder).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        let path = entry.path();
+        if path.starts_with(keys_folder)
+            || !entry.file_type().is_file()
+            || path.extension().map_or(true, |e| e != "pbo")
+        {
+            continue;
+        }
+        checked += 1;
+        if !check_pbo(path, &keys)? {
+            rejected += 1;
+        }
+    }
+
+    info!("Checked {} PBOs against {} keys", checked, keys.len());
+    if rejected == 0 {
+        Ok(())
+    } else {
+        Err(HEMTTError::User(format!(
+            "{} of {} PBOs would be rejected",
+            rejected, checked
+        )))
+    }
+}
+
+fn read_keys(folder: &Path) -> Result<Vec<BIPublicKey>, HEMTTError> {
+    let mut keys = Vec::new();
+    if !folder.is_dir() {
+        return Ok(keys);
+    }
+    for file in std::fs::read_dir(folder)? {
+        let file = file?.path();
+        if file.extension().map_or(false, |e| e == "bikey") {
+            match BIPublicKey::read(&mut open_file!(file)?) {
+                Ok(key) => keys.push(key),
+                Err(e) => warn!("Unable to read key {:?}: {}", file, e),
+            }
+        }
+    }
+    Ok(keys)
+}
+
+/// Reports the problems with a PBO's signatures, returns false if the server would reject it
+fn check_pbo(pbo: &Path, keys: &[BIPublicKey]) -> Result<bool, HEMTTError> {
+    let signatures = hemtt::Prebuilt::signatures(pbo)?;
+    if signatures.is_empty() {
+        warn!("{:?} is not signed", pbo);
+        return Ok(false);
+    }
+    let mut accepted = false;
+    for path in signatures {
+        let sig = match BISign::read(&mut open_file!(path)?) {
+            Ok(sig) => sig,
+            Err(e) => {
+                warn!("Unable to read signature {:?}: {}", path, e);
+                continue;
+            }
+        };
+        let key = match keys.iter().find(|key| key.matches(&sig)) {
+            Some(key) => key,
+            None => {
+                warn!(
+                    "{:?} has no matching key for authority `{}`",
+                    path,
+                    sig.name()
+                );
+                continue;
+            }
+        };
+        let mut readable = hemtt_pbo::ReadablePbo::from(open_file!(pbo)?)?;
+        match key.verify(&mut readable, &sig).map_err(signing_error) {
+            Ok(()) => accepted = true,
+            Err(e) => warn!("{:?} is invalid: {}", path, e),
+        }
+    }
+    if !accepted {
+        error!("{:?} would be rejected", pbo);
+    }
+    Ok(accepted)
+}
@@ -0,0 +1,141 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use hemtt::{Dependency, Diagnostic, HEMTTError, Project, Severity};
+
+/// Lines the server writes once it is waiting at mission select
+const READY: [&str; 2] = ["Host identity created.", "Game Port:"];
+
+/// Starts a dedicated server with the built mod and its dependencies, waits for
+/// it to reach mission select and reports the problems in its RPT
+pub fn run(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let p = Project::read()?;
+    let root = Project::find_root()?;
+    let timeout = Duration::from_secs(
+        a.value_of("timeout")
+            .unwrap()
+            .parse()
+            .map_err(|_| HEMTTError::User(String::from("timeout must be a number of seconds")))?,
+    );
+    let server = match a.value_of("server") {
+        Some(server) => PathBuf::from(server),
+        None => hemtt::tools::find_arma3()?.join(if cfg!(windows) {
+            "arma3server_x64.exe"
+        } else {
+            "arma3server_x64"
+        }),
+    };
+    if !server.is_file() {
+        return Err(HEMTTError::UserHint(
+            format!("The server {:?} does not exist", server),
+            String::from("provide the server executable with `--server`"),
+        ));
+    }
+
+    let mut mods = vec![Project::output_dir()?.display().to_string()];
+    for name in p.dependencies.keys() {
+        let folder = root.join(Dependency::folder(name));
+        if !folder.exists() {
+            return Err(HEMTTError::UserHint(
+                format!("Dependency `{}` has not been fetched", name),
+                String::from("run `hemtt deps fetch` first"),
+            ));
+        }
+        mods.push(folder.display().to_string());
+    }
+    let profiles = std::env::temp_dir().join("hemtt-loadtest");
+    if profiles.exists() {
+        std::fs::remove_dir_all(&profiles)?;
+    }
+    create_dir!(profiles)?;
+
+    info!("Starting the server with {} mods", mods.len());
+    let mut child = std::process::Command::new(&server)
+        .current_dir(server.parent().unwrap())
+        .arg(format!("-profiles={}", profiles.display()))
+        .arg("-name=hemtt")
+        .arg(format!("-mod={}", mods.join(";")))
+        .stdout(std::process::Stdio::null())
+        .spawn()?;
+    let start = Instant::now();
+    let rpt = loop {
+        std::thread::sleep(Duration::from_secs(1));
+        let rpt = read_rpt(&profiles)?;
+        if READY.iter().any(|line| rpt.contains(line)) {
+            break rpt;
+        }
+        if child.try_wait()?.is_some() {
+            return Err(HEMTTError::Generic(format!(
+                "The server stopped before reaching mission select\n{}",
+                summary(&rpt)
+            )));
+        }
+        if start.elapsed() > timeout {
+            child.kill()?;
+            return Err(HEMTTError::Generic(format!(
+                "Timed out waiting for the server to reach mission select\n{}",
+                summary(&rpt)
+            )));
+        }
+    };
+    child.kill()?;
+    child.wait()?;
+    info!(
+        "The server reached mission select in {}s",
+        start.elapsed().as_secs()
+    );
+
+    let mut failed = 0;
+    let mut diagnostics = Vec::new();
+    for (problem, _) in super::rpt::problems(&rpt) {
+        let severity = if problem.is_error() {
+            error!("{}", problem);
+            failed += 1;
+            Severity::Error
+        } else {
+            warn!("{}", problem);
+            Severity::Warning
+        };
+        diagnostics.push(Diagnostic::new(
+            problem.rule(),
+            severity,
+            problem.to_string(),
+            None,
+        ));
+    }
+    crate::report::write(&diagnostics, &[])?;
+    if failed == 0 {
+        Ok(())
+    } else {
+        Err(HEMTTError::User(format!(
+            "{} problems were found while loading the mod",
+            failed
+        )))
+    }
+}
+
+/// Contents of the newest RPT in the profiles folder
+fn read_rpt(profiles: &Path) -> Result<String, HEMTTError> {
+    let mut newest: Option<(std::time::SystemTime, PathBuf)> = None;
+    for entry in std::fs::read_dir(profiles)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().map_or(true, |ext| ext != "rpt") {
+            continue;
+        }
+        let modified = entry.metadata()?.modified()?;
+        if newest.as_ref().map_or(true, |(time, _)| modified > *time) {
+            newest = Some((modified, path));
+        }
+    }
+    match newest {
+        Some((_, path)) => Ok(super::rpt::decode(&std::fs::read(path)?)),
+        None => Ok(String::new()),
+    }
+}
+
+/// The last lines of an RPT
+fn summary(rpt: &str) -> String {
+    let lines: Vec<&str> = rpt.lines().collect();
+    lines[lines.len().saturating_sub(20)..].join("\n")
+}
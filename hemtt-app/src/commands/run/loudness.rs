@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use hemtt::{Diagnostic, HEMTTError, Severity, Span};
+
+/// Samples at or above this level are at full scale
+const FULL_SCALE: f32 = 0.999;
+/// Consecutive samples of a channel at full scale that are clipping, a single
+/// one is the peak of a normalized sound
+const CLIPPED_RUN: usize = 3;
+
+/// Levels of a sound, between 0 and 1
+#[derive(Default)]
+struct Loudness {
+    /// Root mean square of every sample
+    rms: f32,
+    peak: f32,
+    /// Times a channel stayed at full scale for `CLIPPED_RUN` samples or more
+    clips: usize,
+}
+
+impl Loudness {
+    /// Ex: 0.5 is -6.0 dBFS
+    fn db(level: f32) -> f32 {
+        20.0 * level.log10()
+    }
+}
+
+/// Measures every sound in a folder, reporting the ones that clip and the ones
+/// far louder or quieter than the other sounds of their addon
+pub fn run(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let folder = PathBuf::from(a.value_of("folder").unwrap());
+    let difference: f32 =
+        a.value_of("difference").unwrap().parse().map_err(|_| {
+            HEMTTError::User(String::from("`--difference` needs a number of decibels"))
+        })?;
+
+    let mut addons: BTreeMap<String, Vec<(PathBuf, Loudness)>> = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(&folder).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        let path = entry.path();
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if !entry.file_type().is_file() || !["ogg", "wav", "wss"].contains(&extension.as_str()) {
+            continue;
+        }
+        let loudness = match measure(path, &extension) {
+            Ok(Some(loudness)) => loudness,
+            Ok(None) => {
+                debug!("{} is not PCM or Vorbis, skipping", path.display());
+                continue;
+            }
+            Err(e) => {
+                warn!("{} could not be read: {}", path.display(), e);
+                continue;
+            }
+        };
+        addons
+            .entry(addon(&folder, path))
+            .or_default()
+            .push((path.to_path_buf(), loudness));
+    }
+
+    let mut diagnostics = Vec::new();
+    for (addon, sounds) in &addons {
+        // Silent sounds have no level to compare
+        let audible = sounds.iter().filter(|(_, l)| l.rms > 0.0).count();
+        let median = median(sounds.iter().map(|(_, l)| l.rms).filter(|rms| *rms > 0.0));
+        println!("{}", addon);
+        for (path, loudness) in sounds {
+            let rms = Loudness::db(loudness.rms);
+            let mut notes = Vec::new();
+            if loudness.clips > 0 {
+                notes.push(format!("clips {} times", loudness.clips));
+                diagnostics.push(problem(
+                    "audio-clipping",
+                    path,
+                    format!("{} clips {} times", path.display(), loudness.clips),
+                ));
+            }
+            if let Some(median) = median.filter(|_| audible > 2 && loudness.rms > 0.0) {
+                let off = rms - Loudness::db(median);
+                if off.abs() > difference {
+                    notes.push(format!(
+                        "{:.1} dB {} than the addon",
+                        off.abs(),
+                        if off > 0.0 { "louder" } else { "quieter" }
+                    ));
+                    diagnostics.push(problem(
+                        "audio-loudness",
+                        path,
+                        format!(
+                            "{} is {:.1} dB {} than the median of the sounds in {}",
+                            path.display(),
+                            off.abs(),
+                            if off > 0.0 { "louder" } else { "quieter" },
+                            addon
+                        ),
+                    ));
+                }
+            }
+            println!(
+                "  {:<50} rms {:>6.1} dBFS  peak {:>6.1} dBFS  {}",
+                path.strip_prefix(&folder).unwrap_or(path).display(),
+                rms,
+                Loudness::db(loudness.peak),
+                notes.join(", ")
+            );
+        }
+        println!();
+    }
+    info!(
+        "{} sounds in {} addons, {} problems",
+        addons.values().map(Vec::len).sum::<usize>(),
+        addons.len(),
+        diagnostics.len()
+    );
+    crate::report::write(&diagnostics, &addons.keys().cloned().collect::<Vec<_>>())?;
+    Ok(())
+}
+
+/// Folder of the addon a sound is in, the first folder inside the searched folder
+/// Ex: "addons/main"
+fn addon(folder: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(folder).unwrap_or(path);
+    let mut addon = folder.to_path_buf();
+    if relative.components().count() > 1 {
+        if let Some(first) = relative.components().next() {
+            addon.push(first);
+        }
+    }
+    addon.to_string_lossy().replace('\\', "/")
+}
+
+fn median(levels: impl Iterator<Item = f32>) -> Option<f32> {
+    // A corrupt float WAV can measure as NaN or infinite
+    let mut levels: Vec<f32> = levels.filter(|level| level.is_finite()).collect();
+    if levels.is_empty() {
+        return None;
+    }
+    levels.sort_by(f32::total_cmp);
+    Some(levels[levels.len() / 2])
+}
+
+fn problem(rule: &str, path: &Path, message: String) -> Diagnostic {
+    Diagnostic::new(
+        rule,
+        Severity::Warning,
+        message,
+        Some(Span::new(path.to_string_lossy(), 0, 0, 0)),
+    )
+}
+
+/// `None` for formats that are not measured, ex: compressed WSS
+fn measure(path: &Path, extension: &str) -> Result<Option<Loudness>, HEMTTError> {
+    let mut meter = Meter::default();
+    match extension {
+        "ogg" => {
+            let mut ogg = lewton::inside_ogg::OggStreamReader::new(open_file!(path)?)
+                .map_err(|e| HEMTTError::Generic(e.to_string()))?;
+            meter.channels = usize::from(ogg.ident_hdr.audio_channels);
+            while let Some(packet) = ogg
+                .read_dec_packet_itl()
+                .map_err(|e| HEMTTError::Generic(e.to_string()))?
+            {
+                for sample in packet {
+                    meter.add(f32::from(sample) / 32768.0);
+                }
+            }
+        }
+        "wav" => {
+            let mut wav = hound::WavReader::new(std::io::BufReader::new(open_file!(path)?))
+                .map_err(|e| HEMTTError::Generic(e.to_string()))?;
+            let spec = wav.spec();
+            meter.channels = usize::from(spec.channels);
+            match spec.sample_format {
+                hound::SampleFormat::Float => {
+                    for sample in wav.samples::<f32>() {
+                        meter.add(sample.map_err(|e| HEMTTError::Generic(e.to_string()))?);
+                    }
+                }
+                hound::SampleFormat::Int => {
+                    if !(1..=32).contains(&spec.bits_per_sample) {
+                        return Err(HEMTTError::Generic(format!(
+                            "unsupported bit depth {}",
+                            spec.bits_per_sample
+                        )));
+                    }
+                    let scale = (1_i64 << (spec.bits_per_sample - 1)) as f32;
+                    for sample in wav.samples::<i32>() {
+                        meter.add(
+                            sample.map_err(|e| HEMTTError::Generic(e.to_string()))? as f32 / scale,
+                        );
+                    }
+                }
+            }
+        }
+        _ => {
+            // "WSS0", compression, then the format as in a WAV
+            let mut data = Vec::new();
+            open_file!(path)?.read_to_end(&mut data)?;
+            if data.len() < 26 || &data[..4] != b"WSS0" {
+                return Err(HEMTTError::Generic(String::from("not a WSS file")));
+            }
+            let compression = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+            let bits = u16::from_le_bytes([data[22], data[23]]);
+            if compression != 0 || bits != 16 {
+                return Ok(None);
+            }
+            meter.channels = usize::from(u16::from_le_bytes([data[10], data[11]]));
+            for sample in data[26..].chunks_exact(2) {
+                meter.add(f32::from(i16::from_le_bytes([sample[0], sample[1]])) / 32768.0);
+            }
+        }
+    }
+    Ok(Some(meter.finish()))
+}
+
+/// Measures interleaved samples
+#[derive(Default)]
+struct Meter {
+    channels: usize,
+    count: usize,
+    sum: f64,
+    peak: f32,
+    clips: usize,
+    /// Samples at full scale in a row, for each channel
+    runs: Vec<usize>,
+}
+
+impl Meter {
+    fn add(&mut self, sample: f32) {
+        if self.runs.len() < self.channels.max(1) {
+            self.runs.resize(self.channels.max(1), 0);
+        }
+        let level = sample.abs();
+        self.sum += f64::from(sample) * f64::from(sample);
+        self.peak = self.peak.max(level);
+        let run = &mut self.runs[self.count % self.runs.len()];
+        if level >= FULL_SCALE {
+            *run += 1;
+            if *run == CLIPPED_RUN {
+                self.clips += 1;
+            }
+        } else {
+            *run = 0;
+        }
+        self.count += 1;
+    }
+
+    fn finish(self) -> Loudness {
+        if self.count == 0 {
+            return Loudness::default();
+        }
+        Loudness {
+            rms: (self.sum / self.count as f64).sqrt() as f32,
+            peak: self.peak,
+            clips: self.clips,
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::Command;
+use hemtt::HEMTTError;
+
+mod includes;
+pub mod keycheck;
+mod loadtest;
+mod loudness;
+mod patch;
+mod pdrive;
+mod rpt;
+mod translation;
+
+pub struct Run;
+impl Command for Run {
+    fn register(&self) -> clap::App {
+        clap::SubCommand::with_name("run")
+            .version(*crate::VERSION)
+            .about("Run a utility")
+            .subcommand(
+                clap::SubCommand::with_name("includes")
+                    .about("Describe the include folders, prefixes and macros of the build as JSON for editors")
+                    .arg(
+                        clap::Arg::with_name("output")
+                            .long("output")
+                            .short("o")
+                            .help("File to write the description to, instead of printing it")
+                            .takes_value(true),
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("keycheck")
+                    .about("Report the PBOs a server would reject based on its keys")
+                    .arg(
+                        clap::Arg::with_name("folder")
+                            .help("Folder containing the mods to check")
+                            .required(true),
+                    )
+                    .arg(
+                        clap::Arg::with_name("keys")
+                            .long("keys")
+                            .help("Folder of the server's public keys, defaults to `{folder}/keys`")
+                            .takes_value(true),
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("loadtest")
+                    .about("Check that the built mod loads on a dedicated server without errors")
+                    .arg(
+                        clap::Arg::with_name("server")
+                            .long("server")
+                            .help("Path to the server executable, defaults to the one in the Arma 3 folder")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        clap::Arg::with_name("timeout")
+                            .long("timeout")
+                            .help("Seconds to wait for the server to reach mission select")
+                            .takes_value(true)
+                            .default_value("300"),
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("loudness")
+                    .about("Report sounds that clip or are far louder or quieter than the rest of their addon")
+                    .arg(
+                        clap::Arg::with_name("folder")
+                            .help("Folder containing the sounds, grouped by the folders in it")
+                            .default_value("addons"),
+                    )
+                    .arg(
+                        clap::Arg::with_name("difference")
+                            .long("difference")
+                            .help("Decibels a sound may differ from the median loudness of its addon")
+                            .takes_value(true)
+                            .default_value("12"),
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("patch")
+                    .about("Update a release folder with the patches created by `hemtt patch`")
+                    .arg(
+                        clap::Arg::with_name("patch")
+                            .help("Folder containing the patches and their manifest")
+                            .required(true),
+                    )
+                    .arg(
+                        clap::Arg::with_name("folder")
+                            .help("Release folder to update, such as `@mod`")
+                            .required(true),
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("pdrive")
+                    .about("Link the project's addons, mounts and dependencies into a P-drive layout")
+                    .arg(
+                        clap::Arg::with_name("folder")
+                            .help("Folder to create the links in, such as the root of P:")
+                            .required(true),
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("rpt")
+                    .about("Summarize the errors in an RPT by the project files they come from")
+                    .arg(
+                        clap::Arg::with_name("file")
+                            .help("RPT file to read")
+                            .required(true),
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("translation")
+                    .about("Work with the stringtables of a folder")
+                    .subcommand(
+                        clap::SubCommand::with_name("coverage")
+                            .about("Show how many keys are translated into every language, failing below a minimum")
+                            .arg(
+                                clap::Arg::with_name("folder")
+                                    .help("Folder containing the stringtables")
+                                    .default_value("addons"),
+                            )
+                            .arg(
+                                clap::Arg::with_name("min")
+                                    .long("min")
+                                    .help("Lowest percentage of keys translated into a language, ex: `german=90`, in addition to `translations.minimum` of the project")
+                                    .takes_value(true)
+                                    .multiple(true)
+                                    .number_of_values(1),
+                            ),
+                    )
+                    .subcommand(
+                        clap::SubCommand::with_name("normalize")
+                            .about("Sort keys and languages, normalize indentation and remove empty entries")
+                            .arg(
+                                clap::Arg::with_name("folder")
+                                    .help("Folder containing the stringtables")
+                                    .default_value("addons"),
+                            ),
+                    )
+                    .subcommand(
+                        clap::SubCommand::with_name("convert")
+                            .about("Convert between keys directly in packages and keys grouped in containers")
+                            .arg(
+                                clap::Arg::with_name("to")
+                                    .long("to")
+                                    .help("Layout to convert to")
+                                    .takes_value(true)
+                                    .possible_values(&["flat", "grouped"])
+                                    .required(true),
+                            )
+                            .arg(
+                                clap::Arg::with_name("folder")
+                                    .help("Folder containing the stringtables")
+                                    .default_value("addons"),
+                            ),
+                    ),
+            )
+    }
+
+    fn require_project(&self) -> bool {
+        false
+    }
+
+    fn run_no_project(&self, a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+        match a.subcommand() {
+            ("includes", Some(b)) => includes::run(b),
+            ("keycheck", Some(b)) => keycheck::run(b),
+            ("loadtest", Some(b)) => loadtest::run(b),
+            ("loudness", Some(b)) => loudness::run(b),
+            ("patch", Some(b)) => patch::run(b),
+            ("pdrive", Some(b)) => pdrive::run(b),
+            ("rpt", Some(b)) => rpt::run(b),
+            ("translation", Some(b)) => translation::run(b),
+            _ => Err(HEMTTError::User(String::from(
+                "No utility was provided, use `run help` to see all utilities and options",
+            ))),
+        }
+    }
+}
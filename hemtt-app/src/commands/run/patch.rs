@@ -0,0 +1,92 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use hemtt::delta::{Action, Manifest};
+use hemtt::HEMTTError;
+
+/// Updates a release folder with the patches created by `hemtt patch`
+///
+/// Every file is checked against the manifest before anything is changed, so a
+/// folder of the wrong version is left as it was
+pub fn run(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let patches = PathBuf::from(a.value_of("patch").unwrap());
+    let folder = PathBuf::from(a.value_of("folder").unwrap());
+    let manifest: Manifest =
+        serde_json::from_reader(open_file!(patches.join(hemtt::delta::MANIFEST))?)
+            .map_err(|e| HEMTTError::User(format!("Invalid patch manifest: {}", e)))?;
+
+    let mut paths = Vec::with_capacity(manifest.files.len());
+    for change in &manifest.files {
+        paths.push(change.relative().ok_or_else(|| {
+            HEMTTError::User(format!(
+                "Invalid patch manifest: `{}` is outside of the release",
+                change.path
+            ))
+        })?);
+    }
+
+    for (change, path) in manifest.files.iter().zip(&paths) {
+        let target = folder.join(path);
+        match change.action {
+            Action::Patch if !target.exists() => {
+                return Err(HEMTTError::User(format!(
+                    "{} is missing, the folder is not version {}",
+                    change.path, manifest.from
+                )));
+            }
+            Action::Patch | Action::Remove if target.exists() => {
+                if change.from_hash.as_ref() != Some(&crate::tasks::file_sha1(&target)?) {
+                    return Err(HEMTTError::User(format!(
+                        "{} has changed, the folder is not version {}",
+                        change.path, manifest.from
+                    )));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (change, path) in manifest.files.iter().zip(&paths) {
+        let target = folder.join(path);
+        if crate::dry_run(&format!("{:?}", change.action).to_lowercase(), &target) {
+            continue;
+        }
+        match change.action {
+            Action::Patch => {
+                debug!("Patching {}", change.path);
+                let patch = std::fs::read(hemtt::delta::patch_path(&patches.join(path)))?;
+                let data = hemtt::delta::apply(&std::fs::read(&target)?, &patch).map_err(|e| {
+                    HEMTTError::User(format!("Unable to patch {}: {}", change.path, e))
+                })?;
+                create_file!(&target)?.write_all(&data)?;
+            }
+            Action::Add => {
+                debug!("Adding {}", change.path);
+                create_dir!(target.parent().unwrap())?;
+                copy_file!(patches.join(path), &target)?;
+            }
+            Action::Remove => {
+                if target.exists() {
+                    debug!("Removing {}", change.path);
+                    remove_file!(&target)?;
+                }
+            }
+        }
+        if change.action != Action::Remove
+            && change.to_hash.as_ref() != Some(&crate::tasks::file_sha1(&target)?)
+        {
+            return Err(HEMTTError::User(format!(
+                "{} does not match version {} after patching",
+                change.path, manifest.to
+            )));
+        }
+    }
+    info!(
+        "Updated {:?} from {} to {}, {} files changed",
+        folder,
+        manifest.from,
+        manifest.to,
+        manifest.files.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use hemtt::{HEMTTError, Project};
+
+use crate::{context::Context, tasks::PDrive};
+
+/// Creates the virtual P-drive of the project as symbolic links in a folder, for
+/// tools that need the files on a real one
+pub fn run(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let p = Project::read()?;
+    let root = Project::find_root()?;
+    let target = PathBuf::from(a.value_of("folder").unwrap());
+    let ctx = Context::new(&p)?;
+    let mut prefixes = HashMap::new();
+    for addon in hemtt::get_all_addons()? {
+        prefixes
+            .entry(crate::context::prefix(&ctx, &addon)?.to_lowercase())
+            .or_insert_with(|| addon.source().to_string());
+    }
+    let mut linked = 0;
+    for (prefix, folder) in PDrive::new(&p, &prefixes).mounts() {
+        let folder = root.join(folder);
+        if !prefix.is_empty() {
+            if link(&folder, &target.join(prefix))? {
+                linked += 1;
+            }
+            continue;
+        }
+        // Include folders are already laid out by prefix
+        if !folder.exists() {
+            continue;
+        }
+        for entry in std::fs::read_dir(&folder)? {
+            let entry = entry?;
+            if link(&entry.path(), &target.join(entry.file_name()))? {
+                linked += 1;
+            }
+        }
+    }
+    info!("Linked {} folders into {:?}", linked, target);
+    Ok(())
+}
+
+/// Links a folder, unless something is already at the link
+fn link(source: &Path, link: &Path) -> Result<bool, HEMTTError> {
+    if link.symlink_metadata().is_ok() {
+        debug!("{:?} already exists, skipping", link);
+        return Ok(false);
+    }
+    if crate::dry_run(&format!("link {:?} =>", source), link) {
+        return Ok(false);
+    }
+    create_dir!(link.parent().unwrap())?;
+    symlink(source, link).map_err(|e| {
+        HEMTTError::UserHint(
+            format!("Unable to link {:?} => {:?}: {}", source, link, e),
+            String::from(if cfg!(windows) {
+                "enable Developer Mode or run as administrator to create symbolic links"
+            } else {
+                "check that you can write to the folder"
+            }),
+        )
+    })?;
+    Ok(true)
+}
+
+#[cfg(unix)]
+fn symlink(source: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, link)
+}
+
+#[cfg(windows)]
+fn symlink(source: &Path, link: &Path) -> std::io::Result<()> {
+    if source.is_dir() {
+        std::os::windows::fs::symlink_dir(source, link)
+    } else {
+        std::os::windows::fs::symlink_file(source, link)
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use hemtt::{HEMTTError, Project};
+
+use crate::{context::Context, tasks::find_no_case};
+
+/// A line of an RPT that shows a problem with a mod
+#[derive(Debug, PartialEq)]
+pub enum Problem {
+    MissingAddon(String),
+    Config(String),
+    Script(String),
+    /// Reported but not an error
+    BaseClass(String),
+}
+
+impl Problem {
+    pub fn is_error(&self) -> bool {
+        !matches!(self, Self::BaseClass(_))
+    }
+
+    /// Name of the kind of problem, for reports
+    pub fn rule(&self) -> &'static str {
+        match self {
+            Self::MissingAddon(_) => "missing-addon",
+            Self::Config(_) => "config",
+            Self::Script(_) => "script",
+            Self::BaseClass(_) => "base-class",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            Self::MissingAddon(line)
+            | Self::Config(line)
+            | Self::Script(line)
+            | Self::BaseClass(line) => line,
+        }
+    }
+
+    /// File and line the problem is in, as written by the game
+    /// Ex: `File z\ace\addons\common\functions\fnc_x.sqf..., line 12`
+    fn location(&self) -> Option<(&str, usize)> {
+        let message = self.message();
+        let start = message.find("File ")? + 5;
+        let end = start + message[start..].find(", line ")?;
+        let line = message[end + 7..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>()
+            .parse()
+            .ok()?;
+        Some((message[start..end].trim_end_matches("..."), line))
+    }
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MissingAddon(line) => write!(f, "missing addon: {}", line),
+            Self::Config(line) => write!(f, "config error: {}", line),
+            Self::Script(line) => write!(f, "script error: {}", line),
+            Self::BaseClass(line) => write!(f, "base class changed: {}", line),
+        }
+    }
+}
+
+/// Problems in an RPT and how often each occurred, in the order they first occurred
+pub fn problems(rpt: &str) -> Vec<(Problem, usize)> {
+    let mut problems: Vec<(Problem, usize)> = Vec::new();
+    let mut lines = rpt.lines().map(strip_time).peekable();
+    while let Some(line) = lines.next() {
+        let problem = if line.contains("requires addon") {
+            Problem::MissingAddon(line.to_string())
+        } else if line.starts_with("File ") && line.contains(", line ")
+            || line.starts_with("Config :")
+        {
+            Problem::Config(line.to_string())
+        } else if line.starts_with("Error in expression") {
+            // Followed by the position, the error and the file it is in
+            let mut message = line.to_string();
+            while let Some(next) = lines.peek().copied().map(str::trim) {
+                let file = next.starts_with("File ");
+                if !file && !next.starts_with("Error") {
+                    break;
+                }
+                if !next.starts_with("Error position") {
+                    message.push_str(" | ");
+                    message.push_str(next);
+                }
+                lines.next();
+                if file {
+                    break;
+                }
+            }
+            Problem::Script(message)
+        } else if line.starts_with("Updating base class") {
+            Problem::BaseClass(line.to_string())
+        } else {
+            continue;
+        };
+        match problems.iter_mut().find(|(p, _)| *p == problem) {
+            Some((_, count)) => *count += 1,
+            None => problems.push((problem, 1)),
+        }
+    }
+    problems
+}
+
+/// Removes the time RPT lines start with
+/// Ex: ` 1:23:45 Error in expression`
+fn strip_time(line: &str) -> &str {
+    let trimmed = line.trim_start();
+    match trimmed.split_once(' ') {
+        Some((time, rest))
+            if time.len() >= 7 && time.chars().all(|c| c.is_ascii_digit() || c == ':') =>
+        {
+            rest
+        }
+        _ => trimmed,
+    }
+}
+
+/// Text of an RPT, the game writes it as Windows-1252
+pub fn decode(data: &[u8]) -> String {
+    encoding_rs::WINDOWS_1252
+        .decode_without_bom_handling(data)
+        .0
+        .into_owned()
+}
+
+/// Prints the problems in an RPT grouped by the project files they are in
+pub fn run(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let path = PathBuf::from(a.value_of("file").unwrap());
+    let rpt = decode(&std::fs::read(&path)?);
+    let p = Project::read()?;
+    let sources = sources(&p)?;
+
+    let mut mapped: BTreeMap<(PathBuf, usize), Vec<(Problem, usize)>> = BTreeMap::new();
+    let mut unmapped = Vec::new();
+    for (problem, count) in problems(&rpt) {
+        match problem
+            .location()
+            .and_then(|(file, line)| map(&sources, &p, file).map(|file| (file, line)))
+        {
+            Some(location) => mapped.entry(location).or_default().push((problem, count)),
+            None => unmapped.push((problem, count)),
+        }
+    }
+
+    for ((file, line), problems) in &mapped {
+        println!("{}:{}", file.display(), line);
+        if let Some(source) = std::fs::read_to_string(file)
+            .ok()
+            .and_then(|source| source.lines().nth(line.saturating_sub(1)).map(String::from))
+        {
+            println!("  {:>5} | {}", line, source.trim_end());
+        }
+        for (problem, count) in problems {
+            print_problem(problem, *count);
+        }
+        println!();
+    }
+    if !unmapped.is_empty() {
+        println!("Not in the project");
+        for (problem, count) in &unmapped {
+            print_problem(problem, *count);
+        }
+        println!();
+    }
+    info!(
+        "{} problems in {} project files, {} elsewhere",
+        mapped.values().map(Vec::len).sum::<usize>(),
+        mapped
+            .keys()
+            .map(|(file, _)| file)
+            .collect::<std::collections::HashSet<_>>()
+            .len(),
+        unmapped.len()
+    );
+    Ok(())
+}
+
+fn print_problem(problem: &Problem, count: usize) {
+    if count > 1 {
+        println!("  {} ({} times)", problem, count);
+    } else {
+        println!("  {}", problem);
+    }
+}
+
+/// The PBO prefix of every addon with its folder and lowercase name
+fn sources(p: &Project) -> Result<Vec<(String, PathBuf, String)>, HEMTTError> {
+    let ctx = Context::new(p)?;
+    let mut sources = Vec::new();
+    for addon in hemtt::get_all_addons()? {
+        sources.push((
+            crate::context::prefix(&ctx, &addon)?,
+            PathBuf::from(addon.source()),
+            addon.name().to_lowercase(),
+        ));
+    }
+    Ok(sources)
+}
+
+/// File of the project that a path in the game refers to, either through the
+/// PBO prefix of an addon or the name of a function
+///
+/// The game resolves paths without case, the file is found with the case it has on disk
+/// Ex: `z\ace\addons\common\functions\fnc_x.sqf` or `ace_common_fnc_x`
+fn map(sources: &[(String, PathBuf, String)], p: &Project, file: &str) -> Option<PathBuf> {
+    for (prefix, folder, _) in sources {
+        if let Some(rest) = hemtt::strip_prefix_no_case(file, prefix) {
+            if let Some((path, _)) = find_no_case(folder, &rest.replace('\\', "/")) {
+                return Some(path);
+            }
+        }
+    }
+    let file = file.trim_start_matches('\\').to_lowercase();
+    let (component, function) = file.split_once("_fnc_")?;
+    for (_, folder, addon) in sources {
+        if component != format!("{}_{}", p.prefix().to_lowercase(), addon) {
+            continue;
+        }
+        for name in &[
+            format!("fnc_{}.sqf", function),
+            format!("fn_{}.sqf", function),
+        ] {
+            if let Some((path, _)) = find_no_case(folder, &format!("functions/{}", name)) {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
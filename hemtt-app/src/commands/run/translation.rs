@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use hemtt::{Diagnostic, HEMTTError, Project, Severity};
+use hemtt_stringtable::{Stringtable, LANGUAGES};
+
+pub fn run(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    match a.subcommand() {
+        ("coverage", Some(b)) => coverage(b),
+        ("normalize", Some(b)) => normalize(b),
+        ("convert", Some(b)) => convert(b),
+        _ => Err(HEMTTError::User(String::from(
+            "No mode was provided, use `run translation help` to see all modes",
+        ))),
+    }
+}
+
+/// Shows the percentage of keys translated into every language, and fails when a
+/// language is below its minimum
+///
+/// Minimums from `--min` replace the ones of the project for the same language
+fn coverage(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let mut stringtables = Vec::new();
+    for path in self::stringtables(a.value_of("folder").unwrap())? {
+        let source = std::fs::read_to_string(&path)?;
+        stringtables.push(read(&path, &source)?);
+    }
+    // Read after the stringtables, reading the project moves to its root
+    let configured = match Project::read() {
+        Ok(p) => p.translations.minimum,
+        // The stringtables can be checked without a project
+        Err(HEMTTError::NoProjectFound) => BTreeMap::new(),
+        Err(e) => return Err(e),
+    };
+    let mut minimum: BTreeMap<String, u8> = configured
+        .into_iter()
+        .map(|(language, percent)| (language.to_lowercase(), percent))
+        .collect();
+    for value in a.values_of("min").into_iter().flatten() {
+        let (language, percent) = value
+            .split_once('=')
+            .and_then(|(language, percent)| {
+                let percent = percent.trim_end_matches('%').parse::<u8>().ok()?;
+                Some((language.to_lowercase(), percent)).filter(|_| percent <= 100)
+            })
+            .ok_or_else(|| {
+                HEMTTError::UserHint(
+                    format!("Invalid minimum `{}`", value),
+                    String::from("use `--min <language>=<percent>`, ex: `--min german=90`"),
+                )
+            })?;
+        minimum.insert(language, percent);
+    }
+
+    let total: usize = stringtables.iter().map(|s| s.keys().count()).sum();
+    let mut languages: Vec<String> = stringtables
+        .iter()
+        .flat_map(|s| s.keys())
+        .flat_map(|key| key.translations.iter())
+        .map(|t| t.language.to_lowercase())
+        .chain(minimum.keys().cloned())
+        .collect();
+    languages.sort();
+    languages.dedup();
+    languages.sort_by_key(|language| {
+        LANGUAGES
+            .iter()
+            .position(|l| l.eq_ignore_ascii_case(language))
+            .unwrap_or_else(|| LANGUAGES.len())
+    });
+
+    let mut diagnostics = Vec::new();
+    for language in &languages {
+        let name = LANGUAGES
+            .iter()
+            .find(|l| l.eq_ignore_ascii_case(language))
+            .map_or(language.as_str(), |l| *l);
+        let translated: usize = stringtables.iter().map(|s| s.translated(language)).sum();
+        let percent = if total == 0 {
+            100.0
+        } else {
+            translated as f32 * 100.0 / total as f32
+        };
+        match minimum.get(language) {
+            Some(min) if percent < f32::from(*min) => {
+                error!(
+                    "{:<12} {:>5.1}% {}/{}, below the minimum of {}%",
+                    name, percent, translated, total, min
+                );
+                diagnostics.push(Diagnostic::new(
+                    "translation-coverage",
+                    Severity::Error,
+                    format!(
+                        "{} is {:.1}% translated, below the minimum of {}%",
+                        name, percent, min
+                    ),
+                    None,
+                ));
+            }
+            Some(min) => info!(
+                "{:<12} {:>5.1}% {}/{}, minimum {}%",
+                name, percent, translated, total, min
+            ),
+            None => info!("{:<12} {:>5.1}% {}/{}", name, percent, translated, total),
+        }
+    }
+    crate::report::write(&diagnostics, &[])?;
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(HEMTTError::User(format!(
+            "{} language{} below the minimum translation coverage",
+            diagnostics.len(),
+            if diagnostics.len() == 1 {
+                " is"
+            } else {
+                "s are"
+            }
+        )))
+    }
+}
+
+/// Rewrites every stringtable in a folder in its canonical form
+fn normalize(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let mut changed = 0;
+    let paths = stringtables(a.value_of("folder").unwrap())?;
+    for path in &paths {
+        let source = std::fs::read_to_string(path)?;
+        let mut stringtable = read(path, &source)?;
+        stringtable.normalize();
+        let xml = stringtable.to_xml();
+        if xml == source {
+            continue;
+        }
+        changed += 1;
+        if !crate::dry_run("normalize", path) {
+            std::fs::write(path, xml)?;
+        }
+        info!("Normalized {}", path.display());
+    }
+    info!(
+        "{} of {} stringtables were normalized",
+        changed,
+        paths.len()
+    );
+    Ok(())
+}
+
+/// Converts every stringtable in a folder between the flat and grouped layouts
+fn convert(a: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    let grouped = a.value_of("to") == Some("grouped");
+    let paths = stringtables(a.value_of("folder").unwrap())?;
+    for path in &paths {
+        let source = std::fs::read_to_string(path)?;
+        let mut stringtable = read(path, &source)?;
+        if grouped {
+            stringtable.group();
+        } else {
+            stringtable.flatten();
+        }
+        if !crate::dry_run("convert", path) {
+            std::fs::write(path, stringtable.to_xml())?;
+        }
+    }
+    info!(
+        "{} stringtables were converted to the {} layout",
+        paths.len(),
+        a.value_of("to").unwrap()
+    );
+    Ok(())
+}
+
+/// Every `stringtable.xml` in a folder, hidden folders are skipped
+pub fn stringtables<P: AsRef<Path>>(folder: P) -> Result<Vec<PathBuf>, HEMTTError> {
+    let mut paths = Vec::new();
+    for entry in walkdir::WalkDir::new(folder)
+        .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+    {
+        let entry = entry.map_err(std::io::Error::from)?;
+        if entry.file_type().is_file()
+            && entry
+                .file_name()
+                .to_string_lossy()
+                .eq_ignore_ascii_case("stringtable.xml")
+        {
+            paths.push(entry.into_path());
+        }
+    }
+    Ok(paths)
+}
+
+pub fn read(path: &Path, source: &str) -> Result<Stringtable, HEMTTError> {
+    Stringtable::read(source)
+        .map_err(|e| HEMTTError::User(format!("Unable to read {}: {}", path.display(), e)))
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use clap::{App, ArgMatches, SubCommand};
+use hemtt::{HEMTTError, Project};
+use hemtt_arma_config::simplify::{Class, Entry};
+
+use crate::Command;
+
+/// Root classes with scopes that are audited
+const AUDITED: [&str; 4] = ["CfgVehicles", "CfgWeapons", "CfgMagazines", "CfgGlasses"];
+
+/// Roots where public classes are shown in the arsenal unless `scopeArsenal = 0`
+const ARSENAL: [&str; 2] = ["CfgWeapons", "CfgGlasses"];
+
+struct ScopeClass {
+    root: &'static str,
+    name: String,
+    parent: String,
+    addon: String,
+    properties: HashMap<&'static str, i32>,
+}
+
+pub struct Scopes;
+impl Command for Scopes {
+    fn register(&self) -> App {
+        SubCommand::with_name("scopes")
+            .version(*crate::VERSION)
+            .about("List classes in the built configs with inconsistent scopes")
+            .args(&super::building_args())
+    }
+
+    fn can_announce(&self) -> bool {
+        false
+    }
+
+    fn run(&self, a: &ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        let built = Project::output_dir()?;
+        let selected: Vec<String> = crate::get_addons_from_args(a)?
+            .iter()
+            .map(|addon| addon.name().to_string())
+            .collect();
+        let mut classes = Vec::new();
+        for addon in hemtt::get_all_addons()? {
+            let path = addon.destination(&built, Some(p.prefix()), None);
+            if !path.exists() {
+                if selected.iter().any(|s| s == addon.name()) {
+                    warn!("`{}` has not been built, skipping", addon.name());
+                }
+                continue;
+            }
+            if let Some(config) = super::docs::read_config(&path)? {
+                for name in &AUDITED {
+                    if let Some(class) = config.root.class(name) {
+                        collect(*name, class, addon.name(), &mut classes);
+                    }
+                }
+            }
+        }
+
+        let lookup: HashMap<(&str, String), &ScopeClass> = classes
+            .iter()
+            .map(|c| ((c.root, c.name.to_lowercase()), c))
+            .collect();
+        let mut found = 0;
+        for class in classes.iter().filter(|c| selected.contains(&c.addon)) {
+            for problem in problems(class, &classes, &lookup) {
+                found += 1;
+                println!(
+                    "{}: {} >> {} {}",
+                    class.addon, class.root, class.name, problem
+                );
+            }
+        }
+        if found == 0 {
+            info!("No inconsistent scopes were found");
+        } else {
+            warn!("{} inconsistent scopes were found", found);
+        }
+        Ok(())
+    }
+}
+
+fn collect(root: &'static str, class: &Class, addon: &str, classes: &mut Vec<ScopeClass>) {
+    for (name, entry) in &class.entries {
+        if let Entry::Class(c) = entry {
+            if c.external || c.deletion {
+                continue;
+            }
+            let mut properties = HashMap::new();
+            for key in &["scope", "scopeCurator", "scopeArsenal"] {
+                if let Some(Entry::Int(i)) = c.get(key) {
+                    properties.insert(*key, *i);
+                }
+            }
+            classes.push(ScopeClass {
+                root,
+                name: name.to_string(),
+                parent: c.parent.clone(),
+                addon: addon.to_string(),
+                properties,
+            });
+        }
+    }
+}
+
+/// Value of a scope, inherited from the parents that can be found in the project
+fn resolve(
+    class: &ScopeClass,
+    key: &str,
+    lookup: &HashMap<(&str, String), &ScopeClass>,
+) -> Option<i32> {
+    let mut current = class;
+    let mut depth = 0;
+    loop {
+        if let Some(value) = current.properties.get(key) {
+            return Some(*value);
+        }
+        depth += 1;
+        current = match lookup.get(&(class.root, current.parent.to_lowercase())) {
+            Some(parent) if depth < 64 => parent,
+            _ => return None,
+        };
+    }
+}
+
+fn problems(
+    class: &ScopeClass,
+    classes: &[ScopeClass],
+    lookup: &HashMap<(&str, String), &ScopeClass>,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    let scope = resolve(class, "scope", lookup);
+    let curator = resolve(class, "scopeCurator", lookup);
+    let arsenal = resolve(class, "scopeArsenal", lookup);
+    let public = scope == Some(2);
+    if class.root == "CfgVehicles" {
+        if public && curator == Some(0) {
+            problems.push(String::from(
+                "is public but hidden from Zeus with `scopeCurator = 0`",
+            ));
+        }
+        if !public && curator == Some(2) {
+            problems.push(String::from(
+                "is not public but visible in Zeus with `scopeCurator = 2`",
+            ));
+        }
+    }
+    if !public && arsenal == Some(2) {
+        problems.push(String::from(
+            "is not public but visible in the arsenal with `scopeArsenal = 2`",
+        ));
+    }
+    let in_arsenal = public
+        && match arsenal {
+            Some(arsenal) => arsenal == 2,
+            None => ARSENAL.contains(&class.root),
+        };
+    if in_arsenal {
+        if let Some(child) = classes
+            .iter()
+            .find(|c| c.root == class.root && c.parent.eq_ignore_ascii_case(&class.name))
+        {
+            problems.push(format!(
+                "is the parent of `{}` but visible in the arsenal",
+                child.name
+            ));
+        }
+    }
+    problems
+}
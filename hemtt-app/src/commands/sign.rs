@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::{HEMTTError, Project};
+use hemtt_signing::BIPrivateKey;
+
+use crate::tasks::{signing_error, ReleaseOptions};
+use crate::Command;
+
+pub struct Sign;
+impl Command for Sign {
+    fn register(&self) -> App {
+        SubCommand::with_name("sign")
+            .version(*crate::VERSION)
+            .about("Sign every PBO in a release or folder without rebuilding")
+            .arg(
+                Arg::with_name("folder")
+                    .help("Folder containing the PBOs, defaults to the current release"),
+            )
+            .arg(
+                Arg::with_name("key")
+                    .long("key")
+                    .help("Private key to sign with, defaults to the project's signing keys")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1),
+            )
+            .arg(
+                Arg::with_name("replace")
+                    .long("replace")
+                    .help("Remove existing signatures and public keys from other authorities"),
+            )
+    }
+
+    fn run(&self, a: &ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        let folder = if let Some(folder) = a.value_of("folder") {
+            PathBuf::from(folder)
+        } else {
+            crate::tasks::release_root(&p, &ReleaseOptions::default())?
+        };
+        if !folder.is_dir() {
+            return Err(HEMTTError::UserHint(
+                format!("Folder {:?} does not exist", folder),
+                String::from("build a release first or pass the folder to sign"),
+            ));
+        }
+        let keys = if let Some(paths) = a.values_of("key") {
+            paths
+                .map(|path| BIPrivateKey::read(&mut open_file!(path)?).map_err(signing_error))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            crate::tasks::signing_keys(&p)?
+        };
+        let version = crate::tasks::sig_version(&p)?;
+        let keys_folder = p.layout.keys(&folder);
+        let pbos = pbos(&folder)?;
+        if a.is_present("replace") {
+            remove_other_authorities(&keys, &keys_folder, &pbos)?;
+        }
+        for key in &keys {
+            crate::tasks::public_key(key, &keys_folder)?;
+            for pbo in &pbos {
+                debug!("Signing {:?} with `{}`", pbo, key.name());
+                crate::tasks::sign(key, pbo, version)?;
+            }
+            info!("Signed {} PBOs with `{}`", pbos.len(), key.name());
+        }
+        Ok(())
+    }
+}
+
+/// Every PBO inside the folder
+fn pbos(folder: &Path) -> Result<Vec<PathBuf>, HEMTTError> {
+    let mut pbos = Vec::new();
+    for entry in walkdir::WalkDir::new(folder).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        if entry.file_type().is_file() && entry.path().extension().map_or(false, |e| e == "pbo") {
+            pbos.push(entry.into_path());
+        }
+    }
+    Ok(pbos)
+}
+
+/// Removes signatures and public keys that were not created by the keys
+fn remove_other_authorities(
+    own: &[BIPrivateKey],
+    keys: &Path,
+    pbos: &[PathBuf],
+) -> Result<(), HEMTTError> {
+    let mut stale = Vec::new();
+    if keys.is_dir() {
+        for file in std::fs::read_dir(keys)? {
+            let file = file?.path();
+            if file.extension().map_or(false, |e| e == "bikey")
+                && file
+                    .file_stem()
+                    .map_or(true, |stem| !own.iter().any(|key| stem == key.name()))
+            {
+                stale.push(file);
+            }
+        }
+    }
+    for pbo in pbos {
+        let signatures: Vec<PathBuf> = own
+            .iter()
+            .map(|key| crate::tasks::signature(key, pbo))
+            .collect();
+        stale.extend(
+            hemtt::Prebuilt::signatures(pbo)?
+                .into_iter()
+                .filter(|signature| !signatures.contains(signature)),
+        );
+    }
+    for file in stale {
+        if !crate::dry_run("delete", &file) {
+            debug!("Removing {:?}", file);
+            std::fs::remove_file(&file)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::{HEMTTError, Project};
+use regex::Regex;
+use serde_json::json;
+
+use crate::Command;
+
+/// Asset kinds and the extensions counted for them
+const ASSETS: [(&str, &[&str]); 5] = [
+    ("models", &["p3d"]),
+    ("textures", &["paa", "pac"]),
+    ("materials", &["rvmat"]),
+    ("sounds", &["ogg", "wss", "wav"]),
+    ("animations", &["rtm"]),
+];
+
+#[derive(Default)]
+struct AddonStats {
+    sqf_lines: usize,
+    functions: usize,
+    classes: usize,
+    strings: usize,
+    assets: BTreeMap<&'static str, usize>,
+}
+
+impl AddonStats {
+    fn add(&mut self, other: &Self) {
+        self.sqf_lines += other.sqf_lines;
+        self.functions += other.functions;
+        self.classes += other.classes;
+        self.strings += other.strings;
+        for (kind, count) in &other.assets {
+            *self.assets.entry(*kind).or_insert(0) += count;
+        }
+    }
+
+    fn json(&self) -> serde_json::Value {
+        json!({
+            "sqf_lines": self.sqf_lines,
+            "functions": self.functions,
+            "classes": self.classes,
+            "strings": self.strings,
+            "assets": self.assets,
+        })
+    }
+}
+
+pub struct Stats;
+impl Command for Stats {
+    fn register(&self) -> App {
+        SubCommand::with_name("stats")
+            .version(*crate::VERSION)
+            .about("Report metrics of the project's addons")
+            .arg(
+                Arg::with_name("format")
+                    .long("format")
+                    .help("Format of the report")
+                    .possible_values(&["text", "json"])
+                    .default_value("text"),
+            )
+            .arg(
+                Arg::with_name("output")
+                    .long("output")
+                    .help("File to write the report to, defaults to stdout")
+                    .takes_value(true),
+            )
+    }
+
+    fn can_announce(&self) -> bool {
+        false
+    }
+
+    fn run(&self, a: &ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        let class = Regex::new(r"\bclass\s+\w+\s*(:\s*\w+\s*)?\{").unwrap();
+        let mut addons = BTreeMap::new();
+        let mut total = AddonStats::default();
+        for addon in hemtt::get_all_addons()? {
+            let stats = addon_stats(Path::new(addon.source()), &class)?;
+            total.add(&stats);
+            addons.insert(addon.source().to_string(), stats);
+        }
+
+        let report = match a.value_of("format").unwrap() {
+            "json" => serde_json::to_string_pretty(&json!({
+                "project": p.name(),
+                "version": p.version(),
+                "addons": addons
+                    .iter()
+                    .map(|(name, stats)| (name.clone(), stats.json()))
+                    .collect::<serde_json::Map<_, _>>(),
+                "total": total.json(),
+            }))
+            .map_err(|e| HEMTTError::Generic(e.to_string()))?,
+            _ => text(&addons, &total),
+        };
+        if let Some(output) = a.value_of("output") {
+            create_file!(output)?.write_all(report.as_bytes())?;
+        } else {
+            println!("{}", report);
+        }
+        Ok(())
+    }
+}
+
+fn addon_stats(folder: &Path, class: &Regex) -> Result<AddonStats, HEMTTError> {
+    let mut stats = AddonStats::default();
+    for (kind, _) in &ASSETS {
+        stats.assets.insert(*kind, 0);
+    }
+    for entry in walkdir::WalkDir::new(folder) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        let ext = entry
+            .path()
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match ext.as_str() {
+            "sqf" => {
+                stats.sqf_lines += read(entry.path())?.lines().count();
+                if name.starts_with("fnc_") || name.starts_with("fn_") {
+                    stats.functions += 1;
+                }
+            }
+            "cpp" | "hpp" => stats.classes += class.find_iter(&read(entry.path())?).count(),
+            _ if name == "stringtable.xml" => {
+                stats.strings += read(entry.path())?.to_lowercase().matches("<key ").count()
+            }
+            _ => {
+                if let Some((kind, _)) =
+                    ASSETS.iter().find(|(_, exts)| exts.contains(&ext.as_str()))
+                {
+                    *stats.assets.get_mut(kind).unwrap() += 1;
+                }
+            }
+        }
+    }
+    Ok(stats)
+}
+
+fn read(path: &Path) -> Result<String, HEMTTError> {
+    let mut data = Vec::new();
+    open_file!(path)?.read_to_end(&mut data)?;
+    Ok(String::from_utf8_lossy(&data).to_string())
+}
+
+fn text(addons: &BTreeMap<String, AddonStats>, total: &AddonStats) -> String {
+    let width = addons.keys().map(String::len).max().unwrap_or(0).max(5);
+    let mut out = format!(
+        "{:<width$} {:>9} {:>9} {:>9} {:>9}",
+        "addon",
+        "sqf lines",
+        "functions",
+        "classes",
+        "strings",
+        width = width
+    );
+    for (kind, _) in &ASSETS {
+        out.push_str(&format!(" {:>10}", kind));
+    }
+    let total_name = String::from("total");
+    for (name, stats) in addons.iter().chain(std::iter::once((&total_name, total))) {
+        out.push_str(&format!(
+            "\n{:<width$} {:>9} {:>9} {:>9} {:>9}",
+            name,
+            stats.sqf_lines,
+            stats.functions,
+            stats.classes,
+            stats.strings,
+            width = width
+        ));
+        for (kind, _) in &ASSETS {
+            out.push_str(&format!(" {:>10}", stats.assets.get(kind).unwrap_or(&0)));
+        }
+    }
+    out
+}
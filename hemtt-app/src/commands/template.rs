@@ -50,16 +50,44 @@ impl Command for Template {
     fn run_no_project(&self, a: &ArgMatches) -> Result<(), HEMTTError> {
         if let ("init", Some(b)) = a.subcommand() {
             return match b.value_of("template").unwrap() {
+                "minimal" => hemtt::templates::init(
+                    hemtt::templates::Templates::Minimal,
+                    std::env::current_dir()?,
+                ),
                 "cba" => hemtt::templates::init(
                     hemtt::templates::Templates::CBA,
                     std::env::current_dir()?,
                 ),
+                "assets" => hemtt::templates::init(
+                    hemtt::templates::Templates::Assets,
+                    std::env::current_dir()?,
+                ),
+                "dayz" => hemtt::templates::init(
+                    hemtt::templates::Templates::DayZ,
+                    std::env::current_dir()?,
+                ),
+                "reforger" => hemtt::templates::init(
+                    hemtt::templates::Templates::Reforger,
+                    std::env::current_dir()?,
+                ),
                 unknown => Err(HEMTTError::TemplateUnknown(unknown.to_string())),
             };
         }
         let p = Project::read()?;
         let template: Box<dyn hemtt::Template> = match p.template().to_lowercase().as_str() {
+            "minimal" => Box::new(hemtt::templates::minimal::Minimal::new(
+                hemtt::Project::find_root()?,
+            )),
             "cba" => Box::new(hemtt::templates::cba::CBA::new(hemtt::Project::find_root()?)),
+            "assets" => Box::new(hemtt::templates::assets::Assets::new(
+                hemtt::Project::find_root()?,
+            )),
+            "dayz" => Box::new(hemtt::templates::dayz::DayZ::new(
+                hemtt::Project::find_root()?,
+            )),
+            "reforger" => Box::new(hemtt::templates::reforger::Reforger::new(
+                hemtt::Project::find_root()?,
+            )),
             _ => return Err(HEMTTError::TemplateUnknown(p.template().to_string())),
         };
         match a.subcommand() {
@@ -84,6 +112,10 @@ impl Command for Template {
                 if let Some(addon) = Addon::locate(&addon)? {
                     template.new_function(&addon, &name)?;
                     Ok(())
+                } else if p.game.is_enfusion() {
+                    // The project itself is the addon
+                    template.new_function(&Addon::new(addon, AddonLocation::Addons)?, &name)?;
+                    Ok(())
                 } else {
                     panic!("addon not found");
                 }
@@ -0,0 +1,313 @@
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::{HEMTTError, UserConfig};
+use semver::Version;
+use sha2::{Digest, Sha256};
+
+use crate::Command;
+
+const RELEASES: &str = "https://api.github.com/repos/synixebrett/HEMTT/releases";
+
+pub struct Update;
+impl Command for Update {
+    fn register(&self) -> App {
+        SubCommand::with_name("update")
+            .version(*crate::VERSION)
+            .about("Update HEMTT to the latest release")
+            .arg(
+                Arg::with_name("channel")
+                    .long("channel")
+                    .help("Release channel, defaults to the channel in the user config")
+                    .takes_value(true)
+                    .possible_values(&["stable", "beta", "nightly"]),
+            )
+            .arg(
+                Arg::with_name("check")
+                    .long("check")
+                    .help("Only check if an update is available"),
+            )
+            .arg(
+                Arg::with_name("rollback")
+                    .long("rollback")
+                    .help("Restore the version that was replaced by the last update")
+                    .conflicts_with_all(&["channel", "check"]),
+            )
+    }
+
+    fn require_project(&self) -> bool {
+        false
+    }
+
+    fn run_no_project(&self, a: &ArgMatches) -> Result<(), HEMTTError> {
+        if a.is_present("rollback") {
+            return rollback();
+        }
+        let config = UserConfig::read()?;
+        let channel = a
+            .value_of("channel")
+            .unwrap_or(&config.update.channel)
+            .to_string();
+        let current = Version::parse(env!("CARGO_PKG_VERSION"))
+            .map_err(|e| HEMTTError::Generic(e.to_string()))?;
+        let (version, release) = latest(&config, &channel)?.ok_or_else(|| {
+            HEMTTError::User(format!(
+                "No releases were found on the `{}` channel",
+                channel
+            ))
+        })?;
+        if version <= current {
+            info!("HEMTT {} is the latest `{}` release", current, channel);
+            return Ok(());
+        }
+        info!(
+            "HEMTT {} is available on the `{}` channel",
+            version, channel
+        );
+        if a.is_present("check") {
+            return Ok(());
+        }
+        let name = asset_name();
+        let checksum = format!("{}.sha256", name);
+        let (url, checksum_url) = asset_url(&release, name)
+            .zip(asset_url(&release, &checksum))
+            .ok_or_else(|| {
+                HEMTTError::User(format!(
+                    "Release {} has no `{}` with a `{}` for {}",
+                    version,
+                    name,
+                    checksum,
+                    std::env::consts::OS
+                ))
+            })?;
+        let binary = download(&config, &url, &checksum_url)?;
+        install(&binary)?;
+        info!("Updated HEMTT {} => {}", current, version);
+        Ok(())
+    }
+}
+
+/// How often a new version is looked for when running a command
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// Tells the user when a new version is available on their channel, when enabled
+/// by `update.check` in the user config
+///
+/// Checked at most once a day, failures are only logged as the command does not
+/// depend on it
+pub fn check_for_update() {
+    let config = match UserConfig::read() {
+        Ok(config) => config,
+        Err(_) => return,
+    };
+    if !config.update.check {
+        return;
+    }
+    // The time of the last check is kept next to the user config
+    let stamp =
+        match UserConfig::path().and_then(|p| p.parent().map(|d| d.join("last_update_check"))) {
+            Some(stamp) => stamp,
+            None => return,
+        };
+    let recent = std::fs::metadata(&stamp)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map_or(false, |elapsed| elapsed < CHECK_INTERVAL);
+    if recent {
+        return;
+    }
+    if let Err(e) = stamp
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&stamp, ""))
+    {
+        debug!("Unable to record the update check: {}", e);
+    }
+    let current = match Version::parse(env!("CARGO_PKG_VERSION")) {
+        Ok(current) => current,
+        Err(_) => return,
+    };
+    match latest(&config, &config.update.channel) {
+        Ok(Some((version, _))) if version > current => info!(
+            "HEMTT {} is available on the `{}` channel, run `hemtt update` to install it",
+            version, config.update.channel
+        ),
+        Ok(_) => {}
+        Err(e) => debug!("Unable to check for updates: {}", e),
+    }
+}
+
+fn request(config: &UserConfig, url: &str) -> Result<ureq::Response, HEMTTError> {
+    let mut request = crate::http::agent()?.get(url).set(
+        "User-Agent",
+        &format!("hemtt/{}", env!("CARGO_PKG_VERSION")),
+    );
+    if let Some(token) = config.github_token() {
+        request = request.set("Authorization", &format!("token {}", token));
+    }
+    request
+        .call()
+        .map_err(|e| HEMTTError::Network(format!("Unable to reach `{}`: {}", url, e)))
+}
+
+/// Newest release on a channel
+///
+/// * `stable`: releases
+/// * `beta`: releases and pre-releases, except nightly builds
+/// * `nightly`: every release
+fn latest(
+    config: &UserConfig,
+    channel: &str,
+) -> Result<Option<(Version, serde_json::Value)>, HEMTTError> {
+    let releases: serde_json::Value =
+        serde_json::from_str(&request(config, RELEASES)?.into_string()?)
+            .map_err(|e| HEMTTError::Generic(format!("Invalid response from GitHub: {}", e)))?;
+    Ok(releases
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|release| !release["draft"].as_bool().unwrap_or(false))
+        .filter(|release| {
+            let prerelease = release["prerelease"].as_bool().unwrap_or(false);
+            let nightly = release["tag_name"]
+                .as_str()
+                .map_or(false, |tag| tag.contains("nightly"));
+            match channel {
+                "stable" => !prerelease,
+                "beta" => !nightly,
+                _ => true,
+            }
+        })
+        .filter_map(|release| {
+            let tag = release["tag_name"].as_str()?.trim_start_matches('v');
+            Version::parse(tag).ok().map(|version| (version, release))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0)))
+}
+
+/// Name of the release asset for the current platform
+fn asset_name() -> &'static str {
+    match std::env::consts::OS {
+        "windows" => "hemtt-windows.zip",
+        "macos" => "hemtt-macos.zip",
+        _ => "hemtt-linux.zip",
+    }
+}
+
+/// Download of a release asset, matched by its exact name
+fn asset_url(release: &serde_json::Value, name: &str) -> Option<String> {
+    release["assets"]
+        .as_array()?
+        .iter()
+        .find(|asset| asset["name"].as_str() == Some(name))
+        .and_then(|asset| asset["browser_download_url"].as_str())
+        .map(str::to_string)
+}
+
+/// Downloads the zipped release, verifying it against its published SHA-256 checksum,
+/// and extracts the binary
+fn download(config: &UserConfig, url: &str, checksum_url: &str) -> Result<Vec<u8>, HEMTTError> {
+    debug!("Downloading {}", checksum_url);
+    let checksum = request(config, checksum_url)?.into_string()?;
+    let expected = checksum
+        .split_whitespace()
+        .next()
+        .map(str::to_lowercase)
+        .ok_or_else(|| HEMTTError::Generic(format!("`{}` is empty", checksum_url)))?;
+    debug!("Downloading {}", url);
+    let mut data = Vec::new();
+    request(config, url)?.into_reader().read_to_end(&mut data)?;
+    let actual = format!("{:x}", Sha256::digest(&data));
+    if actual != expected {
+        return Err(HEMTTError::Generic(format!(
+            "The checksum of `{}` does not match the published checksum, expected {} but found {}",
+            url, expected, actual
+        )));
+    }
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| HEMTTError::Generic(format!("`{}` is not a valid zip: {}", url, e)))?;
+    let name = if cfg!(windows) { "hemtt.exe" } else { "hemtt" };
+    let mut file = archive
+        .by_name(name)
+        .map_err(|_| HEMTTError::Generic(format!("`{}` does not contain {}", url, name)))?;
+    let mut binary = Vec::new();
+    file.read_to_end(&mut binary)?;
+    Ok(binary)
+}
+
+/// Copy of the binary that was replaced by the last update
+pub fn previous_binary() -> Result<PathBuf, HEMTTError> {
+    let mut path = std::env::current_exe()?.into_os_string();
+    path.push(".previous");
+    Ok(PathBuf::from(path))
+}
+
+/// Swaps the running binary with the previous binary, a second rollback undoes the first
+fn rollback() -> Result<(), HEMTTError> {
+    let exe = std::env::current_exe()?;
+    let previous = previous_binary()?;
+    if !previous.exists() {
+        return Err(HEMTTError::UserHint(
+            String::from("There is no previous version to roll back to"),
+            String::from("a previous version is kept after running `hemtt update`"),
+        ));
+    }
+    if crate::dry_run("restore", &previous) {
+        return Ok(());
+    }
+    let mut swap = exe.clone().into_os_string();
+    swap.push(".swap");
+    let swap = PathBuf::from(swap);
+    std::fs::rename(&exe, &swap)?;
+    if let Err(e) = std::fs::rename(&previous, &exe) {
+        std::fs::rename(&swap, &exe)?;
+        return Err(e.into());
+    }
+    std::fs::rename(&swap, &previous)?;
+    info!("Restored the previous version of HEMTT");
+    Ok(())
+}
+
+/// Replaces the running binary, keeping the current one as the previous binary
+///
+/// The new binary is written next to the running binary first, so a failed write
+/// leaves the running binary in place
+fn install(binary: &[u8]) -> Result<(), HEMTTError> {
+    let exe = std::env::current_exe()?;
+    let previous = previous_binary()?;
+    if crate::dry_run("replace", &exe) {
+        return Ok(());
+    }
+    let mut update = exe.clone().into_os_string();
+    update.push(".update");
+    let update = PathBuf::from(update);
+    if let Err(e) = write_binary(&update, binary) {
+        let _ = std::fs::remove_file(&update);
+        return Err(e);
+    }
+    // The running binary can be renamed but not overwritten on Windows
+    if let Err(e) = std::fs::rename(&exe, &previous) {
+        let _ = std::fs::remove_file(&update);
+        return Err(e.into());
+    }
+    if let Err(e) = std::fs::rename(&update, &exe) {
+        std::fs::rename(&previous, &exe)?;
+        let _ = std::fs::remove_file(&update);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+fn write_binary(path: &Path, binary: &[u8]) -> Result<(), HEMTTError> {
+    std::fs::write(path, binary)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use hemtt::{HEMTTError, Project};
+
+use crate::tasks::ReleaseOptions;
+use crate::Command;
+
+pub struct Verify {}
+impl Command for Verify {
+    fn register(&self) -> App {
+        SubCommand::with_name("verify")
+            .version(*crate::VERSION)
+            .about("Check that a server with the given keys accepts every PBO of the release")
+            .arg(
+                Arg::with_name("folder")
+                    .help("Folder containing the PBOs, defaults to the current release"),
+            )
+            .arg(
+                Arg::with_name("against")
+                    .long("against")
+                    .help("Folder of the server's public keys")
+                    .takes_value(true)
+                    .required(true),
+            )
+    }
+
+    fn run(&self, a: &ArgMatches, p: Project) -> Result<(), HEMTTError> {
+        let folder = if let Some(folder) = a.value_of("folder") {
+            PathBuf::from(folder)
+        } else {
+            crate::tasks::release_root(&p, &ReleaseOptions::default())?
+        };
+        if !folder.is_dir() {
+            return Err(HEMTTError::UserHint(
+                format!("Folder {:?} does not exist", folder),
+                String::from("build a release first or pass the folder to verify"),
+            ));
+        }
+        let keys = PathBuf::from(a.value_of("against").unwrap());
+        if !keys.is_dir() {
+            return Err(HEMTTError::User(format!(
+                "Keys folder {:?} does not exist",
+                keys
+            )));
+        }
+        super::run::keycheck::check(&folder, &keys)
+    }
+}
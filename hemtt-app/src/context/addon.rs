@@ -1,4 +1,4 @@
-use hemtt::{Addon, HEMTTError};
+use hemtt::{Addon, Diagnostic, HEMTTError, Severity, Span};
 use hemtt_handlebars::Variables;
 use vfs::VfsPath;
 
@@ -12,53 +12,14 @@ pub struct AddonContext<'a, 'b> {
 
     failed: Option<HEMTTError>,
     skip: bool,
+    /// Stage and task the addon is in, addons move through the flow on their own
+    message_info: Option<(String, String)>,
 }
 
 impl<'a, 'b> AddonContext<'a, 'b> {
     pub fn new(global: &'b Context<'a>, addon: Addon) -> Result<Self, HEMTTError> {
         let fs = global.fs().join(addon.source())?;
-        let prefix_file = fs.join("$PBOPREFIX$")?;
-        let prefix_gen = format!(
-            "{}\\{}\\{}",
-            global.project.mainprefix(),
-            global.project.prefix(),
-            addon.source()
-        )
-        .replace("/", "\\");
-        let prefix = if prefix_file.exists()? {
-            let mut source = String::new();
-            prefix_file.open_file()?.read_to_string(&mut source)?;
-            let mut prefix = "";
-            'search: for line in source.lines() {
-                if line.is_empty() {
-                    break;
-                }
-
-                let eq: Vec<String> = line.split('=').map(|s| s.to_string()).collect();
-                if eq.len() == 1 {
-                    prefix = line.trim_matches('\\');
-                    break 'search;
-                } else {
-                    let header = eq[0].clone();
-                    if header == "prefix" {
-                        prefix = line.trim_matches('\\');
-                        break 'search;
-                    }
-                }
-            }
-            let prefix =
-                hemtt_handlebars::render(prefix, &Variables::from(global.project())).unwrap();
-            if prefix.is_empty() {
-                warn!("Could not determine a prefix for {} using the $PBOPREFIX$ file, a prefix will be generated", addon.source());
-                prefix_gen
-            } else {
-                debug!("Using prefix from $PBOPREFIX$ for {}", addon.source());
-                prefix.to_string()
-            }
-        } else {
-            debug!("Using generated prefix for {}", addon.source());
-            prefix_gen
-        };
+        let prefix = prefix(global, &addon)?;
         Ok(Self {
             global,
             addon,
@@ -67,6 +28,7 @@ impl<'a, 'b> AddonContext<'a, 'b> {
 
             failed: None,
             skip: false,
+            message_info: None,
         })
     }
 
@@ -83,6 +45,12 @@ impl<'a, 'b> AddonContext<'a, 'b> {
     }
 
     pub fn set_failed(&mut self, err: HEMTTError) {
+        let mut diagnostic = Diagnostic::from(&err);
+        if diagnostic.span.is_none() {
+            diagnostic.span = Some(self.span());
+        }
+        self.global.report(diagnostic);
+        crate::dashboard::failed(self.addon.name());
         self.failed = Some(err);
     }
 
@@ -94,6 +62,16 @@ impl<'a, 'b> AddonContext<'a, 'b> {
         self.skip = skip;
     }
 
+    pub fn set_message_info(&mut self, stage: String, task: String) {
+        self.message_info = Some((stage, task));
+    }
+
+    pub fn message_info(&self) -> (String, String) {
+        self.message_info
+            .clone()
+            .unwrap_or_else(|| self.global.message_info.read().unwrap().clone())
+    }
+
     pub fn fs(&self) -> &VfsPath {
         &self.fs
     }
@@ -106,8 +84,13 @@ impl<'a, 'b> AddonContext<'a, 'b> {
         &self.prefix
     }
 
+    /// Location of the addon's folder, used for problems without a line
+    fn span(&self) -> Span {
+        Span::new(self.addon.source(), 0, 0, 0)
+    }
+
     pub fn info(&self, message: &str) {
-        let (stage, task) = self.global.message_info.read().unwrap().clone();
+        let (stage, task) = self.message_info();
         info!(
             "[{}] [{:^width$}] [{}] {}",
             stage,
@@ -119,7 +102,14 @@ impl<'a, 'b> AddonContext<'a, 'b> {
     }
 
     pub fn warn(&self, message: &str) {
-        let (stage, task) = self.global.message_info.read().unwrap().clone();
+        let (stage, task) = self.message_info();
+        self.global.report(Diagnostic::new(
+            task.clone(),
+            Severity::Warning,
+            message,
+            Some(self.span()),
+        ));
+        crate::dashboard::warning(self.addon.name());
         warn!(
             "[{}] [{:^width$}] [{}] {}",
             stage,
@@ -131,7 +121,7 @@ impl<'a, 'b> AddonContext<'a, 'b> {
     }
 
     pub fn error(&self, message: &str) {
-        let (stage, task) = self.global.message_info.read().unwrap().clone();
+        let (stage, task) = self.message_info();
         error!(
             "[{}] [{:^width$}] [{}] {}",
             stage,
@@ -143,7 +133,7 @@ impl<'a, 'b> AddonContext<'a, 'b> {
     }
 
     pub fn debug(&self, message: &str) {
-        let (stage, task) = self.global.message_info.read().unwrap().clone();
+        let (stage, task) = self.message_info();
         debug!(
             "[{}] [{:^width$}] [{}] {}",
             stage,
@@ -155,7 +145,7 @@ impl<'a, 'b> AddonContext<'a, 'b> {
     }
 
     pub fn trace(&self, message: &str) {
-        let (stage, task) = self.global.message_info.read().unwrap().clone();
+        let (stage, task) = self.message_info();
         trace!(
             "[{}] [{:^width$}] [{}] {}",
             stage,
@@ -167,6 +157,52 @@ impl<'a, 'b> AddonContext<'a, 'b> {
     }
 }
 
+/// Prefix of an addon, read from its `$PBOPREFIX$` or generated from the project
+/// Ex: "z\hemtt\addons\main"
+pub fn prefix(global: &Context, addon: &Addon) -> Result<String, HEMTTError> {
+    let prefix_file = global.fs().join(addon.source())?.join("$PBOPREFIX$")?;
+    let prefix_gen = format!(
+        "{}\\{}\\{}",
+        global.project.mainprefix(),
+        global.project.prefix(),
+        addon.source()
+    )
+    .replace("/", "\\");
+    Ok(if prefix_file.exists()? {
+        let mut source = String::new();
+        prefix_file.open_file()?.read_to_string(&mut source)?;
+        let mut prefix = "";
+        'search: for line in source.lines() {
+            if line.is_empty() {
+                break;
+            }
+
+            let eq: Vec<String> = line.split('=').map(|s| s.to_string()).collect();
+            if eq.len() == 1 {
+                prefix = line.trim_matches('\\');
+                break 'search;
+            } else {
+                let header = eq[0].clone();
+                if header == "prefix" {
+                    prefix = line.trim_matches('\\');
+                    break 'search;
+                }
+            }
+        }
+        let prefix = hemtt_handlebars::render(prefix, &Variables::from(global.project())).unwrap();
+        if prefix.is_empty() {
+            warn!("Could not determine a prefix for {} using the $PBOPREFIX$ file, a prefix will be generated", addon.source());
+            prefix_gen
+        } else {
+            debug!("Using prefix from $PBOPREFIX$ for {}", addon.source());
+            prefix.to_string()
+        }
+    } else {
+        debug!("Using generated prefix for {}", addon.source());
+        prefix_gen
+    })
+}
+
 pub struct AddonListContext<'a, 'b> {
     global: &'b Context<'a>,
     addons: Vec<AddonContext<'a, 'b>>,
@@ -198,4 +234,13 @@ impl<'a, 'b> AddonListContext<'a, 'b> {
     pub fn failed(&self) -> bool {
         self.addons().iter().any(|a| a.failed())
     }
+
+    /// Folders of the addons
+    /// Ex: "addons/main"
+    pub fn sources(&self) -> Vec<String> {
+        self.addons()
+            .iter()
+            .map(|a| a.addon().source().to_string())
+            .collect()
+    }
 }
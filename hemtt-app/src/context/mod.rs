@@ -1,4 +1,4 @@
-use std::{path::PathBuf, sync::RwLock};
+use std::{path::PathBuf, sync::RwLock, time::Instant};
 
 use state::Container;
 use vfs::{
@@ -6,11 +6,11 @@ use vfs::{
     MemoryFS, PhysicalFS, VfsPath,
 };
 
-use crate::Project;
-use hemtt::{Addon, HEMTTError};
+use crate::{report::timings::Timing, Project};
+use hemtt::{Addon, Diagnostic, HEMTTError};
 
 mod addon;
-pub use addon::{AddonContext, AddonListContext};
+pub use addon::{prefix, AddonContext, AddonListContext};
 
 pub struct Context<'a> {
     project: &'a Project,
@@ -19,6 +19,8 @@ pub struct Context<'a> {
     root: PathBuf,
     // stage: &Stage,
     message_info: RwLock<(String, String)>,
+    diagnostics: RwLock<Vec<Diagnostic>>,
+    start: Instant,
     pub container: Container![Send + Sync],
 }
 
@@ -39,6 +41,8 @@ impl<'a> Context<'a> {
             root,
 
             message_info: RwLock::new((String::from("internal init"), String::from("new"))),
+            diagnostics: RwLock::new(Vec::new()),
+            start: Instant::now(),
             container: <Container![Send + Sync]>::new(),
         })
     }
@@ -66,6 +70,20 @@ impl<'a> Context<'a> {
     pub fn set_message_info(&self, stage: String, task: String) {
         *self.message_info.write().unwrap() = (stage, task);
     }
+
+    /// Records a problem for `--report`
+    pub fn report(&self, diagnostic: Diagnostic) {
+        self.diagnostics.write().unwrap().push(diagnostic);
+    }
+
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.read().unwrap().clone()
+    }
+
+    /// How long the tasks took for `--timings`, from their spans
+    pub fn timings(&self) -> Vec<Timing> {
+        crate::report::timings::recorded(self.start)
+    }
 }
 
 impl<'a, 'b> Context<'a> {
@@ -0,0 +1,273 @@
+use std::collections::BTreeMap;
+use std::io::Stdout;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use tui::backend::CrosstermBackend;
+use tui::layout::{Constraint, Direction, Layout};
+use tui::style::{Color, Modifier, Style};
+use tui::text::Spans;
+use tui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
+use tui::{Frame, Terminal};
+
+/// Time between redraws, and between checks for keys
+const REFRESH: Duration = Duration::from_millis(100);
+
+/// Lines scrolled by Page Up and Page Down
+const PAGE: usize = 10;
+
+/// Addon, status, step, warnings and time
+const WIDTHS: [Constraint; 5] = [
+    Constraint::Percentage(30),
+    Constraint::Length(8),
+    Constraint::Percentage(45),
+    Constraint::Length(8),
+    Constraint::Length(8),
+];
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    static ref STATE: Mutex<State> = Mutex::new(State::default());
+}
+
+#[derive(Default)]
+struct State {
+    addons: BTreeMap<String, Addon>,
+    logs: Vec<String>,
+    /// Lines scrolled up from the end of the log, the log follows new lines at 0
+    scroll: usize,
+}
+
+struct Addon {
+    /// Stage and task the addon is in
+    step: String,
+    status: Status,
+    warnings: usize,
+    start: Instant,
+    elapsed: Duration,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Running,
+    Done,
+    Failed,
+}
+
+/// Live table of the addons being built with a log below it, shown with `--tui`
+/// until it is dropped
+///
+/// The log is printed when the dashboard closes, so it stays in the terminal
+pub struct Dashboard {
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Shows the dashboard when it is enabled and the output is a terminal
+pub fn start() -> Option<Dashboard> {
+    if !*crate::TUI {
+        return None;
+    }
+    if let Err(e) =
+        terminal::enable_raw_mode().and_then(|()| execute!(std::io::stdout(), EnterAlternateScreen))
+    {
+        restore();
+        warn!("Unable to show the dashboard: {}", e);
+        return None;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+    let thread = std::thread::spawn(|| {
+        if let Err(e) = run() {
+            RUNNING.store(false, Ordering::SeqCst);
+            restore();
+            warn!("The dashboard stopped: {}", e);
+        }
+    });
+    Some(Dashboard {
+        thread: Some(thread),
+    })
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        restore();
+        for line in &STATE.lock().unwrap().logs {
+            println!("{}", line);
+        }
+    }
+}
+
+/// The addon started a task
+pub fn task(addon: &str, stage: &str, task: &str) {
+    if !*crate::TUI {
+        return;
+    }
+    let mut state = STATE.lock().unwrap();
+    let addon = state
+        .addons
+        .entry(addon.to_string())
+        .or_insert_with(|| Addon {
+            step: String::new(),
+            status: Status::Running,
+            warnings: 0,
+            start: Instant::now(),
+            elapsed: Duration::default(),
+        });
+    addon.step = format!("{} {}", stage, task);
+    addon.elapsed = addon.start.elapsed();
+}
+
+pub fn warning(addon: &str) {
+    if let Some(addon) = STATE.lock().unwrap().addons.get_mut(addon) {
+        addon.warnings += 1;
+    }
+}
+
+pub fn failed(addon: &str) {
+    if let Some(addon) = STATE.lock().unwrap().addons.get_mut(addon) {
+        addon.status = Status::Failed;
+        addon.elapsed = addon.start.elapsed();
+    }
+}
+
+/// Keeps a line of the log while the dashboard is shown, `false` when it is not
+pub fn capture(text: &str) -> bool {
+    if !RUNNING.load(Ordering::SeqCst) {
+        return false;
+    }
+    STATE
+        .lock()
+        .unwrap()
+        .logs
+        .extend(text.lines().map(str::to_string));
+    true
+}
+
+/// Every addon went through every task
+pub fn completed() {
+    for addon in STATE.lock().unwrap().addons.values_mut() {
+        if addon.status == Status::Running {
+            addon.status = Status::Done;
+            addon.elapsed = addon.start.elapsed();
+        }
+    }
+}
+
+fn restore() {
+    let _ = execute!(std::io::stdout(), LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+}
+
+fn run() -> Result<(), std::io::Error> {
+    let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+    terminal.hide_cursor()?;
+    while RUNNING.load(Ordering::SeqCst) {
+        if event::poll(REFRESH)? {
+            if let Event::Key(key) = event::read()? {
+                press(key);
+            }
+        }
+        terminal.draw(draw)?;
+    }
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+fn press(key: KeyEvent) {
+    // The terminal does not send Ctrl-C as a signal while the dashboard is shown
+    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        if crate::interrupt::interrupted() {
+            // The second Ctrl-C exits at once
+            restore();
+        }
+        crate::interrupt::request();
+        return;
+    }
+    let mut state = STATE.lock().unwrap();
+    let lines = state.logs.len();
+    state.scroll = match key.code {
+        KeyCode::Up => state.scroll + 1,
+        KeyCode::Down => state.scroll.saturating_sub(1),
+        KeyCode::PageUp => state.scroll + PAGE,
+        KeyCode::PageDown => state.scroll.saturating_sub(PAGE),
+        KeyCode::Home => lines,
+        KeyCode::End => 0,
+        _ => return,
+    }
+    .min(lines);
+}
+
+fn draw(f: &mut Frame<CrosstermBackend<Stdout>>) {
+    let state = STATE.lock().unwrap();
+    let size = f.size();
+    let height = (state.addons.len() as u16 + 3).min(size.height / 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(height), Constraint::Min(3)].as_ref())
+        .split(size);
+
+    let done = state
+        .addons
+        .values()
+        .filter(|addon| addon.status != Status::Running)
+        .count();
+    let rows = state.addons.iter().map(|(name, addon)| {
+        let (status, color) = match addon.status {
+            Status::Running => ("running", Color::Yellow),
+            Status::Done => ("done", Color::Green),
+            Status::Failed => ("failed", Color::Red),
+        };
+        let elapsed = if addon.status == Status::Running {
+            addon.start.elapsed()
+        } else {
+            addon.elapsed
+        };
+        let warnings = if addon.warnings > 0 {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(name.as_str()),
+            Cell::from(status).style(Style::default().fg(color)),
+            Cell::from(addon.step.as_str()),
+            Cell::from(addon.warnings.to_string()).style(warnings),
+            Cell::from(format!("{:.1}s", elapsed.as_secs_f32())),
+        ])
+    });
+    let table = Table::new(rows)
+        .header(
+            Row::new(vec!["Addon", "Status", "Step", "Warnings", "Time"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "Addons {}/{}",
+            done,
+            state.addons.len()
+        )))
+        .widths(&WIDTHS);
+    f.render_widget(table, chunks[0]);
+
+    let lines = chunks[1].height.saturating_sub(2) as usize;
+    let end = state.logs.len().saturating_sub(state.scroll);
+    let text: Vec<Spans> = state.logs[end.saturating_sub(lines)..end]
+        .iter()
+        .map(|line| Spans::from(line.as_str()))
+        .collect();
+    let title = if state.scroll > 0 {
+        format!("Log, {} lines up, End to follow", state.scroll)
+    } else {
+        String::from("Log, arrows and Page Up to scroll")
+    };
+    let log = Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(log, chunks[1]);
+}
@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+
+use hemtt::{enfusion, HEMTTError, Project};
+
+use crate::tasks::ReleaseOptions;
+
+/// Folders of the project that are not part of the Enfusion addon
+const EXCLUDED: [&str; 4] = ["releases", "keys", "deps", "include"];
+
+/// Files of the Enfusion addon, everything in the project except for HEMTT's
+/// own files and hidden folders
+fn files(root: &Path) -> Result<Vec<PathBuf>, HEMTTError> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !name.starts_with('.')
+                && !(entry.depth() == 1
+                    && (EXCLUDED.contains(&name.as_ref()) || name.starts_with("hemtt")))
+        })
+    {
+        let entry = entry.map_err(|e| HEMTTError::Generic(e.to_string()))?;
+        if entry.file_type().is_file() {
+            files.push(entry.path().strip_prefix(root).unwrap().to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+/// Validates the `addon.gproj` and the `*.conf` files of the project
+pub fn check(p: &Project) -> Result<(), HEMTTError> {
+    warn!("{} support is experimental", p.game);
+    let root = Project::find_root()?;
+    if !root.join(enfusion::GPROJ).exists() {
+        return Err(HEMTTError::UserHint(
+            format!("`{}` was not found", enfusion::GPROJ),
+            String::from(
+                "create the project in the Workbench, or with `hemtt template init reforger`",
+            ),
+        ));
+    }
+    let mut problems = 0;
+    for file in files(&root)? {
+        let gproj = file == Path::new(enfusion::GPROJ);
+        if !gproj && file.extension().map_or(true, |ext| ext != "conf") {
+            continue;
+        }
+        let nodes = match enfusion::parse(&std::fs::read_to_string(root.join(&file))?) {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                error!("{}: {}", file.display(), e);
+                problems += 1;
+                continue;
+            }
+        };
+        if gproj {
+            for problem in enfusion::validate_gproj(&nodes) {
+                error!("{}: {}", file.display(), problem);
+                problems += 1;
+            }
+        }
+    }
+    if problems != 0 {
+        return Err(HEMTTError::User(format!(
+            "{} problems found in the Enfusion metadata",
+            problems
+        )));
+    }
+    Ok(())
+}
+
+/// Copies the files of the Enfusion addon into a folder, replacing its contents
+pub fn package(target: &Path) -> Result<(), HEMTTError> {
+    let root = Project::find_root()?;
+    if crate::dry_run("package", target) {
+        return Ok(());
+    }
+    if target.exists() {
+        std::fs::remove_dir_all(target)?;
+    }
+    let files = files(&root)?;
+    for file in &files {
+        let dest = target.join(file);
+        create_dir!(dest.parent().unwrap())?;
+        copy_file!(root.join(file), &dest)?;
+    }
+    info!("Packaged {} files into {:?}", files.len(), target);
+    Ok(())
+}
+
+/// Builds an Enfusion project, the addon is validated and copied as is into the
+/// output folder, or into the release folder ready for the Workshop upload
+pub fn build(p: &Project, args: &clap::ArgMatches) -> Result<(), HEMTTError> {
+    check(p)?;
+    if !args.is_present("release") {
+        return package(&Project::output_dir()?.join(p.modname()));
+    }
+    let opts = ReleaseOptions::from_args(args, p)?;
+    package(&crate::tasks::release_root(p, &opts)?)?;
+    for (release, archive) in crate::tasks::release_archives(p, &opts)? {
+        info!("Archiving {:?} => {:?}", release, archive);
+        if crate::dry_run("create", &archive) {
+            continue;
+        }
+        create_dir!(archive.parent().unwrap())?;
+        crate::tasks::archive_folder(&release, &archive)?;
+    }
+    Ok(())
+}
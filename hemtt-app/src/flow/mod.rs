@@ -1,3 +1,4 @@
+use std::io::Write;
 use std::time::Instant;
 
 use rayon::prelude::*;
@@ -11,13 +12,19 @@ use crate::{
     context::{AddonListContext, Context},
     HEMTTError, Project,
 };
-use hemtt::Addon;
+use hemtt::{Addon, Diagnostic};
 
 // #[derive(Clone)]
 pub struct Flow {
     pub tasks: Vec<Box<dyn Task>>,
 }
 
+/// Tasks that every addon runs through in order, on its own
+///
+/// A slow addon only holds up the tasks after it for itself, the other addons
+/// keep moving through the pipeline.
+type Pipeline<'a> = Vec<(Stage, &'a dyn Task)>;
+
 impl Flow {
     /// Execute the flow against a vector of addons
     pub fn execute(&self, addons: Vec<Addon>, p: &Project) -> Result<(), HEMTTError> {
@@ -30,66 +37,157 @@ impl Flow {
         }
 
         let mut ctx_addons = ctx.get_list(addons)?;
-
-        for stage in Stage::all() {
-            for task in &self.tasks {
-                if ctx_addons.addons().is_empty() {
-                    continue;
-                }
-                if !ctx_addons.failed() && task.hooks().contains(&stage) {
-                    ctx_addons
-                        .global()
-                        .set_message_info(stage.to_string(), task.name());
-                    debug!(
-                        "[{}] [{:^width$}] Starting",
-                        stage,
-                        task.name(),
-                        width = ctx_addons.global().task_pad()
-                    );
-                    let start = Instant::now();
-                    self.call(&stage, &**task, &mut ctx_addons)?;
-                    let elapsed = start.elapsed();
-                    info!(
-                        "[{}] [{:^width$}] Completed in {} ms",
-                        stage,
-                        task.name(),
-                        elapsed.as_secs_f32() * 1000f32
-                            + elapsed.subsec_nanos() as f32 / 1_000_000f32,
-                        width = ctx_addons.global().task_pad()
-                    );
-                }
-            }
-        }
+        self.run(&mut ctx_addons)?;
 
         for addon in ctx_addons.addons() {
             if let Some(e) = addon.get_failed() {
                 error!("{}", e);
             }
         }
+        crate::report::write(&ctx_addons.global().diagnostics(), &ctx_addons.sources())?;
+        if *crate::TIMINGS {
+            let path = Project::find_root()?.join("hemtt-timings.html");
+            crate::report::timings::write(&ctx_addons.global().timings(), &path)?;
+            info!("Timings written to {}", path.display());
+        }
         Ok(())
     }
 
-    fn call(
+    /// Runs the flow against the addons with files replaced by the given contents,
+    /// returning the problems found instead of reporting them
+    ///
+    /// The files are only replaced in memory
+    /// Ex: `("addons/main/config.cpp", "class CfgPatches {};")`
+    pub fn diagnose(
+        &self,
+        addons: Vec<Addon>,
+        p: &Project,
+        files: &[(String, String)],
+    ) -> Result<Vec<Diagnostic>, HEMTTError> {
+        let mut ctx = Context::new(p)?;
+        for (path, text) in files {
+            ctx.fs()
+                .join(path)?
+                .create_file()?
+                .write_all(text.as_bytes())?;
+        }
+        let mut ctx_addons = ctx.get_list(addons)?;
+        if let Err(e) = self.run(&mut ctx_addons) {
+            // Addons that failed are in the diagnostics
+            if !ctx_addons.failed() {
+                return Err(e);
+            }
+        }
+        Ok(ctx_addons.global().diagnostics())
+    }
+
+    /// Runs every pipeline, until an addon fails
+    fn run(&self, ctx_addons: &mut AddonListContext) -> Result<(), HEMTTError> {
+        for pipeline in self.pipelines() {
+            if ctx_addons.addons().is_empty() || ctx_addons.failed() {
+                continue;
+            }
+            crate::interrupt::check()?;
+            let start = Instant::now();
+            for (stage, task) in &pipeline {
+                crate::interrupt::check()?;
+                ctx_addons
+                    .global()
+                    .set_message_info(stage.to_string(), task.name());
+                debug!(
+                    "[{}] [{:^width$}] Starting",
+                    stage,
+                    task.name(),
+                    width = ctx_addons.global().task_pad()
+                );
+                let _span = info_span!("task", stage = %stage, task = %task.name()).entered();
+                self.call_single(stage, *task, ctx_addons)?;
+            }
+            self.call(&pipeline, ctx_addons)?;
+            let elapsed = start.elapsed();
+            let (first, last) = (&pipeline[0].0, &pipeline[pipeline.len() - 1].0);
+            info!(
+                "[{}] [{:^width$}] Completed in {} ms",
+                if first == last {
+                    first.to_string()
+                } else {
+                    format!("{}-{}", first, last)
+                },
+                pipeline
+                    .iter()
+                    .map(|(_, task)| task.name())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                elapsed.as_secs_f32() * 1000f32 + elapsed.subsec_nanos() as f32 / 1_000_000f32,
+                width = ctx_addons.global().task_pad()
+            );
+        }
+        crate::dashboard::completed();
+        Ok(())
+    }
+
+    /// Splits the tasks of every stage into pipelines
+    ///
+    /// A pipeline ends before a stage or a task that waits for every addon
+    fn pipelines(&self) -> Vec<Pipeline> {
+        let mut pipelines: Vec<Pipeline> = Vec::new();
+        for stage in Stage::all() {
+            for task in self
+                .tasks
+                .iter()
+                .filter(|task| task.hooks().contains(&stage))
+            {
+                let waits = task.barrier(&stage)
+                    || (stage.barrier()
+                        && pipelines
+                            .last()
+                            .and_then(|pipeline| pipeline.last())
+                            .map_or(false, |(last, _)| *last != stage));
+                match pipelines.last_mut() {
+                    Some(pipeline) if !waits => pipeline.push((stage.clone(), &**task)),
+                    _ => pipelines.push(vec![(stage.clone(), &**task)]),
+                }
+            }
+        }
+        pipelines
+    }
+
+    fn call_single(
         &self,
         stage: &Stage,
         task: &dyn Task,
         addons: &mut AddonListContext,
     ) -> Result<(), HEMTTError> {
-        {
-            match stage {
-                Stage::Check => task.check_single(addons)?,
-                Stage::PreBuild => task.prebuild_single(addons)?,
-                Stage::Build => task.build_single(addons)?,
-                Stage::PostBuild => task.postbuild_single(addons)?,
-                Stage::Release => task.release_single(addons)?,
-                Stage::PostRelease => task.postrelease_single(addons)?,
-                Stage::Script => {}
-                Stage::None => {}
-            };
+        match stage {
+            Stage::Check => task.check_single(addons),
+            Stage::PreBuild => task.prebuild_single(addons),
+            Stage::Build => task.build_single(addons),
+            Stage::PostBuild => task.postbuild_single(addons),
+            Stage::Release => task.release_single(addons),
+            Stage::PostRelease => task.postrelease_single(addons),
+            Stage::Script => Ok(()),
+            Stage::None => Ok(()),
         }
+    }
+
+    fn call(
+        &self,
+        pipeline: &[(Stage, &dyn Task)],
+        addons: &mut AddonListContext,
+    ) -> Result<(), HEMTTError> {
         addons.mut_addons().par_iter_mut().for_each(|mut addon| {
-            if !addon.failed() {
-                match match stage {
+            let _span = info_span!("addon", addon = addon.addon().name()).entered();
+            for (stage, task) in pipeline {
+                if addon.failed() || crate::interrupt::interrupted() {
+                    break;
+                }
+                if addon.skip() && stage.skippable() {
+                    continue;
+                }
+                addon.set_message_info(stage.to_string(), task.name());
+                crate::dashboard::task(addon.addon().name(), &stage.to_string(), &task.name());
+                let _span = info_span!("task", stage = %stage, task = %task.name()).entered();
+                let result = match stage {
                     Stage::Check => task.check(&mut addon),
                     Stage::PreBuild => task.prebuild(&mut addon),
                     Stage::Build => task.build(&mut addon),
@@ -98,18 +196,17 @@ impl Flow {
                     Stage::PostRelease => task.postrelease(&mut addon),
                     Stage::Script => Ok(()),
                     Stage::None => Ok(()),
-                } {
-                    Ok(_) => {}
-                    Err(e) => {
-                        addon.set_failed(e);
-                    }
                 };
+                if let Err(e) = result {
+                    addon.set_failed(e);
+                }
             }
         });
-        let mut failed = false;
+        crate::interrupt::check()?;
+        let mut failed = 0;
         addons.addons().iter().for_each(|addon| {
             if addon.failed() {
-                failed = true;
+                failed += 1;
                 error!(
                     "Unable to build `{}`: {:?}",
                     addon.addon().source(),
@@ -117,9 +214,85 @@ impl Flow {
                 )
             }
         });
-        if failed {
-            std::process::exit(1);
+        if failed > 0 {
+            crate::report::write(&addons.global().diagnostics(), &addons.sources())?;
+            let failures: Vec<_> = addons.addons().iter().filter(|a| a.failed()).collect();
+            if failures
+                .iter()
+                .all(|a| matches!(a.get_failed(), Some(HEMTTError::Signing(_))))
+            {
+                return Err(HEMTTError::Signing(format!(
+                    "Unable to sign {} addon{}",
+                    failed,
+                    if failed == 1 { "" } else { "s" }
+                )));
+            }
+            if failures
+                .iter()
+                .all(|a| a.message_info().0 == Stage::Check.to_string())
+            {
+                return Err(HEMTTError::AddonsFailedChecks(failed));
+            }
+            return Err(HEMTTError::AddonsFailed(failed));
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Flow, Stage, Task};
+
+    /// Waits for the tasks before it
+    struct Waits(&'static str, [Stage; 1]);
+    impl Task for Waits {
+        fn name(&self) -> String {
+            self.0.to_string()
+        }
+        fn hooks(&self) -> &[Stage] {
+            &self.1
+        }
+    }
+
+    /// Opts out of waiting
+    struct Streams(&'static str, [Stage; 1]);
+    impl Task for Streams {
+        fn name(&self) -> String {
+            self.0.to_string()
+        }
+        fn hooks(&self) -> &[Stage] {
+            &self.1
+        }
+        fn barrier(&self, _: &Stage) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn pipelines() {
+        let flow = Flow {
+            tasks: vec![
+                Box::new(Streams("a", [Stage::Check])),
+                Box::new(Streams("b", [Stage::Check])),
+                Box::new(Waits("c", [Stage::Check])),
+                Box::new(Streams("d", [Stage::Check])),
+                Box::new(Streams("e", [Stage::PreBuild])),
+                Box::new(Streams("f", [Stage::Build])),
+                Box::new(Waits("g", [Stage::Build])),
+                Box::new(Streams("h", [Stage::Release])),
+            ],
+        };
+        let names = flow
+            .pipelines()
+            .iter()
+            .map(|pipeline| {
+                pipeline
+                    .iter()
+                    .map(|(_, task)| task.name())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["a,b", "c,d", "e,f", "g", "h"]);
+    }
+}
@@ -15,6 +15,17 @@ impl Stage {
         use Stage::*;
         vec![Check, PreBuild, Build, PostBuild, Release, PostRelease]
     }
+
+    /// Every addon finishes the stages before this one before any addon starts it,
+    /// checks can stop the build and a failed build is never released
+    pub fn barrier(&self) -> bool {
+        matches!(self, Self::PreBuild | Self::Release)
+    }
+
+    /// Addons marked as skipped do not run this stage
+    pub fn skippable(&self) -> bool {
+        matches!(self, Self::PreBuild | Self::Build | Self::PostBuild)
+    }
 }
 
 impl std::fmt::Display for Stage {
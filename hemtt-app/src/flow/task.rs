@@ -7,6 +7,14 @@ use crate::{
 pub trait Task: std::marker::Send + std::marker::Sync {
     fn name(&self) -> String;
     fn hooks(&self) -> &[super::Stage];
+    /// Whether the `_single` method of a stage waits for every addon to finish the
+    /// tasks before it, otherwise it runs before any addon reaches the task
+    ///
+    /// Tasks without a `_single` method, or whose `_single` method does not depend
+    /// on the earlier tasks, opt out so addons keep moving through the pipeline
+    fn barrier(&self, _: &super::Stage) -> bool {
+        true
+    }
     fn check(&self, _: &mut AddonContext) -> Result<(), HEMTTError> {
         Ok(())
     }
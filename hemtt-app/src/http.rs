@@ -0,0 +1,59 @@
+use std::io::BufReader;
+use std::sync::Arc;
+
+use hemtt::{HEMTTError, UserConfig};
+
+/// HTTP agent used by every network feature
+///
+/// The proxy is read from `proxy` in the user config, then the `HTTPS_PROXY`,
+/// `HTTP_PROXY` and `ALL_PROXY` environment variables. A `ca_bundle` in the
+/// user config is trusted in addition to the bundled root certificates.
+pub fn agent() -> Result<ureq::Agent, HEMTTError> {
+    online("access the network")?;
+    let config = UserConfig::read()?;
+    let mut builder = ureq::AgentBuilder::new();
+    if let Some(proxy) = config.proxy.clone().or_else(proxy_from_env) {
+        debug!("Using proxy {}", proxy);
+        builder = builder.proxy(
+            ureq::Proxy::new(&proxy)
+                .map_err(|e| HEMTTError::User(format!("Invalid proxy `{}`: {}", proxy, e)))?,
+        );
+    }
+    if let Some(bundle) = config.ca_bundle {
+        debug!("Trusting certificates from {:?}", bundle);
+        let mut tls = rustls::ClientConfig::new();
+        tls.root_store
+            .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+        tls.root_store
+            .add_pem_file(&mut BufReader::new(open_file!(bundle)?))
+            .map_err(|_| {
+                HEMTTError::User(format!("Invalid CA bundle {:?}, expected PEM", bundle))
+            })?;
+        builder = builder.tls_config(Arc::new(tls));
+    }
+    Ok(builder.build())
+}
+
+/// Fails if HEMTT is running with `--offline`
+pub fn online(action: &str) -> Result<(), HEMTTError> {
+    if *crate::OFFLINE {
+        return Err(HEMTTError::UserHint(
+            format!("Unable to {} in offline mode", action),
+            String::from("remove `--offline` and unset HEMTT_OFFLINE to allow network access"),
+        ));
+    }
+    Ok(())
+}
+
+fn proxy_from_env() -> Option<String> {
+    [
+        "HTTPS_PROXY",
+        "https_proxy",
+        "HTTP_PROXY",
+        "http_proxy",
+        "ALL_PROXY",
+        "all_proxy",
+    ]
+    .iter()
+    .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+}
@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use hemtt::HEMTTError;
+
+/// Exit code of a process stopped by Ctrl-C, the same as `HEMTTError::Interrupted`
+pub const EXIT_CODE: i32 = 130;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    /// Files that are removed when HEMTT is stopped before they are complete
+    static ref PARTIAL: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+}
+
+/// Catches Ctrl-C
+///
+/// The first Ctrl-C lets the steps that are running finish and stops the ones
+/// after them, the second removes the files being written and exits at once
+pub fn install() {
+    if let Err(e) = ctrlc::set_handler(request) {
+        debug!("Unable to handle Ctrl-C: {}", e);
+    }
+}
+
+/// Handles a Ctrl-C, also when it is read as a key instead of a signal
+pub fn request() {
+    if INTERRUPTED.swap(true, Ordering::SeqCst) {
+        cleanup();
+        std::process::exit(EXIT_CODE);
+    }
+    warn!("Stopping after the current steps, press Ctrl-C again to stop now");
+}
+
+/// Whether Ctrl-C was pressed
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Fails once Ctrl-C was pressed, between the steps of long operations
+pub fn check() -> Result<(), HEMTTError> {
+    if interrupted() {
+        Err(HEMTTError::Interrupted)
+    } else {
+        Ok(())
+    }
+}
+
+/// Removes every file that is not complete
+fn cleanup() {
+    for path in PARTIAL.lock().unwrap().drain(..) {
+        if path.exists() {
+            warn!("Removing the incomplete {:?}", path);
+            let _ = std::fs::remove_file(hemtt::long_path(&path));
+        }
+    }
+}
+
+/// A file being written, it is removed if it is not completed
+///
+/// Ex: a PBO that is packed or an archive that is zipped
+pub struct Partial {
+    path: PathBuf,
+    complete: bool,
+}
+
+impl Partial {
+    pub fn new(path: &Path) -> Self {
+        PARTIAL.lock().unwrap().push(path.to_path_buf());
+        Self {
+            path: path.to_path_buf(),
+            complete: false,
+        }
+    }
+
+    /// The file was written completely and is kept
+    pub fn complete(mut self) {
+        self.complete = true;
+    }
+}
+
+impl Drop for Partial {
+    fn drop(&mut self) {
+        PARTIAL.lock().unwrap().retain(|path| path != &self.path);
+        if !self.complete && self.path.exists() {
+            debug!("Removing the incomplete {:?}", self.path);
+            let _ = std::fs::remove_file(hemtt::long_path(&self.path));
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::time::Instant;
 
 #[macro_use]
-extern crate log;
+extern crate tracing;
 
 #[macro_use]
 extern crate hemtt_macros;
@@ -10,21 +10,57 @@ extern crate hemtt_macros;
 use clap::App;
 use hemtt::{Addon, HEMTTError, Project};
 
+mod build_lock;
 mod ci;
 mod command;
 mod commands;
 mod context;
+mod dashboard;
+mod enfusion;
 mod flow;
+mod http;
+mod interrupt;
+mod logging;
+mod notify;
+mod plugin;
+mod report;
 mod startup;
+mod steam;
 mod tasks;
 
 use command::Command;
 use flow::{Flow, Stage, Task};
+pub use logging::init_logging;
 
 lazy_static::lazy_static! {
     pub static ref CI: bool = std::env::args().any(|x| x == "--ci") || ci::is_ci();
     pub static ref DEBUG: bool = std::env::args().any(|x| x == "--debug");
     pub static ref TRACE: bool = std::env::args().any(|x| x == "--trace");
+    pub static ref DRY_RUN: bool = std::env::args().any(|x| x == "--dry-run");
+    pub static ref TIMINGS: bool = std::env::args().any(|x| x == "--timings");
+    /// The dashboard needs a terminal, the output is unchanged otherwise
+    pub static ref TUI: bool = std::env::args().any(|x| x == "--tui")
+        && !*CI
+        && crossterm::tty::IsTty::is_tty(&std::io::stdout());
+    pub static ref OFFLINE: bool = std::env::args().any(|x| x == "--offline")
+        || std::env::var("HEMTT_OFFLINE").map_or(false, |v| !v.is_empty() && v != "0" && v != "false");
+    pub static ref JSON_LOG: bool = {
+        let args: Vec<String> = std::env::args().collect();
+        args.windows(2).any(|w| w[0] == "--log-format" && w[1] == "json")
+            || args.iter().any(|x| x == "--log-format=json")
+    };
+    pub static ref REPORT: Option<String> = {
+        let args: Vec<String> = std::env::args().collect();
+        args.iter()
+            .enumerate()
+            .find_map(|(i, x)| {
+                if x == "--report" {
+                    args.get(i + 1).cloned()
+                } else {
+                    x.strip_prefix("--report=").map(str::to_string)
+                }
+            })
+    };
 
     pub static ref VERSION: &'static str = {
         let mut version = env!("CARGO_PKG_VERSION").to_string();
@@ -39,17 +75,29 @@ lazy_static::lazy_static! {
     };
 }
 
-static GIT_IGNORE: [&str; 4] = ["releases/*", "*.biprivatekey", "keys/*", ".hemtt/local*"];
+static GIT_IGNORE: [&str; 7] = [
+    "releases/*",
+    "*.biprivatekey",
+    "keys/*",
+    ".hemtt/local*",
+    "hemtt.local*",
+    "deps/*",
+    ".hemttout/*",
+];
 
 pub fn execute(input: &[String], root: bool) -> Result<(), HEMTTError> {
     rayon::ThreadPoolBuilder::new()
         .num_threads(1usize)
         .build_global()
         .unwrap();
+    if root {
+        interrupt::install();
+    }
 
     let mut app = App::new("HEMTT")
         .version(*crate::VERSION)
         .about(env!("CARGO_PKG_DESCRIPTION"))
+        .setting(clap::AppSettings::AllowExternalSubcommands)
         .arg(
             clap::Arg::with_name("debug")
                 .global(true)
@@ -62,21 +110,82 @@ pub fn execute(input: &[String], root: bool) -> Result<(), HEMTTError> {
                 .help("Turn trace information on")
                 .long("trace"),
         )
+        .arg(
+            clap::Arg::with_name("dry-run")
+                .global(true)
+                .help("Print the files that would be changed without changing them")
+                .long("dry-run"),
+        )
+        .arg(
+            clap::Arg::with_name("log-format")
+                .global(true)
+                .help("Format of the log in the terminal, JSON lines include the addon and task")
+                .long("log-format")
+                .takes_value(true)
+                .possible_values(&["text", "json"]),
+        )
+        .arg(
+            clap::Arg::with_name("offline")
+                .global(true)
+                .help("Fail instead of accessing the network, also enabled by HEMTT_OFFLINE")
+                .long("offline"),
+        )
+        .arg(
+            clap::Arg::with_name("report")
+                .global(true)
+                .help("Write the problems found to a report")
+                .long("report")
+                .takes_value(true)
+                .possible_values(&["github", "junit", "sarif"]),
+        )
         .arg(
             clap::Arg::with_name("time")
                 .global(true)
                 .help("Time the execution")
                 .long("time"),
+        )
+        .arg(
+            clap::Arg::with_name("timings")
+                .global(true)
+                .help("Write the time every task took for every addon to hemtt-timings.html")
+                .long("timings"),
+        )
+        .arg(
+            clap::Arg::with_name("tui")
+                .global(true)
+                .help("Show a live table of the addons and a scrollable log while building")
+                .long("tui"),
         );
 
     let mut commands: Vec<Box<dyn Command>> = Vec::new();
     let mut hash_commands: HashMap<String, &Box<dyn Command>> = HashMap::new();
 
+    commands.push(Box::new(commands::Auth {}));
     commands.push(Box::new(commands::Bug {}));
     commands.push(Box::new(commands::Build {}));
+    commands.push(Box::new(commands::Check {}));
     commands.push(Box::new(commands::Clean {}));
+    commands.push(Box::new(commands::Config {}));
+    commands.push(Box::new(commands::Create {}));
+    commands.push(Box::new(commands::Deps {}));
+    commands.push(Box::new(commands::Dev {}));
+    commands.push(Box::new(commands::Docs {}));
+    commands.push(Box::new(commands::Explain {}));
+    commands.push(Box::new(commands::Fmt {}));
+    commands.push(Box::new(commands::Gen {}));
+    commands.push(Box::new(commands::Graph {}));
+    commands.push(Box::new(commands::Lsp {}));
+    commands.push(Box::new(commands::Patch {}));
+    commands.push(Box::new(commands::Photoshoot {}));
     commands.push(Box::new(commands::Project {}));
+    commands.push(Box::new(commands::Publish {}));
+    commands.push(Box::new(commands::Run {}));
+    commands.push(Box::new(commands::Scopes {}));
+    commands.push(Box::new(commands::Sign {}));
+    commands.push(Box::new(commands::Stats {}));
     commands.push(Box::new(commands::Template {}));
+    commands.push(Box::new(commands::Update {}));
+    commands.push(Box::new(commands::Verify {}));
 
     for command in commands.iter() {
         let sub = command.register();
@@ -98,10 +207,16 @@ pub fn execute(input: &[String], root: bool) -> Result<(), HEMTTError> {
                 let sub_matches = matches.subcommand_matches(v).unwrap();
                 if root && c.can_announce() {
                     info!("HEMTT {}", *crate::VERSION);
+                    if !*CI {
+                        commands::check_for_update();
+                    }
                 }
                 if c.require_project() {
                     let project = Project::read()?;
                     // info!("Environment: {}", project::environment());
+                    if root {
+                        notify::check_hooks(&project);
+                    }
                     if root && c.can_announce() {
                         info!("{} {}", project.name(), project.version());
                         startup::startup();
@@ -111,7 +226,7 @@ pub fn execute(input: &[String], root: bool) -> Result<(), HEMTTError> {
                     c.run_no_project(sub_matches)?;
                 }
             }
-            None => error!("No command"),
+            None => commands::external::run(v, matches.subcommand_matches(v).unwrap())?,
         },
         None => error!("No command"),
     }
@@ -128,6 +243,15 @@ pub fn execute(input: &[String], root: bool) -> Result<(), HEMTTError> {
     Ok(())
 }
 
+/// Prints the change to the filesystem when running with `--dry-run`,
+/// returns true if the change should be skipped
+pub fn dry_run<P: std::fmt::Debug>(action: &str, path: P) -> bool {
+    if *DRY_RUN {
+        info!("[dry-run] {} {:?}", action, path);
+    }
+    *DRY_RUN
+}
+
 pub fn log_path(new: bool) -> std::path::PathBuf {
     let mut path = std::env::temp_dir();
     if new {
@@ -147,8 +271,9 @@ pub fn get_addons_from_args(args: &clap::ArgMatches) -> Result<Vec<Addon>, HEMTT
     use hemtt::project::{addon_matches, get_addon_from_location, get_addon_from_locations};
     use hemtt::AddonLocation;
     let all = args.value_of("addons").unwrap_or("") == "all";
+    // Campaigns are selected with the addons
     let mut addons: Vec<Addon> = if args.is_present("addons") && !all {
-        get_addon_from_location(&AddonLocation::Addons)?
+        get_addon_from_locations(&[AddonLocation::Addons, AddonLocation::Campaigns])?
             .into_iter()
             .filter(|a| {
                 args.values_of("addons")
@@ -156,7 +281,9 @@ pub fn get_addons_from_args(args: &clap::ArgMatches) -> Result<Vec<Addon>, HEMTT
                     .any(|x| addon_matches(a.name(), x))
             })
             .collect()
-    } else if all || (!args.is_present("opts") && !args.is_present("compats")) {
+    } else if all {
+        get_addon_from_locations(&[AddonLocation::Addons, AddonLocation::Campaigns])?
+    } else if !args.is_present("opts") && !args.is_present("compats") {
         get_addon_from_location(&AddonLocation::Addons)?
     } else {
         Vec::new()
@@ -193,7 +320,12 @@ pub fn get_addons_from_args(args: &clap::ArgMatches) -> Result<Vec<Addon>, HEMTT
         addons.extend(get_addon_from_locations(&[
             AddonLocation::Optionals,
             AddonLocation::Compats,
+            AddonLocation::Campaigns,
         ])?);
     }
+    if let Some(filters) = args.values_of("filter") {
+        let filters: Vec<&str> = filters.collect();
+        addons.retain(|a| filters.iter().any(|x| addon_matches(a.name(), x)));
+    }
     Ok(addons)
 }
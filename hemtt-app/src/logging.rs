@@ -0,0 +1,144 @@
+use std::io::Write;
+use std::sync::Mutex;
+
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::{self, format, FmtContext, FormatEvent, FormatFields, MakeWriter};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Sends the log to the terminal, as text or as JSON with `--log-format json`, and
+/// every line with its spans to the log file
+///
+/// Addons and the tasks that run for them are spans, `--timings` is recorded
+/// from the spans of the tasks
+pub fn init_logging(level: LevelFilter, lsp: bool) {
+    let terminal = Terminal { lsp };
+    let text = if *crate::JSON_LOG {
+        None
+    } else {
+        Some(
+            fmt::layer()
+                .event_format(Line {
+                    target: level == LevelFilter::TRACE,
+                })
+                .with_ansi(
+                    !lsp && !*crate::TUI && crossterm::tty::IsTty::is_tty(&std::io::stdout()),
+                )
+                .with_writer(terminal)
+                .with_filter(level),
+        )
+    };
+    let json = if *crate::JSON_LOG {
+        Some(
+            fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .with_writer(terminal)
+                .with_filter(level),
+        )
+    } else {
+        None
+    };
+    let file = fmt::layer()
+        .with_ansi(false)
+        .with_writer(Mutex::new(create_file!(crate::log_path(true)).unwrap()))
+        .with_filter(LevelFilter::TRACE);
+    let timings = if *crate::TIMINGS {
+        Some(crate::report::timings::Recorder)
+    } else {
+        None
+    };
+    tracing_subscriber::registry()
+        .with(text)
+        .with(json)
+        .with(file)
+        .with(timings)
+        .init();
+}
+
+/// Ex: "[WARN ] Removing the incomplete "releases/1.0.0.zip.partial""
+struct Line {
+    /// Where the line was logged from, for `--trace`
+    target: bool,
+}
+
+impl<S, N> FormatEvent<S, N> for Line
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: format::Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let metadata = event.metadata();
+        let level = format!("{:<5}", metadata.level());
+        if writer.has_ansi_escapes() {
+            let color = match *metadata.level() {
+                Level::ERROR => 31,
+                Level::WARN => 33,
+                Level::INFO => 32,
+                Level::DEBUG => 34,
+                _ => 35,
+            };
+            write!(writer, "[\x1b[{}m{}\x1b[0m] ", color, level)?;
+        } else {
+            write!(writer, "[{}] ", level)?;
+        }
+        if self.target {
+            write!(writer, "{}: ", metadata.target())?;
+        }
+        ctx.format_fields(writer.by_ref(), event)?;
+        writeln!(writer)
+    }
+}
+
+/// Warnings and errors go to stderr and the rest to stdout, everything goes to
+/// stderr for the language server and to the dashboard while it is shown
+#[derive(Clone, Copy)]
+struct Terminal {
+    lsp: bool,
+}
+
+impl<'a> MakeWriter<'a> for Terminal {
+    type Writer = TerminalWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        TerminalWriter { stderr: self.lsp }
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        TerminalWriter {
+            stderr: self.lsp || *meta.level() <= Level::WARN,
+        }
+    }
+}
+
+struct TerminalWriter {
+    stderr: bool,
+}
+
+impl Write for TerminalWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if !crate::dashboard::capture(&String::from_utf8_lossy(buf)) {
+            if self.stderr {
+                std::io::stderr().write_all(buf)?;
+            } else {
+                std::io::stdout().write_all(buf)?;
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.stderr {
+            std::io::stderr().flush()
+        } else {
+            std::io::stdout().flush()
+        }
+    }
+}
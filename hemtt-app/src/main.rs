@@ -1,53 +1,31 @@
 #[macro_use]
-extern crate log;
+extern crate tracing;
 
-#[macro_use]
-extern crate hemtt_macros;
-
-use simplelog::{
-    ColorChoice, CombinedLogger, ConfigBuilder, LevelFilter, LevelPadding, TermLogger,
-    TerminalMode, WriteLogger,
-};
+use tracing::level_filters::LevelFilter;
 
 use hemtt_app::*;
 
 fn main() {
     let args: Vec<_> = std::env::args().collect();
 
-    let config = ConfigBuilder::new()
-        .set_location_level(LevelFilter::Trace)
-        .set_target_level(LevelFilter::Trace)
-        .set_thread_level(LevelFilter::Trace)
-        .set_time_level(LevelFilter::Off)
-        .set_level_padding(LevelPadding::Right)
-        .build();
-
     let level = match (*DEBUG, *TRACE) {
-        (_, true) => LevelFilter::Trace,
-        (true, _) => LevelFilter::Debug,
-        _ => LevelFilter::Info,
+        (_, true) => LevelFilter::TRACE,
+        (true, _) => LevelFilter::DEBUG,
+        _ => LevelFilter::INFO,
     };
-    CombinedLogger::init(vec![
-        TermLogger::new(
-            level,
-            config.clone(),
-            TerminalMode::Mixed,
-            ColorChoice::Auto,
-        ),
-        WriteLogger::new(
-            LevelFilter::Trace,
-            config,
-            create_file!(log_path(true)).unwrap(),
-        ),
-    ])
-    .unwrap();
+    // The language server talks to the editor on stdout
+    init_logging(level, args.get(1).map(String::as_str) == Some("lsp"));
 
     debug!("args: {:?}", args);
     if let Err(e) = crate::execute(&args, true) {
-        error!("{}", e);
+        error!("[{}] {}", e.code(), e);
+        info!(
+            "For more information about this error, try `hemtt explain {}`",
+            e.code()
+        );
         if !*CI && e.can_submit_bug() {
             println!("Do you want to submit a bug report?");
         }
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     }
 }
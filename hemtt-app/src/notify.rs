@@ -0,0 +1,246 @@
+use std::io::Write;
+use std::process::Stdio;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use hemtt::{HEMTTError, Hook, Project};
+use hemtt_handlebars::Variables;
+use serde_json::{json, Value as Json};
+
+const SUCCESS_COLOR: u32 = 0x2ecc71;
+const FAILURE_COLOR: u32 = 0xe74c3c;
+
+/// Announces that a release or publish completed or failed, to the project's
+/// notification targets and the hooks of the event
+///
+/// A notification that can not be sent is only a warning, the release itself
+/// already happened
+///
+/// Arguments:
+/// * `kind`: "release" or "publish", the event is `{kind}.completed` or `{kind}.failed`
+/// * `action`: what was done, Ex: "Release", "Publish to the workshop"
+/// * `changelog`: changes of the version, read from CHANGELOG.md when not given
+pub fn notify<T>(
+    p: &Project,
+    kind: &str,
+    action: &str,
+    start: Instant,
+    result: &Result<T, HEMTTError>,
+    changelog: Option<&str>,
+) {
+    finished(p, kind, start, result, json!({ "action": action }));
+    let webhook = match p.notifications.discord_webhook() {
+        Some(webhook) => webhook,
+        None => return,
+    };
+    if *crate::DRY_RUN {
+        info!("[dry-run] notify Discord");
+        return;
+    }
+    let message = discord_message(p, action, start, result, changelog);
+    match post(&webhook, &message) {
+        Ok(()) => debug!("Notified Discord"),
+        Err(e) => warn!("Unable to notify Discord: {}", e),
+    }
+}
+
+/// Triggers the hooks of `{kind}.completed` or `{kind}.failed`, with the duration
+/// and the error in the event
+pub fn finished<T>(
+    p: &Project,
+    kind: &str,
+    start: Instant,
+    result: &Result<T, HEMTTError>,
+    mut details: Json,
+) {
+    details["duration"] = json!(start.elapsed().as_secs());
+    let status = match result {
+        Ok(_) => "completed",
+        Err(e) => {
+            details["error"] = Json::String(e.to_string());
+            "failed"
+        }
+    };
+    event(p, &format!("{}.{}", kind, status), details);
+}
+
+/// Warns about the events of hooks that do not exist, when the project is loaded,
+/// as a hook with only misspelled events is never triggered
+pub fn check_hooks(p: &Project) {
+    for hook in &p.notifications.hooks {
+        for unknown in hook.unknown_events() {
+            warn!(
+                "Hook event `{}` does not exist, events are: {}",
+                unknown,
+                hemtt::EVENTS.join(", ")
+            );
+        }
+    }
+}
+
+/// Triggers the hooks of an event
+///
+/// The event is posted as JSON to the hooks with a `url`, and written to the
+/// input of the hooks with a `command`, which also have it in `HEMTT_EVENT`
+pub fn event(p: &Project, event: &str, details: Json) {
+    let hooks: Vec<&Hook> = p
+        .notifications
+        .hooks
+        .iter()
+        .filter(|hook| hook.handles(event))
+        .collect();
+    if hooks.is_empty() {
+        return;
+    }
+    let mut payload = json!({
+        "event": event,
+        "project": {
+            "name": p.name(),
+            "prefix": p.prefix(),
+            "version": p.version().to_string(),
+        },
+        "timestamp": SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or_default(),
+    });
+    if let Json::Object(details) = details {
+        for (key, value) in details {
+            payload[key] = value;
+        }
+    }
+    for hook in hooks {
+        if *crate::DRY_RUN {
+            info!("[dry-run] trigger hook for `{}`", event);
+            continue;
+        }
+        if let Some(url) = &hook.url {
+            if let Err(e) = post(url, &payload) {
+                warn!("Unable to post `{}` to {}: {}", event, url, e);
+            }
+        }
+        if !hook.command.is_empty() {
+            if let Err(e) = run(&hook.command, event, &payload) {
+                warn!(
+                    "Hook `{}` failed for `{}`: {}",
+                    hook.command.join(" "),
+                    event,
+                    e
+                );
+            }
+        }
+    }
+}
+
+fn post(url: &str, body: &Json) -> Result<(), HEMTTError> {
+    crate::http::agent()?
+        .post(url)
+        .set("Content-Type", "application/json")
+        .send_string(&body.to_string())
+        .map_err(|e| HEMTTError::Network(format!("Unable to reach `{}`: {}", url, e)))?;
+    Ok(())
+}
+
+fn run(command: &[String], event: &str, payload: &Json) -> Result<(), HEMTTError> {
+    debug!("Running hook `{}` for `{}`", command.join(" "), event);
+    let mut child = std::process::Command::new(&command[0])
+        .args(&command[1..])
+        .current_dir(Project::find_root()?)
+        .env("HEMTT_EVENT", event)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    let written = match child.stdin.take() {
+        Some(mut stdin) => stdin.write_all(payload.to_string().as_bytes()),
+        None => Ok(()),
+    };
+    // The hook is waited for even when it did not read its input
+    let status = child.wait()?;
+    if let Err(e) = written {
+        if e.kind() != std::io::ErrorKind::BrokenPipe {
+            return Err(e.into());
+        }
+    }
+    if status.success() {
+        Ok(())
+    } else {
+        Err(HEMTTError::Generic(format!("exited with {}", status)))
+    }
+}
+
+fn discord_message<T>(
+    p: &Project,
+    action: &str,
+    start: Instant,
+    result: &Result<T, HEMTTError>,
+    changelog: Option<&str>,
+) -> Json {
+    let title = format!("{} {}", p.name(), p.version());
+    let mut fields = Vec::new();
+    let mut embed = match result {
+        Ok(_) => {
+            let links = links(p);
+            if !links.is_empty() {
+                fields.push(json!({ "name": "Links", "value": links.join("\n") }));
+            }
+            json!({
+                "title": format!("{}: {} completed", title, action),
+                "description": changelog
+                    .map(String::from)
+                    .or_else(|| changelog_excerpt(p))
+                    .unwrap_or_default(),
+                "color": SUCCESS_COLOR,
+            })
+        }
+        Err(e) => json!({
+            "title": format!("{}: {} failed", title, action),
+            "description": format!("```\n{}\n```", e),
+            "color": FAILURE_COLOR,
+        }),
+    };
+    fields.push(json!({
+        "name": "Duration",
+        "value": duration(start.elapsed().as_secs()),
+        "inline": true,
+    }));
+    embed["fields"] = Json::Array(fields);
+    if !p.url.is_empty() {
+        embed["url"] = Json::String(p.url.clone());
+    }
+    json!({
+        "username": "HEMTT",
+        "embeds": [embed],
+    })
+}
+
+/// Markdown links to the workshop item and the project's `links`
+fn links(p: &Project) -> Vec<String> {
+    let mut links = Vec::new();
+    if let Some(id) = p.workshop {
+        links.push(format!(
+            "[Steam Workshop](https://steamcommunity.com/sharedfiles/filedetails/?id={})",
+            id
+        ));
+    }
+    let vars = Variables::from(p);
+    for (name, template) in &p.notifications.links {
+        match hemtt_handlebars::render(template, &vars) {
+            Ok(url) => links.push(format!("[{}]({})", name, url)),
+            Err(e) => warn!("Invalid link `{}`: {}", name, e),
+        }
+    }
+    links
+}
+
+fn changelog_excerpt(p: &Project) -> Option<String> {
+    let changelog =
+        std::fs::read_to_string(Project::find_root().ok()?.join("CHANGELOG.md")).ok()?;
+    hemtt::changelog_excerpt(&changelog, &p.version().to_string())
+}
+
+/// Ex: "1m 32s"
+fn duration(seconds: u64) -> String {
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    }
+}
@@ -0,0 +1,307 @@
+use std::io::{Read, Write};
+use std::path::Path;
+
+use hemtt::HEMTTError;
+use vfs::VfsPath;
+use wasmtime::{Caller, Config, Engine, Linker, Module, Store};
+
+use crate::{context::AddonContext, Stage, Task};
+
+/// Folder containing the project's plugins
+pub const PLUGINS_DIR: &str = ".hemtt/plugins";
+
+/// Instructions a plugin can run for each addon before it is stopped, so a plugin
+/// that never returns fails the build instead of hanging it
+const FUEL: u64 = 1_000_000_000;
+
+/// Exports that register a plugin for a stage, each is called once per addon
+const EXPORTS: [(&str, Stage); 6] = [
+    ("hemtt_check", Stage::Check),
+    ("hemtt_prebuild", Stage::PreBuild),
+    ("hemtt_build", Stage::Build),
+    ("hemtt_postbuild", Stage::PostBuild),
+    ("hemtt_release", Stage::Release),
+    ("hemtt_postrelease", Stage::PostRelease),
+];
+
+/// Build stages provided by a WebAssembly module in `.hemtt/plugins/`
+///
+/// The module registers a stage by exporting `hemtt_<stage>`, a function without
+/// parameters returning an `i32`, where any value other than 0 fails the addon.
+/// It must export its `memory` and can import the following from `hemtt`:
+///
+/// * `log(level: i32, msg: *u8, msg_len: i32)`: 0 error, 1 warn, 2 info, 3 debug, 4 trace
+/// * `addon(buf: *u8, buf_len: i32) -> i64`: the addon as JSON, with `name`, `source` and `prefix`
+/// * `read_file(path: *u8, path_len: i32, buf: *u8, buf_len: i32) -> i64`
+/// * `write_file(path: *u8, path_len: i32, data: *u8, data_len: i32) -> i32`
+/// * `list_dir(path: *u8, path_len: i32, buf: *u8, buf_len: i32) -> i64`: entries separated by `\n`
+///
+/// Paths are relative to the project root and use the build's virtual filesystem,
+/// so files written by a plugin are packed without changing the project.
+/// Functions filling a buffer return the full length of the data, or -1 on error,
+/// if it is larger than the buffer they can be called again with a larger buffer.
+/// Each call can run about a billion instructions before the plugin is stopped.
+pub struct Plugin {
+    name: String,
+    engine: Engine,
+    linker: Linker<State>,
+    module: Module,
+    hooks: Vec<Stage>,
+}
+
+impl Plugin {
+    pub fn new(path: &Path) -> Result<Self, HEMTTError> {
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        let unable = |e: &dyn std::fmt::Display| {
+            HEMTTError::User(format!("Unable to load plugin {:?}: {}", path, e))
+        };
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|e| unable(&e))?;
+        let module = Module::from_file(&engine, path).map_err(|e| unable(&e))?;
+        let linker = linker(&engine).map_err(|e| unable(&e))?;
+        let hooks = EXPORTS
+            .iter()
+            .filter(|(export, _)| module.exports().any(|e| e.name() == *export))
+            .map(|(_, stage)| stage.clone())
+            .collect::<Vec<_>>();
+        if hooks.is_empty() {
+            warn!("Plugin `{}` does not export any stages", name);
+        }
+        Ok(Self {
+            name,
+            engine,
+            linker,
+            module,
+            hooks,
+        })
+    }
+
+    /// Every plugin in the project's plugins folder
+    pub fn load() -> Result<Vec<Box<dyn Task>>, HEMTTError> {
+        let mut plugins: Vec<Box<dyn Task>> = Vec::new();
+        if !Path::new(PLUGINS_DIR).is_dir() {
+            return Ok(plugins);
+        }
+        let mut paths = std::fs::read_dir(PLUGINS_DIR)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.sort();
+        for path in paths {
+            if path.extension().map_or(false, |e| e == "wasm") {
+                debug!("Loading plugin {:?}", path);
+                plugins.push(Box::new(Self::new(&path)?));
+            }
+        }
+        Ok(plugins)
+    }
+
+    fn call(&self, stage: &Stage, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let export = EXPORTS.iter().find(|(_, s)| s == stage).unwrap().0;
+        let addon = serde_json::json!({
+            "name": ctx.addon().name(),
+            "source": ctx.addon().source(),
+            "prefix": ctx.prefix(),
+        })
+        .to_string();
+        let mut store = Store::new(
+            &self.engine,
+            State {
+                plugin: self.name.clone(),
+                fs: ctx.global().fs().clone(),
+                addon,
+            },
+        );
+        let failed = |e: &dyn std::fmt::Display| {
+            HEMTTError::Generic(format!("Plugin `{}` failed: {}", self.name, e))
+        };
+        store.add_fuel(FUEL).map_err(|e| failed(&e))?;
+        let instance = self
+            .linker
+            .instantiate(&mut store, &self.module)
+            .map_err(|e| failed(&e))?;
+        let func = instance
+            .get_typed_func::<(), i32, _>(&mut store, export)
+            .map_err(|e| failed(&e))?;
+        let code = func.call(&mut store, ()).map_err(|e| {
+            if store.fuel_consumed() >= Some(FUEL) {
+                HEMTTError::Generic(format!(
+                    "Plugin `{}` was stopped after running {} instructions",
+                    self.name, FUEL
+                ))
+            } else {
+                failed(&e)
+            }
+        })?;
+        if code != 0 {
+            return Err(HEMTTError::Generic(format!(
+                "Plugin `{}` failed with code {}",
+                self.name, code
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Task for Plugin {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &self.hooks
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        self.call(&Stage::Check, ctx)
+    }
+
+    fn prebuild(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        self.call(&Stage::PreBuild, ctx)
+    }
+
+    fn build(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        self.call(&Stage::Build, ctx)
+    }
+
+    fn postbuild(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        self.call(&Stage::PostBuild, ctx)
+    }
+
+    fn release(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        self.call(&Stage::Release, ctx)
+    }
+
+    fn postrelease(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        self.call(&Stage::PostRelease, ctx)
+    }
+}
+
+struct State {
+    plugin: String,
+    fs: VfsPath,
+    addon: String,
+}
+
+fn linker(engine: &Engine) -> Result<Linker<State>, anyhow::Error> {
+    let mut linker = Linker::new(engine);
+    linker.func_wrap(
+        "hemtt",
+        "log",
+        |caller: Caller<'_, State>, level: i32, ptr: i32, len: i32| {
+            let message = read_string(&caller, ptr, len).unwrap_or_default();
+            let plugin = &caller.data().plugin;
+            match level {
+                0 => error!("[{}] {}", plugin, message),
+                1 => warn!("[{}] {}", plugin, message),
+                2 => info!("[{}] {}", plugin, message),
+                3 => debug!("[{}] {}", plugin, message),
+                _ => trace!("[{}] {}", plugin, message),
+            }
+        },
+    )?;
+    linker.func_wrap(
+        "hemtt",
+        "addon",
+        |mut caller: Caller<'_, State>, buf: i32, buf_len: i32| {
+            let addon = caller.data().addon.clone();
+            write_buffer(&mut caller, buf, buf_len, addon.as_bytes())
+        },
+    )?;
+    linker.func_wrap(
+        "hemtt",
+        "read_file",
+        |mut caller: Caller<'_, State>, ptr: i32, len: i32, buf: i32, buf_len: i32| {
+            let data = path(&caller, ptr, len).and_then(|path| {
+                let mut data = Vec::new();
+                path.open_file().ok()?.read_to_end(&mut data).ok()?;
+                Some(data)
+            });
+            match data {
+                Some(data) => write_buffer(&mut caller, buf, buf_len, &data),
+                None => -1,
+            }
+        },
+    )?;
+    linker.func_wrap(
+        "hemtt",
+        "write_file",
+        |caller: Caller<'_, State>, ptr: i32, len: i32, data: i32, data_len: i32| {
+            let written = path(&caller, ptr, len).and_then(|path| {
+                let data = read_bytes(&caller, data, data_len)?;
+                path.parent()?.create_dir_all().ok()?;
+                path.create_file().ok()?.write_all(&data).ok()
+            });
+            if written.is_some() {
+                0
+            } else {
+                -1
+            }
+        },
+    )?;
+    linker.func_wrap(
+        "hemtt",
+        "list_dir",
+        |mut caller: Caller<'_, State>, ptr: i32, len: i32, buf: i32, buf_len: i32| {
+            let entries = path(&caller, ptr, len).and_then(|path| {
+                Some(
+                    path.read_dir()
+                        .ok()?
+                        .map(|entry| entry.filename())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                )
+            });
+            match entries {
+                Some(entries) => write_buffer(&mut caller, buf, buf_len, entries.as_bytes()),
+                None => -1,
+            }
+        },
+    )?;
+    Ok(linker)
+}
+
+fn read_bytes(caller: &Caller<'_, State>, ptr: i32, len: i32) -> Option<Vec<u8>> {
+    let memory = caller.get_export("memory")?.into_memory()?;
+    memory
+        .data(caller)
+        .get(ptr as usize..(ptr as usize).checked_add(len as usize)?)
+        .map(<[u8]>::to_vec)
+}
+
+fn read_string(caller: &Caller<'_, State>, ptr: i32, len: i32) -> Option<String> {
+    String::from_utf8(read_bytes(caller, ptr, len)?).ok()
+}
+
+/// Path in the virtual filesystem, relative to the project root
+fn path(caller: &Caller<'_, State>, ptr: i32, len: i32) -> Option<VfsPath> {
+    let path = read_string(caller, ptr, len)?.replace("\\", "/");
+    if path.split('/').any(|part| part == "..") {
+        return None;
+    }
+    caller.data().fs.join(path.trim_start_matches('/')).ok()
+}
+
+/// Copies as much of the data as fits into the buffer, returning the length of the data
+fn write_buffer(caller: &mut Caller<'_, State>, ptr: i32, len: i32, data: &[u8]) -> i64 {
+    let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+        Some(memory) => memory,
+        None => return -1,
+    };
+    let size = data.len().min(len.max(0) as usize);
+    let end = match (ptr as usize).checked_add(size) {
+        Some(end) => end,
+        None => return -1,
+    };
+    match memory.data_mut(caller).get_mut(ptr as usize..end) {
+        Some(buffer) => {
+            buffer.copy_from_slice(&data[..size]);
+            data.len() as i64
+        }
+        None => -1,
+    }
+}
@@ -0,0 +1,47 @@
+use hemtt::{Diagnostic, Severity};
+
+/// Prints the diagnostics as GitHub Actions workflow commands,
+/// which show up as annotations on pull requests
+pub fn write(diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        let command = match diagnostic.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "notice",
+        };
+        let mut properties = Vec::new();
+        if let Some(span) = &diagnostic.span {
+            properties.push(format!(
+                "file={}",
+                escape_property(&super::relative_path(&span.path))
+            ));
+            if span.line > 0 {
+                properties.push(format!("line={}", span.line));
+                properties.push(format!("col={}", span.column.max(1)));
+                properties.push(format!(
+                    "endColumn={}",
+                    span.column.max(1) + span.length.max(1)
+                ));
+            }
+        }
+        properties.push(format!("title={}", escape_property(&diagnostic.rule)));
+        println!(
+            "::{} {}::{}",
+            command,
+            properties.join(","),
+            escape_data(&diagnostic.message)
+        );
+    }
+}
+
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(property: &str) -> String {
+    escape_data(property)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
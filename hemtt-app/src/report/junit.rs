@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::Path;
+
+use hemtt::{Diagnostic, HEMTTError, Severity};
+use hemtt_stringtable::escape;
+
+/// Name of the suite of the problems that are not in an addon
+const PROJECT: &str = "project";
+
+/// Writes the diagnostics as a JUnit XML report, for GitLab and Jenkins
+///
+/// Every addon is a test suite and every rule that found a problem in it is a
+/// test case, failed by errors. An addon without problems has a single passing
+/// test case. Problems outside of the addons are in the `project` suite.
+pub fn write(diagnostics: &[Diagnostic], addons: &[String], path: &Path) -> Result<(), HEMTTError> {
+    let mut suites: BTreeMap<&str, BTreeMap<&str, Vec<&Diagnostic>>> = addons
+        .iter()
+        .map(|addon| (addon.as_str(), BTreeMap::new()))
+        .collect();
+    for diagnostic in diagnostics {
+        let file = diagnostic
+            .span
+            .as_ref()
+            .map(|span| super::relative_path(&span.path))
+            .unwrap_or_default();
+        let suite = addons
+            .iter()
+            .find(|addon| file == **addon || file.starts_with(&format!("{}/", addon)))
+            .map_or(PROJECT, String::as_str);
+        suites
+            .entry(suite)
+            .or_default()
+            .entry(diagnostic.rule.as_str())
+            .or_default()
+            .push(diagnostic);
+    }
+    if suites.is_empty() {
+        suites.insert(PROJECT, BTreeMap::new());
+    }
+
+    let mut xml = String::new();
+    let mut total = (0, 0);
+    for (suite, rules) in &suites {
+        let mut cases = String::new();
+        let mut failures = 0;
+        for (rule, problems) in rules {
+            let failed = problems.iter().find(|d| d.severity == Severity::Error);
+            let text = problems
+                .iter()
+                .map(|d| line(d))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let _ = writeln!(
+                cases,
+                "    <testcase classname=\"{}\" name=\"{}\">",
+                escape(suite),
+                escape(rule)
+            );
+            if let Some(first_error) = failed {
+                failures += 1;
+                let _ = writeln!(
+                    cases,
+                    "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+                    escape(rule),
+                    escape(&first_error.message),
+                    escape(&text)
+                );
+            } else {
+                let _ = writeln!(cases, "      <system-out>{}</system-out>", escape(&text));
+            }
+            cases.push_str("    </testcase>\n");
+        }
+        let tests = rules.len().max(1);
+        if rules.is_empty() {
+            let _ = writeln!(
+                cases,
+                "    <testcase classname=\"{}\" name=\"checks\"/>",
+                escape(suite)
+            );
+        }
+        total = (total.0 + tests, total.1 + failures);
+        let _ = write!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n{}  </testsuite>\n",
+            escape(suite),
+            tests,
+            failures,
+            cases
+        );
+    }
+
+    let mut file = create_file!(path)?;
+    write!(
+        file,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"HEMTT {}\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n{}</testsuites>\n",
+        escape(*crate::VERSION),
+        total.0,
+        total.1,
+        xml
+    )?;
+    Ok(())
+}
+
+/// Ex: "addons/main/config.cpp:3:5: error: Undefined macro `QUOTE`"
+fn line(diagnostic: &Diagnostic) -> String {
+    let location = match &diagnostic.span {
+        Some(span) if span.line > 0 => format!(
+            "{}:{}:{}: ",
+            super::relative_path(&span.path),
+            span.line,
+            span.column.max(1)
+        ),
+        Some(span) => format!("{}: ", super::relative_path(&span.path)),
+        None => String::new(),
+    };
+    format!(
+        "{}{}: {}",
+        location,
+        diagnostic.severity.as_str(),
+        diagnostic.message
+    )
+}
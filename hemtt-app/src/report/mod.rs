@@ -0,0 +1,32 @@
+use hemtt::{Diagnostic, HEMTTError, Project};
+
+mod github;
+mod junit;
+mod sarif;
+pub mod timings;
+
+/// Writes the diagnostics in the format requested with `--report`, `addons` are
+/// the folders of the addons that were checked
+pub fn write(diagnostics: &[Diagnostic], addons: &[String]) -> Result<(), HEMTTError> {
+    match crate::REPORT.as_deref() {
+        Some("junit") => {
+            let path = Project::find_root()?.join("hemtt-junit.xml");
+            junit::write(diagnostics, addons, &path)?;
+            info!("Report written to {}", path.display());
+        }
+        Some("sarif") => {
+            let path = Project::find_root()?.join("hemtt.sarif");
+            sarif::write(diagnostics, &path)?;
+            info!("Report written to {}", path.display());
+        }
+        Some("github") => github::write(diagnostics),
+        Some(format) => warn!("Unknown report format `{}`", format),
+        None => {}
+    }
+    Ok(())
+}
+
+/// Path of the file relative to the project root, with forward slashes
+fn relative_path(path: &str) -> String {
+    path.replace("\\", "/").trim_start_matches('/').to_string()
+}
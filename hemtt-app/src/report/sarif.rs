@@ -0,0 +1,54 @@
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::Path;
+
+use hemtt::{Diagnostic, HEMTTError};
+use serde_json::{json, Value};
+
+/// Writes the diagnostics as a SARIF 2.1.0 log, for GitHub code scanning
+pub fn write(diagnostics: &[Diagnostic], path: &Path) -> Result<(), HEMTTError> {
+    let rules: BTreeSet<&str> = diagnostics.iter().map(|d| d.rule.as_str()).collect();
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "HEMTT",
+                    "version": *crate::VERSION,
+                    "informationUri": "https://github.com/synixebrett/HEMTT",
+                    "rules": rules.iter().map(|rule| json!({ "id": rule })).collect::<Vec<Value>>(),
+                }
+            },
+            "results": diagnostics.iter().map(result).collect::<Vec<Value>>(),
+        }]
+    });
+    create_file!(path)?.write_all(
+        serde_json::to_string_pretty(&log)
+            .map_err(|e| HEMTTError::Generic(e.to_string()))?
+            .as_bytes(),
+    )?;
+    Ok(())
+}
+
+fn result(diagnostic: &Diagnostic) -> Value {
+    let mut result = json!({
+        "ruleId": diagnostic.rule,
+        "level": diagnostic.severity.as_str(),
+        "message": { "text": diagnostic.message },
+    });
+    if let Some(span) = &diagnostic.span {
+        let mut location = json!({
+            "artifactLocation": { "uri": super::relative_path(&span.path) },
+        });
+        if span.line > 0 {
+            location["region"] = json!({
+                "startLine": span.line,
+                "startColumn": span.column.max(1),
+                "endColumn": span.column.max(1) + span.length.max(1),
+            });
+        }
+        result["locations"] = json!([{ "physicalLocation": location }]);
+    }
+    result
+}
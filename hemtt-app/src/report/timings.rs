@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use hemtt::HEMTTError;
+use hemtt_stringtable::escape;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+lazy_static::lazy_static! {
+    /// Timings are recorded from here and moved to when the flow started
+    static ref START: Instant = Instant::now();
+    static ref RECORDED: Mutex<Vec<Timing>> = Mutex::new(Vec::new());
+}
+
+/// A task running for an addon, or for every addon at once when there is no addon
+#[derive(Clone, Debug)]
+pub struct Timing {
+    pub stage: String,
+    pub task: String,
+    pub addon: Option<String>,
+    /// Thread of the pool it ran on, `None` for the main thread
+    pub thread: Option<usize>,
+    /// Since the flow started
+    pub start: Duration,
+    pub end: Duration,
+}
+
+impl Timing {
+    fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// Timings of the spans of the tasks that closed after `start`, since `start`
+pub fn recorded(start: Instant) -> Vec<Timing> {
+    let offset = start.saturating_duration_since(*START);
+    RECORDED
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|timing| timing.start >= offset)
+        .map(|timing| Timing {
+            start: timing.start - offset,
+            end: timing.end - offset,
+            ..timing.clone()
+        })
+        .collect()
+}
+
+/// Records a timing when a `task` span closes, the addon is a field of the task
+/// or of the `addon` span it is in
+///
+/// Ex: `info_span!("task", stage = %stage, task = %task.name())`
+pub struct Recorder;
+
+/// Fields of a span and when it started
+struct Started {
+    stage: Option<String>,
+    task: Option<String>,
+    addon: Option<String>,
+    thread: Option<usize>,
+    start: Instant,
+}
+
+impl Visit for Started {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let value = Some(format!("{:?}", value));
+        match field.name() {
+            "stage" => self.stage = value,
+            "task" => self.task = value,
+            "addon" => self.addon = value,
+            _ => {}
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{}", value));
+    }
+}
+
+impl<S> Layer<S> for Recorder
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        lazy_static::initialize(&START);
+        if let Some(span) = ctx.span(id) {
+            let mut started = Started {
+                stage: None,
+                task: None,
+                addon: None,
+                thread: rayon::current_thread_index(),
+                start: Instant::now(),
+            };
+            attrs.record(&mut started);
+            span.extensions_mut().insert(started);
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(&id) {
+            Some(span) if span.name() == "task" => span,
+            _ => return,
+        };
+        let extensions = span.extensions();
+        let started = match extensions.get::<Started>() {
+            Some(started) => started,
+            None => return,
+        };
+        let addon = started.addon.clone().or_else(|| {
+            span.scope().skip(1).find_map(|parent| {
+                parent
+                    .extensions()
+                    .get::<Started>()
+                    .and_then(|started| started.addon.clone())
+            })
+        });
+        RECORDED.lock().unwrap().push(Timing {
+            stage: started.stage.clone().unwrap_or_default(),
+            task: started.task.clone().unwrap_or_default(),
+            addon,
+            thread: started.thread,
+            start: started.start.saturating_duration_since(*START),
+            end: START.elapsed(),
+        });
+    }
+}
+
+/// Width of the timeline in pixels
+const WIDTH: f64 = 1200.0;
+const ROW: usize = 24;
+
+/// Writes the timings as an HTML page with a timeline of every thread and the
+/// slowest tasks and addons
+pub fn write(timings: &[Timing], path: &Path) -> Result<(), HEMTTError> {
+    let total = timings
+        .iter()
+        .map(|t| t.end)
+        .max()
+        .unwrap_or_default()
+        .as_secs_f64()
+        .max(0.001);
+    let mut threads: Vec<Option<usize>> = timings.iter().map(|t| t.thread).collect();
+    threads.sort_unstable();
+    threads.dedup();
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>HEMTT Timings</title>\n<style>\n{}</style>\n</head>\n<body>\n",
+        STYLE
+    );
+    let _ = writeln!(
+        html,
+        "<h1>HEMTT Timings</h1>\n<p>{} tasks on {} threads in {:.2}s, {} {}</p>",
+        timings.len(),
+        threads.len(),
+        total,
+        escape(&std::env::args().skip(1).collect::<Vec<_>>().join(" ")),
+        escape(*crate::VERSION)
+    );
+
+    let _ = writeln!(
+        html,
+        "<h2>Timeline</h2>\n<svg width=\"{}\" height=\"{}\">",
+        WIDTH + 100.0,
+        threads.len() * ROW + 20
+    );
+    for (row, thread) in threads.iter().enumerate() {
+        let y = row * ROW;
+        let _ = writeln!(
+            html,
+            "<text x=\"0\" y=\"{}\">{}</text>",
+            y + 16,
+            thread.map_or_else(|| String::from("main"), |t| format!("thread {}", t))
+        );
+        for timing in timings.iter().filter(|t| t.thread == *thread) {
+            let x = 100.0 + timing.start.as_secs_f64() / total * WIDTH;
+            let width = (timing.duration().as_secs_f64() / total * WIDTH).max(1.0);
+            let _ = writeln!(
+                html,
+                "<rect x=\"{:.1}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\"><title>{}</title></rect>",
+                x,
+                y + 2,
+                width,
+                ROW - 4,
+                color(&timing.task),
+                escape(&format!(
+                    "{} [{}] [{}] {:.1} ms",
+                    timing.addon.as_deref().unwrap_or("all addons"),
+                    timing.stage,
+                    timing.task,
+                    timing.duration().as_secs_f64() * 1000.0
+                ))
+            );
+        }
+    }
+    let _ = writeln!(html, "</svg>");
+
+    let mut tasks: BTreeMap<(&str, &str), (Duration, usize, Option<&Timing>)> = BTreeMap::new();
+    let mut addons: BTreeMap<&str, Duration> = BTreeMap::new();
+    for timing in timings {
+        let task = tasks
+            .entry((timing.stage.as_str(), timing.task.as_str()))
+            .or_insert((Duration::default(), 0, None));
+        task.0 += timing.duration();
+        task.1 += 1;
+        if timing.addon.is_some() && task.2.map_or(true, |t| t.duration() < timing.duration()) {
+            task.2 = Some(timing);
+        }
+        if let Some(addon) = &timing.addon {
+            *addons.entry(addon.as_str()).or_default() += timing.duration();
+        }
+    }
+
+    let mut tasks: Vec<_> = tasks.into_iter().collect();
+    tasks.sort_by(|a, b| (b.1).0.cmp(&(a.1).0));
+    let _ = writeln!(
+        html,
+        "<h2>Tasks</h2>\n<table>\n<tr><th>Stage</th><th>Task</th><th>Total</th><th>Runs</th><th>Slowest addon</th></tr>"
+    );
+    for ((stage, task), (duration, runs, slowest)) in tasks {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(stage),
+            escape(task),
+            ms(duration),
+            runs,
+            slowest.map_or_else(String::new, |t| format!(
+                "{} ({})",
+                escape(t.addon.as_deref().unwrap_or_default()),
+                ms(t.duration())
+            ))
+        );
+    }
+    let _ = writeln!(html, "</table>");
+
+    let mut addons: Vec<_> = addons.into_iter().collect();
+    addons.sort_by(|a, b| b.1.cmp(&a.1));
+    let _ = writeln!(
+        html,
+        "<h2>Addons</h2>\n<table>\n<tr><th>Addon</th><th>Total</th></tr>"
+    );
+    for (addon, duration) in addons {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td></tr>",
+            escape(addon),
+            ms(duration)
+        );
+    }
+    let _ = writeln!(html, "</table>\n</body>\n</html>");
+
+    create_file!(path)?.write_all(html.as_bytes())?;
+    Ok(())
+}
+
+const STYLE: &str = "body { font-family: sans-serif; }
+table { border-collapse: collapse; }
+td, th { border: 1px solid #ccc; padding: 2px 8px; text-align: left; }
+svg text { font-size: 12px; }
+rect:hover { stroke: #000; }
+";
+
+fn ms(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// A color for each task, the same every build
+fn color(task: &str) -> String {
+    let hue = task.bytes().fold(0u32, |hash, b| {
+        hash.wrapping_mul(31).wrapping_add(u32::from(b))
+    }) % 360;
+    format!("hsl({}, 60%, 60%)", hue)
+}
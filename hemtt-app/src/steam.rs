@@ -0,0 +1,149 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use hemtt::{HEMTTError, UserConfig};
+
+/// The command that logs steamcmd in
+///
+/// steamcmd caches the session of an account after `hemtt auth steam login`, so
+/// only the username is needed. In CI the `HEMTT_STEAM_PASSWORD` environment
+/// variable, and `HEMTT_STEAM_GUARD` for a Steam Guard code, log in without a
+/// cached session
+///
+/// Arguments:
+/// * `required`: an account is required, otherwise steamcmd logs in anonymously without one
+pub fn login(required: bool) -> Result<Vec<String>, HEMTTError> {
+    let username = match UserConfig::read()?.steam_username() {
+        Some(username) => username,
+        None if !required => return Ok(vec![String::from("login"), String::from("anonymous")]),
+        None => {
+            return Err(HEMTTError::UserHint(
+                String::from("No Steam account is logged in"),
+                String::from(
+                    "run `hemtt auth steam login`, or set HEMTT_STEAM_USERNAME and HEMTT_STEAM_PASSWORD in CI",
+                ),
+            ))
+        }
+    };
+    Ok(login_as(username))
+}
+
+/// The command that logs steamcmd in as an account, with the password from the
+/// environment when it is set
+pub fn login_as(username: String) -> Vec<String> {
+    let mut command = vec![String::from("login"), username];
+    if let Some(password) = env("HEMTT_STEAM_PASSWORD") {
+        command.push(password);
+        if let Some(code) = env("HEMTT_STEAM_GUARD") {
+            command.push(code);
+        }
+    }
+    command
+}
+
+/// Runs steamcmd with a list of commands, each a command and its arguments,
+/// `quit` is added at the end
+///
+/// steamcmd shares the terminal, so it can prompt for a password or Steam Guard code.
+/// When `HEMTT_STEAM_PASSWORD` is set the commands are written to steamcmd's
+/// stdin instead of its arguments, so the password is not visible to other processes
+pub fn steamcmd(commands: &[Vec<String>]) -> Result<bool, HEMTTError> {
+    if env("HEMTT_STEAM_PASSWORD").is_some() {
+        run(commands, Input::Commands)
+    } else {
+        run(commands, Input::Terminal)
+    }
+}
+
+/// Logs steamcmd out of an account, which clears its cached session
+///
+/// steamcmd can only log out after logging in with the cached session, stdin is
+/// closed so it does not prompt for a password when there is no session
+pub fn logout(username: String) -> Result<bool, HEMTTError> {
+    run(
+        &[
+            vec![String::from("login"), username],
+            vec![String::from("logout")],
+        ],
+        Input::Closed,
+    )
+}
+
+/// How commands are passed to steamcmd
+enum Input {
+    /// As arguments, steamcmd can prompt on the terminal
+    Terminal,
+    /// On stdin
+    Commands,
+    /// As arguments, steamcmd can not prompt
+    Closed,
+}
+
+fn run(commands: &[Vec<String>], input: Input) -> Result<bool, HEMTTError> {
+    crate::http::online("run steamcmd")?;
+    let mut command = Command::new("steamcmd");
+    match input {
+        Input::Terminal | Input::Closed => {
+            for words in commands {
+                command.arg(format!("+{}", words[0])).args(&words[1..]);
+            }
+            command.arg("+quit");
+            if let Input::Closed = input {
+                command.stdin(Stdio::null());
+            }
+        }
+        Input::Commands => {
+            // steamcmd has no escapes, a quote or line break would end the word or command
+            if commands
+                .iter()
+                .flatten()
+                .any(|word| word.chars().any(|c| c == '"' || c.is_control()))
+            {
+                return Err(HEMTTError::UserHint(
+                    String::from("A steamcmd argument contains a quote or a line break"),
+                    String::from(
+                        "check HEMTT_STEAM_USERNAME, HEMTT_STEAM_PASSWORD and HEMTT_STEAM_GUARD",
+                    ),
+                ));
+            }
+            command.stdin(Stdio::piped());
+        }
+    }
+    let mut child = command.spawn().map_err(|e| {
+        HEMTTError::UserHint(
+            format!("Unable to run steamcmd: {}", e),
+            String::from("install steamcmd and make sure it is in your PATH"),
+        )
+    })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let mut script = String::new();
+        for words in commands {
+            let words: Vec<String> = words.iter().map(|word| quote(word)).collect();
+            script.push_str(&words.join(" "));
+            script.push('\n');
+        }
+        script.push_str("quit\n");
+        let written = stdin.write_all(script.as_bytes());
+        drop(stdin);
+        if let Err(e) = written {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(e.into());
+        }
+    }
+    Ok(child.wait()?.success())
+}
+
+/// Quotes a word of a steamcmd command that contains whitespace, words with quotes or
+/// line breaks are rejected by [`run`]
+fn quote(word: &str) -> String {
+    if word.chars().any(char::is_whitespace) {
+        format!("\"{}\"", word)
+    } else {
+        word.to_string()
+    }
+}
+
+fn env(var: &str) -> Option<String> {
+    std::env::var(var).ok().filter(|value| !value.is_empty())
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Child, ExitStatus, Stdio};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use hemtt::Project;
+
+use crate::{
+    context::{AddonContext, AddonListContext},
+    tasks::PDrive,
+    HEMTTError, Stage, Task,
+};
+
+/// Files created by binarize that replace the addon's own
+const BINARIZED_EXTS: [&str; 3] = ["wrp", "p3d", "rtm"];
+
+/// Binarizes the terrain addons with binarize from the Arma 3 Tools, the `.wrp`
+/// and models it creates are packed instead of the exported ones
+///
+/// Binarize reads the files the terrain references from a P-drive, it is given
+/// `.hemttout/binarize/pdrive` with the project's P-drive mounts linked in at
+/// their prefixes. Binarize is only available on Windows, elsewhere terrains are
+/// packed as exported
+///
+/// The binarized files are left in `.hemttout/binarize` and packed from there,
+/// as a terrain's `.wrp` is too large to keep in memory
+///
+/// A terrain is stopped once it takes longer than `terrain.timeout`
+pub struct Binarize {}
+impl Task for Binarize {
+    fn name(&self) -> String {
+        String::from("binarize")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Build]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn build_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        ctx.global().container.set(Binarized::default());
+        let terrain = &ctx.global().project().terrain;
+        if !cfg!(windows)
+            || !ctx
+                .addons()
+                .iter()
+                .any(|addon| terrain.is_terrain(addon.addon().name()))
+        {
+            return Ok(());
+        }
+        let pdrive = pdrive_folder()?;
+        if crate::dry_run("mount the P-drive for binarize", &pdrive) {
+            return Ok(());
+        }
+        if pdrive.exists() {
+            std::fs::remove_dir_all(&pdrive)?;
+        }
+        for (prefix, folder) in ctx.global().container.get::<PDrive>().mounts() {
+            mount(folder, &pdrive.join(prefix))?;
+        }
+        Ok(())
+    }
+
+    fn build(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let terrain = &ctx.global().project().terrain;
+        if !terrain.is_terrain(ctx.addon().name()) {
+            return Ok(());
+        }
+        if !cfg!(windows) {
+            ctx.warn("binarize is only available on Windows, the terrain is packed as exported");
+            return Ok(());
+        }
+        let binarize = hemtt::tools::find_bi_tool("binarize")?;
+        let pdrive = pdrive_folder()?;
+        let source = pdrive.join(ctx.prefix().replace('\\', "/").trim_matches('/'));
+        let target = Project::output_dir()?
+            .join("binarize")
+            .join(ctx.addon().name());
+        if crate::dry_run("binarize", &target) {
+            return Ok(());
+        }
+        if target.exists() {
+            std::fs::remove_dir_all(&target)?;
+        }
+        create_dir!(target)?;
+        ctx.info(&format!(
+            "Binarizing, this can take up to {} minutes",
+            terrain.timeout().as_secs() / 60
+        ));
+        let start = Instant::now();
+        let child = std::process::Command::new(&binarize)
+            .args(&["-always", "-silent", "-maxProcesses=0"])
+            .arg(format!("-addon={}", pdrive.display()))
+            .arg(format!("-textures={}", pdrive.display()))
+            .arg(&source)
+            .arg(&target)
+            .stdout(Stdio::null())
+            .spawn()?;
+        let status = wait(child, terrain.timeout())?;
+        if !status.success() {
+            return Err(HEMTTError::Generic(format!(
+                "binarize failed with {}",
+                status
+            )));
+        }
+
+        let mut files = HashMap::new();
+        let target = hemtt::long_path(&target);
+        for entry in walkdir::WalkDir::new(&target) {
+            let entry = entry.map_err(|e| HEMTTError::Generic(e.to_string()))?;
+            let binarized = entry.path().extension().map_or(false, |ext| {
+                BINARIZED_EXTS.contains(&ext.to_string_lossy().to_lowercase().as_str())
+            });
+            if !entry.file_type().is_file() || !binarized {
+                continue;
+            }
+            let path = entry
+                .path()
+                .strip_prefix(&target)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/");
+            files.insert(path, entry.into_path());
+        }
+        ctx.debug(&format!(
+            "Binarized {} files in {}s",
+            files.len(),
+            start.elapsed().as_secs()
+        ));
+        ctx.global()
+            .container
+            .get::<Binarized>()
+            .0
+            .write()
+            .unwrap()
+            .insert(ctx.addon().source().to_string(), files);
+        Ok(())
+    }
+}
+
+/// Files created by binarize for each addon, by their path in the addon
+#[derive(Default)]
+pub struct Binarized(RwLock<HashMap<String, HashMap<String, PathBuf>>>);
+impl Binarized {
+    /// Files that replace the addon's own when it is packed
+    /// Ex: {"data/map.wrp": ".hemttout/binarize/map/data/map.wrp"}
+    pub fn files(&self, source: &str) -> HashMap<String, PathBuf> {
+        self.0
+            .read()
+            .unwrap()
+            .get(source)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Folder that the P-drive is mounted in for binarize
+fn pdrive_folder() -> Result<PathBuf, HEMTTError> {
+    Ok(Project::output_dir()?.join("binarize").join("pdrive"))
+}
+
+/// Hard links the files of a mount into the P-drive folder, copying them when
+/// they are on another drive, the first mount of a file is kept
+fn mount(folder: &Path, target: &Path) -> Result<(), HEMTTError> {
+    if !folder.is_dir() {
+        return Ok(());
+    }
+    let folder = hemtt::long_path(folder);
+    for entry in walkdir::WalkDir::new(&folder) {
+        let entry = entry.map_err(|e| HEMTTError::Generic(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let file = target.join(entry.path().strip_prefix(&folder).unwrap());
+        if hemtt::long_path(&file).exists() {
+            continue;
+        }
+        create_dir!(file.parent().unwrap())?;
+        if std::fs::hard_link(entry.path(), hemtt::long_path(&file)).is_err() {
+            copy_file!(entry.path(), &file)?;
+        }
+    }
+    Ok(())
+}
+
+/// Waits for binarize to exit, it is stopped once it takes longer than the timeout
+fn wait(mut child: Child, timeout: Duration) -> Result<ExitStatus, HEMTTError> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if start.elapsed() > timeout {
+            child.kill()?;
+            child.wait()?;
+            return Err(HEMTTError::UserHint(
+                format!(
+                    "binarize did not finish within {} seconds",
+                    timeout.as_secs()
+                ),
+                String::from("raise `terrain.timeout` for large terrains"),
+            ));
+        }
+        std::thread::sleep(Duration::from_secs(1));
+    }
+}
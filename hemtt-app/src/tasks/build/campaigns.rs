@@ -0,0 +1,159 @@
+use hemtt::AddonLocation;
+use hemtt_arma_config::simplify::{Class, Config, Entry};
+
+use crate::{context::AddonContext, HEMTTError, Stage, Task};
+
+/// Validates the `CfgMissions` of the addons in `campaigns/`
+///
+/// Each campaign's `directory` must be a folder of the addon with a
+/// `description.ext`, and the missions used by its chapters must be in the
+/// folder's `missions`
+pub struct Campaigns {}
+impl Task for Campaigns {
+    fn name(&self) -> String {
+        String::from("campaigns")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Build]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn build(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        if ctx.addon().location() != AddonLocation::Campaigns {
+            return Ok(());
+        }
+        let config = super::addon_config(ctx)?.ok_or_else(|| {
+            HEMTTError::User(format!(
+                "Campaign `{}` does not have a config.cpp",
+                ctx.addon().name()
+            ))
+        })?;
+        let campaigns: Vec<(&String, &Class)> = config
+            .root
+            .class("CfgMissions")
+            .and_then(|missions| missions.class("Campaigns"))
+            .map(|campaigns| {
+                campaigns
+                    .entries
+                    .iter()
+                    .filter_map(|(name, entry)| match entry {
+                        Entry::Class(class) => Some((name, class)),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut problems = Vec::new();
+        if config.root.class("CfgPatches").is_none() {
+            problems.push(String::from("config.cpp does not declare `CfgPatches`"));
+        }
+        if campaigns.is_empty() {
+            problems.push(String::from(
+                "config.cpp does not declare a campaign in `CfgMissions >> Campaigns`",
+            ));
+        }
+        for (name, campaign) in campaigns {
+            match campaign.get("directory") {
+                Some(Entry::Str(directory)) => {
+                    for problem in directory_problems(ctx, directory)? {
+                        problems.push(format!("campaign `{}` {}", name, problem));
+                    }
+                }
+                _ => problems.push(format!("campaign `{}` does not declare `directory`", name)),
+            }
+        }
+        if problems.is_empty() {
+            return Ok(());
+        }
+        for problem in &problems {
+            ctx.error(problem);
+        }
+        Err(HEMTTError::User(format!(
+            "Campaign `{}` has {} problems in its CfgMissions",
+            ctx.addon().name(),
+            problems.len()
+        )))
+    }
+}
+
+/// Problems with the folder a campaign's `directory` points at
+fn directory_problems(ctx: &AddonContext, directory: &str) -> Result<Vec<String>, HEMTTError> {
+    let directory = directory.trim_matches('\\');
+    let folder = match hemtt::strip_prefix_no_case(directory, ctx.prefix()) {
+        Some(folder) if !folder.is_empty() => folder.replace('\\', "/"),
+        _ => {
+            return Ok(vec![format!(
+                "has `directory` `{}`, which is not inside the addon's prefix `{}`",
+                directory,
+                ctx.prefix()
+            )])
+        }
+    };
+    let source = format!("{}/{}", ctx.addon().source(), folder);
+    if !ctx
+        .global()
+        .fs()
+        .join(&source)?
+        .join("description.ext")?
+        .exists()?
+    {
+        return Ok(vec![format!(
+            "has `directory` `{}`, but {}/description.ext does not exist",
+            directory, source
+        )]);
+    }
+    // The description.ext in the build is already rapified, the source is read instead
+    let description = match std::fs::read_to_string(format!("{}/description.ext", source))
+        .ok()
+        .and_then(|text| hemtt_arma_config::parse(&text).ok())
+        .and_then(|ast| Config::from_ast(ast).ok())
+    {
+        Some(description) => description,
+        None => return Ok(Vec::new()),
+    };
+    let mut problems = Vec::new();
+    if let Some(campaign) = description.root.class("Campaign") {
+        let mut templates = Vec::new();
+        templates_of(campaign, &mut templates);
+        for template in templates {
+            if !ctx
+                .global()
+                .fs()
+                .join(&source)?
+                .join("missions")?
+                .join(&template)?
+                .exists()?
+            {
+                problems.push(format!(
+                    "uses mission `{}`, which is not in {}/missions",
+                    template, source
+                ));
+            }
+        }
+    } else {
+        problems.push(format!(
+            "has no `Campaign` class in {}/description.ext",
+            source
+        ));
+    }
+    Ok(problems)
+}
+
+/// The `template` of every mission in the chapters of a campaign
+fn templates_of(class: &Class, templates: &mut Vec<String>) {
+    for (name, entry) in &class.entries {
+        match entry {
+            Entry::Class(class) => templates_of(class, templates),
+            Entry::Str(template) if name.eq_ignore_ascii_case("template") => {
+                if !template.is_empty() {
+                    templates.push(template.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use vfs::VfsFileType;
+
+use crate::{context::AddonContext, HEMTTError, Stage, Task};
+
+/// Writes each addon's files as they are about to be packed, after they have
+/// been populated, preprocessed and rapified, into a folder on disk
+///
+/// Nothing is written without a folder
+pub struct DumpVfs {
+    folder: Option<PathBuf>,
+}
+impl DumpVfs {
+    pub fn new(folder: Option<PathBuf>) -> Self {
+        Self { folder }
+    }
+}
+impl Task for DumpVfs {
+    fn name(&self) -> String {
+        String::from("dumpvfs")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Build]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn build(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let target = match &self.folder {
+            Some(folder) => folder.join(ctx.addon().source()),
+            None => return Ok(()),
+        };
+        let folder = ctx.global().fs().join(ctx.addon().source())?;
+        if crate::dry_run("dump", &target) {
+            return Ok(());
+        }
+        if target.exists() {
+            std::fs::remove_dir_all(&target)?;
+        }
+        for entry in folder.walk_dir()? {
+            let entry = entry?;
+            if entry.metadata()?.file_type != VfsFileType::File {
+                continue;
+            }
+            let dest = target.join(entry.as_str()[folder.as_str().len()..].trim_start_matches('/'));
+            create_dir!(dest.parent().unwrap())?;
+            std::io::copy(&mut entry.open_file()?, &mut create_file!(&dest)?)?;
+        }
+        ctx.debug(&format!("Dumped to {:?}", target));
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+use hemtt::{Diagnostic, Severity, Span};
+use hemtt_arma_config::simplify::{ArrayElement, Config, Entry};
+
+use crate::{
+    context::{AddonContext, AddonListContext},
+    HEMTTError, Stage, Task,
+};
+
+/// Classes defined by the config of an addon
+struct Defined {
+    addon: String,
+    patches: Vec<String>,
+    requires: Vec<String>,
+    classes: Vec<(String, Span)>,
+}
+
+#[derive(Default)]
+struct Definitions(RwLock<Vec<Defined>>);
+
+/// Warns when two addons define the same class inside a root class and neither
+/// requires the other, which class wins then depends on the load order
+///
+/// Addons that are not part of the build, or are skipped by the cache, are
+/// preprocessed after the build so every addon of the project is compared.
+pub struct Duplicates {}
+impl Task for Duplicates {
+    fn name(&self) -> String {
+        String::from("duplicates")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Build, Stage::PostBuild]
+    }
+
+    fn barrier(&self, stage: &Stage) -> bool {
+        // Compares the classes of every addon
+        *stage == Stage::PostBuild
+    }
+
+    fn build_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        ctx.global().container.set(Definitions::default());
+        Ok(())
+    }
+
+    fn build(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let path = ctx
+            .global()
+            .fs()
+            .join(ctx.addon().source())?
+            .join("config.cpp")?;
+        if !path.exists()? {
+            return Ok(());
+        }
+        let mut buf = String::new();
+        path.open_file()?.read_to_string(&mut buf)?;
+        let maps = ctx.global().container.get::<crate::tasks::SourceMaps>();
+        // Errors are reported by rapify
+        if let Some(defined) = define(ctx.addon().name(), path.as_str(), &buf, |line, column| {
+            maps.source(path.as_str(), line, column)
+        }) {
+            ctx.global()
+                .container
+                .get::<Definitions>()
+                .0
+                .write()
+                .unwrap()
+                .push(defined);
+        }
+        Ok(())
+    }
+
+    fn postbuild_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        let definitions = ctx.global().container.get::<Definitions>();
+        // Addons that were not built are preprocessed here so that they are compared too
+        for addon in hemtt::get_all_addons()? {
+            if definitions
+                .0
+                .read()
+                .unwrap()
+                .iter()
+                .any(|d| d.addon == addon.name())
+            {
+                continue;
+            }
+            let path = ctx.global().fs().join(addon.source())?.join("config.cpp")?;
+            if !path.exists()? {
+                continue;
+            }
+            let rendered = match crate::tasks::render_file(&path, addon.source(), ctx.global()) {
+                Ok(rendered) => rendered,
+                Err(e) => {
+                    debug!("Unable to preprocess `{}`: {}", path.as_str(), e);
+                    continue;
+                }
+            };
+            if let Some(defined) = define(
+                addon.name(),
+                path.as_str(),
+                &rendered.export(),
+                |line, column| rendered.source_at(line, column).map(|t| t.span()),
+            ) {
+                definitions.0.write().unwrap().push(defined);
+            }
+        }
+        let defined = definitions.0.read().unwrap();
+        let requires = required(&defined);
+        let mut seen: HashMap<String, Vec<(usize, &str, &Span)>> = HashMap::new();
+        for (i, addon) in defined.iter().enumerate() {
+            for (class, span) in &addon.classes {
+                let others = seen.entry(class.to_lowercase()).or_default();
+                for (j, _, other) in others.iter() {
+                    if *j == i || requires[i].contains(j) || requires[*j].contains(&i) {
+                        continue;
+                    }
+                    let message = format!(
+                        "`{}` is defined by both `{}` at {} and `{}` at {}, add one to the `requiredAddons` of the other",
+                        class, defined[*j].addon, other, addon.addon, span
+                    );
+                    warn!("{}", message);
+                    ctx.global().report(Diagnostic::new(
+                        self.name(),
+                        Severity::Warning,
+                        message,
+                        Some(span.clone()),
+                    ));
+                }
+                others.push((i, class, span));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Classes and CfgPatches of a preprocessed config, `None` if it can not be parsed
+///
+/// Arguments:
+/// * `addon`: name of the addon
+/// * `path`: path of the config
+/// * `buf`: preprocessed config
+/// * `source`: location in the source of a line and column of the preprocessed config
+fn define<F>(addon: &str, path: &str, buf: &str, source: F) -> Option<Defined>
+where
+    F: Fn(usize, usize) -> Option<Span>,
+{
+    let ast = hemtt_arma_config::parse(buf).ok()?;
+    let classes = ast
+        .definitions()
+        .into_iter()
+        .map(|d| {
+            let span =
+                source(d.line, d.column).unwrap_or_else(|| Span::new(path, d.line, d.column, 1));
+            (format!("{} >> {}", d.root, d.class), span)
+        })
+        .collect();
+    let (patches, requires) = Config::from_ast(ast)
+        .map(|config| patches(&config))
+        .unwrap_or_default();
+    Some(Defined {
+        addon: addon.to_string(),
+        patches,
+        requires,
+        classes,
+    })
+}
+
+/// CfgPatches classes of a config and the addons they require, in lowercase
+fn patches(config: &Config) -> (Vec<String>, Vec<String>) {
+    let mut patches = Vec::new();
+    let mut requires = Vec::new();
+    if let Some(cfg_patches) = config.root.class("CfgPatches") {
+        for (name, entry) in &cfg_patches.entries {
+            if let Entry::Class(patch) = entry {
+                patches.push(name.to_lowercase());
+                if let Some(Entry::Array(array)) = patch.get("requiredAddons") {
+                    requires.extend(array.elements.iter().filter_map(|e| {
+                        if let ArrayElement::Str(s) = e {
+                            Some(s.to_lowercase())
+                        } else {
+                            None
+                        }
+                    }));
+                }
+            }
+        }
+    }
+    (patches, requires)
+}
+
+/// Addons each addon requires, directly or through other addons of the project
+fn required(defined: &[Defined]) -> Vec<HashSet<usize>> {
+    let owners: HashMap<&str, usize> = defined
+        .iter()
+        .enumerate()
+        .flat_map(|(i, addon)| addon.patches.iter().map(move |p| (p.as_str(), i)))
+        .collect();
+    defined
+        .iter()
+        .map(|addon| {
+            let mut found = HashSet::new();
+            let mut queue: Vec<&str> = addon.requires.iter().map(String::as_str).collect();
+            while let Some(patch) = queue.pop() {
+                if let Some(owner) = owners.get(patch) {
+                    if found.insert(*owner) {
+                        queue.extend(defined[*owner].requires.iter().map(String::as_str));
+                    }
+                }
+            }
+            found
+        })
+        .collect()
+}
@@ -0,0 +1,53 @@
+use hemtt::{Mission, Project};
+use vfs::{SeekAndRead, VfsFileType};
+
+use crate::{context::AddonListContext, HEMTTError, Stage, Task};
+
+pub fn pack(mission: &Mission, ctx: &AddonListContext) -> Result<(), HEMTTError> {
+    let mut pbo = hemtt_pbo::WritablePbo::<Box<dyn SeekAndRead>>::new();
+    let root = ctx.global().fs().join(mission.source())?;
+    for entry in root.walk_dir()? {
+        let entry = entry?;
+        if entry.metadata()?.file_type == VfsFileType::File {
+            let name = entry
+                .as_str()
+                .trim_start_matches(root.as_str())
+                .trim_start_matches('/')
+                .to_string();
+            trace!("[{}] pack: {:?}", mission.name(), name);
+            pbo.add_file(name, entry.open_file()?)?;
+        }
+    }
+    let pbo_path = mission.destination(Project::output_dir()?);
+    if crate::dry_run("create", &pbo_path) {
+        return Ok(());
+    }
+    debug!("Creating mission PBO at {:?}", pbo_path);
+    create_dir!(pbo_path.parent().unwrap())?;
+    pbo.write(&mut create_file!(&pbo_path)?)?;
+    Ok(())
+}
+
+/// Packs every folder in `missions/` into `mpmissions/{name}.{map}.pbo` of the output folder
+pub struct PackMissions {}
+impl Task for PackMissions {
+    fn name(&self) -> String {
+        String::from("missions")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Build]
+    }
+
+    fn build_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        for mission in hemtt::get_missions()? {
+            info!(
+                "Packing mission `{}` for `{}`",
+                mission.name(),
+                mission.map()
+            );
+            pack(&mission, ctx)?;
+        }
+        Ok(())
+    }
+}
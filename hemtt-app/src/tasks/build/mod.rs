@@ -1,5 +1,52 @@
+use hemtt_arma_config::simplify::Config;
+
+use crate::{context::AddonContext, HEMTTError};
+
 mod rapify;
 pub use rapify::Rapify;
 
+mod duplicates;
+pub use duplicates::Duplicates;
+
+mod patches;
+pub use patches::PatchesMetadata;
+
+mod strings;
+pub use strings::Strings;
+
+mod binarize;
+pub use binarize::{Binarize, Binarized};
+
+mod campaigns;
+pub use campaigns::Campaigns;
+
+mod dump;
+pub use dump::DumpVfs;
+
 mod pack;
 pub use pack::Pack;
+
+mod size;
+pub use size::Size;
+
+mod missions;
+pub use missions::PackMissions;
+
+/// The addon's preprocessed `config.cpp`, if it has one that can be parsed
+///
+/// Parse errors are reported by rapify
+pub fn addon_config(ctx: &AddonContext) -> Result<Option<Config>, HEMTTError> {
+    let path = ctx
+        .global()
+        .fs()
+        .join(ctx.addon().source())?
+        .join("config.cpp")?;
+    if !path.exists()? {
+        return Ok(None);
+    }
+    let mut buf = String::new();
+    path.open_file()?.read_to_string(&mut buf)?;
+    Ok(hemtt_arma_config::parse(&buf)
+        .ok()
+        .and_then(|ast| Config::from_ast(ast).ok()))
+}
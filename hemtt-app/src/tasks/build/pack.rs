@@ -1,7 +1,7 @@
 use hemtt::Project;
-use vfs::{PhysicalFS, SeekAndRead, VfsFileType};
+use vfs::{SeekAndRead, VfsFileType};
 
-use crate::{context::AddonContext, HEMTTError, Stage, Task};
+use crate::{context::AddonContext, tasks::Binarized, HEMTTError, Stage, Task};
 
 pub struct Pack {}
 impl Task for Pack {
@@ -13,9 +13,37 @@ impl Task for Pack {
         &[Stage::Build]
     }
 
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
     fn build(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
         let mut pbo = hemtt_pbo::WritablePbo::<Box<dyn SeekAndRead>>::new();
-        for entry in ctx.global().fs().join(ctx.addon().source())?.walk_dir()? {
+        let compression = &ctx.global().project().compression;
+        let terrain = &ctx.global().project().terrain;
+        let add = |pbo: &mut hemtt_pbo::WritablePbo<Box<dyn SeekAndRead>>,
+                   name: &str,
+                   relative: &str,
+                   file: Box<dyn SeekAndRead>|
+         -> Result<(), HEMTTError> {
+            if terrain.compressed(compression, ctx.addon().name(), relative) {
+                ctx.debug(&format!("pack compressed: {:?}", name));
+                pbo.add_file_compressed(name, file)?;
+            } else {
+                ctx.debug(&format!("pack: {:?}", name));
+                pbo.add_file(name, file)?;
+            }
+            Ok(())
+        };
+        // Binarized files are read from disk instead of the addon's own
+        let mut binarized = ctx
+            .global()
+            .container
+            .try_get::<Binarized>()
+            .map(|b| b.files(ctx.addon().source()))
+            .unwrap_or_default();
+        let folder = ctx.global().fs().join(ctx.addon().source())?;
+        for entry in folder.walk_dir()? {
             let entry = entry?;
             if entry.filename().contains(".ht.") {
                 continue;
@@ -25,17 +53,36 @@ impl Task for Pack {
                     && entry.parent().unwrap().join("config.bin")?.exists()?
                 {
                     ctx.debug("skipping config.cpp");
-                } else {
-                    ctx.debug(&format!("pack: {:?}", entry.as_str()));
-                    pbo.add_file(entry.as_str(), entry.open_file()?)?;
+                    continue;
                 }
+                let relative = &entry.as_str()[folder.as_str().len()..];
+                let file: Box<dyn SeekAndRead> =
+                    match binarized.remove(relative.trim_start_matches('/')) {
+                        Some(path) => Box::new(open_file!(path)?),
+                        None => entry.open_file()?,
+                    };
+                add(&mut pbo, entry.as_str(), relative, file)?;
             }
         }
-        let pbo_path = vfs::VfsPath::from(PhysicalFS::new(Project::find_root()?))
-            .join(&ctx.addon().location().to_string())?
-            .join(&ctx.addon().pbo(Some(ctx.global().project().prefix())))?;
-        ctx.debug(&format!("Creating PBO at {}", pbo_path.as_str()));
-        pbo.write(&mut pbo_path.create_file()?)?;
+        // Created by binarize without a file of the addon to replace
+        for (relative, path) in binarized {
+            let relative = format!("/{}", relative);
+            let name = format!("{}{}", folder.as_str(), relative);
+            add(&mut pbo, &name, &relative, Box::new(open_file!(path)?))?;
+        }
+        let pbo_path = ctx.addon().destination(
+            &Project::output_dir()?,
+            Some(ctx.global().project().prefix()),
+            None,
+        );
+        if crate::dry_run("create", &pbo_path) {
+            return Ok(());
+        }
+        ctx.debug(&format!("Creating PBO at {:?}", pbo_path));
+        create_dir!(pbo_path.parent().unwrap())?;
+        let partial = crate::interrupt::Partial::new(&pbo_path);
+        pbo.write(&mut create_file!(&pbo_path)?)?;
+        partial.complete();
         Ok(())
     }
 }
@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+
+use hemtt_arma_config::simplify::{ArrayElement, Class, Entry};
+
+use crate::{context::AddonContext, HEMTTError, Stage, Task};
+
+/// Warns when the CfgPatches of an addon do not declare the project's
+/// `version`, `versionStr`, `author` and `url`, do not require the project's
+/// `required_version` of the game, or have stale or missing `units[]` and
+/// `weapons[]`
+///
+/// Zeus only offers the vehicles listed in `units[]`
+pub struct PatchesMetadata {}
+impl Task for PatchesMetadata {
+    fn name(&self) -> String {
+        String::from("patches")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Build]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn build(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let lints = &ctx.global().project().lints;
+        let (metadata, required) = (lints.patches, lints.required_version);
+        let (units, weapons) = (lints.units, lints.weapons);
+        if !metadata && !required && !units && !weapons {
+            return Ok(());
+        }
+        let config = match super::addon_config(ctx)? {
+            Some(config) => config,
+            None => return Ok(()),
+        };
+        let cfg_patches = match config.root.class("CfgPatches") {
+            Some(cfg_patches) => cfg_patches,
+            None => return Ok(()),
+        };
+        let vehicles = defined(&config.root, "CfgVehicles");
+        let weapon_classes = defined(&config.root, "CfgWeapons");
+        let mut listed = HashSet::new();
+        for (name, entry) in &cfg_patches.entries {
+            if let Entry::Class(patch) = entry {
+                let mut found = Vec::new();
+                for (array, root, classes, enabled) in &[
+                    ("units", "CfgVehicles", &vehicles, units),
+                    ("weapons", "CfgWeapons", &weapon_classes, weapons),
+                ] {
+                    if *enabled {
+                        for class in list(patch, array) {
+                            if !classes.iter().any(|(c, _)| c.eq_ignore_ascii_case(&class)) {
+                                found.push(format!(
+                                    "lists `{}` in `{}[]`, which is not defined in {}",
+                                    class, array, root
+                                ));
+                            }
+                            if *array == "units" {
+                                listed.insert(class.to_lowercase());
+                            }
+                        }
+                    }
+                }
+                if metadata {
+                    found.extend(problems(patch, ctx));
+                }
+                if required {
+                    found.extend(required_version(patch, ctx));
+                }
+                for problem in found {
+                    ctx.warn(&format!("CfgPatches `{}` {}", name, problem));
+                }
+            }
+        }
+        if units {
+            for (class, _) in vehicles.iter().filter(|(class, public)| {
+                *public == Some(true) && !listed.contains(&class.to_lowercase())
+            }) {
+                ctx.warn(&format!(
+                    "CfgPatches do not list the vehicle `{}` with `scope = 2` in `units[]`",
+                    class
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Classes defined in a root class of the config, and whether they are public
+/// with `scope = 2`, set on the class or inherited from a parent in the addon
+///
+/// Classes that are only declared or deleted are not defined, whether a class is
+/// public is unknown when it inherits its scope from a class outside the addon
+fn defined(root: &Class, name: &str) -> Vec<(String, Option<bool>)> {
+    root.class(name).map_or_else(Vec::new, |class| {
+        class
+            .entries
+            .iter()
+            .filter_map(|(name, entry)| match entry {
+                Entry::Class(child) if !child.external && !child.deletion => {
+                    Some((name.clone(), public(class, child)))
+                }
+                _ => None,
+            })
+            .collect()
+    })
+}
+
+/// The class has `scope = 2`, following its parents in the same root class
+fn public(root: &Class, class: &Class) -> Option<bool> {
+    let mut class = class;
+    // A limit on the depth stops at classes that inherit from each other
+    for _ in 0..root.entries.len() + 1 {
+        match class.get("scope") {
+            Some(Entry::Int(scope)) => return Some(*scope == 2),
+            Some(Entry::Float(scope)) => return Some((*scope - 2.0).abs() < f32::EPSILON),
+            Some(_) => return Some(false),
+            None => {}
+        }
+        if class.parent.is_empty() {
+            return Some(false);
+        }
+        class = match root.get(&class.parent) {
+            Some(Entry::Class(parent)) if !parent.external && !parent.deletion => parent,
+            _ => return None,
+        };
+    }
+    None
+}
+
+/// Classes in an array of a CfgPatches class
+/// Ex: `units[] = {"my_car"};`
+fn list(patch: &Class, array: &str) -> Vec<String> {
+    match patch.get(array) {
+        Some(Entry::Array(array)) => array
+            .elements
+            .iter()
+            .filter_map(|e| {
+                if let ArrayElement::Str(s) = e {
+                    Some(s.clone())
+                } else {
+                    None
+                }
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn problems(patch: &Class, ctx: &AddonContext) -> Vec<String> {
+    let p = ctx.global().project();
+    let version = p.version();
+    let versions = [
+        version.to_string(),
+        format!("{}.{}.{}", version.major, version.minor, version.patch),
+        p.version_macros()
+            .into_iter()
+            .find(|(name, _)| name == "VERSION")
+            .map(|(_, value)| value)
+            .unwrap_or_default(),
+    ];
+    let mut problems = Vec::new();
+    match value(patch, "version") {
+        Some(found) if !versions.iter().any(|v| version_prefix(&found, v)) => problems.push(
+            format!("has `version` `{}`, expected `{}`", found, versions[0]),
+        ),
+        Some(_) => {}
+        None => problems.push(String::from("does not declare `version`")),
+    }
+    match value(patch, "versionStr") {
+        Some(found) if !versions.contains(&found) => problems.push(format!(
+            "has `versionStr` `{}`, expected `{}`",
+            found, versions[0]
+        )),
+        Some(_) => {}
+        None => problems.push(String::from("does not declare `versionStr`")),
+    }
+    for (key, expected) in &[("author", p.author()), ("url", p.url.as_str())] {
+        if expected.is_empty() {
+            continue;
+        }
+        match value(patch, key) {
+            Some(found) if found != *expected => problems.push(format!(
+                "has `{}` `{}`, expected `{}`",
+                key, found, expected
+            )),
+            Some(_) => {}
+            None => problems.push(format!("does not declare `{}`", key)),
+        }
+    }
+    problems
+}
+
+/// `requiredVersion` is the project's `required_version`, an addon requiring a
+/// newer version would not load on every version the mod claims to support
+fn required_version(patch: &Class, ctx: &AddonContext) -> Option<String> {
+    let expected = &ctx.global().project().required_version;
+    if expected.is_empty() {
+        return None;
+    }
+    let expected_number = match expected.parse::<f64>() {
+        Ok(number) => number,
+        Err(_) => {
+            return Some(format!(
+                "cannot be checked, the project's `required_version` `{}` is not a number",
+                expected
+            ))
+        }
+    };
+    let found = match value(patch, "requiredVersion") {
+        Some(found) => found,
+        None => return Some(String::from("does not declare `requiredVersion`")),
+    };
+    match found.parse::<f64>() {
+        Ok(number) if (number - expected_number).abs() < 0.000_1 => None,
+        Ok(number) if number > expected_number => Some(format!(
+            "requires version `{}`, newer than the project's `required_version` `{}`",
+            found, expected
+        )),
+        _ => Some(format!(
+            "has `requiredVersion` `{}`, expected `{}`",
+            found, expected
+        )),
+    }
+}
+
+/// `found` is the version or its first parts, compared part by part
+/// Ex: `1.2` is a prefix of `1.2.3`, but not of `1.20.0`
+fn version_prefix(found: &str, version: &str) -> bool {
+    let found: Vec<&str> = found.split('.').collect();
+    let version: Vec<&str> = version.split('.').collect();
+    found.len() <= version.len()
+        && found
+            .iter()
+            .zip(&version)
+            .all(|(f, v)| match (f.parse::<u64>(), v.parse::<u64>()) {
+                (Ok(f), Ok(v)) => f == v,
+                _ => f == v,
+            })
+}
+
+/// A property of a class as text
+fn value(class: &Class, key: &str) -> Option<String> {
+    match class.get(key)? {
+        Entry::Str(s) => Some(s.clone()),
+        Entry::Float(f) => Some(f.to_string()),
+        Entry::Int(i) => Some(i.to_string()),
+        _ => None,
+    }
+}
@@ -8,9 +8,8 @@ pub fn can_rapify(path: &str) -> bool {
     let path = PathBuf::from(path);
     let name = path
         .extension()
-        .unwrap_or_else(|| std::ffi::OsStr::new(""))
-        .to_str()
-        .unwrap();
+        .and_then(std::ffi::OsStr::to_str)
+        .unwrap_or_default();
     ["cpp", "rvmat", "ext"].contains(&name)
 }
 
@@ -24,6 +23,10 @@ impl Task for Rapify {
         &[Stage::Build]
     }
 
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
     fn build(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
         for entry in ctx.global().fs().join(ctx.addon().source())?.walk_dir()? {
             let entry = entry?;
@@ -31,9 +34,13 @@ impl Task for Rapify {
                 ctx.debug(&format!("rapify: {:?}", entry.as_str()));
                 let mut buf = String::new();
                 entry.open_file()?.read_to_string(&mut buf)?;
-                let simplified =
-                    hemtt_arma_config::simplify::Config::from_ast(hemtt_arma_config::parse(&buf)?)
-                        .unwrap();
+                let ast = hemtt_arma_config::parse(&buf).map_err(|e| {
+                    ctx.global()
+                        .container
+                        .get::<crate::tasks::SourceMaps>()
+                        .locate(e, entry.as_str(), ctx.global().fs())
+                })?;
+                let simplified = hemtt_arma_config::simplify::Config::from_ast(ast).unwrap();
                 let mut out = if entry.filename() == "config.cpp" {
                     entry.parent().unwrap().join("config.bin")?
                 } else {
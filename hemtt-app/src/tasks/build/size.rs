@@ -0,0 +1,70 @@
+use hemtt::Project;
+
+use crate::{context::AddonContext, HEMTTError, Stage, Task};
+
+const MEGABYTE: u64 = 1024 * 1024;
+
+/// Warns when a packed PBO is larger or has more files than the project allows,
+/// or when a single file in it is larger than the project allows
+///
+/// The limits are `pbo_size`, `pbo_files` and `file_size` of `[lints]`
+pub struct Size {}
+impl Task for Size {
+    fn name(&self) -> String {
+        String::from("size")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::PostBuild]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn postbuild(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let lints = ctx.global().project().lints.clone();
+        let pbo_path = ctx.addon().destination(
+            &Project::output_dir()?,
+            Some(ctx.global().project().prefix()),
+            None,
+        );
+        // Not packed with `--dry-run`
+        if !pbo_path.exists() {
+            return Ok(());
+        }
+
+        let size = std::fs::metadata(hemtt::long_path(&pbo_path))?.len();
+        if lints.pbo_size != 0 && size > lints.pbo_size * MEGABYTE {
+            ctx.warn(&format!(
+                "PBO is {} MB, more than the {} MB allowed by `lints.pbo_size`",
+                size / MEGABYTE,
+                lints.pbo_size
+            ));
+        }
+
+        let files = hemtt_pbo::ReadablePbo::from(open_file!(&pbo_path)?)?.files();
+        if lints.pbo_files != 0 && files.len() > lints.pbo_files {
+            ctx.warn(&format!(
+                "PBO has {} files, more than the {} allowed by `lints.pbo_files`",
+                files.len(),
+                lints.pbo_files
+            ));
+        }
+        if lints.file_size != 0 {
+            for header in &files {
+                // Compressed files are loaded at their original size
+                let size = u64::from(header.size().max(header.original()));
+                if size > lints.file_size * MEGABYTE {
+                    ctx.warn(&format!(
+                        "`{}` is {} MB, more than the {} MB allowed by `lints.file_size`",
+                        header.filename(),
+                        size / MEGABYTE,
+                        lints.file_size
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use hemtt_arma_config::simplify::{Class, Entry};
+
+use crate::{context::AddonContext, HEMTTError, Stage, Task};
+
+/// Properties shown to players that should be translatable
+const DISPLAYED: [&str; 8] = [
+    "displayName",
+    "displayNameShort",
+    "description",
+    "descriptionShort",
+    "tooltip",
+    "text",
+    "textPlural",
+    "textSingular",
+];
+
+/// Warns about display properties with literal text instead of a `$STR_` stringtable entry
+pub struct Strings {}
+impl Task for Strings {
+    fn name(&self) -> String {
+        String::from("strings")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Build]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn build(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let lints = &ctx.global().project().lints;
+        if !lints.strings {
+            return Ok(());
+        }
+        let config = match super::addon_config(ctx)? {
+            Some(config) => config,
+            None => return Ok(()),
+        };
+        let mut literals = Vec::new();
+        literal(&config.root, "", &mut literals);
+        for (path, text) in literals {
+            if !lints.string_allowed(&text) {
+                ctx.warn(&format!(
+                    "`{}` is not localized: \"{}\", use a `$STR_` entry",
+                    path, text
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Display properties with literal text in a class and its children
+fn literal(class: &Class, path: &str, literals: &mut Vec<(String, String)>) {
+    for (name, entry) in &class.entries {
+        let child = if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{} >> {}", path, name)
+        };
+        match entry {
+            Entry::Class(c) => literal(c, &child, literals),
+            Entry::Str(text)
+                if DISPLAYED.iter().any(|d| d.eq_ignore_ascii_case(name))
+                    && !text.trim().is_empty()
+                    && !text.to_lowercase().starts_with("$str") =>
+            {
+                literals.push((child, text.to_string()))
+            }
+            _ => {}
+        }
+    }
+}
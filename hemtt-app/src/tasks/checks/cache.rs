@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use hemtt::{AddonLocation, Project};
+use sha1::{Digest, Sha1};
+
+use crate::{
+    context::{AddonContext, AddonListContext},
+    HEMTTError, Stage, Task,
+};
+
+/// Folder of the artifact store, relative to the project root
+pub const CACHE_DIR: &str = ".hemttout/cache";
+
+/// Feeds the relative paths and contents of the files in a folder into the hasher
+fn hash_files<F: Fn(&Path) -> bool>(
+    hasher: &mut Sha1,
+    folder: &Path,
+    filter: F,
+) -> Result<(), HEMTTError> {
+    for entry in walkdir::WalkDir::new(folder).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        if !entry.file_type().is_file() || !filter(entry.path()) {
+            continue;
+        }
+        hasher.update(entry.path().to_string_lossy().replace("\\", "/").as_bytes());
+        let mut data = Vec::new();
+        open_file!(entry.path())?.read_to_end(&mut data)?;
+        hasher.update(&data);
+    }
+    Ok(())
+}
+
+fn is_header(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(std::ffi::OsStr::to_str),
+        Some("hpp") | Some("h") | Some("inc")
+    )
+}
+
+/// Files that make up the project's configuration, relative to the project root
+const CONFIG_FILES: [&str; 4] = [
+    "hemtt.toml",
+    "hemtt.local.toml",
+    "hemtt.local.json",
+    ".hemtt",
+];
+
+/// Stores built PBOs keyed by the hash of their inputs,
+/// restoring them instead of rebuilding when the inputs are seen again
+///
+/// The inputs are the addon's files, every header and include folder the preprocessor
+/// can resolve and the project's configuration. Cached PBOs are only restored when building.
+pub struct Cache {
+    shared: RwLock<Vec<u8>>,
+    hashes: RwLock<HashMap<String, String>>,
+}
+impl Cache {
+    pub fn new() -> Self {
+        Self {
+            shared: RwLock::new(Vec::new()),
+            hashes: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn artifact(hash: &str) -> Result<PathBuf, HEMTTError> {
+        Ok(Project::find_root()?
+            .join(CACHE_DIR)
+            .join(format!("{}.pbo", hash)))
+    }
+}
+impl Task for Cache {
+    fn name(&self) -> String {
+        String::from("cache")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Check, Stage::Build, Stage::PostBuild]
+    }
+
+    fn check_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        // Inputs shared by every addon: the project, HEMTT itself and all headers
+        let p = ctx.global().project();
+        let mut hasher = Sha1::new();
+        hasher.update(*crate::VERSION);
+        hasher.update(
+            serde_json::to_string(p)
+                .map_err(|e| HEMTTError::Generic(e.to_string()))?
+                .as_bytes(),
+        );
+        for file in &CONFIG_FILES {
+            let path = Path::new(file);
+            if path.exists() {
+                hash_files(&mut hasher, path, |_| true)?;
+            }
+        }
+        for location in AddonLocation::first_class() {
+            if location.exists() {
+                hash_files(&mut hasher, Path::new(&location.to_string()), is_header)?;
+            }
+        }
+        for include in crate::tasks::includes(p) {
+            hash_files(&mut hasher, Path::new(&include), |_| true)?;
+        }
+        *self.shared.write().unwrap() = hasher.finalize().to_vec();
+        Ok(())
+    }
+
+    fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let mut hasher = Sha1::new();
+        hasher.update(&*self.shared.read().unwrap());
+        hash_files(&mut hasher, Path::new(ctx.addon().source()), |_| true)?;
+        let hash = format!("{:x}", hasher.finalize());
+        ctx.trace(&format!("input hash: {}", hash));
+
+        if Self::artifact(&hash)?.exists() {
+            ctx.set_skip(true);
+            ctx.debug(&format!("Found in cache: {}", hash));
+        }
+        self.hashes
+            .write()
+            .unwrap()
+            .insert(ctx.addon().source().to_string(), hash);
+        Ok(())
+    }
+
+    fn build_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        let hashes = self.hashes.read().unwrap();
+        for addon in ctx.addons().iter().filter(|addon| addon.skip()) {
+            let hash = match hashes.get(addon.addon().source()) {
+                Some(hash) => hash,
+                None => continue,
+            };
+            let target = addon.addon().destination(
+                &Project::output_dir()?,
+                Some(ctx.global().project().prefix()),
+                None,
+            );
+            if !crate::dry_run("restore", &target) {
+                create_dir!(target.parent().unwrap())?;
+                let artifact = Self::artifact(hash)?;
+                copy_file!(&artifact, &target)?;
+            }
+            addon.debug(&format!("Restored from cache: {}", hash));
+        }
+        Ok(())
+    }
+
+    fn postbuild(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        if let Some(hash) = self.hashes.read().unwrap().get(ctx.addon().source()) {
+            let target = ctx.addon().destination(
+                &Project::output_dir()?,
+                Some(ctx.global().project().prefix()),
+                None,
+            );
+            let artifact = Self::artifact(hash)?;
+            if crate::dry_run("create", &artifact) {
+                return Ok(());
+            }
+            create_dir!(artifact.parent().unwrap())?;
+            ctx.debug(&format!("Storing in cache: {}", hash));
+            copy_file!(&target, &artifact)?;
+        }
+        Ok(())
+    }
+}
@@ -12,13 +12,17 @@ impl Task for Clean {
         &[Stage::Check]
     }
 
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
     fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
         let target = ctx.addon().destination(
-            &hemtt::Project::find_root()?,
+            &hemtt::Project::output_dir()?,
             Some(ctx.global().project().prefix()),
             None,
         );
-        if target.exists() {
+        if target.exists() && !crate::dry_run("delete", &target) {
             remove_file!(target)?;
         }
         Ok(())
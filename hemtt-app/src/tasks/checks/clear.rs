@@ -1,12 +1,9 @@
-use std::path::PathBuf;
-
-use regex::Regex;
 use strum::IntoEnumIterator;
 
 use crate::{context::AddonListContext, HEMTTError, Stage, Task};
-use hemtt::AddonLocation;
+use hemtt::{AddonLocation, Project};
 
-// Clears all pbo files that are not part of the hemtt project
+// Clears all pbo files in the output folder that are not part of the hemtt project
 #[derive(Clone)]
 pub struct Clear {}
 impl Task for Clear {
@@ -19,22 +16,25 @@ impl Task for Clear {
     }
 
     fn check_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
-        let re = Regex::new(r"(?m)(.+?)\.pbo$").unwrap();
         let mut targets = Vec::new();
         for data in &*ctx.addons() {
             targets.push(data.addon().pbo(Some(ctx.global().project().prefix())));
         }
+        let output = Project::output_dir()?;
         for dir in AddonLocation::iter() {
-            let dir = dir.to_string();
-            if !PathBuf::from(&dir).exists() {
+            let dir = output.join(dir.to_string());
+            if !dir.exists() {
                 continue;
             }
             for entry in std::fs::read_dir(dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                let loc = path.display().to_string();
-                if !path.is_dir() && re.is_match(&loc) && !targets.contains(&loc) {
-                    remove_file!(&loc)?;
+                let path = entry?.path();
+                let name = path.file_name().unwrap().to_string_lossy().to_string();
+                if !path.is_dir()
+                    && name.ends_with(".pbo")
+                    && !targets.contains(&name)
+                    && !crate::dry_run("delete", &path)
+                {
+                    remove_file!(&path)?;
                 }
             }
         }
@@ -0,0 +1,165 @@
+use std::path::Path;
+
+use crate::{context::AddonContext, HEMTTError, Stage, Task};
+
+/// Extensions of the text files that are packed
+pub const TEXT_EXTS: [&str; 16] = [
+    "sqf", "sqm", "sqs", "hpp", "h", "inc", "cpp", "ext", "xml", "txt", "rvmat", "bikb", "fsm",
+    "cfg", "html", "csv",
+];
+
+const BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// Problems with the encoding of a file
+#[derive(Debug, PartialEq)]
+enum Problem {
+    Bom,
+    Utf16,
+    NotUtf8,
+    Mixed,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Bom => "starts with a UTF-8 byte order mark",
+                Self::Utf16 => "is encoded as UTF-16",
+                Self::NotUtf8 => "is not encoded as UTF-8",
+                Self::Mixed => "mixes UTF-8 with another encoding",
+            }
+        )
+    }
+}
+
+/// Checks that every text file is UTF-8 without a byte order mark,
+/// `fix` rewrites the files as UTF-8
+pub struct Encoding {
+    pub fix: bool,
+}
+impl Task for Encoding {
+    fn name(&self) -> String {
+        String::from("encoding")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Check]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        if !ctx.global().project().lints.encoding {
+            return Ok(());
+        }
+        for path in addon_text_files(ctx)? {
+            let data = std::fs::read(&path)?;
+            let problem = match problem(&data) {
+                Some(problem) => problem,
+                None => continue,
+            };
+            if self.fix {
+                if !crate::dry_run("fix", &path) {
+                    std::fs::write(&path, decode(&data).as_bytes())?;
+                }
+                ctx.info(&format!(
+                    "{} {}, rewritten as UTF-8",
+                    path.display(),
+                    problem
+                ));
+            } else {
+                ctx.warn(&format!("{} {}", path.display(), problem));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Text files of the addon that are checked, the layers generated for terrains are left out
+pub fn addon_text_files(ctx: &AddonContext) -> Result<Vec<std::path::PathBuf>, HEMTTError> {
+    let terrain = &ctx.global().project().terrain;
+    let source = ctx.addon().source();
+    Ok(text_files(source)?
+        .into_iter()
+        .filter(|path| {
+            !terrain.is_layer(
+                ctx.addon().name(),
+                &path.strip_prefix(source).unwrap_or(path).to_string_lossy(),
+            )
+        })
+        .collect())
+}
+
+/// Every text file in the folder
+pub fn text_files<P: AsRef<Path>>(folder: P) -> Result<Vec<std::path::PathBuf>, HEMTTError> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(folder).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        if entry.file_type().is_file()
+            && entry.path().extension().map_or(false, |ext| {
+                TEXT_EXTS.contains(&ext.to_string_lossy().to_lowercase().as_str())
+            })
+        {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+fn problem(data: &[u8]) -> Option<Problem> {
+    if data.starts_with(&[0xFF, 0xFE]) || data.starts_with(&[0xFE, 0xFF]) {
+        return Some(Problem::Utf16);
+    }
+    if data.starts_with(BOM) {
+        return Some(Problem::Bom);
+    }
+    match std::str::from_utf8(data) {
+        Ok(_) => None,
+        Err(_) => {
+            // Valid multi-byte UTF-8 sequences next to invalid bytes
+            if String::from_utf8_lossy(data)
+                .chars()
+                .any(|c| c != '\u{FFFD}' && !c.is_ascii())
+            {
+                Some(Problem::Mixed)
+            } else {
+                Some(Problem::NotUtf8)
+            }
+        }
+    }
+}
+
+/// Decodes the file as UTF-8, bytes that are not UTF-8 are read as Windows-1252
+fn decode(data: &[u8]) -> String {
+    if let Some((encoding, bom)) = encoding_rs::Encoding::for_bom(data) {
+        return encoding
+            .decode_without_bom_handling(&data[bom..])
+            .0
+            .into_owned();
+    }
+    let mut out = String::with_capacity(data.len());
+    let mut rest = data;
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                out.push_str(valid);
+                return out;
+            }
+            Err(e) => {
+                let (valid, invalid) = rest.split_at(e.valid_up_to());
+                out.push_str(std::str::from_utf8(valid).unwrap());
+                let len = e.error_len().unwrap_or_else(|| invalid.len());
+                out.push_str(
+                    &encoding_rs::WINDOWS_1252
+                        .decode_without_bom_handling(&invalid[..len])
+                        .0,
+                );
+                rest = &invalid[len..];
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::{
+    context::{AddonContext, AddonListContext},
+    HEMTTError, Stage, Task,
+};
+
+/// Checks that every `#include` matches the case of the file on disk, Windows
+/// finds the file either way but case sensitive file systems do not
+pub struct IncludeCase {
+    /// Addon folder of each lowercase prefix
+    prefixes: RwLock<HashMap<String, String>>,
+}
+impl IncludeCase {
+    pub fn new() -> Self {
+        Self {
+            prefixes: RwLock::new(HashMap::new()),
+        }
+    }
+}
+impl Task for IncludeCase {
+    fn name(&self) -> String {
+        String::from("includecase")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Check]
+    }
+
+    fn check_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        let mut prefixes = self.prefixes.write().unwrap();
+        for addon in ctx.addons() {
+            prefixes.insert(
+                addon.prefix().replace('\\', "/").to_lowercase(),
+                addon.addon().source().to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        if !ctx.global().project().lints.include_case {
+            return Ok(());
+        }
+        let folders = crate::tasks::includes(ctx.global().project());
+        let prefixes = self.prefixes.read().unwrap();
+        for path in super::addon_text_files(ctx)? {
+            let data = std::fs::read(&path)?;
+            for (line, include) in includes(&String::from_utf8_lossy(&data)) {
+                let (actual, problems) = match resolve(&path, &include, &prefixes, &folders) {
+                    Some(found) => found,
+                    None => continue,
+                };
+                if problems > 0 {
+                    ctx.warn(&format!(
+                        "{}:{} includes `{}`, but the file is `{}`",
+                        path.display(),
+                        line,
+                        include,
+                        actual.display()
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Line number and path of every `#include` in the text
+fn includes(text: &str) -> Vec<(usize, String)> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let rest = line.trim_start().strip_prefix("#include")?.trim_start();
+            let end = match rest.chars().next()? {
+                '"' => '"',
+                '<' => '>',
+                _ => return None,
+            };
+            let include = &rest[1..];
+            Some((i + 1, include[..include.find(end)?].to_string()))
+        })
+        .collect()
+}
+
+/// Finds the included file without case, returning its path and the number of
+/// folders and files in the include that have a different case
+fn resolve(
+    from: &Path,
+    include: &str,
+    prefixes: &HashMap<String, String>,
+    folders: &[String],
+) -> Option<(PathBuf, usize)> {
+    let include = include.replace('\\', "/");
+    if !include.starts_with('/') {
+        return find_no_case(from.parent()?, &include);
+    }
+    if let Some((source, rest)) = prefixes
+        .iter()
+        .find_map(|(prefix, source)| Some((source, hemtt::strip_prefix_no_case(&include, prefix)?)))
+    {
+        return find_no_case(Path::new(source), rest);
+    }
+    folders
+        .iter()
+        .find_map(|folder| find_no_case(Path::new(folder), include.trim_start_matches('/')))
+}
+
+/// Walks down from the folder, matching each part of the path without case
+pub fn find_no_case(folder: &Path, path: &str) -> Option<(PathBuf, usize)> {
+    let mut current = folder.to_path_buf();
+    let mut problems = 0;
+    for component in Path::new(path).components() {
+        let part = match component {
+            Component::Normal(part) => part.to_string_lossy(),
+            Component::ParentDir => {
+                current.push("..");
+                continue;
+            }
+            _ => continue,
+        };
+        let names: Vec<String> = std::fs::read_dir(&current)
+            .ok()?
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        let actual = if names.iter().any(|name| *name == part) {
+            part.to_string()
+        } else {
+            problems += 1;
+            names
+                .into_iter()
+                .find(|name| name.eq_ignore_ascii_case(&part))?
+        };
+        current.push(actual);
+    }
+    Some((current, problems))
+}
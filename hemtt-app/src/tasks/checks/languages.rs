@@ -0,0 +1,65 @@
+use hemtt_stringtable::Stringtable;
+
+use crate::{context::AddonContext, HEMTTError, Stage, Task};
+
+/// Warns about languages in stringtables that Arma 3 does not support,
+/// their translations are never shown
+pub struct Languages {}
+impl Task for Languages {
+    fn name(&self) -> String {
+        String::from("languages")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Check]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        if !ctx.global().project().lints.languages {
+            return Ok(());
+        }
+        for entry in walkdir::WalkDir::new(ctx.addon().source())
+            .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+        {
+            let entry = entry.map_err(std::io::Error::from)?;
+            if !entry.file_type().is_file()
+                || !entry
+                    .file_name()
+                    .to_string_lossy()
+                    .eq_ignore_ascii_case("stringtable.xml")
+            {
+                continue;
+            }
+            let path = entry.path();
+            let stringtable = match Stringtable::read(&std::fs::read_to_string(path)?) {
+                Ok(stringtable) => stringtable,
+                Err(e) => {
+                    ctx.warn(&format!("{} could not be read: {}", path.display(), e));
+                    continue;
+                }
+            };
+            for key in stringtable.keys() {
+                for translation in &key.translations {
+                    if hemtt_stringtable::is_language(&translation.language) {
+                        continue;
+                    }
+                    let hint = hemtt_stringtable::closest_language(&translation.language)
+                        .map(|l| format!(", did you mean `{}`?", l))
+                        .unwrap_or_default();
+                    ctx.warn(&format!(
+                        "{} `{}` has a translation in `{}`, which is not a supported language{}",
+                        path.display(),
+                        key.id,
+                        translation.language,
+                        hint
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
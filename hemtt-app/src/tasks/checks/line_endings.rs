@@ -0,0 +1,103 @@
+use hemtt::LineEnding;
+
+use crate::{context::AddonContext, HEMTTError, Stage, Task};
+
+/// Checks that the lines of every text file end the same way, using the
+/// ending configured for the extension if there is one, `fix` rewrites them
+pub struct LineEndings {
+    pub fix: bool,
+}
+impl Task for LineEndings {
+    fn name(&self) -> String {
+        String::from("lineendings")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Check]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let lints = &ctx.global().project().lints;
+        if !lints.line_endings {
+            return Ok(());
+        }
+        for path in super::addon_text_files(ctx)? {
+            let data = std::fs::read(&path)?;
+            let (lf, crlf) = count(&data);
+            let expected = path
+                .extension()
+                .and_then(|ext| {
+                    lints
+                        .line_ending_exts
+                        .get(&ext.to_string_lossy().to_lowercase())
+                })
+                .copied();
+            let problem = match expected {
+                Some(LineEnding::Lf) if crlf > 0 => {
+                    format!("has {} CRLF line endings, expected LF", crlf)
+                }
+                Some(LineEnding::Crlf) if lf > 0 => {
+                    format!("has {} LF line endings, expected CRLF", lf)
+                }
+                None if lf > 0 && crlf > 0 => {
+                    format!("mixes {} LF and {} CRLF line endings", lf, crlf)
+                }
+                _ => continue,
+            };
+            if self.fix {
+                let ending = expected.unwrap_or(if crlf > lf {
+                    LineEnding::Crlf
+                } else {
+                    LineEnding::Lf
+                });
+                if !crate::dry_run("fix", &path) {
+                    std::fs::write(&path, normalize(&data, ending))?;
+                }
+                ctx.info(&format!(
+                    "{} {}, rewritten with {}",
+                    path.display(),
+                    problem,
+                    ending
+                ));
+            } else {
+                ctx.warn(&format!("{} {}", path.display(), problem));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Number of LF and CRLF line endings
+fn count(data: &[u8]) -> (usize, usize) {
+    let mut lf = 0;
+    let mut crlf = 0;
+    for (i, byte) in data.iter().enumerate() {
+        if *byte == b'\n' {
+            if i > 0 && data[i - 1] == b'\r' {
+                crlf += 1;
+            } else {
+                lf += 1;
+            }
+        }
+    }
+    (lf, crlf)
+}
+
+fn normalize(data: &[u8], ending: LineEnding) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    for (i, byte) in data.iter().enumerate() {
+        if *byte == b'\r' && data.get(i + 1) == Some(&b'\n') {
+            continue;
+        }
+        if *byte == b'\n' {
+            out.extend_from_slice(ending.as_str().as_bytes());
+        } else {
+            out.push(*byte);
+        }
+    }
+    out
+}
@@ -1,9 +1,23 @@
+mod cache;
 mod clean;
 mod clear;
-mod modtime;
+mod encoding;
+mod include_case;
+mod languages;
+mod line_endings;
+mod models;
 mod names;
+mod separators;
+mod textures;
 
+pub use cache::{Cache, CACHE_DIR};
 pub use clean::Clean;
 pub use clear::Clear;
-pub use modtime::ModTime;
+pub use encoding::{addon_text_files, Encoding};
+pub use include_case::{find_no_case, IncludeCase};
+pub use languages::Languages;
+pub use line_endings::LineEndings;
+pub use models::Models;
 pub use names::*;
+pub use separators::Separators;
+pub use textures::Textures;
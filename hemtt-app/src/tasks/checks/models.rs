@@ -0,0 +1,159 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::RwLock;
+
+use hemtt_p3d::P3D;
+use hemtt_pbo::ReadablePbo;
+
+use crate::{
+    context::{AddonContext, AddonListContext},
+    HEMTTError, Stage, Task,
+};
+
+/// Checks that the textures, materials and proxies of every unbinarized model
+/// are in the project or its fetched dependencies, players get a popup for
+/// every missing one
+///
+/// Files of the game, under `a3\`, are not checked.
+pub struct Models {
+    /// Addon folder of each lowercase prefix
+    prefixes: RwLock<HashMap<String, String>>,
+    /// Lowercase path of every file in the PBOs of the fetched dependencies
+    dependencies: RwLock<HashSet<String>>,
+}
+impl Models {
+    pub fn new() -> Self {
+        Self {
+            prefixes: RwLock::new(HashMap::new()),
+            dependencies: RwLock::new(HashSet::new()),
+        }
+    }
+}
+impl Task for Models {
+    fn name(&self) -> String {
+        String::from("models")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Check]
+    }
+
+    fn check_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        if !ctx.global().project().lints.models {
+            return Ok(());
+        }
+        // Models can use the files of addons that are not being built
+        *self.prefixes.write().unwrap() = crate::tasks::prefixes(ctx)?;
+        *self.dependencies.write().unwrap() = dependency_files()?;
+        Ok(())
+    }
+
+    fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        if !ctx.global().project().lints.models {
+            return Ok(());
+        }
+        let folders = crate::tasks::includes(ctx.global().project());
+        let prefixes = self.prefixes.read().unwrap();
+        let dependencies = self.dependencies.read().unwrap();
+        // Without the files of the dependencies, only the project's own files are known
+        let outside = !dependencies.is_empty() || ctx.global().project().dependencies.is_empty();
+        for entry in walkdir::WalkDir::new(ctx.addon().source())
+            .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+        {
+            let entry = entry.map_err(std::io::Error::from)?;
+            let path = entry.path();
+            if !entry.file_type().is_file()
+                || !path
+                    .extension()
+                    .map_or(false, |e| e.eq_ignore_ascii_case("p3d"))
+            {
+                continue;
+            }
+            let p3d = match P3D::read(std::io::BufReader::new(open_file!(path)?)) {
+                Ok(Some(p3d)) => p3d,
+                Ok(None) => {
+                    ctx.debug(&format!("{} is binarized, skipping", path.display()));
+                    continue;
+                }
+                Err(e) => {
+                    ctx.warn(&format!("{} could not be read: {}", path.display(), e));
+                    continue;
+                }
+            };
+            let proxies = p3d.proxies();
+            let references = p3d
+                .textures()
+                .into_iter()
+                .map(|texture| ("texture", texture))
+                .chain(
+                    p3d.materials()
+                        .into_iter()
+                        .map(|material| ("material", material)),
+                )
+                .chain(proxies.iter().map(|proxy| ("proxy", proxy.as_str())));
+            for (kind, reference) in references {
+                let file = reference.replace('\\', "/");
+                let file = file.trim_start_matches('/');
+                let lower = file.to_lowercase();
+                if lower.starts_with("a3/") {
+                    continue;
+                }
+                let found = match prefixes.iter().find_map(|(prefix, source)| {
+                    Some((source, hemtt::strip_prefix_no_case(file, prefix)?))
+                }) {
+                    Some((source, rest)) => super::find_no_case(Path::new(source), rest).is_some(),
+                    None if !outside => continue,
+                    None => {
+                        dependencies.contains(&lower)
+                            || folders.iter().any(|folder| {
+                                super::find_no_case(Path::new(folder), file).is_some()
+                            })
+                    }
+                };
+                if !found {
+                    ctx.warn(&format!(
+                        "{} uses the {} `{}`, which is not in the project or its dependencies",
+                        path.display(),
+                        kind,
+                        reference
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase path of every file in the PBOs of the fetched dependencies
+/// Ex: "x/cba/addons/main/script_macros_common.hpp"
+fn dependency_files() -> Result<HashSet<String>, HEMTTError> {
+    let mut files = HashSet::new();
+    let folder = Path::new(hemtt::DEPS_DIR);
+    if !folder.exists() {
+        return Ok(files);
+    }
+    for entry in walkdir::WalkDir::new(folder) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        if !entry
+            .path()
+            .extension()
+            .map_or(false, |e| e.eq_ignore_ascii_case("pbo"))
+        {
+            continue;
+        }
+        let pbo = ReadablePbo::from(open_file!(entry.path())?)?;
+        let prefix = match pbo.extension("prefix") {
+            Some(prefix) => prefix.replace('\\', "/").to_lowercase(),
+            None => continue,
+        };
+        let prefix = prefix.trim_matches('/');
+        for header in pbo.files() {
+            files.insert(format!(
+                "{}/{}",
+                prefix,
+                header.filename().replace('\\', "/").to_lowercase()
+            ));
+        }
+    }
+    Ok(files)
+}
@@ -14,6 +14,10 @@ impl Task for NotEmpty {
         &[Stage::Check]
     }
 
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
     fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
         let empty = std::fs::read_dir(ctx.addon().source())?.count() == 0;
         if empty {
@@ -39,6 +43,10 @@ impl Task for ValidName {
         &[Stage::Check]
     }
 
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
     fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
         // WARN: addon name standards
         let addon = ctx.addon();
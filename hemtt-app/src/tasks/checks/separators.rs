@@ -0,0 +1,130 @@
+use crate::{context::AddonContext, HEMTTError, Stage, Task};
+
+/// Extensions of the files that are configs, where quoted file paths are checked
+const CONFIG_EXTS: [&str; 6] = ["cpp", "hpp", "h", "inc", "ext", "rvmat"];
+
+/// Extensions of the files that config properties point to
+const PATH_EXTS: [&str; 14] = [
+    "p3d", "paa", "pac", "jpg", "png", "rvmat", "sqf", "sqs", "fsm", "rtm", "wss", "ogg", "hpp",
+    "bikb",
+];
+
+/// Checks that includes and file paths in configs use backslashes, the
+/// preprocessor accepts forward slashes but the game does not always find the
+/// file, `fix` rewrites them with backslashes
+pub struct Separators {
+    pub fix: bool,
+}
+impl Task for Separators {
+    fn name(&self) -> String {
+        String::from("separators")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Check]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        if !ctx.global().project().lints.separators {
+            return Ok(());
+        }
+        for path in super::addon_text_files(ctx)? {
+            let config = path.extension().map_or(false, |ext| {
+                CONFIG_EXTS.contains(&ext.to_string_lossy().to_lowercase().as_str())
+            });
+            let text = match String::from_utf8(std::fs::read(&path)?) {
+                Ok(text) => text,
+                // Reported by the encoding check
+                Err(_) => continue,
+            };
+            let found = problems(&text, config);
+            if found.is_empty() {
+                continue;
+            }
+            for (line, file) in &found {
+                let message = format!(
+                    "{}:{} uses forward slashes in `{}`",
+                    path.display(),
+                    line,
+                    file
+                );
+                if self.fix {
+                    ctx.info(&format!("{}, rewritten with backslashes", message));
+                } else {
+                    ctx.warn(&message);
+                }
+            }
+            if self.fix && !crate::dry_run("fix", &path) {
+                std::fs::write(&path, fix(&text, config))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Line number and path of every include or config path with forward slashes
+fn problems(text: &str, config: bool) -> Vec<(usize, String)> {
+    text.lines()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            paths(line, config)
+                .into_iter()
+                .map(move |(start, end)| (i + 1, line[start..end].to_string()))
+        })
+        .collect()
+}
+
+/// The text with the forward slashes of every problem replaced
+fn fix(text: &str, config: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let mut last = 0;
+        for (start, end) in paths(line, config) {
+            out.push_str(&line[last..start]);
+            out.push_str(&line[start..end].replace('/', "\\"));
+            last = end;
+        }
+        out.push_str(&line[last..]);
+    }
+    out
+}
+
+/// Byte ranges of the paths in the line that use forward slashes
+fn paths(line: &str, config: bool) -> Vec<(usize, usize)> {
+    let trimmed = line.trim_start();
+    if let Some(rest) = trimmed.strip_prefix("#include") {
+        let rest = rest.trim_start();
+        // `rest` is the end of the line, the path starts after its opening quote
+        let offset = line.len() - rest.len() + 1;
+        let end = match rest.chars().next() {
+            Some('"') => '"',
+            Some('<') => '>',
+            _ => return Vec::new(),
+        };
+        return match rest[1..].find(end) {
+            Some(len) if rest[1..1 + len].contains('/') => vec![(offset, offset + len)],
+            _ => Vec::new(),
+        };
+    }
+    if !config || trimmed.starts_with("//") {
+        return Vec::new();
+    }
+    let mut found = Vec::new();
+    let mut quotes = line.match_indices('"').map(|(i, _)| i);
+    while let (Some(start), Some(end)) = (quotes.next(), quotes.next()) {
+        let value = &line[start + 1..end];
+        let is_path = value.contains('/')
+            && !value.contains("://")
+            && value.rsplit('.').next().map_or(false, |ext| {
+                value.contains('.') && PATH_EXTS.contains(&ext.to_lowercase().as_str())
+            });
+        if is_path {
+            found.push((start + 1, end));
+        }
+    }
+    found
+}
@@ -0,0 +1,80 @@
+use hemtt_paa::Paa;
+
+use crate::{context::AddonContext, HEMTTError, Stage, Task};
+
+/// Warns about textures whose width or height is not a power of two, and PAAs
+/// in a format Arma 3 does not support, they are rendered broken in game
+///
+/// Both the PAAs and the PNG and TGA images they are made from are checked
+pub struct Textures {}
+impl Task for Textures {
+    fn name(&self) -> String {
+        String::from("textures")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Check]
+    }
+
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
+    fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        if !ctx.global().project().lints.textures {
+            return Ok(());
+        }
+        for entry in walkdir::WalkDir::new(ctx.addon().source())
+            .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+        {
+            let entry = entry.map_err(std::io::Error::from)?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path = entry.path();
+            let extension = path
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            let (width, height) = match extension.as_str() {
+                "paa" | "pac" => match Paa::dimensions(open_file!(path)?) {
+                    Ok(Some((format, width, height))) => {
+                        if !format.is_supported() {
+                            ctx.warn(&format!(
+                                "{} is {:?}, which is not a supported PAA format",
+                                path.display(),
+                                format
+                            ));
+                        }
+                        (u32::from(width), u32::from(height))
+                    }
+                    Ok(None) => {
+                        ctx.warn(&format!("{} is not a PAA", path.display()));
+                        continue;
+                    }
+                    Err(e) => {
+                        ctx.warn(&format!("{} could not be read: {}", path.display(), e));
+                        continue;
+                    }
+                },
+                "png" | "tga" => match image::image_dimensions(path) {
+                    Ok(dimensions) => dimensions,
+                    Err(e) => {
+                        ctx.warn(&format!("{} could not be read: {}", path.display(), e));
+                        continue;
+                    }
+                },
+                _ => continue,
+            };
+            if !width.is_power_of_two() || !height.is_power_of_two() {
+                ctx.warn(&format!(
+                    "{} is {}x{}, textures need a power of two width and height",
+                    path.display(),
+                    width,
+                    height
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 mod build;
 mod checks;
 mod prebuild;
+mod release;
 
 pub use build::*;
 pub use checks::*;
 pub use prebuild::*;
+pub use release::*;
@@ -1,8 +1,11 @@
+mod pdrive;
+pub use pdrive::PDrive;
+
 mod prefix;
-pub use prefix::Prefix;
+pub use prefix::{prefixes, Prefix};
 
 mod preprocess;
-pub use preprocess::Preprocess;
+pub use preprocess::{includes, predefined, render_file, Preprocess, SourceMaps, PREPROCESSED};
 
 mod populate;
 pub use populate::Populate;
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use hemtt::Project;
+
+/// Folders mounted by their prefix on a virtual P-drive, so absolute paths can be
+/// found without the project being laid out on a real one
+/// Ex: `\z\ace\addons\main\script_mod.hpp` => `addons/main/script_mod.hpp`
+pub struct PDrive(Vec<(String, PathBuf)>);
+impl PDrive {
+    /// Mounts the project's addons by prefix, then the project's `mounts`, then
+    /// the include folders at the root, which hold the fetched dependencies
+    ///
+    /// Arguments:
+    /// * `p`: project being built
+    /// * `prefixes`: addon folder of each prefix
+    pub fn new(p: &Project, prefixes: &HashMap<String, String>) -> Self {
+        let mut mounts: Vec<(String, PathBuf)> = prefixes
+            .iter()
+            .map(|(prefix, source)| (normalize(prefix).to_lowercase(), PathBuf::from(source)))
+            .collect();
+        mounts.sort();
+        mounts.extend(
+            p.mounts
+                .iter()
+                .map(|(prefix, folder)| (normalize(prefix).to_lowercase(), folder.clone())),
+        );
+        mounts.extend(
+            super::includes(p)
+                .into_iter()
+                .map(|folder| (String::new(), PathBuf::from(folder))),
+        );
+        Self(mounts)
+    }
+
+    /// Prefix and folder of every mount, in the order they are searched
+    pub fn mounts(&self) -> &[(String, PathBuf)] {
+        &self.0
+    }
+
+    /// Files an absolute path could be, the most specific mount first
+    pub fn locate(&self, path: &str) -> Vec<PathBuf> {
+        let path = normalize(path);
+        let mut found: Vec<(usize, PathBuf)> = self
+            .0
+            .iter()
+            .filter_map(|(prefix, folder)| {
+                let rest = hemtt::strip_prefix_no_case(&path, prefix)?;
+                Some((prefix.len(), folder.join(rest)))
+            })
+            .collect();
+        found.sort_by(|a, b| b.0.cmp(&a.0));
+        found.into_iter().map(|(_, file)| file).collect()
+    }
+}
+
+/// Path with `/` between folders and none at the start or end
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/").trim_matches('/').to_string()
+}
@@ -10,9 +10,8 @@ pub fn can_populate(path: &str) -> bool {
     let path = PathBuf::from(path);
     let name = path
         .file_name()
-        .unwrap_or_else(|| std::ffi::OsStr::new(""))
-        .to_str()
-        .unwrap();
+        .and_then(std::ffi::OsStr::to_str)
+        .unwrap_or_default();
     name.contains(".ht.") || name.ends_with(".ht") || name == "$PBOPREFIX$"
 }
 
@@ -27,22 +26,18 @@ pub fn populate(source: VfsPath, dest: VfsPath, ctx: &mut AddonContext) -> Resul
     ctx.debug(&format!("`{}` => `{}`", source.as_str(), dest.as_str()));
     let mut buf = String::new();
     source.open_file()?.read_to_string(&mut buf)?;
-    match hemtt_handlebars::render(&buf.replace("\\{", "\\\\{"), &{
+    let out = hemtt_handlebars::render(&buf.replace("\\{", "\\\\{"), &{
         let mut vars = Variables::from(ctx.global().project());
         vars.append(ctx.addon().into());
         vars
-    }) {
-        Ok(out) => {
-            dest.create_file()?.write_all(out.as_bytes())?;
-            Ok(())
-        }
-        Err(err) => {
-            error!("Populate error: {}", err);
-            panic!("TODO convert error type")
-        }
-    }
+    })
+    .map_err(|err| HEMTTError::User(format!("Unable to render `{}`: {}", source.as_str(), err)))?;
+    dest.create_file()?.write_all(out.as_bytes())?;
+    Ok(())
 }
 
+/// Renders `.ht` files into the file without `.ht`, and files matching the project's
+/// `render` patterns in place, using the project and addon variables
 pub struct Populate {}
 impl Populate {}
 impl Task for Populate {
@@ -54,6 +49,10 @@ impl Task for Populate {
         &[Stage::Check]
     }
 
+    fn barrier(&self, _: &Stage) -> bool {
+        false
+    }
+
     fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
         for entry in ctx.global().fs().join(ctx.addon().source())?.walk_dir()? {
             let entry = entry?;
@@ -67,8 +66,13 @@ impl Task for Populate {
         }
         for entry in ctx.global().fs().join(ctx.addon().source())?.walk_dir()? {
             let entry = entry?;
-            if entry.metadata()?.file_type == VfsFileType::File && can_populate(entry.as_str()) {
+            if entry.metadata()?.file_type != VfsFileType::File {
+                continue;
+            }
+            if can_populate(entry.as_str()) {
                 populate(entry.clone(), destination(entry)?, ctx)?;
+            } else if ctx.global().project().is_rendered(entry.as_str()) {
+                populate(entry.clone(), entry, ctx)?;
             }
         }
         Ok(())
@@ -24,34 +24,57 @@ impl Task for Prefix {
         &[Stage::Check, Stage::PreBuild]
     }
 
+    fn barrier(&self, stage: &Stage) -> bool {
+        // Every prefix is checked before the map is shared
+        *stage == Stage::PreBuild
+    }
+
     fn check(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
         ctx.debug(&format!("prefix: {}", ctx.prefix()));
-        if let Some(a) = self.seen.read().unwrap().get(&ctx.prefix().to_string()) {
-            ctx.warn(&format!(
-                "Prefix `{}` is already in use by `{}`!",
-                ctx.prefix(),
-                a
+        for problem in ctx.addon().prefix_problems(ctx.prefix()) {
+            ctx.warn(&format!("Prefix `{}` {}", ctx.prefix(), problem));
+        }
+        // Arma resolves prefixes without case, so every map of prefixes is keyed lowercase
+        let key = ctx.prefix().to_lowercase();
+        let mut seen = self.seen.write().unwrap();
+        if let Some(other) = seen.get(&key) {
+            return Err(HEMTTError::AddonPrefixConflict(
+                ctx.prefix().to_string(),
+                ctx.addon().source().to_string(),
+                other.clone(),
             ));
-            return Ok(());
         }
-        self.seen
-            .write()
-            .unwrap()
-            .insert(ctx.prefix().to_string(), ctx.addon().source().to_string());
+        seen.insert(key, ctx.addon().source().to_string());
         Ok(())
     }
 
     fn prebuild_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        let prefixes = prefixes(ctx)?;
         ctx.global()
             .container
-            .set(PrefixMap(self.seen.read().unwrap().clone()));
+            .set(super::PDrive::new(ctx.global().project(), &prefixes));
         Ok(())
     }
 }
 
-pub struct PrefixMap(HashMap<String, String>);
-impl PrefixMap {
-    pub fn inner(&self) -> &HashMap<String, String> {
-        &self.0
+/// Addon folder of each lowercase prefix, for the addons in the context and the
+/// other addons of the project, which can still be used by the ones being built
+pub fn prefixes(ctx: &AddonListContext) -> Result<HashMap<String, String>, HEMTTError> {
+    let mut prefixes = HashMap::new();
+    for addon in ctx.addons() {
+        prefixes.insert(
+            addon.prefix().to_lowercase(),
+            addon.addon().source().to_string(),
+        );
+    }
+    for addon in hemtt::get_all_addons()? {
+        if prefixes.values().any(|source| source == addon.source()) {
+            continue;
+        }
+        let prefix = crate::context::prefix(ctx.global(), &addon)?;
+        prefixes
+            .entry(prefix.to_lowercase())
+            .or_insert_with(|| addon.source().to_string());
     }
+    Ok(prefixes)
 }
@@ -1,39 +1,167 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
 
-use hemtt_arma_config::resolver::{ResolvedFile, Resolver};
+use hemtt_arma_config::{
+    resolver::{ResolvedFile, Resolver},
+    Rendered,
+};
+use hemtt_handlebars::Variables;
 use vfs::{VfsFileType, VfsPath};
 
-use crate::{context::AddonContext, HEMTTError, Stage, Task};
+use crate::{
+    context::{AddonContext, AddonListContext, Context},
+    HEMTTError, Stage, Task,
+};
 
-use super::prefix::PrefixMap;
+use super::PDrive;
+
+/// Extensions of the files that are preprocessed
+pub const PREPROCESSED: [&str; 3] = ["cpp", "rvmat", "ext"];
 
 pub fn can_preprocess(path: &str) -> bool {
     let path = PathBuf::from(path);
     let name = path
         .extension()
-        .unwrap_or_else(|| std::ffi::OsStr::new(""))
-        .to_str()
-        .unwrap();
-    ["cpp", "rvmat", "ext"].contains(&name)
+        .and_then(std::ffi::OsStr::to_str)
+        .unwrap_or_default();
+    PREPROCESSED.contains(&name)
 }
 
 pub fn preprocess(path: VfsPath, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
     ctx.debug(&format!("Preprocessing: {}", path.as_str()));
-    let mut buf = String::new();
-    path.open_file()?.read_to_string(&mut buf)?;
-    let processed = hemtt_arma_config::preprocess(
-        hemtt_arma_config::tokenize(&buf, path.as_str()).unwrap(),
-        ctx.addon().source(),
-        VfsResolver::new(
-            ctx.global().fs().clone(),
-            ctx.global().container.get::<PrefixMap>(),
-        ),
-    );
+    let rendered = render_file(&path, ctx.addon().source(), ctx.global())?;
     let mut f = path.create_file()?;
-    f.write_all(hemtt_arma_config::render(processed?).export().as_bytes())?;
+    f.write_all(rendered.export().as_bytes())?;
+    ctx.global()
+        .container
+        .get::<SourceMaps>()
+        .0
+        .write()
+        .unwrap()
+        .insert(path.as_str().to_string(), rendered);
     Ok(())
 }
 
+/// Preprocesses a file without writing the output back
+///
+/// Arguments:
+/// * `path`: file to preprocess
+/// * `root`: source folder of the addon the file belongs to
+/// * `global`: context of the build, after the prebuild stage has set the macros
+pub fn render_file(path: &VfsPath, root: &str, global: &Context) -> Result<Rendered, HEMTTError> {
+    let mut buf = String::new();
+    path.open_file()?.read_to_string(&mut buf)?;
+    let processed = hemtt_arma_config::preprocess_with_defines(
+        hemtt_arma_config::tokenize(&buf, path.as_str())
+            .map_err(|e| hemtt_arma_config::source_error(path.as_str(), &buf, e))?,
+        root,
+        VfsResolver::new(global.fs().clone(), global.container.get::<PDrive>()),
+        &global.container.get::<Predefined>().0,
+    )
+    .map_err(|e| with_source_line(e, global.fs()))?;
+    Ok(hemtt_arma_config::render(processed))
+}
+
+/// Preprocessed files, used to find the source of errors in the output
+pub struct SourceMaps(RwLock<HashMap<String, Rendered>>);
+impl SourceMaps {
+    /// Location in the source of a position in the preprocessed output of a file
+    pub fn source(&self, path: &str, line: usize, column: usize) -> Option<hemtt::Span> {
+        self.0
+            .read()
+            .unwrap()
+            .get(path)
+            .and_then(|rendered| rendered.source_at(line, column))
+            .map(|token| token.span())
+    }
+
+    /// Points an error in the preprocessed output of a file at the source that produced it
+    ///
+    /// Arguments:
+    /// * `err`: error with a location in the output
+    /// * `path`: path of the preprocessed file
+    /// * `fs`: filesystem of the build
+    pub fn locate(&self, err: HEMTTError, path: &str, fs: &VfsPath) -> HEMTTError {
+        let err = match err {
+            HEMTTError::Source(mut e) => {
+                let maps = self.0.read().unwrap();
+                if let Some(token) = maps
+                    .get(path)
+                    .and_then(|rendered| rendered.source_at(e.span.line, e.span.column))
+                {
+                    e.span = token.span();
+                    e.line = None;
+                } else {
+                    e.span.path = path.to_string();
+                }
+                HEMTTError::Source(e)
+            }
+            _ => err,
+        };
+        with_source_line(err, fs)
+    }
+}
+
+/// Adds the text of the offending line to a source error
+pub fn with_source_line(err: HEMTTError, fs: &VfsPath) -> HEMTTError {
+    match err {
+        HEMTTError::Source(mut e) if e.line.is_none() => {
+            let mut source = String::new();
+            if fs
+                .join(e.span.path.trim_start_matches('/'))
+                .and_then(|file| file.open_file())
+                .and_then(|mut file| file.read_to_string(&mut source).map_err(Into::into))
+                .is_ok()
+            {
+                e.line = source
+                    .lines()
+                    .nth(e.span.line.saturating_sub(1))
+                    .map(str::to_string);
+            }
+            HEMTTError::Source(e)
+        }
+        _ => err,
+    }
+}
+
+/// Folders that are searched for absolute includes
+pub fn includes(p: &hemtt::Project) -> Vec<String> {
+    let mut includes: Vec<String> = p
+        .include
+        .iter()
+        .map(|i| {
+            i.to_string_lossy()
+                .replace("\\", "/")
+                .trim_start_matches("./")
+                .to_string()
+        })
+        .collect();
+    let deps = hemtt::deps_include();
+    if deps.exists() {
+        includes.push(deps.to_string_lossy().replace("\\", "/"));
+    }
+    includes
+}
+
+/// Macros defined before every preprocessed file
+pub struct Predefined(Vec<(String, String)>);
+
+/// Names and values of the macros defined before every preprocessed file
+pub fn predefined(p: &hemtt::Project) -> Result<Vec<(String, String)>, HEMTTError> {
+    let mut defines = p.version_macros();
+    if let Some(length) = p.git_hash {
+        let hash =
+            hemtt_handlebars::render(&format!("{{{{git \"id {}\"}}}}", length), &Variables::new())
+                .map_err(|e| HEMTTError::User(format!("Unable to read the git hash: {}", e)))?;
+        debug!("Defining GIT_HASH as `{}`", hash);
+        defines.push((String::from("GIT_HASH"), format!("\"{}\"", hash)));
+    }
+    Ok(defines)
+}
+
 pub struct Preprocess {}
 
 impl Task for Preprocess {
@@ -45,6 +173,15 @@ impl Task for Preprocess {
         &[Stage::Check, Stage::PreBuild, Stage::PostBuild]
     }
 
+    fn prebuild_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        ctx.global()
+            .container
+            .set(SourceMaps(RwLock::new(HashMap::new())));
+        let defines = predefined(ctx.global().project())?;
+        ctx.global().container.set(Predefined(defines));
+        Ok(())
+    }
+
     fn prebuild(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
         for entry in ctx.global().fs().join(ctx.addon().source())?.walk_dir()? {
             let entry = entry?;
@@ -61,10 +198,10 @@ impl Task for Preprocess {
 }
 
 #[derive(Clone)]
-struct VfsResolver<'a>(Arc<VfsPath>, &'a PrefixMap);
+struct VfsResolver<'a>(Arc<VfsPath>, &'a PDrive);
 impl<'a> VfsResolver<'a> {
-    pub fn new(path: VfsPath, prefixes: &'a PrefixMap) -> Self {
-        Self(Arc::new(path), prefixes)
+    pub fn new(path: VfsPath, pdrive: &'a PDrive) -> Self {
+        Self(Arc::new(path), pdrive)
     }
 }
 impl<'a> Resolver for VfsResolver<'a> {
@@ -87,39 +224,32 @@ impl<'a> Resolver for VfsResolver<'a> {
                 Ok(ResolvedFile::new(new_path.as_str(), buf))
             }
             Err(e) => {
-                let to = to.replace("\\", "/");
-                // Check for prefix
-                if let Some((prefix, path)) =
-                    self.1.inner().iter().find(|(prefix, _)| {
-                        to.starts_with(&format!("/{}", prefix.replace("\\", "/")))
-                    })
-                {
-                    let new_path = self
-                        .0
-                        .join(&path.trim_start_matches('/'))
-                        .unwrap()
-                        .join(to.trim_start_matches(&format!("/{}/", prefix.replace("\\", "/"))))
-                        .unwrap();
-                    new_path
-                        .open_file()
-                        .unwrap()
-                        .read_to_string(&mut buf)
-                        .unwrap();
-                    Ok(ResolvedFile::new(new_path.as_str(), buf))
-                } else {
-                    // TODO use the project's includes vec
-                    if PathBuf::from("include").exists() {
-                        let new_path = self.0.join(&format!("include{}", to)).unwrap();
-                        new_path
-                            .open_file()
-                            .unwrap()
-                            .read_to_string(&mut buf)
-                            .unwrap();
-                        Ok(ResolvedFile::new(new_path.as_str(), buf))
+                for file in self.1.locate(&to) {
+                    // Mounts outside of the project are read from disk
+                    let found = if file.is_relative()
+                        && file
+                            .components()
+                            .all(|c| matches!(c, std::path::Component::Normal(_)))
+                    {
+                        let new_path = self.0.join(&file.to_string_lossy().replace('\\', "/"))?;
+                        match new_path.open_file() {
+                            Ok(mut f) => {
+                                f.read_to_string(&mut buf)?;
+                                Some(new_path.as_str().to_string())
+                            }
+                            Err(_) => None,
+                        }
                     } else {
-                        Err(e.into())
+                        std::fs::read_to_string(&file).ok().map(|data| {
+                            buf = data;
+                            file.to_string_lossy().to_string()
+                        })
+                    };
+                    if let Some(path) = found {
+                        return Ok(ResolvedFile::new(&path, buf));
                     }
                 }
+                Err(e.into())
             }
         }
     }
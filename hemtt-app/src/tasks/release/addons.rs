@@ -0,0 +1,118 @@
+use hemtt::{AddonLocation, Prebuilt, Project};
+
+use crate::{
+    context::{AddonContext, AddonListContext},
+    HEMTTError, Stage, Task,
+};
+
+use super::{Checksums, ReleaseOptions, ReleaseRoot};
+
+/// Copies the built PBOs, prebuilt PBOs, missions and extra files into the release folder,
+/// and into one folder per selected kit
+///
+/// Extra files of a kit are only copied into that kit's release, in addition to the
+/// project's extra files
+///
+/// Extra files tagged with a platform are only copied into that platform's release
+/// when platforms are released separately
+///
+/// Built PBOs that match the existing release are left in place, the other files
+/// are copied up to `--jobs` at a time. The checksums of the released PBOs are
+/// recorded, so a release that failed part way resumes without copying them again
+pub struct Release {
+    opts: ReleaseOptions,
+}
+impl Release {
+    pub fn new(opts: ReleaseOptions) -> Self {
+        Self { opts }
+    }
+}
+impl Task for Release {
+    fn name(&self) -> String {
+        String::from("release")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Release]
+    }
+
+    fn release_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        let p = ctx.global().project();
+        let output = Project::output_dir()?;
+        let missions = hemtt::get_missions()?;
+        let files = p.files.entries()?;
+        ctx.global().container.set(Checksums::load()?);
+        let mut copies = Vec::new();
+        for ReleaseRoot {
+            path: release,
+            platform,
+            kit,
+        } in super::roots(p, &self.opts)?
+        {
+            let mut files = files.clone();
+            if let Some(kit) = &kit {
+                files.extend(kit.files.entries()?);
+            }
+            for mission in &missions {
+                let source = mission.destination(&output);
+                let target = p.layout.missions(&release).join(mission.pbo());
+                debug!("Releasing mission {:?} => {:?}", source, target);
+                copies.push((source, target));
+            }
+            for prebuilt in &p.prebuilt {
+                let addons = p.layout.location(&release, AddonLocation::Addons);
+                for pbo in prebuilt.pbos()? {
+                    let mut files = vec![pbo.clone()];
+                    if !prebuilt.resign {
+                        files.extend(Prebuilt::signatures(&pbo)?);
+                    }
+                    for file in files {
+                        let target = addons.join(file.file_name().unwrap());
+                        debug!("Releasing prebuilt {:?} => {:?}", file, target);
+                        copies.push((file, target));
+                    }
+                }
+            }
+            for (file, destination) in &files {
+                if !p
+                    .platforms
+                    .includes(&destination.to_string_lossy(), platform)
+                {
+                    continue;
+                }
+                let target = p.layout.files(&release).join(destination);
+                debug!("Releasing file {:?} => {:?}", file, target);
+                copies.push((file.clone(), target));
+            }
+        }
+        super::release_files(&copies, &self.opts)
+    }
+
+    fn release(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let p = ctx.global().project();
+        let source = ctx
+            .addon()
+            .destination(&Project::output_dir()?, Some(p.prefix()), None);
+        let checksums = ctx.global().container.get::<Checksums>();
+        let hash = super::file_sha1(&source)?;
+        let mut copies = Vec::new();
+        for target in super::addon_targets(p, &self.opts, ctx.addon())? {
+            if checksums.is_released(&target, &hash)? {
+                ctx.debug(&format!("{:?} is already released", target));
+                continue;
+            }
+            if super::is_unchanged(&source, &target)? {
+                ctx.debug(&format!("{:?} is unchanged", target));
+                checksums.set_file(&target, &hash);
+                continue;
+            }
+            ctx.debug(&format!("{:?} => {:?}", source, target));
+            copies.push((source.clone(), target));
+        }
+        super::release_files(&copies, &self.opts)?;
+        for (_, target) in &copies {
+            checksums.set_file(target, &hash);
+        }
+        checksums.save()
+    }
+}
@@ -0,0 +1,80 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::{context::AddonListContext, HEMTTError, Stage, Task};
+
+use super::ReleaseOptions;
+
+/// Zips each release folder, named by the project's `release_archive` template
+pub struct Archive {
+    opts: ReleaseOptions,
+}
+impl Archive {
+    pub fn new(opts: ReleaseOptions) -> Self {
+        Self { opts }
+    }
+}
+impl Task for Archive {
+    fn name(&self) -> String {
+        String::from("archive")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::PostRelease]
+    }
+
+    fn barrier(&self, stage: &Stage) -> bool {
+        // Every file must be released and signed before it is archived
+        *stage == Stage::PostRelease
+    }
+
+    fn postrelease_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        for (release, archive) in super::release_archives(ctx.global().project(), &self.opts)? {
+            info!("Archiving {:?} => {:?}", release, archive);
+            if crate::dry_run("create", &archive) {
+                continue;
+            }
+            create_dir!(archive.parent().unwrap())?;
+            archive_folder(&release, &archive)?;
+        }
+        Ok(())
+    }
+}
+
+/// Zips a folder, keeping the folder itself as the top level entry
+pub fn archive_folder(source: &Path, target: &Path) -> Result<(), HEMTTError> {
+    // Entries are named relative to the same long path that is walked
+    let source = hemtt::long_path(source);
+    let base = source.parent().unwrap();
+    // Written next to the archive and renamed, so the archive is never incomplete
+    let mut temp = target.as_os_str().to_owned();
+    temp.push(".partial");
+    let temp = PathBuf::from(temp);
+    let partial = crate::interrupt::Partial::new(&temp);
+    let mut zip = zip::ZipWriter::new(create_file!(temp)?);
+    let options = zip::write::FileOptions::default();
+    for entry in walkdir::WalkDir::new(&source) {
+        let entry = entry.map_err(|e| HEMTTError::Generic(e.to_string()))?;
+        let name = entry
+            .path()
+            .strip_prefix(base)
+            .unwrap()
+            .to_string_lossy()
+            .replace('\\', "/");
+        if entry.file_type().is_dir() {
+            zip.add_directory(name, options).map_err(zip_error)?;
+        } else {
+            zip.start_file(name, options).map_err(zip_error)?;
+            zip.write_all(&std::fs::read(entry.path())?)?;
+        }
+    }
+    zip.finish().map_err(zip_error)?;
+    drop(zip);
+    rename_file!(&temp, target)?;
+    partial.complete();
+    Ok(())
+}
+
+fn zip_error(err: zip::result::ZipError) -> HEMTTError {
+    HEMTTError::Generic(format!("Archive error: {}", err))
+}
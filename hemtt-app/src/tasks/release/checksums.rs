@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use hemtt::{HEMTTError, Project};
+use serde_json::{json, Value as Json};
+
+/// Checksums of the released PBOs and of the PBOs their signatures were created
+/// for, kept in `.hemttout/release.json`
+///
+/// A release that failed part way is resumed from it, PBOs that were already
+/// copied and signed are left as they are. It is saved after each addon is released
+/// or signed, by replacing the file so an interrupted save leaves the previous one.
+pub struct Checksums {
+    path: PathBuf,
+    data: Mutex<Data>,
+}
+
+#[derive(Default)]
+struct Data {
+    /// SHA-1 of each released PBO
+    files: BTreeMap<String, String>,
+    /// SHA-1 of the PBO each signature was created for
+    signatures: BTreeMap<String, String>,
+}
+
+impl Checksums {
+    pub fn load() -> Result<Self, HEMTTError> {
+        let path = Project::output_dir()?.join("release.json");
+        let mut data = Data::default();
+        if path.exists() {
+            let json: Json = serde_json::from_reader(open_file!(&path)?).map_err(|e| {
+                HEMTTError::UserHint(
+                    format!("Unable to read {:?}: {}", path, e),
+                    String::from("delete it to release every PBO again"),
+                )
+            })?;
+            data.files = read_map(&json["files"]);
+            data.signatures = read_map(&json["signatures"]);
+        }
+        Ok(Self {
+            path,
+            data: Mutex::new(data),
+        })
+    }
+
+    /// The PBO was released with the checksum and is still in place, unchanged
+    pub fn is_released(&self, target: &Path, hash: &str) -> Result<bool, HEMTTError> {
+        if self.file(target).as_deref() != Some(hash) || !target.exists() {
+            return Ok(false);
+        }
+        Ok(super::file_sha1(target)? == hash)
+    }
+
+    /// Checksum the PBO was released with
+    pub fn file(&self, target: &Path) -> Option<String> {
+        self.data.lock().unwrap().files.get(&key(target)).cloned()
+    }
+
+    pub fn set_file(&self, target: &Path, hash: &str) {
+        self.data
+            .lock()
+            .unwrap()
+            .files
+            .insert(key(target), hash.to_string());
+    }
+
+    /// The signature exists and was created for the PBO with the checksum
+    pub fn is_signed(&self, signature: &Path, hash: &str) -> bool {
+        signature.exists()
+            && self
+                .data
+                .lock()
+                .unwrap()
+                .signatures
+                .get(&key(signature))
+                .map(String::as_str)
+                == Some(hash)
+    }
+
+    pub fn set_signed(&self, signature: &Path, hash: &str) {
+        self.data
+            .lock()
+            .unwrap()
+            .signatures
+            .insert(key(signature), hash.to_string());
+    }
+
+    /// Writes the checksums, so a release that fails keeps what was done
+    pub fn save(&self) -> Result<(), HEMTTError> {
+        if *crate::DRY_RUN {
+            return Ok(());
+        }
+        // Held until the file is replaced, so saves from other addons wait
+        let data = self.data.lock().unwrap();
+        let json = json!({
+            "files": data.files,
+            "signatures": data.signatures,
+        });
+        create_dir!(self.path.parent().unwrap())?;
+        let temp = self.path.with_extension("json.tmp");
+        create_file!(&temp)?.write_all(
+            serde_json::to_string_pretty(&json)
+                .map_err(|e| HEMTTError::Generic(e.to_string()))?
+                .as_bytes(),
+        )?;
+        rename_file!(&temp, &self.path)?;
+        Ok(())
+    }
+}
+
+fn key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn read_map(json: &Json) -> BTreeMap<String, String> {
+    json.as_object()
+        .map(|map| {
+            map.iter()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
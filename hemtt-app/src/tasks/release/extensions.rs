@@ -0,0 +1,95 @@
+use hemtt::{Extension, Project};
+
+use crate::{context::AddonListContext, HEMTTError, Stage, Task};
+
+use super::ReleaseOptions;
+
+/// Builds the project's extensions and copies them into the root of each release folder,
+/// named as Arma loads them on the platform each file was built for
+pub struct Extensions {
+    opts: ReleaseOptions,
+}
+impl Extensions {
+    pub fn new(opts: ReleaseOptions) -> Self {
+        Self { opts }
+    }
+}
+impl Task for Extensions {
+    fn name(&self) -> String {
+        String::from("extensions")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::Release]
+    }
+
+    fn release_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        let p = ctx.global().project();
+        let root = Project::find_root()?;
+        let mut copies = Vec::new();
+        for (name, extension) in &p.extensions {
+            if let Some((artifact, platforms)) = extension.conflicts().into_iter().next() {
+                return Err(HEMTTError::UserHint(
+                    format!(
+                        "Extension `{}` has `{}` as the artifact of {}",
+                        name,
+                        artifact,
+                        platforms
+                            .iter()
+                            .map(|p| format!("`{}`", p))
+                            .collect::<Vec<_>>()
+                            .join(" and ")
+                    ),
+                    String::from("set the file built for each platform in `artifacts`"),
+                ));
+            }
+            let folder = root.join(&extension.path);
+            if !crate::dry_run(&format!("build extension `{}` in", name), &folder) {
+                build(name, extension, &folder)?;
+            }
+            for (platform, artifact) in extension.artifacts(name) {
+                let artifact = root.join(artifact);
+                if !artifact.exists() && !*crate::DRY_RUN {
+                    return Err(HEMTTError::UserHint(
+                        format!("Extension `{}` did not build {:?}", name, artifact),
+                        String::from("set `artifact` or `artifacts` to the files the extension's command builds"),
+                    ));
+                }
+                for release in super::roots(p, &self.opts)? {
+                    if release.platform.map_or(false, |r| r != platform) {
+                        continue;
+                    }
+                    let target = release.path.join(platform.extension_file(name));
+                    debug!("Releasing extension {:?} => {:?}", artifact, target);
+                    copies.push((artifact.clone(), target));
+                }
+            }
+        }
+        super::release_files(&copies, &self.opts)
+    }
+}
+
+fn build(name: &str, extension: &Extension, folder: &std::path::Path) -> Result<(), HEMTTError> {
+    let command = extension.command();
+    info!("Building extension `{}` with `{}`", name, command.join(" "));
+    let status = std::process::Command::new(&command[0])
+        .args(&command[1..])
+        .current_dir(folder)
+        .status()
+        .map_err(|e| {
+            HEMTTError::User(format!(
+                "Unable to run `{}` for extension `{}`: {}",
+                command[0], name, e
+            ))
+        })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(HEMTTError::User(format!(
+            "Extension `{}` failed to build, `{}` exited with {}",
+            name,
+            command.join(" "),
+            status
+        )))
+    }
+}
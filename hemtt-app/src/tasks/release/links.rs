@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::{context::AddonListContext, HEMTTError, Stage, Task};
+
+use super::ReleaseOptions;
+
+/// Replaces files that are identical across the release folders with hard links
+/// to a single copy, such as the keys and PBOs shared by standalone optionals,
+/// kits and platforms
+///
+/// Only runs when `link_duplicates` is enabled, files are left as copies when the
+/// file system does not support hard links
+pub struct Links {
+    opts: ReleaseOptions,
+}
+impl Links {
+    pub fn new(opts: ReleaseOptions) -> Self {
+        Self { opts }
+    }
+}
+impl Task for Links {
+    fn name(&self) -> String {
+        String::from("links")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::PostRelease]
+    }
+
+    fn barrier(&self, stage: &Stage) -> bool {
+        // Every file must be released and signed before it is linked
+        *stage == Stage::PostRelease
+    }
+
+    fn postrelease_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        let p = ctx.global().project();
+        if !p.link_duplicates.unwrap_or(false) {
+            return Ok(());
+        }
+        // Only files of the same size can be identical, the rest are never hashed
+        let mut sizes: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        for release in super::release_roots(p, &self.opts)? {
+            if !release.exists() {
+                continue;
+            }
+            for entry in walkdir::WalkDir::new(hemtt::long_path(&release)) {
+                let entry = entry.map_err(|e| HEMTTError::Generic(e.to_string()))?;
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let len = entry
+                    .metadata()
+                    .map_err(|e| HEMTTError::Generic(e.to_string()))?
+                    .len();
+                sizes.entry(len).or_default().push(entry.into_path());
+            }
+        }
+        let mut files: HashMap<(u64, Vec<u8>), Vec<PathBuf>> = HashMap::new();
+        for (len, paths) in sizes.into_iter().filter(|(_, paths)| paths.len() > 1) {
+            for path in paths {
+                files
+                    .entry((len, super::file_hash(&path)?))
+                    .or_default()
+                    .push(path);
+            }
+        }
+
+        let mut linked = 0;
+        let mut saved = 0;
+        for ((len, _), paths) in files {
+            let (original, duplicates) = match paths.split_first() {
+                Some((original, duplicates)) if !duplicates.is_empty() => (original, duplicates),
+                _ => continue,
+            };
+            for duplicate in duplicates {
+                if crate::dry_run(&format!("link {:?} =>", original), duplicate) {
+                    continue;
+                }
+                match link(original, duplicate) {
+                    Ok(()) => {
+                        linked += 1;
+                        saved += len;
+                    }
+                    Err(e) => {
+                        debug!("Unable to link {:?} => {:?}: {}", original, duplicate, e);
+                    }
+                }
+            }
+        }
+        if linked > 0 {
+            info!(
+                "Linked {} duplicate files, saving {:.1} MB",
+                linked,
+                saved as f64 / 1_048_576f64
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Replaces a file with a hard link, keeping the file if the link can not be made
+fn link(original: &Path, duplicate: &Path) -> std::io::Result<()> {
+    let original = hemtt::long_path(original);
+    let duplicate = hemtt::long_path(duplicate);
+    let mut temp = duplicate.as_os_str().to_owned();
+    temp.push(".link");
+    let temp = PathBuf::from(temp);
+    std::fs::hard_link(&original, &temp)?;
+    if let Err(e) = std::fs::rename(&temp, &duplicate) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e);
+    }
+    Ok(())
+}
@@ -0,0 +1,527 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use hemtt::{Addon, AddonLocation, HEMTTError, Kit, Platform, Project};
+use hemtt_handlebars::Variables;
+use rayon::prelude::*;
+use serde_json::Value as Json;
+use sha1::{Digest, Sha1};
+
+mod addons;
+pub use addons::Release;
+
+mod archive;
+pub use archive::{archive_folder, Archive};
+
+mod checksums;
+pub use checksums::Checksums;
+
+mod extensions;
+pub use extensions::Extensions;
+
+mod links;
+pub use links::Links;
+
+mod preset;
+pub use preset::Preset;
+
+mod sign;
+pub use sign::{
+    private_key, public_key, sig_version, sign, signature, signing_error, signing_keys, Sign,
+};
+
+mod workshop;
+pub use workshop::{workshop_id, workshop_problems, Workshop};
+
+/// Files copied into the release at the same time when `--jobs` is not given
+const COPY_JOBS: usize = 4;
+
+/// Folder in `releases` that releases are built in before they are complete
+const STAGING: &str = ".staging";
+
+/// What is being released, the selected kits and the release channel
+#[derive(Clone)]
+pub struct ReleaseOptions {
+    /// The mod itself is released, unless only kits are selected with `--kit`
+    pub main: bool,
+    pub kits: Vec<(String, Kit)>,
+    pub channel: String,
+    /// Threads that files are copied into the release with, `--jobs` of them
+    /// shared by every task, files are copied one at a time without it
+    pub copies: Option<Arc<rayon::ThreadPool>>,
+    /// Version of an earlier release, instead of the project's version
+    pub version: Option<String>,
+    /// The release folders are built in `releases/.staging` and moved into
+    /// place by `promote_release` once the release is complete
+    pub staged: bool,
+}
+impl Default for ReleaseOptions {
+    fn default() -> Self {
+        Self {
+            main: true,
+            kits: Vec::new(),
+            channel: String::new(),
+            copies: None,
+            version: None,
+            staged: false,
+        }
+    }
+}
+impl ReleaseOptions {
+    pub fn from_args(args: &clap::ArgMatches, p: &Project) -> Result<Self, HEMTTError> {
+        Ok(Self {
+            main: !args.is_present("kit"),
+            kits: kits_from_args(args, p)?,
+            channel: args.value_of("channel").unwrap_or_default().to_string(),
+            copies: copy_pool(match args.value_of("jobs") {
+                Some(jobs) => jobs.parse().ok().filter(|jobs| *jobs > 0).ok_or_else(|| {
+                    HEMTTError::User(format!(
+                        "`--jobs` must be a positive number, not `{}`",
+                        jobs
+                    ))
+                })?,
+                None => COPY_JOBS,
+            })?,
+            version: None,
+            staged: false,
+        })
+    }
+}
+
+/// Root folder of the release
+/// Ex: "releases/1.0.0/@mod"
+pub fn release_root(p: &Project, opts: &ReleaseOptions) -> Result<PathBuf, HEMTTError> {
+    releases_root(p, opts, p.modname(), None, None)
+}
+
+/// A release folder being created
+pub struct ReleaseRoot {
+    pub path: PathBuf,
+    /// Set when platforms are released separately
+    pub platform: Option<Platform>,
+    pub kit: Option<Kit>,
+}
+
+/// All release folders being created, one for the mod itself and each kit,
+/// and for each platform when platforms are released separately
+/// Ex: "releases/1.0.0/linux/@mod_server"
+pub fn roots(p: &Project, opts: &ReleaseOptions) -> Result<Vec<ReleaseRoot>, HEMTTError> {
+    let mut roots = Vec::new();
+    for platform in p.platforms.releases() {
+        if opts.main {
+            roots.push(ReleaseRoot {
+                path: releases_root(p, opts, p.modname(), None, platform)?,
+                platform,
+                kit: None,
+            });
+        }
+        for (name, kit) in &opts.kits {
+            let modname = kit.modname(p.modname(), name);
+            roots.push(ReleaseRoot {
+                path: releases_root(p, opts, &modname, Some(name), platform)?,
+                platform,
+                kit: Some(kit.clone()),
+            });
+        }
+    }
+    Ok(roots)
+}
+
+/// All release folders being created
+pub fn release_roots(p: &Project, opts: &ReleaseOptions) -> Result<Vec<PathBuf>, HEMTTError> {
+    Ok(roots(p, opts)?.into_iter().map(|root| root.path).collect())
+}
+
+/// Archive of each release folder, when the project has an archive name
+/// Ex: "releases/mod-1.0.0.zip"
+pub fn release_archives(
+    p: &Project,
+    opts: &ReleaseOptions,
+) -> Result<Vec<(PathBuf, PathBuf)>, HEMTTError> {
+    if p.release_archive().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut names = Vec::new();
+    if opts.main {
+        names.push((p.modname().to_string(), None));
+    }
+    names.extend(
+        opts.kits
+            .iter()
+            .map(|(name, kit)| (kit.modname(p.modname(), name), Some(name.as_str()))),
+    );
+    let mut archives = Vec::new();
+    for platform in p.platforms.releases() {
+        for (modname, kit) in &names {
+            let vars = naming_variables(p, opts, modname, *kit, platform);
+            let mut archive = render_name(p.release_archive(), &vars)?;
+            if let Some(platform) = platform {
+                if !p.release_archive().contains("platform") {
+                    archive = format!("{}-{}", archive, platform);
+                }
+            }
+            archives.push((
+                releases_root(p, opts, modname, *kit, platform)?,
+                releases_dir()?.join(format!("{}.zip", archive)),
+            ));
+        }
+    }
+    Ok(archives)
+}
+
+/// Released PBOs of an addon, one for each release folder the addon belongs in
+pub fn addon_targets(
+    p: &Project,
+    opts: &ReleaseOptions,
+    addon: &Addon,
+) -> Result<Vec<PathBuf>, HEMTTError> {
+    let standalone =
+        if addon.location() == AddonLocation::Optionals && p.folder_optionals.unwrap_or(true) {
+            Some(standalone_name(p, opts, addon)?)
+        } else {
+            None
+        };
+    let mut roots = Vec::new();
+    for platform in p.platforms.releases() {
+        if opts.main {
+            roots.push(releases_root(p, opts, p.modname(), None, platform)?);
+        }
+        for (name, kit) in opts.kits.iter().filter(|(_, kit)| kit.contains(addon)) {
+            let modname = kit.modname(p.modname(), name);
+            roots.push(releases_root(p, opts, &modname, Some(name), platform)?);
+        }
+    }
+    Ok(roots
+        .into_iter()
+        .map(|root| {
+            addon.layout_destination(root, &p.layout, Some(p.prefix()), standalone.as_deref())
+        })
+        .collect())
+}
+
+/// The released file exists and has the same contents as the source
+pub fn is_unchanged(source: &Path, target: &Path) -> Result<bool, HEMTTError> {
+    let source = hemtt::long_path(source);
+    let target = hemtt::long_path(target);
+    if !target.exists() || source.metadata()?.len() != target.metadata()?.len() {
+        return Ok(false);
+    }
+    Ok(file_hash(&source)? == file_hash(&target)?)
+}
+
+fn file_hash(path: &Path) -> Result<Vec<u8>, HEMTTError> {
+    let mut hasher = Sha1::new();
+    std::io::copy(&mut open_file!(path)?, &mut hasher)?;
+    Ok(hasher.finalize().to_vec())
+}
+
+/// SHA-1 of a file as hexadecimal, as written in patch manifests
+pub fn file_sha1(path: &Path) -> Result<String, HEMTTError> {
+    Ok(file_hash(path)?
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Copies a file into the release, creating its folder
+///
+/// An existing file is removed first, as it may be linked to other releases
+pub fn release_file(source: &Path, target: &Path) -> Result<(), HEMTTError> {
+    if crate::dry_run(&format!("copy {:?} =>", source), target) {
+        return Ok(());
+    }
+    create_dir!(target.parent().unwrap())?;
+    if hemtt::long_path(target).exists() {
+        remove_file!(target)?;
+    }
+    let partial = crate::interrupt::Partial::new(target);
+    std::fs::copy(hemtt::long_path(source), hemtt::long_path(target))?;
+    partial.complete();
+    Ok(())
+}
+
+/// Threads that copy files into the release, none for a single job
+fn copy_pool(jobs: usize) -> Result<Option<Arc<rayon::ThreadPool>>, HEMTTError> {
+    if jobs < 2 {
+        return Ok(None);
+    }
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map(|pool| Some(Arc::new(pool)))
+        .map_err(|e| HEMTTError::Generic(format!("Unable to start copying: {}", e)))
+}
+
+/// Copies files into the release, on the threads of the release's copy pool
+///
+/// Copying several files at once hides the latency of network shares and hard drives
+pub fn release_files(
+    copies: &[(PathBuf, PathBuf)],
+    opts: &ReleaseOptions,
+) -> Result<(), HEMTTError> {
+    match &opts.copies {
+        Some(pool) if copies.len() > 1 => pool.install(|| {
+            copies
+                .par_iter()
+                .try_for_each(|(source, target)| release_file(source, target))
+        }),
+        _ => {
+            for (source, target) in copies {
+                release_file(source, target)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn releases_dir() -> Result<PathBuf, HEMTTError> {
+    Ok(Project::find_root()?.join("releases"))
+}
+
+fn releases_root(
+    p: &Project,
+    opts: &ReleaseOptions,
+    modname: &str,
+    kit: Option<&str>,
+    platform: Option<Platform>,
+) -> Result<PathBuf, HEMTTError> {
+    let vars = naming_variables(p, opts, modname, kit, platform);
+    let mut root = releases_dir()?;
+    let folder = render_name(p.release_folder(), &vars)?;
+    for part in folder.split(|c| c == '/' || c == '\\') {
+        if part.is_empty() {
+            continue;
+        }
+        let mut components = Path::new(part).components();
+        match (components.next(), components.next()) {
+            (Some(std::path::Component::Normal(_)), None) => root.push(part),
+            _ => {
+                return Err(HEMTTError::User(format!(
+                    "The release folder `{}` must stay inside `releases`, `{}` is not allowed",
+                    folder, part
+                )))
+            }
+        }
+    }
+    if let Some(platform) = platform {
+        if !p.release_folder().contains("platform") {
+            // Ex: "releases/1.0.0/@mod" => "releases/1.0.0/linux/@mod"
+            let name = root.file_name().unwrap().to_owned();
+            root.pop();
+            root.push(platform.as_str());
+            root.push(name);
+        }
+    }
+    if opts.staged {
+        root = staged(&root)?;
+    }
+    Ok(root)
+}
+
+/// Folder a release folder is built in until the release is complete
+/// Ex: "releases/1.0.0/@mod" => "releases/.staging/1.0.0/@mod"
+fn staged(path: &Path) -> Result<PathBuf, HEMTTError> {
+    let releases = releases_dir()?;
+    let relative = path.strip_prefix(&releases).unwrap_or(path);
+    Ok(releases.join(STAGING).join(relative))
+}
+
+/// Folders directly in `releases` that hold the release folders
+/// Ex: "releases/1.0.0"
+fn release_tops(p: &Project, opts: &ReleaseOptions) -> Result<Vec<PathBuf>, HEMTTError> {
+    let releases = releases_dir()?;
+    let opts = ReleaseOptions {
+        staged: false,
+        ..opts.clone()
+    };
+    let mut tops = Vec::new();
+    for root in release_roots(p, &opts)? {
+        let top = match root
+            .strip_prefix(&releases)
+            .ok()
+            .and_then(|path| path.iter().next())
+        {
+            Some(top) => releases.join(top),
+            None => continue,
+        };
+        if !tops.contains(&top) {
+            tops.push(top);
+        }
+    }
+    Ok(tops)
+}
+
+/// Prepares `releases/.staging` for a staged release
+///
+/// A release that failed or was stopped is resumed from the staging folder, an
+/// existing release of the same version is copied into it so its unchanged files
+/// are kept while it stays in place until the new release is complete. Anything
+/// else in the staging folder is left from another release and removed
+pub fn stage_release(p: &Project, opts: &ReleaseOptions) -> Result<(), HEMTTError> {
+    let staging = releases_dir()?.join(STAGING);
+    let tops = release_tops(p, opts)?;
+    if staging.exists() {
+        for entry in std::fs::read_dir(&staging)? {
+            let entry = entry?;
+            let stale = !tops
+                .iter()
+                .any(|top| top.file_name() == Some(entry.file_name().as_os_str()));
+            if stale && !crate::dry_run("remove", &entry.path()) {
+                debug!("Removing the staged release {:?}", entry.path());
+                std::fs::remove_dir_all(hemtt::long_path(&entry.path()))?;
+            }
+        }
+    }
+    for top in tops {
+        let target = staged(&top)?;
+        if target.exists() {
+            info!("Resuming the incomplete release in {:?}", target);
+        } else if top.exists() && !crate::dry_run(&format!("copy {:?} =>", top), &target) {
+            if let Err(e) = copy_release(&top, &target) {
+                let _ = std::fs::remove_dir_all(hemtt::long_path(&target));
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copies a release folder, files are copied instead of linked as some are
+/// written in place while releasing
+fn copy_release(source: &Path, target: &Path) -> Result<(), HEMTTError> {
+    let source = hemtt::long_path(source);
+    for entry in walkdir::WalkDir::new(&source) {
+        let entry = entry.map_err(std::io::Error::from)?;
+        let path = target.join(entry.path().strip_prefix(&source).unwrap());
+        if entry.file_type().is_dir() {
+            create_dir!(&path)?;
+        } else {
+            copy_file!(entry.path(), &path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Moves the complete release from `releases/.staging` into place, replacing the
+/// release folders of the same name
+///
+/// Each folder is moved with a single rename, so a folder in `releases` is always
+/// a complete release
+pub fn promote_release() -> Result<(), HEMTTError> {
+    let releases = releases_dir()?;
+    let staging = releases.join(STAGING);
+    if !staging.exists() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(&staging)? {
+        let source = entry?.path();
+        let target = releases.join(source.file_name().unwrap());
+        if crate::dry_run(&format!("move {:?} =>", source), &target) {
+            continue;
+        }
+        if target.exists() {
+            // Moved aside first, the old release is replaced with a rename as well
+            let mut name = target.file_name().unwrap().to_owned();
+            name.push(".old");
+            let old = staging.join(name);
+            rename_file!(&target, &old)?;
+            rename_file!(&source, &target)?;
+            std::fs::remove_dir_all(hemtt::long_path(&old))?;
+        } else {
+            rename_file!(&source, &target)?;
+        }
+        debug!("Released {:?}", target);
+    }
+    std::fs::remove_dir(&staging)?;
+    Ok(())
+}
+
+/// Variables available to the release folder and archive templates
+fn naming_variables(
+    p: &Project,
+    opts: &ReleaseOptions,
+    modname: &str,
+    kit: Option<&str>,
+    platform: Option<Platform>,
+) -> Variables {
+    let mut vars = Variables::from(p);
+    vars.insert("name", Json::String(p.name().to_string()));
+    vars.insert("prefix", Json::String(p.prefix().to_string()));
+    vars.insert("modname", Json::String(modname.to_string()));
+    vars.insert(
+        "version",
+        Json::String(
+            opts.version
+                .clone()
+                .unwrap_or_else(|| p.version().to_string()),
+        ),
+    );
+    vars.insert("kit", Json::String(kit.unwrap_or_default().to_string()));
+    vars.insert("channel", Json::String(opts.channel.clone()));
+    vars.insert(
+        "platform",
+        Json::String(
+            platform
+                .map(Platform::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        ),
+    );
+    vars
+}
+
+/// Folder name of an optional released as its own mod
+fn standalone_name(
+    p: &Project,
+    opts: &ReleaseOptions,
+    addon: &Addon,
+) -> Result<String, HEMTTError> {
+    let mut vars = naming_variables(p, opts, p.modname(), None, None);
+    vars.append(addon.into());
+    vars.insert("title", Json::String(p.title(addon).to_string()));
+    render_name(p.standalone_name(), &vars)
+}
+
+fn render_name(template: &str, vars: &Variables) -> Result<String, HEMTTError> {
+    hemtt_handlebars::render_raw(template, vars).map_err(|e| {
+        HEMTTError::User(format!(
+            "Invalid release name template `{}`: {}",
+            template, e
+        ))
+    })
+}
+
+/// Looks up the kits requested with `--kit`, or the kits released by default
+/// alongside the mod when releasing without `--kit`
+pub fn kits_from_args(
+    args: &clap::ArgMatches,
+    p: &Project,
+) -> Result<Vec<(String, Kit)>, HEMTTError> {
+    let names = match args.values_of("kit") {
+        Some(names) => names,
+        // Kits released by default keep every release of the project in step
+        None if args.is_present("release") => {
+            return Ok(p
+                .kits
+                .iter()
+                .filter(|(_, kit)| kit.default)
+                .map(|(name, kit)| (name.clone(), kit.clone()))
+                .collect());
+        }
+        None => return Ok(Vec::new()),
+    };
+    names
+        .map(|name| {
+            p.kits
+                .get(name)
+                .map(|kit| (name.to_string(), kit.clone()))
+                .ok_or_else(|| {
+                    HEMTTError::User(format!(
+                        "Unknown kit `{}`, available kits: {}",
+                        name,
+                        p.kits.keys().cloned().collect::<Vec<_>>().join(", ")
+                    ))
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,118 @@
+use std::io::Write;
+
+use hemtt::Project;
+use hemtt_stringtable::escape;
+
+use crate::{context::AddonListContext, HEMTTError, Stage, Task};
+
+use super::{ReleaseOptions, ReleaseRoot};
+
+/// Writes an Arma 3 Launcher preset into each release folder, listing the mod
+/// and the dependencies with a workshop ID, when `launcher_preset` is enabled
+///
+/// The mod is only listed when the project has a `workshop` ID
+pub struct Preset {
+    opts: ReleaseOptions,
+}
+impl Preset {
+    pub fn new(opts: ReleaseOptions) -> Self {
+        Self { opts }
+    }
+}
+impl Task for Preset {
+    fn name(&self) -> String {
+        String::from("preset")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::PostRelease]
+    }
+
+    fn barrier(&self, stage: &Stage) -> bool {
+        // The release folders must exist before the preset is written into them
+        *stage == Stage::PostRelease
+    }
+
+    fn postrelease_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        let p = ctx.global().project();
+        if !p.launcher_preset.unwrap_or(false) {
+            return Ok(());
+        }
+        if p.workshop.is_none() {
+            warn!("The project has no `workshop` ID, the mod is left out of the launcher preset");
+        }
+        for ReleaseRoot { path, .. } in super::roots(p, &self.opts)? {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().trim_start_matches('@').to_string())
+                .unwrap_or_else(|| p.modname().to_string());
+            let target = path.join(format!("{}.html", name));
+            if crate::dry_run("create", &target) {
+                continue;
+            }
+            debug!("Writing the launcher preset {:?}", target);
+            create_file!(&target)?.write_all(preset(p, &name).as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// An Arma 3 Launcher preset, in the format exported by the launcher
+fn preset(p: &Project, name: &str) -> String {
+    let mut mods = Vec::new();
+    if let Some(id) = p.workshop {
+        mods.push((name.to_string(), id));
+    }
+    for (dependency, dep) in &p.dependencies {
+        if let Some(id) = dep.workshop {
+            mods.push((dependency.clone(), id));
+        }
+    }
+    let rows = mods
+        .iter()
+        .map(|(name, id)| {
+            let link = format!(
+                "https://steamcommunity.com/sharedfiles/filedetails/?id={}",
+                id
+            );
+            format!(
+                r#"        <tr data-type="ModContainer">
+          <td data-type="DisplayName">{}</td>
+          <td><span class="from-steam">Steam</span></td>
+          <td><a href="{link}" data-type="Link">{link}</a></td>
+        </tr>
+"#,
+                escape(name),
+                link = link
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<html>
+  <head>
+    <meta name="arma:Type" content="preset" />
+    <meta name="arma:PresetName" content="{name}" />
+    <meta name="generator" content="HEMTT {version}" />
+    <title>Arma 3</title>
+  </head>
+  <body>
+    <h1>Arma 3 - Preset <strong>{name}</strong></h1>
+    <p class="before-list">
+      <em>Drag this file onto the Arma 3 Launcher window to import it.</em>
+    </p>
+    <div class="mod-list">
+      <table>
+{rows}      </table>
+    </div>
+    <div class="dlc-list">
+      <table />
+    </div>
+  </body>
+</html>
+"#,
+        name = escape(name),
+        version = *crate::VERSION,
+        rows = rows
+    )
+}
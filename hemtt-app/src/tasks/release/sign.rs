@@ -0,0 +1,171 @@
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use hemtt::{AddonLocation, Project};
+use hemtt_signing::{BIPrivateKey, BISignVersion};
+
+use crate::{
+    context::{AddonContext, AddonListContext},
+    HEMTTError, Stage, Task,
+};
+
+use super::{Checksums, ReleaseOptions};
+
+/// Signs the released PBOs with every signing key and adds the public keys to the release
+///
+/// Signatures that were created for the released PBO are kept, so a release
+/// that failed part way resumes with the PBOs that are not signed yet
+pub struct Sign {
+    opts: ReleaseOptions,
+    keys: RwLock<Vec<BIPrivateKey>>,
+}
+impl Sign {
+    pub fn new(opts: ReleaseOptions) -> Self {
+        Self {
+            opts,
+            keys: RwLock::new(Vec::new()),
+        }
+    }
+}
+impl Task for Sign {
+    fn name(&self) -> String {
+        String::from("sign")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::PostRelease]
+    }
+
+    fn postrelease_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        let p = ctx.global().project();
+        let keys = signing_keys(p)?;
+        let version = sig_version(p)?;
+        for release in super::release_roots(p, &self.opts)? {
+            for key in &keys {
+                public_key(key, &p.layout.keys(&release))?;
+            }
+            for prebuilt in p.prebuilt.iter().filter(|prebuilt| prebuilt.resign) {
+                for pbo in prebuilt.pbos()? {
+                    let target = p
+                        .layout
+                        .location(&release, AddonLocation::Addons)
+                        .join(pbo.file_name().unwrap());
+                    for key in &keys {
+                        sign(key, &target, version)?;
+                    }
+                }
+            }
+        }
+        *self.keys.write().unwrap() = keys;
+        Ok(())
+    }
+
+    fn postrelease(&self, ctx: &mut AddonContext) -> Result<(), HEMTTError> {
+        let p = ctx.global().project();
+        let keys = self.keys.read().unwrap();
+        let checksums = ctx.global().container.try_get::<Checksums>();
+        for target in super::addon_targets(p, &self.opts, ctx.addon())? {
+            let hash = checksums.and_then(|checksums| checksums.file(&target));
+            for key in keys.iter() {
+                let path = signature(key, &target);
+                if let (Some(checksums), Some(hash)) = (checksums, &hash) {
+                    if checksums.is_signed(&path, hash) {
+                        ctx.debug(&format!(
+                            "{:?} is already signed by `{}`",
+                            target,
+                            key.name()
+                        ));
+                        continue;
+                    }
+                }
+                ctx.debug(&format!("signing {:?} with `{}`", target, key.name()));
+                sign(key, &target, sig_version(p)?)?;
+                if let (Some(checksums), Some(hash)) = (checksums, &hash) {
+                    checksums.set_signed(&path, hash);
+                }
+            }
+        }
+        checksums.map_or(Ok(()), Checksums::save)
+    }
+}
+
+/// Reads the project's private key, generating it if it does not exist
+pub fn private_key(p: &Project) -> Result<BIPrivateKey, HEMTTError> {
+    let keys = Project::find_root()?.join("releases").join("keys");
+    let path = keys.join(format!("{}.biprivatekey", p.key_name()));
+    if path.exists() {
+        debug!("Using private key {:?}", path);
+        return BIPrivateKey::read(&mut open_file!(path)?).map_err(signing_error);
+    }
+    info!("Generating private key `{}`", p.key_name());
+    let key = BIPrivateKey::generate(1024, p.signing_authority()).map_err(signing_error)?;
+    if !crate::dry_run("create", &path) {
+        create_dir!(keys)?;
+        key.write(&mut create_file!(path)?).map_err(signing_error)?;
+    }
+    Ok(key)
+}
+
+/// The project's private key followed by the additional signing keys
+pub fn signing_keys(p: &Project) -> Result<Vec<BIPrivateKey>, HEMTTError> {
+    let mut keys = vec![private_key(p)?];
+    for path in &p.extra_keys {
+        debug!("Using additional private key {:?}", path);
+        if !path.exists() {
+            return Err(HEMTTError::User(format!(
+                "Additional signing key {:?} does not exist",
+                path
+            )));
+        }
+        keys.push(BIPrivateKey::read(&mut open_file!(path)?).map_err(signing_error)?);
+    }
+    Ok(keys)
+}
+
+/// Writes the public key of a private key into a keys folder
+pub fn public_key(key: &BIPrivateKey, keys: &Path) -> Result<(), HEMTTError> {
+    let bikey = keys.join(format!("{}.bikey", key.name()));
+    if crate::dry_run("create", &bikey) {
+        return Ok(());
+    }
+    create_dir!(keys)?;
+    if bikey.exists() {
+        remove_file!(bikey)?;
+    }
+    let public = key.to_public_key().map_err(signing_error)?;
+    public
+        .write(&mut create_file!(bikey)?)
+        .map_err(signing_error)
+}
+
+/// Signature version configured by the project
+pub fn sig_version(p: &Project) -> Result<BISignVersion, HEMTTError> {
+    BISignVersion::from_u32(u32::from(p.sig_version())).map_err(signing_error)
+}
+
+/// Signs a PBO, writing `{pbo}.{authority}.bisign` next to it
+pub fn sign(key: &BIPrivateKey, pbo: &Path, version: BISignVersion) -> Result<(), HEMTTError> {
+    if crate::dry_run("sign", pbo) {
+        return Ok(());
+    }
+    let mut readable = hemtt_pbo::ReadablePbo::from(open_file!(pbo)?)?;
+    let sig = key.sign(&mut readable, version).map_err(signing_error)?;
+    let target = signature(key, pbo);
+    // The signature may be linked to the signatures of other releases
+    if target.exists() {
+        remove_file!(target)?;
+    }
+    sig.write(&mut create_file!(target)?).map_err(signing_error)
+}
+
+/// Path of the signature of a PBO
+/// Ex: "{pbo}.{authority}.bisign"
+pub fn signature(key: &BIPrivateKey, pbo: &Path) -> PathBuf {
+    let mut target = pbo.as_os_str().to_owned();
+    target.push(format!(".{}.bisign", key.name()));
+    PathBuf::from(target)
+}
+
+pub fn signing_error(err: hemtt_signing::BISignError) -> HEMTTError {
+    HEMTTError::Signing(err.to_string())
+}
@@ -0,0 +1,221 @@
+use std::path::{Path, PathBuf};
+
+use hemtt::{Kit, Project};
+use hemtt_arma_config::simplify::{Config, Entry};
+
+use crate::{context::AddonListContext, HEMTTError, Stage, Task};
+
+use super::ReleaseOptions;
+
+/// Largest release accepted by the Steam Workshop
+const MAX_SIZE: u64 = 20 * 1024 * 1024 * 1024;
+
+/// Extensions of the files that usually belong in a released mod
+const ALLOWED_EXTS: [&str; 18] = [
+    "pbo", "bisign", "bikey", "dll", "so", "cpp", "paa", "jpg", "jpeg", "png", "txt", "md", "html",
+    "htm", "pdf", "json", "ebo", "ini",
+];
+
+/// Tags offered by the Arma 3 Publisher
+const TAGS: [&str; 20] = [
+    "Mod",
+    "Air",
+    "Armor",
+    "Campaign",
+    "Character",
+    "Composition",
+    "Cooperative",
+    "Dependency",
+    "Equipment",
+    "Mechanics",
+    "Multiplayer",
+    "Naval",
+    "Scenario",
+    "Server",
+    "Singleplayer",
+    "Sound",
+    "Structure",
+    "Terrain",
+    "Ui",
+    "Weapon",
+];
+
+/// Checks the release folders against the constraints of the Steam Workshop
+/// when the project has a `workshop` ID, before they are archived
+///
+/// The release must be within the size limit, have a `mod.cpp` with a logo that
+/// exists, use valid `workshop_tags`, and a `meta.cpp` in it must have the
+/// project's `workshop` ID, or the kit's for the release of a kit. Files of a type
+/// that does not usually belong in a mod are warned about
+pub struct Workshop {
+    opts: ReleaseOptions,
+}
+impl Workshop {
+    pub fn new(opts: ReleaseOptions) -> Self {
+        Self { opts }
+    }
+}
+impl Task for Workshop {
+    fn name(&self) -> String {
+        String::from("workshop")
+    }
+
+    fn hooks(&self) -> &[Stage] {
+        &[Stage::PostRelease]
+    }
+
+    fn barrier(&self, stage: &Stage) -> bool {
+        // Every file must be in the release before it is checked
+        *stage == Stage::PostRelease
+    }
+
+    fn postrelease_single(&self, ctx: &mut AddonListContext) -> Result<(), HEMTTError> {
+        let p = ctx.global().project();
+        let releases = super::roots(p, &self.opts)?
+            .into_iter()
+            .filter(|root| root.path.exists())
+            .filter_map(|root| workshop_id(p, root.kit.as_ref()).map(|id| (root.path, id)))
+            .collect::<Vec<_>>();
+        if releases.is_empty() {
+            return Ok(());
+        }
+        let problems = workshop_problems(p, &releases)?;
+        if problems.is_empty() {
+            return Ok(());
+        }
+        for problem in &problems {
+            error!("{}", problem);
+        }
+        Err(HEMTTError::User(format!(
+            "The release does not meet {} workshop constraints",
+            problems.len()
+        )))
+    }
+}
+
+/// Workshop item of the mod or of a kit, kits are only published to their own item
+pub fn workshop_id(p: &Project, kit: Option<&Kit>) -> Option<u64> {
+    match kit {
+        Some(kit) => kit.workshop,
+        None => p.workshop,
+    }
+}
+
+/// Problems of the release folders that the Steam Workshop would reject, each
+/// with the ID of its workshop item
+pub fn workshop_problems(
+    p: &Project,
+    releases: &[(PathBuf, u64)],
+) -> Result<Vec<String>, HEMTTError> {
+    let mut problems = Vec::new();
+    for tag in &p.workshop_tags {
+        if !TAGS.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            problems.push(format!(
+                "`{}` is not a workshop tag, options are: {}",
+                tag,
+                TAGS.join(", ")
+            ));
+        }
+    }
+    for (release, id) in releases {
+        for problem in release_problems(release, *id)? {
+            problems.push(format!("{}: {}", release.display(), problem));
+        }
+    }
+    Ok(problems)
+}
+
+/// Problems with a release folder
+fn release_problems(release: &Path, id: u64) -> Result<Vec<String>, HEMTTError> {
+    let mut problems = Vec::new();
+    let mut size = 0;
+    let release = hemtt::long_path(release);
+    for entry in walkdir::WalkDir::new(&release) {
+        let entry = entry.map_err(|e| HEMTTError::Generic(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        size += entry
+            .metadata()
+            .map_err(|e| HEMTTError::Generic(e.to_string()))?
+            .len();
+        if let Some(ext) = entry.path().extension() {
+            let ext = ext.to_string_lossy().to_lowercase();
+            // Only warned about, mods can have good reasons for other files
+            if !ALLOWED_EXTS.contains(&ext.as_str()) {
+                warn!(
+                    "{}: {} is not a file type that usually belongs in a mod",
+                    release.display(),
+                    entry.path().strip_prefix(&release).unwrap().display()
+                );
+            }
+        }
+    }
+    if size > MAX_SIZE {
+        problems.push(format!(
+            "is {} MB, larger than the {} MB allowed",
+            size / 1024 / 1024,
+            MAX_SIZE / 1024 / 1024
+        ));
+    }
+
+    match read_config(&release.join("mod.cpp"))? {
+        Some(config) => {
+            let logos: Vec<&str> = ["logo", "picture"]
+                .iter()
+                .filter_map(|key| match config.root.get(key) {
+                    Some(Entry::Str(path)) if !path.is_empty() => Some(path.as_str()),
+                    _ => None,
+                })
+                .collect();
+            if logos.is_empty() {
+                problems.push(String::from(
+                    "mod.cpp does not declare a `logo` or `picture`",
+                ));
+            }
+            for logo in logos {
+                let path = logo.trim_start_matches('\\').replace('\\', "/");
+                if !release.join(&path).exists() {
+                    problems.push(format!(
+                        "mod.cpp uses `{}`, which is not in the release",
+                        logo
+                    ));
+                }
+            }
+        }
+        None => problems.push(String::from("does not have a mod.cpp")),
+    }
+
+    if let Some(meta) = read_config(&release.join("meta.cpp"))? {
+        let published = match meta.root.get("publishedid") {
+            Some(Entry::Int(published)) => Some(published.to_string()),
+            Some(Entry::Str(published)) => Some(published.clone()),
+            // Too large to be read exactly
+            Some(Entry::Float(_)) => None,
+            _ => {
+                problems.push(String::from("meta.cpp does not declare `publishedid`"));
+                None
+            }
+        };
+        if let Some(published) = published {
+            if published != id.to_string() {
+                problems.push(format!(
+                    "meta.cpp has `publishedid` {}, but the `workshop` ID is {}",
+                    published, id
+                ));
+            }
+        }
+    }
+    Ok(problems)
+}
+
+/// A config of the release, such as `mod.cpp`
+fn read_config(path: &Path) -> Result<Option<Config>, HEMTTError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let ast = hemtt_arma_config::parse(&std::fs::read_to_string(path)?)?;
+    Ok(Some(Config::from_ast(ast).map_err(|e| {
+        HEMTTError::User(format!("{} is not a valid config: {}", path.display(), e))
+    })?))
+}
@@ -68,3 +68,39 @@ pub struct IOPathError {
     pub source: std::io::Error,
     pub path: std::path::PathBuf,
 }
+
+/// Converts a pest error into a source error pointing at the failed location
+///
+/// Arguments:
+/// * `path`: path of the source
+/// * `source`: text that failed to be parsed
+/// * `err`: the error returned by pest
+pub fn source_error<R: pest::RuleType>(
+    path: &str,
+    source: &str,
+    err: pest::error::Error<R>,
+) -> hemtt::HEMTTError {
+    let message = match err.variant {
+        pest::error::ErrorVariant::ParsingError { ref positives, .. } if !positives.is_empty() => {
+            format!("Expected {:?}", positives)
+        }
+        pest::error::ErrorVariant::CustomError { ref message } => message.clone(),
+        _ => String::from("Unexpected input"),
+    };
+    let ((line, column), length) = match err.line_col {
+        pest::error::LineColLocation::Pos(pos) => (pos, 1),
+        pest::error::LineColLocation::Span(start, end) => (
+            start,
+            if start.0 == end.0 {
+                end.1.saturating_sub(start.1).max(1)
+            } else {
+                1
+            },
+        ),
+    };
+    let mut error = hemtt::SourceError::new(message, hemtt::Span::new(path, line, column, length));
+    if let Some(text) = source.lines().nth(line.saturating_sub(1)) {
+        error = error.with_line(text);
+    }
+    error.into()
+}
@@ -12,7 +12,7 @@ pub mod rapify;
 pub mod resolver;
 pub mod simplify;
 
-pub use error::ArmaConfigError;
+pub use error::{source_error, ArmaConfigError};
 pub use linter::{InheritanceStyle, LinterOptions};
-pub use parser::parse;
-pub use preprocess::{preprocess, render, tokenize};
+pub use parser::{parse, Definition, AST};
+pub use preprocess::{preprocess, preprocess_with_defines, render, tokenize, Rendered};
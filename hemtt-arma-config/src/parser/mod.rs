@@ -1,3 +1,4 @@
+use hemtt::HEMTTError;
 use pest::Parser;
 
 mod node;
@@ -16,6 +17,53 @@ pub struct AST {
     pub config: Node,
 }
 
+/// A class defined with a body directly inside a root class
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub root: String,
+    pub class: String,
+    /// Line of the definition, starting at 1
+    pub line: usize,
+    /// Column of the definition, starting at 1
+    pub column: usize,
+}
+
+impl AST {
+    /// Classes defined inside each root class, such as `CfgVehicles >> MyCar`
+    ///
+    /// Forward declarations (`class Car;`) are not definitions
+    pub fn definitions(&self) -> Vec<Definition> {
+        let mut definitions = Vec::new();
+        for (root, props) in classes(&self.config) {
+            for node in props {
+                if let Statement::Class { ident, .. } = &node.statement {
+                    if let Statement::Ident(class) = &ident.statement {
+                        definitions.push(Definition {
+                            root: root.clone(),
+                            class: class.clone(),
+                            line: (node.start.1).0,
+                            column: (node.start.1).1,
+                        });
+                    }
+                }
+            }
+        }
+        definitions
+    }
+}
+
+/// Classes with a body in a config, with their entries
+fn classes(node: &Node) -> Vec<(String, &Vec<Node>)> {
+    match &node.statement {
+        Statement::Config(nodes) => nodes.iter().flat_map(classes).collect(),
+        Statement::Class { ident, props, .. } => match &ident.statement {
+            Statement::Ident(name) => vec![(name.clone(), props)],
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
 // impl AST {
 //     pub fn valid(&self) -> bool {
 //         if let Some(report) = &self.report {
@@ -28,14 +76,16 @@ pub struct AST {
 
 /// Converts a raw string into an AST
 ///
+/// Locations in errors have an empty path, the caller knows where the source came from
+///
 /// ```
 /// let content = "value = 123;";
 /// hemtt_arma_config::parse(content);
 /// ```
-pub fn parse(source: &str) -> Result<AST, String> {
+pub fn parse(source: &str) -> Result<AST, HEMTTError> {
     let clean = source.replace("\r", "");
     let pair = ConfigParser::parse(Rule::file, &clean)
-        .unwrap()
+        .map_err(|e| crate::source_error("", &clean, e))?
         .next()
         .unwrap();
     let pair = pair.into_inner().next().unwrap();
@@ -52,4 +102,35 @@ mod tests {
         let ast = parse("value = 123;");
         println!("{:?}", ast);
     }
+
+    #[test]
+    fn definitions() {
+        let ast = parse(
+            "class CfgPatches {\n    class test {};\n};\nclass CfgVehicles {\n    class Car;\n    class MyCar: Car {\n        class Turrets {};\n    };\n};",
+        )
+        .unwrap();
+        let definitions: Vec<(String, String, usize)> = ast
+            .definitions()
+            .into_iter()
+            .map(|d| (d.root, d.class, d.line))
+            .collect();
+        assert_eq!(
+            definitions,
+            vec![
+                (String::from("CfgPatches"), String::from("test"), 2),
+                (String::from("CfgVehicles"), String::from("MyCar"), 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn error_location() {
+        match parse("value = 123;\nclass Test {\n    value = ;\n};") {
+            Err(hemtt::HEMTTError::Source(e)) => {
+                assert_eq!(e.span.line, 3);
+                assert_eq!(e.line.as_deref(), Some("    value = ;"));
+            }
+            other => panic!("expected a source error, got {:?}", other.map(|_| ())),
+        }
+    }
 }
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::iter::Peekable;
 use std::vec::IntoIter;
 
-use hemtt::HEMTTError;
+use hemtt::{HEMTTError, SourceError, Span};
 use pest::error::Error;
 use pest::Parser;
 
@@ -12,7 +12,7 @@ use token::Whitespace;
 use token::{PreProcessParser, Rule, Token, TokenPos};
 
 mod render;
-pub use render::render;
+pub use render::{render, Rendered};
 
 mod define;
 use define::Define;
@@ -20,6 +20,7 @@ mod ifstate;
 use ifstate::{IfState, IfStates};
 
 use crate::resolver::Resolver;
+use crate::source_error;
 
 pub fn tokenize(source: &str, path: &str) -> Result<Vec<TokenPos>, Error<Rule>> {
     let mut tokens = Vec::new();
@@ -376,6 +377,29 @@ where
     _preprocess(source, root, resolver, &mut defines)
 }
 
+/// Preprocess with macros that are defined before the source is read
+///
+/// Arguments:
+/// * `predefined`: name and body of each macro, as if written with `#define {name} {body}`
+pub fn preprocess_with_defines<R>(
+    source: Vec<TokenPos>,
+    root: &str,
+    resolver: R,
+    predefined: &[(String, String)],
+) -> Result<Vec<TokenPos>, HEMTTError>
+where
+    R: Resolver,
+{
+    let mut defines: HashMap<String, Define> = HashMap::new();
+    let prelude = predefined
+        .iter()
+        .map(|(name, body)| format!("#define {} {}\n", name, body))
+        .collect::<String>();
+    let prelude = tokenize(&prelude, "").map_err(|e| source_error("", &prelude, e))?;
+    _preprocess(prelude, root, resolver.clone(), &mut defines)?;
+    _preprocess(source, root, resolver, &mut defines)
+}
+
 pub fn _preprocess<R>(
     source: Vec<TokenPos>,
     root: &str,
@@ -391,7 +415,8 @@ where
     while let Some(token) = iter.next() {
         match (&token.token(), if_state.reading()) {
             (Token::Directive, r) => {
-                if let Token::Word(directive) = iter.next().unwrap().token() {
+                let word = iter.next().unwrap();
+                if let Token::Word(directive) = word.token() {
                     match (directive.as_str(), r) {
                         ("define", true) => {
                             skip_whitespace!(iter);
@@ -429,9 +454,9 @@ where
                                         },
                                     );
                                 } else {
-                                    return Err(HEMTTError::Generic(
-                                        "define without name".to_string(),
-                                    ));
+                                    return Err(
+                                        SourceError::new("define without name", tp.span()).into()
+                                    );
                                 }
                             }
                         }
@@ -441,12 +466,14 @@ where
                                 if let Token::Word(name) = tp.token().clone() {
                                     defines.remove(&name);
                                 } else {
-                                    return Err(HEMTTError::Generic(
-                                        "undef without name".to_string(),
-                                    ));
+                                    return Err(
+                                        SourceError::new("undef without name", tp.span()).into()
+                                    );
                                 }
                             } else {
-                                return Err(HEMTTError::Generic("undef without name".to_string()));
+                                return Err(
+                                    SourceError::new("undef without name", token.span()).into()
+                                );
                             }
                         }
                         ("ifdef", true) => {
@@ -488,15 +515,32 @@ where
                                 .export()
                                 .trim_matches('"')
                                 .to_owned();
-                            ret.append(&mut _preprocess(
-                                {
-                                    let resolved = resolver.resolve(&root, &token.path(), &file)?;
-                                    super::tokenize(resolved.data(), resolved.path()).unwrap()
-                                },
-                                root,
-                                resolver.clone(),
-                                defines,
-                            )?);
+                            let span = Span::new(
+                                token.path(),
+                                token.start().1 .0,
+                                token.start().1 .1,
+                                word.end().0.saturating_sub(token.start().0),
+                            );
+                            let resolved =
+                                resolver.resolve(&root, &token.path(), &file).map_err(|e| {
+                                    HEMTTError::from(SourceError::new(
+                                        format!("Unable to resolve include `{}`: {}", file, e),
+                                        span.clone(),
+                                    ))
+                                })?;
+                            ret.append(
+                                &mut _preprocess(
+                                    super::tokenize(resolved.data(), resolved.path()).map_err(
+                                        |e| {
+                                            crate::source_error(resolved.path(), resolved.data(), e)
+                                        },
+                                    )?,
+                                    root,
+                                    resolver.clone(),
+                                    defines,
+                                )
+                                .map_err(|e| e.included_from(span))?,
+                            );
                         }
                         (_, false) => {
                             read_line!(iter);
@@ -19,13 +19,25 @@ pub fn render(source: Vec<TokenPos>) -> Rendered {
             cc = 1;
             line = Vec::new();
         } else {
-            line.push((
-                cc,
-                token.to_string().len(),
-                token.path().to_owned(),
-                token.token().clone(),
-            ));
+            let len = token.to_string().len();
+            line.push((cc, len, token.path().to_owned(), token.token().clone()));
+            cc += len;
         }
     }
     Rendered::new(source, map)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::preprocess::tokenize;
+
+    #[test]
+    fn source_at() {
+        let rendered = super::render(tokenize("a = 1;\nvalue = 2;\n", "config.cpp").unwrap());
+        let token = rendered.source_at(2, 9).unwrap();
+        assert_eq!(token.to_string(), "2");
+        assert_eq!(token.span().line, 2);
+        assert_eq!(token.span().column, 9);
+        assert!(rendered.source_at(3, 1).is_none());
+    }
+}
@@ -22,6 +22,38 @@ impl Rendered {
         &self.map
     }
 
+    /// Finds the source token that rendered to a location in the output
+    ///
+    /// Arguments:
+    /// * `line`: line in the output, starting at 1
+    /// * `column`: column in the output, starting at 1
+    pub fn source_at(&self, line: usize, column: usize) -> Option<&TokenPos> {
+        let mut lc = 1;
+        let mut cc = 1;
+        let mut last = None;
+        for token in &self.tokens {
+            if token.token() == &Token::Newline {
+                if lc == line {
+                    return last;
+                }
+                lc += 1;
+                cc = 1;
+                continue;
+            }
+            let len = token.to_string().len();
+            if lc == line {
+                if column < cc + len {
+                    return Some(token);
+                }
+                if !token.token().is_whitespace() {
+                    last = Some(token);
+                }
+            }
+            cc += len;
+        }
+        last
+    }
+
     pub fn export(&self) -> String {
         let mut content = String::new();
         for token in &self.tokens {
@@ -56,6 +56,16 @@ impl TokenPos {
         &self.path
     }
 
+    /// The location of the token in its source
+    pub fn span(&self) -> hemtt::Span {
+        hemtt::Span::new(
+            self.path.clone(),
+            self.start.1 .0,
+            self.start.1 .1,
+            self.end.0.saturating_sub(self.start.0).max(1),
+        )
+    }
+
     pub fn token(&self) -> &Token {
         &self.token
     }
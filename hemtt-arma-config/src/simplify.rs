@@ -14,6 +14,25 @@ pub struct Class {
     pub entries: Vec<(String, Entry)>,
 }
 
+impl Class {
+    /// Finds an entry by name, ignoring case like the game does
+    pub fn get(&self, name: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, e)| e)
+    }
+
+    /// Finds a child class by name, ignoring case like the game does
+    pub fn class(&self, name: &str) -> Option<&Class> {
+        if let Some(Entry::Class(c)) = self.get(name) {
+            Some(c)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Entry {
     Str(String),
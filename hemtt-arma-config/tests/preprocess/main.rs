@@ -265,3 +265,25 @@ value = affirmative;
     // println!("======");
     assert_eq!("\nvalue = affirmative;\n", config.export());
 }
+
+#[test]
+fn predefined() {
+    let content = r#"
+value = VERSION;
+array[] = {VERSION_AR};
+"#;
+    let config = hemtt_arma_config::preprocess_with_defines(
+        hemtt_arma_config::tokenize(content, "").unwrap(),
+        ".",
+        hemtt_arma_config::resolver::Basic,
+        &[
+            (String::from("VERSION"), String::from("1.2.3.0")),
+            (String::from("VERSION_AR"), String::from("1,2,3,0")),
+        ],
+    );
+    let config = hemtt_arma_config::render(config.unwrap());
+    assert_eq!(
+        "\nvalue = 1.2.3.0;\narray[] = {1,2,3,0};\n",
+        config.export()
+    );
+}
@@ -0,0 +1,47 @@
+use handlebars::handlebars_helper;
+
+handlebars_helper!(upper: |s: str| s.to_uppercase());
+handlebars_helper!(lower: |s: str| s.to_lowercase());
+
+// `{{title "my_addon"}}` => "My Addon"
+handlebars_helper!(title: |s: str| {
+    words(s)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+});
+
+// `{{snake "My Addon"}}` => "my_addon"
+handlebars_helper!(snake: |s: str| words(s).join("_").to_lowercase());
+
+// `{{kebab "My Addon"}}` => "my-addon"
+handlebars_helper!(kebab: |s: str| words(s).join("-").to_lowercase());
+
+/// Splits on spaces, underscores and dashes
+fn words(s: &str) -> Vec<&str> {
+    s.split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{render, Variables};
+
+    #[test]
+    fn case() {
+        let vars = Variables::new();
+        assert_eq!(render("{{upper \"abc\"}}", &vars).unwrap(), "ABC");
+        assert_eq!(render("{{lower \"ABC\"}}", &vars).unwrap(), "abc");
+        assert_eq!(render("{{title \"my_addon\"}}", &vars).unwrap(), "My Addon");
+        assert_eq!(render("{{snake \"My Addon\"}}", &vars).unwrap(), "my_addon");
+        assert_eq!(render("{{kebab \"my_addon\"}}", &vars).unwrap(), "my-addon");
+    }
+}
@@ -15,6 +15,35 @@ pub fn helper(
     } else {
         "id".to_string()
     };
+    write(&param, out)
+}
+
+/// `{{git_hash}}`, the short hash of the current commit, same as `{{git "id"}}`
+pub fn hash(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    match h.param(0) {
+        Some(p) => write(&format!("id {}", p.value().render()), out),
+        None => write("id", out),
+    }
+}
+
+/// `{{git_branch}}`, the name of the checked out branch, same as `{{git "branch"}}`
+pub fn branch(
+    _: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    write("branch", out)
+}
+
+fn write(param: &str, out: &mut dyn Output) -> HelperResult {
     let params: Vec<&str> = param.split_whitespace().collect();
 
     let repo = Repository::open(".").map_err(|e| RenderError::new(e.to_string()))?;
@@ -33,7 +62,7 @@ pub fn helper(
                 None => 8,
             };
 
-            let id_sliced = &id[0..length];
+            let id_sliced = &id[0..length.min(id.len())];
             out.write(id_sliced)?;
         }
         "commitCount" | "commit_count" => {
@@ -49,6 +78,10 @@ pub fn helper(
                 .map_err(|e| RenderError::new(e.to_string()))?;
             out.write(&format!("{}", revwalk.count()))?;
         }
+        "branch" => {
+            let head = repo.head().map_err(|e| RenderError::new(e.to_string()))?;
+            out.write(head.shorthand().unwrap_or("HEAD"))?;
+        }
         &_ => {}
     }
     Ok(())
@@ -112,6 +145,31 @@ mod tests {
             "1a6bce22"
         );
         assert_eq!(render("{{git}}", &Variables::new()).unwrap(), "1a6bce22");
+        assert_eq!(
+            render("{{git_hash}}", &Variables::new()).unwrap(),
+            "1a6bce22"
+        );
+        assert_eq!(
+            render("{{git_hash 12}}", &Variables::new()).unwrap(),
+            render("{{git \"id 12\"}}", &Variables::new()).unwrap()
+        );
+        std::env::set_current_dir(orig).unwrap();
+    }
+
+    #[test]
+    fn branch() {
+        let test = TestFolder::new();
+        let orig = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&test.dir).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10)); // Seemed to have occasional issues
+        assert_eq!(
+            render("{{git \"branch\"}}", &Variables::new()).unwrap(),
+            "master"
+        );
+        assert_eq!(
+            render("{{git_branch}}", &Variables::new()).unwrap(),
+            "master"
+        );
         std::env::set_current_dir(orig).unwrap();
     }
 
@@ -0,0 +1,92 @@
+use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext, RenderError};
+
+/// `{{add a b}}`
+pub fn add(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let (a, b) = operands(h)?;
+    out.write(&(a + b).to_string())?;
+    Ok(())
+}
+
+/// `{{sub a b}}`
+pub fn sub(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let (a, b) = operands(h)?;
+    out.write(&(a - b).to_string())?;
+    Ok(())
+}
+
+/// `{{mul a b}}`
+pub fn mul(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let (a, b) = operands(h)?;
+    out.write(&(a * b).to_string())?;
+    Ok(())
+}
+
+/// `{{div a b}}`, integer division
+pub fn div(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let (a, b) = operands(h)?;
+    if b == 0 {
+        return Err(RenderError::new(format!("`{}` divided by zero", h.name())));
+    }
+    out.write(&(a / b).to_string())?;
+    Ok(())
+}
+
+/// Both parameters of a helper as integers, numbers in strings are accepted
+fn operands(h: &Helper) -> Result<(i64, i64), RenderError> {
+    let operand = |index: usize| {
+        let value = h
+            .param(index)
+            .map(|p| p.value())
+            .ok_or_else(|| RenderError::new(format!("`{}` requires two parameters", h.name())))?;
+        value
+            .as_i64()
+            .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+            .ok_or_else(|| {
+                RenderError::new(format!("`{}` expects numbers, got `{}`", h.name(), value))
+            })
+    };
+    Ok((operand(0)?, operand(1)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::value::Value as Json;
+
+    use crate::{render, Variables};
+
+    #[test]
+    fn math() {
+        let mut vars = Variables::new();
+        vars.insert("minor", Json::from(4));
+        assert_eq!(render("{{add minor 1}}", &vars).unwrap(), "5");
+        assert_eq!(render("{{sub minor 1}}", &vars).unwrap(), "3");
+        assert_eq!(render("{{mul minor \"2\"}}", &vars).unwrap(), "8");
+        assert_eq!(render("{{div minor 3}}", &vars).unwrap(), "1");
+        assert!(render("{{div minor 0}}", &vars).is_err());
+        assert!(render("{{add minor \"a\"}}", &vars).is_err());
+    }
+}
@@ -1,5 +1,11 @@
+mod case;
 mod date;
 mod git;
+mod math;
 
+pub use case::{kebab, lower, snake, title, upper};
 pub use date::helper as date;
+pub use git::branch as git_branch;
+pub use git::hash as git_hash;
 pub use git::helper as git;
+pub use math::{add, div, mul, sub};
@@ -9,11 +9,33 @@ use serde_json::value::Value as Json;
 mod helpers;
 
 pub fn render(source: &str, data: &Variables) -> Result<String, TemplateRenderError> {
+    registry().render_template(source, data.inner())
+}
+
+/// Renders without escaping HTML, for file names and other text that is not HTML
+pub fn render_raw(source: &str, data: &Variables) -> Result<String, TemplateRenderError> {
+    let mut handlebars = registry();
+    handlebars.register_escape_fn(no_escape);
+    handlebars.render_template(source, data.inner())
+}
+
+fn registry() -> Handlebars<'static> {
     let mut handlebars = Handlebars::new();
     handlebars.register_helper("date", Box::new(helpers::date));
     handlebars.register_helper("git", Box::new(helpers::git));
+    handlebars.register_helper("git_hash", Box::new(helpers::git_hash));
+    handlebars.register_helper("git_branch", Box::new(helpers::git_branch));
+    handlebars.register_helper("upper", Box::new(helpers::upper));
+    handlebars.register_helper("lower", Box::new(helpers::lower));
+    handlebars.register_helper("title", Box::new(helpers::title));
+    handlebars.register_helper("snake", Box::new(helpers::snake));
+    handlebars.register_helper("kebab", Box::new(helpers::kebab));
+    handlebars.register_helper("add", Box::new(helpers::add));
+    handlebars.register_helper("sub", Box::new(helpers::sub));
+    handlebars.register_helper("mul", Box::new(helpers::mul));
+    handlebars.register_helper("div", Box::new(helpers::div));
     handlebars.set_strict_mode(true);
-    handlebars.render_template(source, data.inner())
+    handlebars
 }
 
 #[derive(Default)]
@@ -44,7 +66,7 @@ mod tests {
     use serde_json::value::Value as Json;
     use std::collections::BTreeMap;
 
-    use crate::{render, Variables};
+    use crate::{render, render_raw, Variables};
 
     #[test]
     fn variables() {
@@ -57,4 +79,18 @@ mod tests {
         var.append(Variables::from(map2));
         assert_eq!(render("{{a}}{{b}}{{c}}", &var).unwrap(), "123");
     }
+
+    #[test]
+    fn raw() {
+        let mut var = Variables::new();
+        var.insert("name", Json::String(String::from("Tom & Jerry's \"mod\"")));
+        assert_eq!(
+            render("{{name}}", &var).unwrap(),
+            "Tom &amp; Jerry&#x27;s &quot;mod&quot;"
+        );
+        assert_eq!(
+            render_raw("{{name}}", &var).unwrap(),
+            "Tom & Jerry's \"mod\""
+        );
+    }
 }
@@ -1,7 +1,7 @@
 #[macro_export]
 macro_rules! create_dir {
     ($e:expr) => {
-        std::fs::create_dir_all(&$e).map_err(|source| {
+        std::fs::create_dir_all(hemtt::long_path(&$e)).map_err(|source| {
             hemtt::HEMTTError::IOPath(hemtt::IOPathError {
                 source,
                 path: std::path::Path::new(&$e.clone()).to_path_buf(),
@@ -13,7 +13,7 @@ macro_rules! create_dir {
 #[macro_export]
 macro_rules! open_file {
     ($e:expr) => {
-        std::fs::File::open(&$e).map_err(|source| {
+        std::fs::File::open(hemtt::long_path(&$e)).map_err(|source| {
             hemtt::HEMTTError::IOPath(hemtt::IOPathError {
                 path: std::path::PathBuf::from(&$e),
                 source,
@@ -26,7 +26,7 @@ macro_rules! open_file {
 macro_rules! create_file {
     ($e:expr) => {{
         let p = $e;
-        std::fs::File::create(&p).map_err(|source| {
+        std::fs::File::create(hemtt::long_path(&p)).map_err(|source| {
             hemtt::HEMTTError::IOPath(hemtt::IOPathError {
                 path: std::path::PathBuf::from(&p),
                 source,
@@ -38,7 +38,7 @@ macro_rules! create_file {
 #[macro_export]
 macro_rules! copy_file {
     ($s:expr, $d:expr) => {
-        std::fs::copy(&$s, &$d).map_err(|source| {
+        std::fs::copy(hemtt::long_path(&$s), hemtt::long_path(&$d)).map_err(|source| {
             hemtt::HEMTTError::GENERIC(
                 format!("Unable to copy file: {}", source),
                 format!("`{:#?}` => `{:#?}`", $s, $d),
@@ -62,7 +62,7 @@ macro_rules! copy_dir {
 #[macro_export]
 macro_rules! rename_file {
     ($s:expr, $d:expr) => {
-        std::fs::rename(&$s, &$d).map_err(|source| {
+        std::fs::rename(hemtt::long_path(&$s), hemtt::long_path(&$d)).map_err(|source| {
             hemtt::HEMTTError::GENERIC(
                 format!("Unable to rename file: {}", source),
                 format!("`{:#?}` => `{:#?}`", $s, $d),
@@ -74,7 +74,7 @@ macro_rules! rename_file {
 #[macro_export]
 macro_rules! remove_file {
     ($s:expr) => {
-        std::fs::remove_file(&$s).map_err(|source| {
+        std::fs::remove_file(hemtt::long_path(&$s)).map_err(|source| {
             hemtt::HEMTTError::IOPath(hemtt::IOPathError {
                 path: std::path::PathBuf::from(&$s),
                 source,
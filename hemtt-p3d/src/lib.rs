@@ -0,0 +1,45 @@
+mod lod;
+pub use lod::Lod;
+
+mod read;
+
+/// An unbinarized model, as saved by Object Builder
+///
+/// Only what the model references is kept, the points and faces are skipped
+#[derive(Debug, Default)]
+pub struct P3D {
+    pub version: u32,
+    pub lods: Vec<Lod>,
+}
+
+impl P3D {
+    /// Every texture used by the faces of the model
+    pub fn textures(&self) -> Vec<&str> {
+        collect(self.lods.iter().flat_map(|lod| lod.textures.iter()))
+    }
+
+    /// Every material used by the faces of the model
+    pub fn materials(&self) -> Vec<&str> {
+        collect(self.lods.iter().flat_map(|lod| lod.materials.iter()))
+    }
+
+    /// Every model placed as a proxy
+    /// Ex: "\a3\data_f\proxies\weapon_slots\muzzle.p3d"
+    pub fn proxies(&self) -> Vec<String> {
+        let mut proxies: Vec<String> = self.lods.iter().flat_map(Lod::proxies).collect();
+        proxies.sort();
+        proxies.dedup();
+        proxies
+    }
+}
+
+fn collect<'a>(paths: impl Iterator<Item = &'a String>) -> Vec<&'a str> {
+    let mut paths: Vec<&str> = paths
+        .map(String::as_str)
+        // Procedural textures, ex: "#(argb,8,8,3)color(1,0,0,1)"
+        .filter(|path| !path.is_empty() && !path.starts_with('#'))
+        .collect();
+    paths.sort_unstable();
+    paths.dedup();
+    paths
+}
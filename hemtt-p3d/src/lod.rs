@@ -0,0 +1,31 @@
+use std::collections::BTreeSet;
+
+/// A level of detail of a model
+#[derive(Debug, Default)]
+pub struct Lod {
+    /// Resolution of the LOD, special LODs like the geometry use large values
+    pub resolution: f32,
+    pub textures: BTreeSet<String>,
+    pub materials: BTreeSet<String>,
+    /// Named selections, proxies are selections named `proxy:{model}.{index}`
+    pub selections: Vec<String>,
+}
+
+impl Lod {
+    /// Models placed as proxies in the LOD
+    /// Ex: "proxy:\a3\data_f\proxies\weapon_slots\muzzle.001" is
+    /// "\a3\data_f\proxies\weapon_slots\muzzle.p3d"
+    pub fn proxies(&self) -> Vec<String> {
+        self.selections
+            .iter()
+            .filter_map(|selection| {
+                let model = selection.strip_prefix("proxy:")?;
+                let model = match model.rfind('.') {
+                    Some(index) => &model[..index],
+                    None => model,
+                };
+                Some(format!("{}.p3d", model))
+            })
+            .collect()
+    }
+}
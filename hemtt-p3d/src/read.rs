@@ -0,0 +1,100 @@
+use std::io::{Error, ErrorKind, Read};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use crate::{Lod, P3D};
+
+impl P3D {
+    /// Reads an unbinarized model
+    ///
+    /// `None` when the model is not unbinarized, ex: binarized `ODOL` models
+    pub fn read<I: Read>(mut input: I) -> Result<Option<Self>, Error> {
+        if signature(&mut input)? != *b"MLOD" {
+            return Ok(None);
+        }
+        let version = input.read_u32::<LittleEndian>()?;
+        let count = input.read_u32::<LittleEndian>()?;
+        let mut lods = Vec::new();
+        for _ in 0..count {
+            lods.push(read_lod(&mut input)?);
+        }
+        Ok(Some(Self { version, lods }))
+    }
+}
+
+fn read_lod<I: Read>(input: &mut I) -> Result<Lod, Error> {
+    let sig = signature(input)?;
+    if sig != *b"P3DM" {
+        return Err(invalid(format!(
+            "unsupported LOD `{}`",
+            String::from_utf8_lossy(&sig)
+        )));
+    }
+    let _major = input.read_u32::<LittleEndian>()?;
+    let _minor = input.read_u32::<LittleEndian>()?;
+    let points = input.read_u32::<LittleEndian>()?;
+    let normals = input.read_u32::<LittleEndian>()?;
+    let faces = input.read_u32::<LittleEndian>()?;
+    let _flags = input.read_u32::<LittleEndian>()?;
+
+    // Position and flags of each point, then the direction of each normal
+    skip(input, u64::from(points) * 16 + u64::from(normals) * 12)?;
+
+    let mut lod = Lod::default();
+    for _ in 0..faces {
+        // Vertex count, 4 vertices of point, normal, u and v, and the flags
+        skip(input, 4 + 4 * 16 + 4)?;
+        lod.textures.insert(cstring(input)?);
+        lod.materials.insert(cstring(input)?);
+    }
+
+    if signature(input)? != *b"TAGG" {
+        return Err(invalid(String::from("missing TAGG after the faces")));
+    }
+    loop {
+        let _active = input.read_u8()?;
+        let name = cstring(input)?;
+        let size = input.read_u32::<LittleEndian>()?;
+        if name == "#EndOfFile#" {
+            break;
+        }
+        skip(input, u64::from(size))?;
+        // Taggs of the LOD start with `#`, the others are named selections
+        if !name.starts_with('#') {
+            lod.selections.push(name);
+        }
+    }
+    lod.resolution = input.read_f32::<LittleEndian>()?;
+    Ok(lod)
+}
+
+fn signature<I: Read>(input: &mut I) -> Result<[u8; 4], Error> {
+    let mut sig = [0; 4];
+    input.read_exact(&mut sig)?;
+    Ok(sig)
+}
+
+fn skip<I: Read>(input: &mut I, length: u64) -> Result<(), Error> {
+    let skipped = std::io::copy(&mut input.by_ref().take(length), &mut std::io::sink())?;
+    if skipped == length {
+        Ok(())
+    } else {
+        Err(Error::from(ErrorKind::UnexpectedEof))
+    }
+}
+
+/// Paths are not always UTF-8, Object Builder saves them in the code page of Windows
+fn cstring<I: Read>(input: &mut I) -> Result<String, Error> {
+    let mut bytes = Vec::new();
+    loop {
+        match input.read_u8()? {
+            0 => break,
+            b => bytes.push(b),
+        }
+    }
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,88 @@
+use hemtt_p3d::P3D;
+
+fn cstring(data: &mut Vec<u8>, text: &str) {
+    data.extend_from_slice(text.as_bytes());
+    data.push(0);
+}
+
+fn u32(data: &mut Vec<u8>, value: u32) {
+    data.extend_from_slice(&value.to_le_bytes());
+}
+
+/// A LOD with a face for each texture and material, and the selections
+fn lod(data: &mut Vec<u8>, faces: &[(&str, &str)], selections: &[&str]) {
+    data.extend_from_slice(b"P3DM");
+    u32(data, 0x1c);
+    u32(data, 0x100);
+    u32(data, 3);
+    u32(data, 1);
+    u32(data, faces.len() as u32);
+    u32(data, 0);
+    data.extend_from_slice(&[0; 3 * 16 + 12]);
+    for (texture, material) in faces {
+        u32(data, 3);
+        data.extend_from_slice(&[0; 4 * 16 + 4]);
+        cstring(data, texture);
+        cstring(data, material);
+    }
+    data.extend_from_slice(b"TAGG");
+    data.push(1);
+    cstring(data, "#SharpEdges#");
+    u32(data, 8);
+    data.extend_from_slice(&[0; 8]);
+    for selection in selections {
+        data.push(1);
+        cstring(data, selection);
+        u32(data, 4);
+        data.extend_from_slice(&[0; 4]);
+    }
+    data.push(1);
+    cstring(data, "#EndOfFile#");
+    u32(data, 0);
+    data.extend_from_slice(&1.0f32.to_le_bytes());
+}
+
+#[test]
+fn references() {
+    let mut data = b"MLOD".to_vec();
+    u32(&mut data, 257);
+    u32(&mut data, 2);
+    lod(
+        &mut data,
+        &[
+            (
+                r"z\test\addons\main\data\body_co.paa",
+                r"z\test\addons\main\data\body.rvmat",
+            ),
+            (r"z\test\addons\main\data\body_co.paa", ""),
+            ("#(argb,8,8,3)color(1,0,0,1)", ""),
+        ],
+        &[r"proxy:\a3\data_f\proxies\weapon_slots\muzzle.001", "camo"],
+    );
+    lod(
+        &mut data,
+        &[(r"z\test\addons\main\data\glass_ca.paa", "")],
+        &[r"proxy:\a3\data_f\proxies\weapon_slots\muzzle.002"],
+    );
+
+    let p3d = P3D::read(std::io::Cursor::new(data)).unwrap().unwrap();
+    assert_eq!(p3d.lods.len(), 2);
+    assert_eq!(
+        p3d.textures(),
+        vec![
+            r"z\test\addons\main\data\body_co.paa",
+            r"z\test\addons\main\data\glass_ca.paa"
+        ]
+    );
+    assert_eq!(p3d.materials(), vec![r"z\test\addons\main\data\body.rvmat"]);
+    assert_eq!(
+        p3d.proxies(),
+        vec![r"\a3\data_f\proxies\weapon_slots\muzzle.p3d".to_string()]
+    );
+}
+
+#[test]
+fn binarized() {
+    let data = b"ODOL\x46\x00\x00\x00".to_vec();
+    assert!(P3D::read(std::io::Cursor::new(data)).unwrap().is_none());
+}
@@ -37,6 +37,12 @@ impl PaXType {
             _ => None,
         }
     }
+
+    /// Formats that Arma 3 renders, the premultiplied DXT2 and DXT4 and the
+    /// uncommon DXT3 are not
+    pub fn is_supported(&self) -> bool {
+        !matches!(self, Self::DXT2 | Self::DXT3 | Self::DXT4)
+    }
 }
 
 impl From<PaXType> for image::dxt::DXTVariant {
@@ -1,4 +1,5 @@
-use std::io::{Error, Read, Seek, SeekFrom};
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Read, Seek, SeekFrom};
 
 use byteorder::{LittleEndian, ReadBytesExt};
 
@@ -8,28 +9,7 @@ impl Paa {
     pub fn read<I: Seek + Read>(mut input: I) -> Result<Self, Error> {
         if let Some(pax) = PaXType::from_stream(&mut input) {
             let mut paa = Self::new(pax);
-            // Read Taggs
-            while {
-                let mut tagg_sig = [0; 4];
-                input.read_exact(&mut tagg_sig)?;
-                if let Ok(ts) = std::str::from_utf8(&tagg_sig) {
-                    ts == "GGAT"
-                } else {
-                    false
-                }
-            } {
-                let name = {
-                    let mut bytes = [0; 4];
-                    input.read_exact(&mut bytes)?;
-                    std::str::from_utf8(&bytes).unwrap().to_string()
-                };
-                paa.taggs.insert(name, {
-                    let mut buffer: Box<[u8]> =
-                        vec![0; (input.read_u32::<LittleEndian>()?) as usize].into_boxed_slice();
-                    input.read_exact(&mut buffer)?;
-                    buffer.to_vec()
-                });
-            }
+            paa.taggs = read_taggs(&mut input)?;
             // Read MipMaps
             if let Some(offs) = paa.taggs.get("SFFO") {
                 for i in 0..(offs.len() / 4) {
@@ -49,4 +29,67 @@ impl Paa {
             panic!("unrecognized file");
         }
     }
+
+    /// Reads the format and the size of the largest mipmap, without reading the mipmaps
+    ///
+    /// `None` when the format is not a PAA format
+    pub fn dimensions<I: Seek + Read>(mut input: I) -> Result<Option<(PaXType, u16, u16)>, Error> {
+        let pax = match PaXType::from_stream(&mut input) {
+            Some(pax) => pax,
+            None => return Ok(None),
+        };
+        let taggs = read_taggs(&mut input)?;
+        let offset = match taggs.get("SFFO") {
+            Some(offs) if offs.len() >= 4 => {
+                u32::from_le_bytes([offs[0], offs[1], offs[2], offs[3]])
+            }
+            _ => return Ok(None),
+        };
+        input.seek(SeekFrom::Start(u64::from(offset)))?;
+        // The highest bit of the width marks LZO compressed mipmaps
+        let width = input.read_u16::<LittleEndian>()? & 0x7FFF;
+        let height = input.read_u16::<LittleEndian>()?;
+        Ok(Some((pax, width, height)))
+    }
+}
+
+/// Reads the taggs following the format, and the first bytes after them
+///
+/// A tagg with a name that is not text, or larger than the rest of the stream,
+/// is `InvalidData`
+fn read_taggs<I: Read + Seek>(input: &mut I) -> Result<HashMap<String, Vec<u8>>, Error> {
+    let position = input.stream_position()?;
+    let length = input.seek(SeekFrom::End(0))?;
+    input.seek(SeekFrom::Start(position))?;
+    let mut taggs = HashMap::new();
+    while {
+        let mut tagg_sig = [0; 4];
+        input.read_exact(&mut tagg_sig)?;
+        if let Ok(ts) = std::str::from_utf8(&tagg_sig) {
+            ts == "GGAT"
+        } else {
+            false
+        }
+    } {
+        let name = {
+            let mut bytes = [0; 4];
+            input.read_exact(&mut bytes)?;
+            std::str::from_utf8(&bytes)
+                .map_err(|_| Error::new(ErrorKind::InvalidData, "tagg name is not text"))?
+                .to_string()
+        };
+        let size = u64::from(input.read_u32::<LittleEndian>()?);
+        if size > length.saturating_sub(input.stream_position()?) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("tagg `{}` is larger than the file", name),
+            ));
+        }
+        taggs.insert(name, {
+            let mut buffer: Box<[u8]> = vec![0; size as usize].into_boxed_slice();
+            input.read_exact(&mut buffer)?;
+            buffer.to_vec()
+        });
+    }
+    Ok(taggs)
 }
@@ -0,0 +1,44 @@
+use hemtt_paa::{PaXType, Paa};
+
+#[test]
+fn dimensions_dxt1() {
+    let file = std::fs::File::open("tests/dxt1.paa").unwrap();
+    let (pax, width, height) = Paa::dimensions(file).unwrap().unwrap();
+    assert!(matches!(pax, PaXType::DXT1));
+    assert_eq!((width, height), (512, 512));
+}
+
+#[test]
+fn dimensions_dxt5() {
+    let file = std::fs::File::open("tests/dxt5.paa").unwrap();
+    let (pax, width, height) = Paa::dimensions(file).unwrap().unwrap();
+    assert!(matches!(pax, PaXType::DXT5));
+    assert_eq!((width, height), (64, 64));
+}
+
+#[test]
+fn dimensions_not_paa() {
+    let data = std::io::Cursor::new(b"class CfgPatches {};".to_vec());
+    assert!(Paa::dimensions(data).unwrap().is_none());
+}
+
+#[test]
+fn dimensions_invalid_tagg() {
+    let mut data = vec![1, 255];
+    data.extend(b"GGAT");
+    data.extend(&[0xFF, 0xFE, 0xFD, 0xFC]);
+    data.extend(&4u32.to_le_bytes());
+    data.extend(&[0; 4]);
+    let error = Paa::dimensions(std::io::Cursor::new(data)).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn dimensions_oversized_tagg() {
+    let mut data = vec![1, 255];
+    data.extend(b"GGATSFFO");
+    data.extend(&u32::MAX.to_le_bytes());
+    data.extend(&[0; 4]);
+    let error = Paa::dimensions(std::io::Cursor::new(data)).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
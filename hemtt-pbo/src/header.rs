@@ -7,6 +7,9 @@ use std::{
 
 use hemtt_io::{ReadExt, WriteExt};
 
+/// Packing method of files stored with LZSS compression, `Cprs`
+pub const METHOD_COMPRESSED: u32 = 0x4370_7273;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Timestamp(u32);
 impl Deref for Timestamp {
@@ -80,6 +83,9 @@ impl Header {
     pub fn size(&self) -> u32 {
         self.size
     }
+    pub fn is_compressed(&self) -> bool {
+        self.method == METHOD_COMPRESSED
+    }
 }
 
 #[test]
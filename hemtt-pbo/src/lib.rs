@@ -2,7 +2,10 @@
 extern crate log;
 
 mod header;
-pub use header::{Header, Timestamp};
+pub use header::{Header, Timestamp, METHOD_COMPRESSED};
+
+mod lzss;
+pub use lzss::{compress, decompress};
 
 mod pbo;
 pub use pbo::{ReadablePbo, WritablePbo};
@@ -0,0 +1,182 @@
+use std::io::{Error, ErrorKind};
+
+/// Furthest back a match can start
+const WINDOW: usize = 4095;
+/// Shortest match worth a pointer, shorter runs are stored as literals
+const MIN_MATCH: usize = 3;
+/// Longest match a pointer can describe
+const MAX_MATCH: usize = 18;
+/// Candidates checked for each position, more finds longer matches but is slower
+const MAX_CHAIN: usize = 256;
+
+const HASH_BITS: usize = 14;
+
+/// Compresses data with the LZSS variant used by PBO entries
+///
+/// Each flag byte describes the next 8 items, a set bit is a literal byte and a
+/// clear bit is a 2 byte pointer to an earlier match. The data is followed by the
+/// checksum of the uncompressed data.
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() / 2 + 8);
+    let mut head = vec![usize::MAX; 1 << HASH_BITS];
+    let mut prev = vec![usize::MAX; data.len()];
+    let mut flags_pos = 0;
+    let mut item = 8;
+    let mut i = 0;
+    while i < data.len() {
+        if item == 8 {
+            flags_pos = out.len();
+            out.push(0);
+            item = 0;
+        }
+        let (distance, length) = longest_match(data, i, &head, &prev);
+        if length >= MIN_MATCH {
+            out.push((distance & 0xFF) as u8);
+            out.push((((distance >> 4) & 0xF0) | (length - MIN_MATCH)) as u8);
+        } else {
+            out[flags_pos] |= 1 << item;
+            out.push(data[i]);
+        }
+        for _ in 0..length.max(1) {
+            if i + MIN_MATCH <= data.len() {
+                let h = hash(&data[i..]);
+                prev[i] = head[h];
+                head[h] = i;
+            }
+            i += 1;
+        }
+        item += 1;
+    }
+    out.extend_from_slice(&checksum(data).to_le_bytes());
+    out
+}
+
+/// Decompresses an LZSS compressed PBO entry, `length` is the size of the uncompressed data
+pub fn decompress(data: &[u8], length: usize) -> Result<Vec<u8>, Error> {
+    let invalid = |msg: &str| Error::new(ErrorKind::InvalidData, msg.to_string());
+    let mut out = Vec::with_capacity(length);
+    let mut i = 0;
+    while out.len() < length {
+        let flags = *data
+            .get(i)
+            .ok_or_else(|| invalid("unexpected end of data"))?;
+        i += 1;
+        for item in 0..8 {
+            if out.len() >= length {
+                break;
+            }
+            if flags & (1 << item) != 0 {
+                out.push(
+                    *data
+                        .get(i)
+                        .ok_or_else(|| invalid("unexpected end of data"))?,
+                );
+                i += 1;
+                continue;
+            }
+            let (low, high) = match (data.get(i), data.get(i + 1)) {
+                (Some(low), Some(high)) => (usize::from(*low), usize::from(*high)),
+                _ => return Err(invalid("unexpected end of data")),
+            };
+            i += 2;
+            let distance = low | ((high & 0xF0) << 4);
+            let count = ((high & 0x0F) + MIN_MATCH).min(length - out.len());
+            if distance > out.len() {
+                // Points before the start of the data, which is read as spaces
+                let spaces = (distance - out.len()).min(count);
+                out.extend(std::iter::repeat(b' ').take(spaces));
+                for _ in spaces..count {
+                    out.push(out[out.len() - distance]);
+                }
+            } else {
+                if distance == 0 {
+                    return Err(invalid("pointer to the current position"));
+                }
+                for _ in 0..count {
+                    out.push(out[out.len() - distance]);
+                }
+            }
+        }
+    }
+    let expected = data
+        .get(i..i + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| invalid("missing checksum"))?;
+    // Older tools sum the bytes as unsigned
+    let unsigned = out
+        .iter()
+        .fold(0u32, |sum, b| sum.wrapping_add(u32::from(*b)));
+    if expected != checksum(&out) && expected != unsigned {
+        return Err(invalid("checksum mismatch"));
+    }
+    Ok(out)
+}
+
+/// Sum of the bytes, which PBO entries add as signed
+fn checksum(data: &[u8]) -> u32 {
+    data.iter()
+        .fold(0u32, |sum, b| sum.wrapping_add(i32::from(*b as i8) as u32))
+}
+
+fn hash(data: &[u8]) -> usize {
+    let value = (usize::from(data[0]) << 16) | (usize::from(data[1]) << 8) | usize::from(data[2]);
+    (value.wrapping_mul(2_654_435_761) >> 8) & ((1 << HASH_BITS) - 1)
+}
+
+/// Distance and length of the longest earlier match of the data at `pos`
+fn longest_match(data: &[u8], pos: usize, head: &[usize], prev: &[usize]) -> (usize, usize) {
+    if pos + MIN_MATCH > data.len() {
+        return (0, 0);
+    }
+    let max = MAX_MATCH.min(data.len() - pos);
+    let mut best = (0, 0);
+    let mut candidate = head[hash(&data[pos..])];
+    let mut chain = 0;
+    while candidate != usize::MAX && pos - candidate <= WINDOW && chain < MAX_CHAIN {
+        let length = (0..max)
+            .take_while(|&n| data[candidate + n] == data[pos + n])
+            .count();
+        if length > best.1 {
+            best = (pos - candidate, length);
+            if length == max {
+                break;
+            }
+        }
+        candidate = prev[candidate];
+        chain += 1;
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compress, decompress};
+
+    #[test]
+    fn round_trip() {
+        let text = b"params [\"_unit\"];\n_unit setDamage 0;\n_unit setDamage 0;\n".repeat(20);
+        let compressed = compress(&text);
+        assert!(compressed.len() < text.len() / 4);
+        assert_eq!(decompress(&compressed, text.len()).unwrap(), text);
+
+        let bytes: Vec<u8> = (0..5000u32).map(|i| (i * 7919 % 251) as u8).collect();
+        assert_eq!(decompress(&compress(&bytes), bytes.len()).unwrap(), bytes);
+        assert_eq!(decompress(&compress(&[]), 0).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn known() {
+        // Literal `a`, then a pointer 1 back for 3 bytes, then the signed checksum
+        let data = [0b0000_0001, b'a', 1, 0, 0x84, 1, 0, 0];
+        assert_eq!(decompress(&data, 4).unwrap(), b"aaaa");
+        assert_eq!(compress(b"aaaa"), data);
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let mut compressed = compress(b"hello hello hello");
+        let last = compressed.len() - 1;
+        compressed[last] ^= 1;
+        assert!(decompress(&compressed, 17).is_err());
+    }
+}
@@ -135,4 +135,24 @@ impl<I: Seek + Read> ReadablePbo<I> {
         }
         None
     }
+
+    /// Retrieves a file from a PBO, decompressing it if it is stored compressed
+    pub fn retrieve_decompressed(
+        &mut self,
+        filename: &str,
+    ) -> Result<Option<Cursor<Box<[u8]>>>, Error> {
+        let header = match self.header(filename) {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        let data = match self.retrieve(filename) {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        if !header.is_compressed() {
+            return Ok(Some(data));
+        }
+        let data = crate::decompress(data.get_ref(), header.original() as usize)?;
+        Ok(Some(Cursor::new(data.into_boxed_slice())))
+    }
 }
@@ -8,12 +8,15 @@ use hemtt_io::WriteExt;
 use indexmap::IndexMap;
 use openssl::hash::{Hasher, MessageDigest};
 
-use crate::{Header, ReadablePbo, Timestamp};
+use crate::{Header, ReadablePbo, Timestamp, METHOD_COMPRESSED};
 
 #[derive(Default)]
 pub struct WritablePbo<I: Seek + Read> {
     extensions: IndexMap<String, String>,
     files: HashMap<String, (I, Header)>,
+    /// Files compressed when the PBO is written, with their compressed data once
+    /// it has been compressed
+    compressed: HashMap<String, Option<Vec<u8>>>,
 }
 
 impl<I: Seek + Read> WritablePbo<I> {
@@ -22,6 +25,7 @@ impl<I: Seek + Read> WritablePbo<I> {
         Self {
             extensions: IndexMap::new(),
             files: HashMap::new(),
+            compressed: HashMap::new(),
         }
     }
 
@@ -50,7 +54,9 @@ impl<I: Seek + Read> WritablePbo<I> {
     pub fn remove_file<S: Into<String>>(&mut self, filename: S) -> Option<(I, Header)> {
         let filename = filename.into();
         trace!("removing file from struct: {}", filename);
-        self.files.remove(&filename.replace("/", "\\"))
+        let filename = filename.replace("/", "\\");
+        self.compressed.remove(&filename);
+        self.files.remove(&filename)
     }
 
     /// Adds or updates a file to the PBO, returns the old file if it existed
@@ -62,6 +68,7 @@ impl<I: Seek + Read> WritablePbo<I> {
         let filename = filename.into();
         trace!("adding file to struct: {}", filename);
         let size = file.seek(SeekFrom::End(0))? as u32;
+        self.compressed.remove(&filename.replace("/", "\\"));
         if size > u32::MAX {
             Err(std::io::Error::from(std::io::ErrorKind::Other))
         } else {
@@ -92,6 +99,7 @@ impl<I: Seek + Read> WritablePbo<I> {
         let filename = filename.into();
         trace!("adding file to struct: {}", filename);
         let size = file.seek(SeekFrom::End(0))?;
+        self.compressed.remove(&filename.replace("/", "\\"));
         if size > u32::MAX as u64 {
             Err(std::io::Error::from(std::io::ErrorKind::Other))
         } else {
@@ -101,6 +109,21 @@ impl<I: Seek + Read> WritablePbo<I> {
         }
     }
 
+    /// Adds or updates a file that is stored compressed when the PBO is written,
+    /// returns the old file if it existed
+    ///
+    /// The file is stored as is if compressing does not make it smaller
+    pub fn add_file_compressed<S: Into<String>>(
+        &mut self,
+        filename: S,
+        file: I,
+    ) -> Result<Option<(I, Header)>> {
+        let filename = filename.into();
+        let old = self.add_file(filename.clone(), file)?;
+        self.compressed.insert(filename.replace("/", "\\"), None);
+        Ok(old)
+    }
+
     /// Retrieves a file from a PBO
     pub fn retrieve_file<S: Into<String>>(
         &mut self,
@@ -134,7 +157,7 @@ impl<I: Seek + Read> WritablePbo<I> {
         &self.extensions
     }
 
-    /// Write the PBO file
+    /// Write the PBO file, the files are streamed into the output one at a time
     pub fn write<O: Write>(&mut self, output: &mut O) -> Result<()> {
         let mut headers: Cursor<Vec<u8>> = Cursor::new(Vec::new());
 
@@ -167,9 +190,9 @@ impl<I: Seek + Read> WritablePbo<I> {
         }
         headers.write_all(b"\0")?;
 
-        let files_sorted = self.files_sorted()?;
+        let files = self.stored_headers()?;
 
-        for header in &files_sorted {
+        for (header, _) in &files {
             header.write(&mut headers)?;
         }
 
@@ -185,12 +208,7 @@ impl<I: Seek + Read> WritablePbo<I> {
         output.write_all(headers.get_ref())?;
         h.update(headers.get_ref()).unwrap();
 
-        for header in &files_sorted {
-            trace!("writing & hashing file {}", header.filename());
-            let cursor = self.retrieve_file(header.filename())?.unwrap();
-            output.write_all(cursor.get_ref())?;
-            h.update(cursor.get_ref()).unwrap();
-        }
+        self.write_data(&files, output, &mut h)?;
 
         output.write_all(&[0])?;
         let hash = &*h.finish().unwrap();
@@ -229,12 +247,12 @@ impl<I: Seek + Read> WritablePbo<I> {
         }
         headers.write_all(b"\0")?;
 
-        let files_sorted = self.files_sorted()?;
+        let files = self.stored_headers()?;
 
-        for header in &files_sorted {
+        for (header, _) in &files {
             let header = Header {
                 filename: header.filename().to_string(),
-                method: 0,
+                method: header.method(),
                 original: header.original(),
                 reserved: 0,
                 timestamp: header.timestamp(),
@@ -253,13 +271,101 @@ impl<I: Seek + Read> WritablePbo<I> {
 
         h.update(headers.get_ref()).unwrap();
 
-        for header in &files_sorted {
-            let cursor = self.retrieve_file(header.filename())?.unwrap();
-            h.update(cursor.get_ref()).unwrap();
-        }
+        self.write_data(&files, &mut std::io::sink(), &mut h)?;
 
         Ok(h.finish().unwrap().to_vec())
     }
+
+    /// Headers of the files as they are stored in alphabetical order, with whether
+    /// each file is compressed
+    ///
+    /// Files are compressed once and kept, so writing the PBO and its checksum
+    /// does not compress them again
+    fn stored_headers(&mut self) -> Result<Vec<(Header, bool)>> {
+        let mut stored = Vec::new();
+        for header in self.files_sorted()? {
+            let filename = header.filename().replace("/", "\\");
+            let cached = match self.compressed.get(&filename) {
+                Some(cached) => cached.is_some(),
+                None => {
+                    stored.push((header, false));
+                    continue;
+                }
+            };
+            if !cached {
+                let data = self.retrieve_file(header.filename())?.unwrap().into_inner();
+                let compressed = crate::lzss::compress(&data);
+                trace!(
+                    "compressed {} from {} to {} bytes",
+                    header.filename(),
+                    data.len(),
+                    compressed.len()
+                );
+                self.compressed.insert(filename.clone(), Some(compressed));
+            }
+            let size = self.compressed[&filename].as_ref().unwrap().len();
+            if size >= header.original() as usize {
+                trace!("storing {} uncompressed", header.filename());
+                stored.push((header, false));
+                continue;
+            }
+            stored.push((
+                Header {
+                    method: METHOD_COMPRESSED,
+                    original: header.size(),
+                    size: size as u32,
+                    ..header
+                },
+                true,
+            ));
+        }
+        Ok(stored)
+    }
+
+    /// Streams the data of the files into the output and the hash
+    fn write_data<O: Write>(
+        &mut self,
+        files: &[(Header, bool)],
+        output: &mut O,
+        h: &mut Hasher,
+    ) -> Result<()> {
+        let mut output = HashingWriter { output, h };
+        for (header, compressed) in files {
+            trace!("writing & hashing file {}", header.filename());
+            let (file, _) = self
+                .files
+                .get_mut(&header.filename().replace("/", "\\"))
+                .unwrap();
+            file.seek(SeekFrom::Start(0))?;
+            if *compressed {
+                let data = self.compressed[&header.filename().replace("/", "\\")]
+                    .as_ref()
+                    .unwrap();
+                output.write_all(data)?;
+            } else {
+                std::io::copy(&mut file.take(u64::from(header.size())), &mut output)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes to an output while hashing what is written
+struct HashingWriter<'a, O: Write> {
+    output: &'a mut O,
+    h: &'a mut Hasher,
+}
+
+impl<'a, O: Write> Write for HashingWriter<'a, O> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let written = self.output.write(buf)?;
+        self.h.update(&buf[..written]).unwrap();
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.output.flush()
+    }
 }
 
 impl<B: Seek + Read> From<ReadablePbo<B>> for WritablePbo<Cursor<Box<[u8]>>> {
@@ -284,7 +390,7 @@ impl<B: Seek + Read> From<ReadablePbo<B>> for WritablePbo<Cursor<Box<[u8]>>> {
 mod tests {
     use std::io::Cursor;
 
-    use crate::WritablePbo;
+    use crate::{ReadablePbo, WritablePbo};
 
     #[test]
     fn empty_pbo() {
@@ -299,4 +405,83 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn files() {
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let mut pbo = WritablePbo::<Cursor<Vec<u8>>>::new();
+        pbo.add_file("b/data.bin", Cursor::new(data.clone()))
+            .unwrap();
+        pbo.add_file("a.sqf", Cursor::new(b"hint 'a';".to_vec()))
+            .unwrap();
+        let mut buffer = Vec::new();
+        pbo.write(&mut Cursor::new(&mut buffer)).unwrap();
+
+        let mut readable = ReadablePbo::from(Cursor::new(buffer)).unwrap();
+        assert!(readable.is_sorted());
+        assert_eq!(readable.checksum().unwrap(), pbo.checksum().unwrap());
+        assert_eq!(
+            &*readable.retrieve("b\\data.bin").unwrap().into_inner(),
+            data.as_slice()
+        );
+        assert_eq!(
+            &*readable.retrieve("a.sqf").unwrap().into_inner(),
+            b"hint 'a';"
+        );
+    }
+
+    #[test]
+    fn compressed_file() {
+        let script = b"_unit setDamage 0;\n".repeat(50);
+        let mut pbo = WritablePbo::<Cursor<Vec<u8>>>::new();
+        pbo.add_file_compressed("script.sqf", Cursor::new(script.clone()))
+            .unwrap();
+        pbo.add_file_compressed("data.bin", Cursor::new(vec![1, 2, 3]))
+            .unwrap();
+        let mut buffer = Vec::new();
+        pbo.write(&mut Cursor::new(&mut buffer)).unwrap();
+
+        let mut readable = ReadablePbo::from(Cursor::new(buffer)).unwrap();
+        assert_eq!(readable.checksum().unwrap(), pbo.checksum().unwrap());
+        let header = readable.header("script.sqf").unwrap();
+        assert!(header.is_compressed());
+        assert!(header.size() < header.original());
+        assert_eq!(
+            &*readable
+                .retrieve_decompressed("script.sqf")
+                .unwrap()
+                .unwrap()
+                .into_inner(),
+            script.as_slice()
+        );
+        // Too small to get smaller
+        assert!(!readable.header("data.bin").unwrap().is_compressed());
+    }
+
+    #[test]
+    fn compressed_replaced() {
+        let first = b"_unit setDamage 0;\n".repeat(50);
+        let second = b"_unit setDamage 1;\n".repeat(40);
+        let mut pbo = WritablePbo::<Cursor<Vec<u8>>>::new();
+        pbo.add_file_compressed("script.sqf", Cursor::new(first))
+            .unwrap();
+        let checksum = pbo.checksum().unwrap();
+        // The compressed data of the replaced file is not reused
+        pbo.add_file_compressed("script.sqf", Cursor::new(second.clone()))
+            .unwrap();
+        assert_ne!(pbo.checksum().unwrap(), checksum);
+        let mut buffer = Vec::new();
+        pbo.write(&mut Cursor::new(&mut buffer)).unwrap();
+
+        let mut readable = ReadablePbo::from(Cursor::new(buffer)).unwrap();
+        assert_eq!(readable.checksum().unwrap(), pbo.checksum().unwrap());
+        assert_eq!(
+            &*readable
+                .retrieve_decompressed("script.sqf")
+                .unwrap()
+                .unwrap()
+                .into_inner(),
+            second.as_slice()
+        );
+    }
 }
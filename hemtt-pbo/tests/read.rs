@@ -131,3 +131,20 @@ fn bi_3den() {
     // test_file(pbo.retrieve("XEH_preStart.sqf").unwrap(), "#include \"script_component.hpp\"\r\n\r\n#include \"XEH_PREP.hpp\"\r\n".to_string());
     test_writeable_pbo(pbo, File::open("tests/3den.pbo").unwrap());
 }
+
+#[test]
+fn ace_weather_write() {
+    // The streamed writer has to reproduce the PBO byte for byte, its trailing
+    // hash is what the PBO is signed with
+    let original = std::fs::read("tests/ace_weather.pbo").unwrap();
+    let pbo = ReadablePbo::from(File::open("tests/ace_weather.pbo").unwrap()).unwrap();
+    let mut writeable: WritablePbo<std::io::Cursor<Box<[u8]>>> = pbo.into();
+    let checksum = vec![
+        210, 213, 255, 98, 5, 201, 111, 118, 217, 52, 219, 91, 163, 179, 230, 89, 98, 139, 31, 78,
+    ];
+    assert_eq!(writeable.checksum().unwrap(), checksum);
+    let mut written = Vec::new();
+    writeable.write(&mut written).unwrap();
+    assert_eq!(&written[written.len() - 20..], checksum.as_slice());
+    assert_eq!(written, original);
+}
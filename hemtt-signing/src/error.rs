@@ -0,0 +1,58 @@
+#[derive(Debug)]
+pub enum BISignError {
+    /// The signature was made by a different key
+    AuthorityMismatch {
+        signed: String,
+        key: String,
+    },
+    /// The PBO does not match the signature
+    HashMismatch {
+        index: u8,
+    },
+    /// The PBO is missing its checksum
+    MissingChecksum,
+    /// The key or signature has a length that is not a usable RSA key length
+    InvalidLength(u32),
+    UnknownVersion(u32),
+
+    // Wrappers
+    IO(std::io::Error),
+    OpenSSL(openssl::error::ErrorStack),
+}
+
+impl std::fmt::Display for BISignError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Self::AuthorityMismatch {
+                ref signed,
+                ref key,
+            } => write!(
+                f,
+                "Signature was made by `{}`, the key is `{}`",
+                signed, key
+            ),
+            Self::HashMismatch { index } => {
+                write!(f, "Hash {} does not match the signature", index)
+            }
+            Self::MissingChecksum => write!(f, "The PBO has no checksum"),
+            Self::InvalidLength(length) => write!(f, "Invalid key length: {} bits", length),
+            Self::UnknownVersion(v) => write!(f, "Unknown BISign version: {}", v),
+            Self::IO(ref e) => write!(f, "IO error: {}", e),
+            Self::OpenSSL(ref e) => write!(f, "OpenSSL error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BISignError {}
+
+impl From<std::io::Error> for BISignError {
+    fn from(err: std::io::Error) -> Self {
+        Self::IO(err)
+    }
+}
+
+impl From<openssl::error::ErrorStack> for BISignError {
+    fn from(err: openssl::error::ErrorStack) -> Self {
+        Self::OpenSSL(err)
+    }
+}
@@ -0,0 +1,210 @@
+use std::io::{Read, Seek, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use hemtt_pbo::ReadablePbo;
+use openssl::bn::BigNum;
+use openssl::hash::{DigestBytes, Hasher, MessageDigest};
+
+use crate::{BISignError, BISignVersion};
+
+/// Files hashed by a v2 signature are everything except these
+const V2_EXCLUDED: [&str; 13] = [
+    "paa", "jpg", "p3d", "tga", "rvmat", "lip", "ogg", "wss", "png", "rtm", "pac", "fxy", "wrp",
+];
+/// Files hashed by a v3 signature
+const V3_INCLUDED: [&str; 10] = [
+    "sqf", "inc", "bikb", "ext", "fsm", "sqm", "hpp", "cfg", "sqs", "h",
+];
+
+/// Generates the three padded hashes a signature is made of
+pub fn generate_hashes<I: Seek + Read>(
+    pbo: &mut ReadablePbo<I>,
+    version: BISignVersion,
+    length: u32,
+) -> Result<(BigNum, BigNum, BigNum), BISignError> {
+    let checksum = pbo.checksum().ok_or(BISignError::MissingChecksum)?;
+    let namehash = namehash(pbo)?;
+    let prefix = pbo.extension("prefix").cloned();
+
+    let mut h = Hasher::new(MessageDigest::sha1())?;
+    h.update(&checksum)?;
+    h.update(&namehash)?;
+    update_prefix(&mut h, prefix.as_deref())?;
+    let hash2 = h.finish()?;
+
+    let mut h = Hasher::new(MessageDigest::sha1())?;
+    h.update(&filehash(pbo, version)?)?;
+    h.update(&namehash)?;
+    update_prefix(&mut h, prefix.as_deref())?;
+    let hash3 = h.finish()?;
+
+    let size = (length / 8) as usize;
+    Ok((
+        pad_hash(&checksum, size)?,
+        pad_hash(&hash2, size)?,
+        pad_hash(&hash3, size)?,
+    ))
+}
+
+fn update_prefix(h: &mut Hasher, prefix: Option<&str>) -> Result<(), BISignError> {
+    if let Some(prefix) = prefix {
+        h.update(prefix.as_bytes())?;
+        if !prefix.ends_with('\\') {
+            h.update(b"\\")?;
+        }
+    }
+    Ok(())
+}
+
+/// Hash of the lowercase names of all non-empty files, in alphabetical order
+fn namehash<I: Seek + Read>(pbo: &mut ReadablePbo<I>) -> Result<DigestBytes, BISignError> {
+    let mut names = pbo
+        .files()
+        .iter()
+        .filter(|header| header.size() > 0)
+        .map(|header| header.filename().to_lowercase())
+        .collect::<Vec<_>>();
+    names.sort();
+    let mut h = Hasher::new(MessageDigest::sha1())?;
+    for name in names {
+        h.update(name.as_bytes())?;
+    }
+    Ok(h.finish()?)
+}
+
+/// Hash of the contents of the files covered by the signature version,
+/// in the alphabetical order of their lowercase names like the BI tools
+fn filehash<I: Seek + Read>(
+    pbo: &mut ReadablePbo<I>,
+    version: BISignVersion,
+) -> Result<DigestBytes, BISignError> {
+    let mut h = Hasher::new(MessageDigest::sha1())?;
+    let mut nothing = true;
+    let mut headers = pbo.files();
+    headers.sort_by_key(|header| header.filename().to_lowercase());
+    for header in headers {
+        let ext = header
+            .filename()
+            .rsplit('.')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        let hashed = match version {
+            BISignVersion::V2 => !V2_EXCLUDED.contains(&ext.as_str()),
+            BISignVersion::V3 => V3_INCLUDED.contains(&ext.as_str()),
+        };
+        if !hashed {
+            continue;
+        }
+        if let Some(cursor) = pbo.retrieve(header.filename()) {
+            h.update(cursor.get_ref())?;
+            nothing = false;
+        }
+    }
+    if nothing {
+        h.update(match version {
+            BISignVersion::V2 => b"nothing",
+            BISignVersion::V3 => b"gnihton",
+        })?;
+    }
+    Ok(h.finish()?)
+}
+
+/// PKCS#1 v1.5 padding of a SHA1 hash
+fn pad_hash(hash: &[u8], size: usize) -> Result<BigNum, BISignError> {
+    if size < 36 + 11 {
+        return Err(BISignError::InvalidLength(size as u32 * 8));
+    }
+    let mut vec: Vec<u8> = vec![0, 1];
+    vec.resize(size - 36, 255);
+    vec.extend(b"\x00\x30\x21\x30\x09\x06\x05\x2b");
+    vec.extend(b"\x0e\x03\x02\x1a\x05\x00\x04\x14");
+    vec.extend(hash);
+    Ok(BigNum::from_slice(&vec)?)
+}
+
+/// Reads a little endian number of `size` bytes
+pub fn read_bignum<I: Read>(input: &mut I, size: usize) -> Result<BigNum, BISignError> {
+    let mut buffer = vec![0; size];
+    input.read_exact(&mut buffer)?;
+    buffer.reverse();
+    Ok(BigNum::from_slice(&buffer)?)
+}
+
+/// Writes a number as `size` little endian bytes
+pub fn write_bignum<O: Write>(output: &mut O, bn: &BigNum, size: usize) -> Result<(), BISignError> {
+    let mut vec = bn.to_vec();
+    vec.reverse();
+    vec.resize(size, 0);
+    output.write_all(&vec)?;
+    Ok(())
+}
+
+/// Reads the `RSA1`/`RSA2` blob header shared by keys and signatures,
+/// returning the key length and exponent
+pub fn read_blob_header<I: Read>(input: &mut I) -> Result<(u32, u32), BISignError> {
+    // block length, blob type & magic
+    input.read_u32::<LittleEndian>()?;
+    input.read_u32::<LittleEndian>()?;
+    input.read_u32::<LittleEndian>()?;
+    input.read_u32::<LittleEndian>()?;
+    let length = input.read_u32::<LittleEndian>()?;
+    let exponent = input.read_u32::<LittleEndian>()?;
+    if !(512..=16384).contains(&length) || length % 16 != 0 {
+        return Err(BISignError::InvalidLength(length));
+    }
+    Ok((length, exponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use hemtt_pbo::ReadablePbo;
+    use openssl::hash::{hash, MessageDigest};
+
+    use crate::BISignVersion;
+
+    /// A PBO with the files in the given order, the PBO writer always sorts them
+    fn pbo(files: &[(&str, &[u8])]) -> ReadablePbo<Cursor<Vec<u8>>> {
+        let mut data = Vec::new();
+        data.push(0);
+        data.write_all(b"sreV").unwrap();
+        data.write_all(&[0; 16]).unwrap();
+        data.write_all(b"prefix\0z\\test\0\0").unwrap();
+        for (name, content) in files {
+            data.write_all(name.as_bytes()).unwrap();
+            data.write_all(&[0; 17]).unwrap();
+            data.write_all(&(content.len() as u32).to_le_bytes())
+                .unwrap();
+        }
+        data.write_all(&[0; 21]).unwrap();
+        for (_, content) in files {
+            data.write_all(content).unwrap();
+        }
+        let checksum = hash(MessageDigest::sha1(), &data).unwrap();
+        data.push(0);
+        data.write_all(&checksum).unwrap();
+        ReadablePbo::from(Cursor::new(data)).unwrap()
+    }
+
+    #[test]
+    fn filehash_sorted() {
+        let files: [(&str, &[u8]); 3] = [
+            ("script.sqf", b"hint 'b'"),
+            ("b.hpp", b"#define B"),
+            ("A.sqf", b"hint 'a'"),
+        ];
+        let mut expected = Vec::new();
+        expected.extend_from_slice(files[2].1);
+        expected.extend_from_slice(files[1].1);
+        expected.extend_from_slice(files[0].1);
+        let expected = hash(MessageDigest::sha1(), &expected).unwrap();
+        let mut unsorted = pbo(&files);
+        assert!(!unsorted.is_sorted());
+        assert_eq!(
+            &*super::filehash(&mut unsorted, BISignVersion::V3).unwrap(),
+            &*expected
+        );
+    }
+}
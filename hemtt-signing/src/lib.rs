@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate log;
+
+mod error;
+pub use error::BISignError;
+
+mod hash;
+
+mod private;
+pub use private::BIPrivateKey;
+
+mod public;
+pub use public::BIPublicKey;
+
+mod signature;
+pub use signature::{BISign, BISignVersion};
@@ -0,0 +1,129 @@
+use std::io::{Read, Seek, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use hemtt_io::{ReadExt, WriteExt};
+use hemtt_pbo::ReadablePbo;
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::rsa::Rsa;
+
+use crate::hash::{generate_hashes, read_bignum, read_blob_header, write_bignum};
+use crate::{BIPublicKey, BISign, BISignError, BISignVersion};
+
+/// A private key used to sign PBOs, stored as a `.biprivatekey`
+pub struct BIPrivateKey {
+    name: String,
+    length: u32,
+    exponent: u32,
+    n: BigNum,
+    p: BigNum,
+    q: BigNum,
+    dp: BigNum,
+    dq: BigNum,
+    qinv: BigNum,
+    d: BigNum,
+}
+
+impl BIPrivateKey {
+    /// Generate a new private key
+    ///
+    /// Arguments:
+    /// * `length`: length of the key in bits, Arma uses 1024
+    /// * `name`: name of the authority
+    pub fn generate<S: Into<String>>(length: u32, name: S) -> Result<Self, BISignError> {
+        let rsa = Rsa::generate(length)?;
+        Ok(Self {
+            name: name.into(),
+            length,
+            exponent: 65537,
+            n: rsa.n().to_owned()?,
+            p: rsa.p().unwrap().to_owned()?,
+            q: rsa.q().unwrap().to_owned()?,
+            dp: rsa.dmp1().unwrap().to_owned()?,
+            dq: rsa.dmq1().unwrap().to_owned()?,
+            qinv: rsa.iqmp().unwrap().to_owned()?,
+            d: rsa.d().to_owned()?,
+        })
+    }
+
+    /// Read a `.biprivatekey`
+    pub fn read<I: Read>(input: &mut I) -> Result<Self, BISignError> {
+        let name = input.read_cstring()?;
+        let (length, exponent) = read_blob_header(input)?;
+        let half = (length / 16) as usize;
+        let full = (length / 8) as usize;
+        Ok(Self {
+            name,
+            length,
+            exponent,
+            n: read_bignum(input, full)?,
+            p: read_bignum(input, half)?,
+            q: read_bignum(input, half)?,
+            dp: read_bignum(input, half)?,
+            dq: read_bignum(input, half)?,
+            qinv: read_bignum(input, half)?,
+            d: read_bignum(input, full)?,
+        })
+    }
+
+    /// Write the `.biprivatekey`
+    pub fn write<O: Write>(&self, output: &mut O) -> Result<(), BISignError> {
+        let half = (self.length / 16) as usize;
+        let full = (self.length / 8) as usize;
+        output.write_cstring(&self.name)?;
+        output.write_u32::<LittleEndian>(self.length / 16 * 9 + 20)?;
+        output.write_all(b"\x07\x02\x00\x00\x00\x24\x00\x00")?;
+        output.write_all(b"RSA2")?;
+        output.write_u32::<LittleEndian>(self.length)?;
+        output.write_u32::<LittleEndian>(self.exponent)?;
+        write_bignum(output, &self.n, full)?;
+        write_bignum(output, &self.p, half)?;
+        write_bignum(output, &self.q, half)?;
+        write_bignum(output, &self.dp, half)?;
+        write_bignum(output, &self.dq, half)?;
+        write_bignum(output, &self.qinv, half)?;
+        write_bignum(output, &self.d, full)?;
+        Ok(())
+    }
+
+    /// Name of the authority
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The public key matching this private key
+    pub fn to_public_key(&self) -> Result<BIPublicKey, BISignError> {
+        Ok(BIPublicKey::new(
+            self.name.clone(),
+            self.length,
+            self.exponent,
+            self.n.to_owned()?,
+        ))
+    }
+
+    /// Sign a PBO
+    pub fn sign<I: Seek + Read>(
+        &self,
+        pbo: &mut ReadablePbo<I>,
+        version: BISignVersion,
+    ) -> Result<BISign, BISignError> {
+        let (hash1, hash2, hash3) = generate_hashes(pbo, version, self.length)?;
+        trace!("signing with `{}` as {:?}", self.name, version);
+
+        let mut ctx = BigNumContext::new()?;
+        let mut sig1 = BigNum::new()?;
+        sig1.mod_exp(&hash1, &self.d, &self.n, &mut ctx)?;
+        let mut sig2 = BigNum::new()?;
+        sig2.mod_exp(&hash2, &self.d, &self.n, &mut ctx)?;
+        let mut sig3 = BigNum::new()?;
+        sig3.mod_exp(&hash3, &self.d, &self.n, &mut ctx)?;
+
+        Ok(BISign::new(
+            version,
+            self.name.clone(),
+            self.length,
+            self.exponent,
+            self.n.to_owned()?,
+            [sig1, sig2, sig3],
+        ))
+    }
+}
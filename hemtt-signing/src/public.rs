@@ -0,0 +1,89 @@
+use std::io::{Read, Seek, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use hemtt_io::{ReadExt, WriteExt};
+use hemtt_pbo::ReadablePbo;
+use openssl::bn::{BigNum, BigNumContext};
+
+use crate::hash::{generate_hashes, read_bignum, read_blob_header, write_bignum};
+use crate::{BISign, BISignError};
+
+/// A public key used by servers to verify PBOs, stored as a `.bikey`
+pub struct BIPublicKey {
+    name: String,
+    length: u32,
+    exponent: u32,
+    n: BigNum,
+}
+
+impl BIPublicKey {
+    pub(crate) fn new(name: String, length: u32, exponent: u32, n: BigNum) -> Self {
+        Self {
+            name,
+            length,
+            exponent,
+            n,
+        }
+    }
+
+    /// Read a `.bikey`
+    pub fn read<I: Read>(input: &mut I) -> Result<Self, BISignError> {
+        let name = input.read_cstring()?;
+        let (length, exponent) = read_blob_header(input)?;
+        let n = read_bignum(input, (length / 8) as usize)?;
+        Ok(Self::new(name, length, exponent, n))
+    }
+
+    /// Write the `.bikey`
+    pub fn write<O: Write>(&self, output: &mut O) -> Result<(), BISignError> {
+        output.write_cstring(&self.name)?;
+        output.write_u32::<LittleEndian>(self.length / 8 + 20)?;
+        output.write_all(b"\x06\x02\x00\x00\x00\x24\x00\x00")?;
+        output.write_all(b"RSA1")?;
+        output.write_u32::<LittleEndian>(self.length)?;
+        output.write_u32::<LittleEndian>(self.exponent)?;
+        write_bignum(output, &self.n, (self.length / 8) as usize)?;
+        Ok(())
+    }
+
+    /// Name of the authority
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The signature was made by the private key matching this key
+    pub fn matches(&self, sig: &BISign) -> bool {
+        self.length == sig.length() && self.n == *sig.n()
+    }
+
+    /// Verify that a signature was made by this key for the PBO
+    pub fn verify<I: Seek + Read>(
+        &self,
+        pbo: &mut ReadablePbo<I>,
+        sig: &BISign,
+    ) -> Result<(), BISignError> {
+        if !self.matches(sig) {
+            return Err(BISignError::AuthorityMismatch {
+                signed: sig.name().to_string(),
+                key: self.name.clone(),
+            });
+        }
+        let (hash1, hash2, hash3) = generate_hashes(pbo, sig.version(), self.length)?;
+        let exponent = BigNum::from_u32(self.exponent)?;
+        let mut ctx = BigNumContext::new()?;
+        for (index, (expected, signed)) in [hash1, hash2, hash3]
+            .iter()
+            .zip(sig.signatures().iter())
+            .enumerate()
+        {
+            let mut hash = BigNum::new()?;
+            hash.mod_exp(signed, &exponent, &self.n, &mut ctx)?;
+            if hash != *expected {
+                return Err(BISignError::HashMismatch {
+                    index: index as u8 + 1,
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use std::io::{Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use hemtt_io::{ReadExt, WriteExt};
+use openssl::bn::BigNum;
+
+use crate::hash::{read_bignum, read_blob_header, write_bignum};
+use crate::BISignError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BISignVersion {
+    V2 = 2,
+    V3 = 3,
+}
+
+impl BISignVersion {
+    pub fn from_u32(version: u32) -> Result<Self, BISignError> {
+        match version {
+            2 => Ok(Self::V2),
+            3 => Ok(Self::V3),
+            _ => Err(BISignError::UnknownVersion(version)),
+        }
+    }
+}
+
+/// The signature of a PBO, stored as a `.bisign`
+pub struct BISign {
+    version: BISignVersion,
+    name: String,
+    length: u32,
+    exponent: u32,
+    n: BigNum,
+    signatures: [BigNum; 3],
+}
+
+impl BISign {
+    pub(crate) fn new(
+        version: BISignVersion,
+        name: String,
+        length: u32,
+        exponent: u32,
+        n: BigNum,
+        signatures: [BigNum; 3],
+    ) -> Self {
+        Self {
+            version,
+            name,
+            length,
+            exponent,
+            n,
+            signatures,
+        }
+    }
+
+    /// Read a `.bisign`
+    pub fn read<I: Read>(input: &mut I) -> Result<Self, BISignError> {
+        let name = input.read_cstring()?;
+        let (length, exponent) = read_blob_header(input)?;
+        let size = (length / 8) as usize;
+        let n = read_bignum(input, size)?;
+        input.read_u32::<LittleEndian>()?;
+        let sig1 = read_bignum(input, size)?;
+        let version = BISignVersion::from_u32(input.read_u32::<LittleEndian>()?)?;
+        input.read_u32::<LittleEndian>()?;
+        let sig2 = read_bignum(input, size)?;
+        input.read_u32::<LittleEndian>()?;
+        let sig3 = read_bignum(input, size)?;
+        Ok(Self::new(
+            version,
+            name,
+            length,
+            exponent,
+            n,
+            [sig1, sig2, sig3],
+        ))
+    }
+
+    /// Write the `.bisign`
+    pub fn write<O: Write>(&self, output: &mut O) -> Result<(), BISignError> {
+        let size = (self.length / 8) as usize;
+        output.write_cstring(&self.name)?;
+        output.write_u32::<LittleEndian>(self.length / 8 + 20)?;
+        output.write_all(b"\x06\x02\x00\x00\x00\x24\x00\x00")?;
+        output.write_all(b"RSA1")?;
+        output.write_u32::<LittleEndian>(self.length)?;
+        output.write_u32::<LittleEndian>(self.exponent)?;
+        write_bignum(output, &self.n, size)?;
+        output.write_u32::<LittleEndian>(self.length / 8)?;
+        write_bignum(output, &self.signatures[0], size)?;
+        output.write_u32::<LittleEndian>(self.version as u32)?;
+        output.write_u32::<LittleEndian>(self.length / 8)?;
+        write_bignum(output, &self.signatures[1], size)?;
+        output.write_u32::<LittleEndian>(self.length / 8)?;
+        write_bignum(output, &self.signatures[2], size)?;
+        Ok(())
+    }
+
+    /// Name of the authority that made the signature
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> BISignVersion {
+        self.version
+    }
+
+    pub(crate) fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub(crate) fn n(&self) -> &BigNum {
+        &self.n
+    }
+
+    pub(crate) fn signatures(&self) -> &[BigNum; 3] {
+        &self.signatures
+    }
+}
@@ -0,0 +1,58 @@
+use std::io::Cursor;
+
+use hemtt_pbo::{ReadablePbo, WritablePbo};
+use hemtt_signing::{BIPrivateKey, BIPublicKey, BISign, BISignVersion};
+
+fn pbo(script: &[u8]) -> ReadablePbo<Cursor<Vec<u8>>> {
+    let mut pbo = WritablePbo::<Cursor<Vec<u8>>>::new();
+    pbo.add_extension("prefix", "z\\test\\addons\\main");
+    pbo.add_file("config.cpp", Cursor::new(b"class CfgPatches {};".to_vec()))
+        .unwrap();
+    pbo.add_file("script.sqf", Cursor::new(script.to_vec()))
+        .unwrap();
+    let mut buffer = Vec::new();
+    pbo.write(&mut buffer).unwrap();
+    ReadablePbo::from(Cursor::new(buffer)).unwrap()
+}
+
+#[test]
+fn sign_and_verify() {
+    let private = BIPrivateKey::generate(1024, "test").unwrap();
+    let public = private.to_public_key().unwrap();
+    let sig = private
+        .sign(&mut pbo(b"hint 'a'"), BISignVersion::V3)
+        .unwrap();
+    assert!(public.verify(&mut pbo(b"hint 'a'"), &sig).is_ok());
+    assert!(public.verify(&mut pbo(b"hint 'b'"), &sig).is_err());
+}
+
+#[test]
+fn roundtrip() {
+    let private = BIPrivateKey::generate(1024, "test").unwrap();
+    let mut buffer = Vec::new();
+    private.write(&mut buffer).unwrap();
+    let private = BIPrivateKey::read(&mut Cursor::new(buffer)).unwrap();
+
+    let mut buffer = Vec::new();
+    private.to_public_key().unwrap().write(&mut buffer).unwrap();
+    let public = BIPublicKey::read(&mut Cursor::new(buffer)).unwrap();
+    assert_eq!(public.name(), "test");
+
+    let mut buffer = Vec::new();
+    private
+        .sign(&mut pbo(b""), BISignVersion::V2)
+        .unwrap()
+        .write(&mut buffer)
+        .unwrap();
+    let sig = BISign::read(&mut Cursor::new(buffer)).unwrap();
+    assert_eq!(sig.version(), BISignVersion::V2);
+    assert!(public.verify(&mut pbo(b""), &sig).is_ok());
+}
+
+#[test]
+fn other_authority() {
+    let first = BIPrivateKey::generate(1024, "first").unwrap();
+    let second = BIPrivateKey::generate(1024, "second").unwrap();
+    let sig = first.sign(&mut pbo(b""), BISignVersion::V3).unwrap();
+    assert!(!second.to_public_key().unwrap().matches(&sig));
+}
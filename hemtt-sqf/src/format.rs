@@ -0,0 +1,393 @@
+use std::ops::Range;
+
+use hemtt::{BraceStyle, Formatting};
+
+use crate::token::{tokenize, Token};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Sqf,
+    Config,
+}
+
+/// Formats SQF source, line endings of the source are kept
+pub fn format(source: &str, options: &Formatting) -> String {
+    format_kind(source, options, Kind::Sqf)
+}
+
+/// Formats a config or a header included by one, line endings of the source are kept
+///
+/// Arrays on lines longer than `line_width` are wrapped and arguments of macros
+/// are not spaced, as the spaces would become part of the arguments.
+pub fn format_config(source: &str, options: &Formatting) -> String {
+    format_kind(source, options, Kind::Config)
+}
+
+fn format_kind(source: &str, options: &Formatting, kind: Kind) -> String {
+    let mut tokens = tokenize(source);
+    match options.brace_style {
+        BraceStyle::Kr => join_braces(&mut tokens),
+        BraceStyle::Allman => split_braces(&mut tokens),
+    }
+    let mut lines = layout(&tokens, options, kind);
+    if kind == Kind::Config {
+        while let Some(array) = lines
+            .iter()
+            .filter(|(line, _)| line.chars().count() > options.line_width)
+            .find_map(|(_, range)| inline_array(&tokens, range.clone()))
+        {
+            wrap(&mut tokens, array);
+            lines = layout(&tokens, options, kind);
+        }
+    }
+
+    let mut out = String::new();
+    if source.starts_with('\u{FEFF}') {
+        out.push('\u{FEFF}');
+    }
+    let mut blank = 0;
+    for (line, _) in lines {
+        if line.is_empty() {
+            blank += 1;
+            continue;
+        }
+        if !out.trim_start_matches('\u{FEFF}').is_empty() {
+            out.push_str(&"\n".repeat(blank.min(options.blank_lines)));
+        }
+        blank = 0;
+        out.push_str(&line);
+        out.push('\n');
+    }
+    if source.contains("\r\n") {
+        out.replace('\n', "\r\n")
+    } else {
+        out
+    }
+}
+
+/// Indents and spaces each line, with the tokens it was made from
+fn layout(
+    tokens: &[(Token, bool)],
+    options: &Formatting,
+    kind: Kind,
+) -> Vec<(String, Range<usize>)> {
+    let unary = unary(tokens);
+    let mut lines = Vec::new();
+    // Open brackets and whether they indent the lines after them
+    let mut stack: Vec<(bool, usize)> = Vec::new();
+    let mut parens = 0;
+    let mut line = String::new();
+    let mut start = 0;
+    let mut indent = None;
+    let mut prev: Option<usize> = None;
+    let mut number = 0;
+    for (i, (token, space)) in tokens.iter().enumerate() {
+        match token {
+            Token::Newline => {
+                let level = indent.unwrap_or_else(|| stack.iter().filter(|(i, _)| *i).count());
+                lines.push((finish(&line, level, options), start..i));
+                line.clear();
+                start = i + 1;
+                indent = None;
+                prev = None;
+                number += 1;
+                continue;
+            }
+            Token::Close(c) => {
+                stack.pop();
+                if *c == ')' && parens > 0 {
+                    parens -= 1;
+                }
+            }
+            Token::Directive(_) => {
+                // Directives are never indented
+                indent = Some(0);
+            }
+            _ => {}
+        }
+        if indent.is_none() && !matches!(token, Token::Close(_)) {
+            indent = Some(stack.iter().filter(|(i, _)| *i).count());
+        }
+        if let Some(p) = prev {
+            let macro_argument = kind == Kind::Config && parens > 0;
+            if macro_argument && tokens[p].0 == Token::Comma {
+                // Spaces would become part of the argument
+            } else if spaced(&tokens[p].0, unary[p], token, *space, unary[i], options) {
+                line.push(' ');
+            }
+        }
+        if let Token::Open(c) = token {
+            let indents = !stack.iter().any(|(i, l)| *i && *l == number);
+            stack.push((indents, number));
+            if *c == '(' {
+                parens += 1;
+            }
+        }
+        line.push_str(&token.to_string());
+        prev = Some(i);
+    }
+    let level = indent.unwrap_or_else(|| stack.iter().filter(|(i, _)| *i).count());
+    lines.push((finish(&line, level, options), start..tokens.len()));
+    lines
+}
+
+fn finish(line: &str, level: usize, options: &Formatting) -> String {
+    let line = line.trim_end();
+    if line.is_empty() {
+        String::new()
+    } else {
+        format!("{}{}", options.indentation(level), line)
+    }
+}
+
+/// The `{` and `}` of an array that is opened and closed within the tokens
+fn inline_array(tokens: &[(Token, bool)], range: Range<usize>) -> Option<(usize, usize)> {
+    for open in range.clone() {
+        if tokens[open].0 != Token::Open('{') || open == 0 {
+            continue;
+        }
+        match &tokens[open - 1].0 {
+            Token::Operator(op) if op == "=" || op == "+=" => {}
+            _ => continue,
+        }
+        let mut depth = 0;
+        for close in open..range.end {
+            match tokens[close].0 {
+                Token::Open(_) => depth += 1,
+                Token::Close(_) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        // Empty arrays can not be wrapped
+                        if close > open + 1 {
+                            return Some((open, close));
+                        }
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Puts each element of an array on its own line
+fn wrap(tokens: &mut Vec<(Token, bool)>, (open, close): (usize, usize)) {
+    let mut breaks = vec![open + 1, close];
+    let mut depth = 0;
+    for (i, (token, _)) in tokens.iter().enumerate().take(close).skip(open + 1) {
+        match token {
+            Token::Open(_) => depth += 1,
+            Token::Close(_) => depth -= 1,
+            Token::Comma if depth == 0 => breaks.push(i + 1),
+            _ => {}
+        }
+    }
+    breaks.sort_unstable();
+    breaks.dedup();
+    for i in breaks.into_iter().rev() {
+        tokens.insert(i, (Token::Newline, false));
+    }
+}
+
+/// Moves a `{` on its own line to the end of the line before it
+fn join_braces(tokens: &mut Vec<(Token, bool)>) {
+    let mut i = 2;
+    while i < tokens.len() {
+        if tokens[i].0 == Token::Open('{')
+            && tokens[i - 1].0 == Token::Newline
+            && opens_block(&tokens[i - 2].0)
+        {
+            tokens.remove(i - 1);
+            tokens[i - 1].1 = true;
+        }
+        i += 1;
+    }
+}
+
+/// Moves a `{` at the end of a line to its own line
+fn split_braces(tokens: &mut Vec<(Token, bool)>) {
+    let mut i = 1;
+    while i < tokens.len() {
+        let ends_line = match tokens.get(i + 1) {
+            None | Some((Token::Newline, _)) => true,
+            Some((Token::Comment(_), _)) => {
+                matches!(tokens.get(i + 2), None | Some((Token::Newline, _)))
+            }
+            _ => false,
+        };
+        if tokens[i].0 == Token::Open('{') && ends_line && opens_block(&tokens[i - 1].0) {
+            tokens.insert(i, (Token::Newline, false));
+            i += 1;
+        }
+        i += 1;
+    }
+}
+
+/// Tokens that can be followed by the `{` of a block
+fn opens_block(token: &Token) -> bool {
+    match token {
+        Token::Word(_) | Token::Close(')') => true,
+        Token::Operator(op) => op == "=",
+        _ => false,
+    }
+}
+
+/// Which `+`, `-` and `!` operators are unary
+fn unary(tokens: &[(Token, bool)]) -> Vec<bool> {
+    let mut unary = Vec::with_capacity(tokens.len());
+    let mut prev: Option<&Token> = None;
+    for (i, (token, space)) in tokens.iter().enumerate() {
+        let is_unary = match token {
+            Token::Operator(op) if op == "!" => true,
+            Token::Operator(op) if op == "-" || op == "+" => match prev {
+                None
+                | Some(Token::Operator(_))
+                | Some(Token::Open(_))
+                | Some(Token::Comma)
+                | Some(Token::Semicolon) => true,
+                // `_array select -1`, kept as written
+                Some(_) => *space && !tokens.get(i + 1).map_or(false, |(_, s)| *s),
+            },
+            _ => false,
+        };
+        unary.push(is_unary);
+        match token {
+            Token::Newline | Token::Comment(_) => {}
+            _ => prev = Some(token),
+        }
+    }
+    unary
+}
+
+/// Whether a space goes between two tokens on the same line
+fn spaced(
+    prev: &Token,
+    prev_unary: bool,
+    token: &Token,
+    space: bool,
+    token_unary: bool,
+    options: &Formatting,
+) -> bool {
+    match (prev, token) {
+        (_, Token::Comment(_)) => true,
+        (_, Token::Comma) | (_, Token::Semicolon) => false,
+        (Token::Open('{'), _) | (_, Token::Close('}')) => space,
+        (Token::Open(_), _) | (_, Token::Close(_)) => false,
+        (Token::Comma, _) | (Token::Semicolon, _) => true,
+        (_, Token::Operator(op)) if op == ":" => false,
+        (Token::Operator(op), _) if op == ":" => true,
+        (Token::Operator(_), _) if prev_unary => false,
+        (_, Token::Operator(_)) if token_unary => true,
+        (_, Token::Operator(_)) | (Token::Operator(_), _) => options.operator_spacing || space,
+        (Token::Word(_), Token::Open('(')) | (Token::Word(_), Token::Open('[')) => space,
+        (_, Token::Open('{')) => true,
+        (Token::Close(_), Token::Word(_))
+        | (Token::Close(_), Token::Number(_))
+        | (Token::Close(_), Token::Str(_)) => true,
+        _ => space,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hemtt::{BraceStyle, Formatting};
+
+    use super::{format, format_config};
+
+    #[test]
+    fn indentation() {
+        assert_eq!(
+            format(
+                "if (_a) then {\n_b = [\n1,\n2\n];\n} else {\n  _c = 1;\n};",
+                &Formatting::default()
+            ),
+            "if (_a) then {\n    _b = [\n        1,\n        2\n    ];\n} else {\n    _c = 1;\n};\n"
+        );
+    }
+
+    #[test]
+    fn spacing() {
+        assert_eq!(
+            format(
+                "_a=_b+-1;_c = !_d&&(_e>=2) ;_f = [1,2] select -1; _g = GVAR(x) ;",
+                &Formatting::default()
+            ),
+            "_a = _b + -1; _c = !_d && (_e >= 2); _f = [1, 2] select -1; _g = GVAR(x);\n"
+        );
+        assert_eq!(
+            format(
+                "_a=_b+1;",
+                &Formatting {
+                    operator_spacing: false,
+                    ..Formatting::default()
+                }
+            ),
+            "_a=_b+1;\n"
+        );
+    }
+
+    #[test]
+    fn braces() {
+        assert_eq!(
+            format("if (_a) then\n{\n_b = 1;\n};", &Formatting::default()),
+            "if (_a) then {\n    _b = 1;\n};\n"
+        );
+        assert_eq!(
+            format(
+                "if (_a) then {\n_b = 1;\n};",
+                &Formatting {
+                    brace_style: BraceStyle::Allman,
+                    tabs: true,
+                    ..Formatting::default()
+                }
+            ),
+            "if (_a) then\n{\n\t_b = 1;\n};\n"
+        );
+    }
+
+    #[test]
+    fn preserved() {
+        let source = "#include \"script_component.hpp\"\n\n\n\n// comment  \nparams [\"_unit\"];\r\n\n{ _x setDamage 0 } forEach units _unit; /* done */\n";
+        assert_eq!(
+            format(source, &Formatting::default()),
+            "#include \"script_component.hpp\"\r\n\r\n// comment\r\nparams [\"_unit\"];\r\n\r\n{ _x setDamage 0 } forEach units _unit; /* done */\r\n"
+        );
+    }
+
+    #[test]
+    fn byte_order_mark() {
+        assert_eq!(
+            format(
+                "\u{FEFF}#include \"script_component.hpp\"\n\nparams [\"_unit\"];",
+                &Formatting::default()
+            ),
+            "\u{FEFF}#include \"script_component.hpp\"\n\nparams [\"_unit\"];\n"
+        );
+    }
+
+    #[test]
+    fn config() {
+        assert_eq!(
+            format_config(
+                "class CfgVehicles {\nclass Car;\nclass MyCar : Car\n{\nscope=2;\nitems[]={\"a\",\"b\"};\nstatement = QUOTE([ARR_2(_a, _b)] call FUNC(x));\n};\n};",
+                &Formatting::default()
+            ),
+            "class CfgVehicles {\n    class Car;\n    class MyCar: Car {\n        scope = 2;\n        items[] = {\"a\", \"b\"};\n        statement = QUOTE([ARR_2(_a,_b)] call FUNC(x));\n    };\n};\n"
+        );
+    }
+
+    #[test]
+    fn config_wrapping() {
+        assert_eq!(
+            format_config(
+                "magazines[] += {\"a\", {\"b\", \"c\"}, \"d\"};",
+                &Formatting {
+                    line_width: 20,
+                    ..Formatting::default()
+                }
+            ),
+            "magazines[] += {\n    \"a\",\n    {\"b\", \"c\"},\n    \"d\"\n};\n"
+        );
+    }
+}
@@ -0,0 +1,4 @@
+mod format;
+mod token;
+
+pub use format::{format, format_config};
@@ -0,0 +1,223 @@
+/// A piece of SQF source
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    /// Commands, variables and keywords
+    Word(String),
+    Number(String),
+    /// A string, including its quotes
+    Str(String),
+    Operator(String),
+    Open(char),
+    Close(char),
+    Comma,
+    Semicolon,
+    /// A comment, `//` comments do not include the end of the line
+    Comment(String),
+    /// A preprocessor directive, including lines continued with `\`
+    Directive(String),
+    Newline,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Word(s)
+            | Self::Number(s)
+            | Self::Str(s)
+            | Self::Operator(s)
+            | Self::Comment(s)
+            | Self::Directive(s) => write!(f, "{}", s),
+            Self::Open(c) | Self::Close(c) => write!(f, "{}", c),
+            Self::Comma => write!(f, ","),
+            Self::Semicolon => write!(f, ";"),
+            Self::Newline => writeln!(f),
+        }
+    }
+}
+
+/// Operators made of symbols, longest first
+const OPERATORS: [&str; 19] = [
+    "==", "!=", ">=", "<=", "&&", "||", ">>", "+=", "=", ">", "<", "!", "+", "-", "*", "/", "%",
+    "^", ":",
+];
+
+/// Splits source into tokens, each with whether whitespace preceded it
+pub fn tokenize(source: &str) -> Vec<(Token, bool)> {
+    let chars: Vec<char> = source.replace("\r\n", "\n").chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut space = false;
+    let mut line_start = true;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let token = match c {
+            '\n' => {
+                i += 1;
+                Token::Newline
+            }
+            ' ' | '\t' | '\r' => {
+                i += 1;
+                space = true;
+                continue;
+            }
+            // A byte order mark is kept by the formatter, it does not start the line
+            '\u{FEFF}' if i == 0 => {
+                i += 1;
+                continue;
+            }
+            '#' if line_start => {
+                while i < chars.len() && (chars[i] != '\n' || chars[i - 1] == '\\') {
+                    i += 1;
+                }
+                Token::Directive(collect(&chars[start..i]).trim_end().to_string())
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                Token::Comment(collect(&chars[start..i]).trim_end().to_string())
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '/' && chars[i - 1] == '*' && i > start + 2)
+                {
+                    i += 1;
+                }
+                i = (i + 1).min(chars.len());
+                Token::Comment(collect(&chars[start..i]))
+            }
+            '"' | '\'' => {
+                i += 1;
+                loop {
+                    if i >= chars.len() {
+                        break;
+                    }
+                    if chars[i] == c {
+                        // Quotes are escaped by doubling them
+                        if chars.get(i + 1) == Some(&c) {
+                            i += 2;
+                            continue;
+                        }
+                        i += 1;
+                        break;
+                    }
+                    i += 1;
+                }
+                Token::Str(collect(&chars[start..i]))
+            }
+            '(' | '[' | '{' => {
+                i += 1;
+                Token::Open(c)
+            }
+            ')' | ']' | '}' => {
+                i += 1;
+                Token::Close(c)
+            }
+            ',' => {
+                i += 1;
+                Token::Comma
+            }
+            ';' => {
+                i += 1;
+                Token::Semicolon
+            }
+            c if c.is_ascii_digit()
+                || (c == '.' && chars.get(i + 1).map_or(false, char::is_ascii_digit))
+                || (c == '$' && chars.get(i + 1).map_or(false, char::is_ascii_hexdigit)) =>
+            {
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric()
+                        || chars[i] == '.'
+                        || ((chars[i] == '-' || chars[i] == '+')
+                            && matches!(chars[i - 1], 'e' | 'E')
+                            && !collect(&chars[start..i]).starts_with("0x")))
+                {
+                    i += 1;
+                }
+                Token::Number(collect(&chars[start..i]))
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                Token::Word(collect(&chars[start..i]))
+            }
+            _ => {
+                let rest = collect(&chars[i..(i + 2).min(chars.len())]);
+                let op = OPERATORS
+                    .iter()
+                    .find(|op| rest.starts_with(*op))
+                    .map_or_else(|| c.to_string(), |op| op.to_string());
+                i += op.chars().count();
+                Token::Operator(op)
+            }
+        };
+        line_start = token == Token::Newline;
+        tokens.push((token, space));
+        space = false;
+    }
+    tokens
+}
+
+fn collect(chars: &[char]) -> String {
+    chars.iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, Token};
+
+    fn tokens(source: &str) -> Vec<Token> {
+        tokenize(source).into_iter().map(|(t, _)| t).collect()
+    }
+
+    #[test]
+    fn statement() {
+        assert_eq!(
+            tokens("_a = [1, \"b\"\"c\"] select 0;"),
+            vec![
+                Token::Word(String::from("_a")),
+                Token::Operator(String::from("=")),
+                Token::Open('['),
+                Token::Number(String::from("1")),
+                Token::Comma,
+                Token::Str(String::from("\"b\"\"c\"")),
+                Token::Close(']'),
+                Token::Word(String::from("select")),
+                Token::Number(String::from("0")),
+                Token::Semicolon,
+            ]
+        );
+    }
+
+    #[test]
+    fn directives_and_comments() {
+        assert_eq!(
+            tokens("#define A(x) \\\n    x\n// note\n_a /* b */ >= 1e-3"),
+            vec![
+                Token::Directive(String::from("#define A(x) \\\n    x")),
+                Token::Newline,
+                Token::Comment(String::from("// note")),
+                Token::Newline,
+                Token::Word(String::from("_a")),
+                Token::Comment(String::from("/* b */")),
+                Token::Operator(String::from(">=")),
+                Token::Number(String::from("1e-3")),
+            ]
+        );
+    }
+
+    #[test]
+    fn byte_order_mark() {
+        assert_eq!(
+            tokens("\u{FEFF}#include \"script_component.hpp\"\n_a"),
+            vec![
+                Token::Directive(String::from("#include \"script_component.hpp\"")),
+                Token::Newline,
+                Token::Word(String::from("_a")),
+            ]
+        );
+    }
+}
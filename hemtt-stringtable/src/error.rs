@@ -0,0 +1,46 @@
+#[derive(Debug)]
+pub enum StringtableError {
+    /// An element was found where it is not allowed
+    Unexpected {
+        element: String,
+        position: usize,
+    },
+    /// A required attribute is missing
+    MissingAttribute {
+        element: String,
+        attribute: String,
+        position: usize,
+    },
+
+    // Wrappers
+    Xml(quick_xml::Error),
+}
+
+impl std::fmt::Display for StringtableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Self::Unexpected {
+                ref element,
+                position,
+            } => write!(f, "Unexpected `{}` at byte {}", element, position),
+            Self::MissingAttribute {
+                ref element,
+                ref attribute,
+                position,
+            } => write!(
+                f,
+                "`{}` at byte {} is missing the `{}` attribute",
+                element, position, attribute
+            ),
+            Self::Xml(ref e) => write!(f, "XML error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StringtableError {}
+
+impl From<quick_xml::Error> for StringtableError {
+    fn from(err: quick_xml::Error) -> Self {
+        Self::Xml(err)
+    }
+}
@@ -0,0 +1,297 @@
+mod error;
+pub use error::StringtableError;
+
+mod read;
+
+mod write;
+pub use write::escape;
+
+/// Languages supported by Arma 3 in the order they are written,
+/// `Original` is the text used when the game's language has no translation
+pub const LANGUAGES: [&str; 15] = [
+    "Original",
+    "English",
+    "Czech",
+    "French",
+    "Spanish",
+    "Italian",
+    "Polish",
+    "Portuguese",
+    "Russian",
+    "German",
+    "Korean",
+    "Japanese",
+    "Chinese",
+    "Chinesesimp",
+    "Turkish",
+];
+
+/// Whether Arma 3 supports a language
+pub fn is_language(language: &str) -> bool {
+    LANGUAGES.iter().any(|l| l.eq_ignore_ascii_case(language))
+}
+
+/// The supported language closest to an unsupported one, if it is likely a typo
+pub fn closest_language(language: &str) -> Option<&'static str> {
+    let language = language.to_lowercase();
+    LANGUAGES
+        .iter()
+        .map(|l| (distance(&language, &l.to_lowercase()), *l))
+        .filter(|(d, l)| *d <= 1 + l.len() / 4)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, l)| l)
+}
+
+/// Edits needed to turn one word into another
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let next = (prev + usize::from(ca != *cb))
+                .min(row[j] + 1)
+                .min(row[j + 1] + 1);
+            prev = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+/// A `stringtable.xml` file
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stringtable {
+    pub name: String,
+    pub packages: Vec<Package>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Package {
+    pub name: String,
+    pub containers: Vec<Container>,
+    pub keys: Vec<Key>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Container {
+    pub name: String,
+    pub keys: Vec<Key>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Key {
+    pub id: String,
+    pub translations: Vec<Translation>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Translation {
+    pub language: String,
+    pub text: String,
+}
+
+impl Stringtable {
+    /// Every key, from packages and their containers
+    pub fn keys(&self) -> impl Iterator<Item = &Key> {
+        self.packages.iter().flat_map(|package| {
+            package
+                .keys
+                .iter()
+                .chain(package.containers.iter().flat_map(|c| c.keys.iter()))
+        })
+    }
+
+    /// Number of keys translated into a language, ignoring case and empty translations
+    pub fn translated(&self, language: &str) -> usize {
+        self.keys()
+            .filter(|key| {
+                key.translations
+                    .iter()
+                    .any(|t| t.language.eq_ignore_ascii_case(language) && !t.text.trim().is_empty())
+            })
+            .count()
+    }
+
+    /// Sorts keys by ID and translations by language, and removes empty
+    /// translations, keys and containers
+    pub fn normalize(&mut self) {
+        for package in &mut self.packages {
+            normalize_keys(&mut package.keys);
+            for container in &mut package.containers {
+                normalize_keys(&mut container.keys);
+            }
+            package.containers.retain(|c| !c.keys.is_empty());
+        }
+    }
+}
+
+impl Stringtable {
+    /// Moves the keys of every container into its package
+    pub fn flatten(&mut self) {
+        for package in &mut self.packages {
+            for container in package.containers.drain(..) {
+                package.keys.extend(container.keys);
+            }
+        }
+    }
+
+    /// Moves the keys of every package into containers named after the part of
+    /// their ID that follows the prefix shared by all keys of the package,
+    /// `STR_Mod_Main_Settings_Enable` goes into `Settings` when the prefix is `STR_Mod_Main_`
+    ///
+    /// Keys without a part after the prefix stay in the package.
+    pub fn group(&mut self) {
+        for package in &mut self.packages {
+            let prefix = common_prefix(package.keys.iter().map(|k| k.id.as_str()));
+            let mut keys = Vec::new();
+            for key in package.keys.drain(..) {
+                let rest = &key.id[prefix.len()..];
+                let name = match rest.find('_') {
+                    Some(end) if end > 0 => rest[..end].to_string(),
+                    _ => {
+                        keys.push(key);
+                        continue;
+                    }
+                };
+                match package
+                    .containers
+                    .iter_mut()
+                    .find(|c| c.name.eq_ignore_ascii_case(&name))
+                {
+                    Some(container) => container.keys.push(key),
+                    None => package.containers.push(Container {
+                        name,
+                        keys: vec![key],
+                    }),
+                }
+            }
+            package.keys = keys;
+        }
+    }
+}
+
+/// Longest prefix shared by the IDs that ends with `_`
+fn common_prefix<'a>(mut ids: impl Iterator<Item = &'a str>) -> String {
+    let first = match ids.next() {
+        Some(first) => first.to_ascii_lowercase(),
+        None => return String::new(),
+    };
+    let mut len = first.len();
+    for id in ids {
+        len = first
+            .bytes()
+            .zip(id.to_ascii_lowercase().bytes())
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count();
+    }
+    match first.as_bytes()[..len].iter().rposition(|b| *b == b'_') {
+        Some(end) => first[..=end].to_string(),
+        None => String::new(),
+    }
+}
+
+fn normalize_keys(keys: &mut Vec<Key>) {
+    for key in keys.iter_mut() {
+        key.translations.retain(|t| !t.text.trim().is_empty());
+        key.translations.sort_by(|a, b| {
+            language_order(&a.language)
+                .cmp(&language_order(&b.language))
+                .then_with(|| a.language.cmp(&b.language))
+        });
+    }
+    keys.retain(|k| !k.translations.is_empty());
+    keys.sort_by_key(|k| k.id.to_lowercase());
+}
+
+/// Position of a language, languages Arma 3 does not support are last
+fn language_order(language: &str) -> usize {
+    LANGUAGES
+        .iter()
+        .position(|l| l.eq_ignore_ascii_case(language))
+        .unwrap_or_else(|| LANGUAGES.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{closest_language, is_language, Stringtable};
+
+    #[test]
+    fn languages() {
+        assert!(is_language("Chinesesimp"));
+        assert!(!is_language("Portugese"));
+        assert_eq!(closest_language("Portugese"), Some("Portuguese"));
+        assert_eq!(closest_language("Deutsch"), None);
+    }
+
+    #[test]
+    fn translated() {
+        let stringtable = Stringtable::read(
+            r#"<Project name="Test"><Package name="Main">
+                <Key ID="STR_Test_A"><English>A</English><German>A</German></Key>
+                <Key ID="STR_Test_B"><English>B</English><German> </German></Key>
+                <Container name="Sub"><Key ID="STR_Test_C"><English>C</English><german>C</german></Key></Container>
+            </Package></Project>"#,
+        )
+        .unwrap();
+        assert_eq!(stringtable.translated("English"), 3);
+        assert_eq!(stringtable.translated("German"), 2);
+        assert_eq!(stringtable.translated("French"), 0);
+    }
+
+    #[test]
+    fn normalize() {
+        let mut stringtable = Stringtable::read(
+            r#"<Project name="Test"><Package name="Main">
+                <Key ID="STR_Test_b"><German>B</German><English>B</English><French></French></Key>
+                <Key ID="STR_Test_A"><English>A</English></Key>
+                <Container name="Empty"><Key ID="STR_Test_C"><English> </English></Key></Container>
+            </Package></Project>"#,
+        )
+        .unwrap();
+        stringtable.normalize();
+        let keys: Vec<(&str, Vec<&str>)> = stringtable
+            .keys()
+            .map(|k| {
+                (
+                    k.id.as_str(),
+                    k.translations.iter().map(|t| t.language.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("STR_Test_A", vec!["English"]),
+                ("STR_Test_b", vec!["English", "German"])
+            ]
+        );
+        assert!(stringtable.packages[0].containers.is_empty());
+    }
+
+    #[test]
+    fn group_and_flatten() {
+        let source = r#"<Project><Package name="Main">
+            <Key ID="STR_Mod_Main_Settings_A"><English>A</English><German>A</German></Key>
+            <Key ID="STR_Mod_Main_Action_B"><English>B</English></Key>
+            <Key ID="STR_Mod_Main_Settings_C"><English>C</English></Key>
+            <Key ID="STR_Mod_Main_D"><English>D</English></Key>
+        </Package></Project>"#;
+        let mut stringtable = Stringtable::read(source).unwrap();
+        stringtable.group();
+        let package = &stringtable.packages[0];
+        assert_eq!(package.keys.len(), 1);
+        assert_eq!(package.containers.len(), 2);
+        assert_eq!(package.containers[0].name, "Settings");
+        assert_eq!(package.containers[0].keys.len(), 2);
+        assert_eq!(package.containers[0].keys[0].translations.len(), 2);
+
+        stringtable.flatten();
+        stringtable.normalize();
+        let mut original = Stringtable::read(source).unwrap();
+        original.normalize();
+        assert_eq!(stringtable, original);
+    }
+}
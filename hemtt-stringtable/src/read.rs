@@ -0,0 +1,140 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::{Container, Key, Package, Stringtable, StringtableError, Translation};
+
+impl Stringtable {
+    /// Reads a stringtable, comments and unknown attributes are not kept
+    pub fn read(source: &str) -> Result<Self, StringtableError> {
+        let mut reader = Reader::from_str(source);
+        let mut buf = Vec::new();
+        let mut stringtable = Self::default();
+        // Open elements, lowercase
+        let mut path: Vec<String> = Vec::new();
+        loop {
+            let position = reader.buffer_position();
+            match reader.read_event(&mut buf)? {
+                Event::Start(ref e) => {
+                    stringtable.start(&path, e, &reader, position)?;
+                    path.push(String::from_utf8_lossy(e.name()).to_lowercase());
+                }
+                Event::Empty(ref e) => stringtable.start(&path, e, &reader, position)?,
+                Event::End(_) => {
+                    path.pop();
+                }
+                Event::Text(ref e) => {
+                    if let Some(translation) = stringtable.translation(&path) {
+                        translation.text.push_str(&e.unescape_and_decode(&reader)?);
+                    }
+                }
+                Event::CData(ref e) => {
+                    if let Some(translation) = stringtable.translation(&path) {
+                        translation.text.push_str(reader.decode(e)?);
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        Ok(stringtable)
+    }
+
+    fn start(
+        &mut self,
+        path: &[String],
+        e: &BytesStart,
+        reader: &Reader<&[u8]>,
+        position: usize,
+    ) -> Result<(), StringtableError> {
+        let element = String::from_utf8_lossy(e.name()).to_string();
+        let name = |attribute: &str| -> Result<String, StringtableError> {
+            self::attribute(e, reader, attribute)?.ok_or_else(|| {
+                StringtableError::MissingAttribute {
+                    element: element.clone(),
+                    attribute: attribute.to_string(),
+                    position,
+                }
+            })
+        };
+        let path: Vec<&str> = path.iter().map(String::as_str).collect();
+        match (path.as_slice(), element.to_lowercase().as_str()) {
+            ([], "project") => {
+                self.name = attribute(e, reader, "name")?.unwrap_or_default();
+            }
+            (["project"], "package") => self.packages.push(Package {
+                name: name("name")?,
+                ..Package::default()
+            }),
+            (["project", "package"], "container") => {
+                let container = Container {
+                    name: name("name")?,
+                    ..Container::default()
+                };
+                self.packages.last_mut().unwrap().containers.push(container);
+            }
+            (["project", "package"], "key") => {
+                let key = Key {
+                    id: name("ID")?,
+                    ..Key::default()
+                };
+                self.packages.last_mut().unwrap().keys.push(key);
+            }
+            (["project", "package", "container"], "key") => {
+                let key = Key {
+                    id: name("ID")?,
+                    ..Key::default()
+                };
+                let package = self.packages.last_mut().unwrap();
+                package.containers.last_mut().unwrap().keys.push(key);
+            }
+            (["project", "package", "key"], _)
+            | (["project", "package", "container", "key"], _) => {
+                let key = self.key(&path).unwrap();
+                key.translations.push(Translation {
+                    language: element,
+                    text: String::new(),
+                });
+            }
+            _ => return Err(StringtableError::Unexpected { element, position }),
+        }
+        Ok(())
+    }
+
+    /// The key that is open
+    fn key(&mut self, path: &[&str]) -> Option<&mut Key> {
+        let package = self.packages.last_mut()?;
+        match path {
+            ["project", "package", "key", ..] => package.keys.last_mut(),
+            ["project", "package", "container", "key", ..] => {
+                package.containers.last_mut()?.keys.last_mut()
+            }
+            _ => None,
+        }
+    }
+
+    /// The translation that is open
+    fn translation(&mut self, path: &[String]) -> Option<&mut Translation> {
+        let path: Vec<&str> = path.iter().map(String::as_str).collect();
+        match path.as_slice() {
+            ["project", "package", "key", _] | ["project", "package", "container", "key", _] => {
+                self.key(&path)?.translations.last_mut()
+            }
+            _ => None,
+        }
+    }
+}
+
+fn attribute(
+    e: &BytesStart,
+    reader: &Reader<&[u8]>,
+    name: &str,
+) -> Result<Option<String>, StringtableError> {
+    for attr in e.attributes() {
+        let attr = attr?;
+        if attr.key.eq_ignore_ascii_case(name.as_bytes()) {
+            return Ok(Some(attr.unescape_and_decode_value(reader)?));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,106 @@
+use crate::{Key, Stringtable};
+
+const INDENT: &str = "    ";
+
+impl Stringtable {
+    /// Writes the stringtable as XML indented with 4 spaces, keys of a package
+    /// are written before its containers
+    pub fn to_xml(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        if self.name.is_empty() {
+            out.push_str("<Project>\n");
+        } else {
+            out.push_str(&format!("<Project name=\"{}\">\n", escape(&self.name)));
+        }
+        for package in &self.packages {
+            out.push_str(&format!(
+                "{}<Package name=\"{}\">\n",
+                INDENT,
+                escape(&package.name)
+            ));
+            write_keys(&mut out, &package.keys, 2);
+            for container in &package.containers {
+                out.push_str(&format!(
+                    "{}<Container name=\"{}\">\n",
+                    INDENT.repeat(2),
+                    escape(&container.name)
+                ));
+                write_keys(&mut out, &container.keys, 3);
+                out.push_str(&format!("{}</Container>\n", INDENT.repeat(2)));
+            }
+            out.push_str(&format!("{}</Package>\n", INDENT));
+        }
+        out.push_str("</Project>\n");
+        out
+    }
+}
+
+fn write_keys(out: &mut String, keys: &[Key], level: usize) {
+    let indent = INDENT.repeat(level);
+    for key in keys {
+        out.push_str(&format!("{}<Key ID=\"{}\">\n", indent, escape(&key.id)));
+        for translation in &key.translations {
+            out.push_str(&format!(
+                "{}{}<{lang}>{}</{lang}>\n",
+                indent,
+                INDENT,
+                escape(&translation.text),
+                lang = translation.language
+            ));
+        }
+        out.push_str(&format!("{}</Key>\n", indent));
+    }
+}
+
+/// Escapes text for an XML attribute or element, leaving out the characters XML 1.0
+/// does not allow, such as control characters from a file's contents
+pub fn escape(text: &str) -> String {
+    text.chars()
+        .filter(|c| {
+            matches!(c, '\t' | '\n' | '\r')
+                || (!c.is_control() && !matches!(c, '\u{FFFE}' | '\u{FFFF}'))
+        })
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Stringtable;
+
+    #[test]
+    fn round_trip() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<Project name="Test">
+    <Package name="Main">
+        <Key ID="STR_Test_Key">
+            <Original>Tom &amp; Jerry</Original>
+            <English>Tom &amp; Jerry &lt;br/&gt;</English>
+        </Key>
+        <Container name="Sub">
+            <Key ID="STR_Test_Sub">
+                <English>Sub</English>
+            </Key>
+        </Container>
+    </Package>
+</Project>
+"#;
+        let stringtable = Stringtable::read(xml).unwrap();
+        assert_eq!(
+            stringtable.packages[0].keys[0].translations[1].text,
+            "Tom & Jerry <br/>"
+        );
+        assert_eq!(stringtable.to_xml(), xml);
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(
+            super::escape("<a href=\"x\">\u{1b}[0m&\u{FFFF}\t</a>"),
+            "&lt;a href=&quot;x&quot;&gt;[0m&amp;\t&lt;/a&gt;"
+        );
+    }
+}
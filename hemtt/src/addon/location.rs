@@ -7,6 +7,8 @@ pub enum AddonLocation {
     Addons,
     Compats,
     Optionals,
+    /// Campaigns, released with the addons
+    Campaigns,
     // Custom(String),
 }
 
@@ -22,6 +24,7 @@ impl AddonLocation {
             Self::Addons => true,
             Self::Compats => true,
             Self::Optionals => true,
+            Self::Campaigns => true,
             // _ => false,
         }
     }
@@ -50,7 +53,12 @@ impl AddonLocation {
 
     /// List of first class locations
     pub fn first_class() -> Vec<Self> {
-        vec![Self::Addons, Self::Compats, Self::Optionals]
+        vec![
+            Self::Addons,
+            Self::Compats,
+            Self::Optionals,
+            Self::Campaigns,
+        ]
     }
 }
 
@@ -60,6 +68,7 @@ impl std::fmt::Display for AddonLocation {
             Self::Addons => write!(f, "addons"),
             Self::Compats => write!(f, "compats"),
             Self::Optionals => write!(f, "optionals"),
+            Self::Campaigns => write!(f, "campaigns"),
             // Self::Custom(s) => write!(f, "{}", s),
         }
     }
@@ -74,6 +83,7 @@ impl std::fmt::Debug for AddonLocation {
                 Self::Addons => String::from("standard(addons)"),
                 Self::Compats => String::from("standard(compats)"),
                 Self::Optionals => String::from("standard(optionals)"),
+                Self::Campaigns => String::from("standard(campaigns)"),
                 // Self::Custom(s) => format!("custom({})", s),
             }
         )
@@ -86,6 +96,7 @@ impl From<&str> for AddonLocation {
             "addons" => Self::Addons,
             "compats" => Self::Compats,
             "optionals" => Self::Optionals,
+            "campaigns" => Self::Campaigns,
             // TODO bring back custom
             _ => panic!("Invalid AddonLocation"), // _ => Self::Custom(loc.to_owned()),
         }
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 mod location;
 pub use location::AddonLocation;
 
-use crate::HEMTTError;
+use crate::{HEMTTError, Layout};
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct Addon {
@@ -76,15 +76,38 @@ impl Addon {
         destination_root: P,
         standalone: Option<&str>,
     ) -> PathBuf {
-        let mut r = destination_root.into();
-        r.push(self.location.to_string());
+        let folder = standalone.map(|modname| self.standalone_folder(modname));
+        self.layout_parent(destination_root, &Layout::default(), folder.as_deref())
+    }
+
+    /// Default folder name of the addon when released as its own mod
+    /// Ex: "@{modname}_{self.name}"
+    pub fn standalone_folder(&self, modname: &str) -> String {
+        format!("@{}_{}", modname, self.name)
+    }
+
+    /// Folder containing the released addon, placed by a release layout
+    ///
+    /// Arguments:
+    /// * `destination_root`: root folder of the destination
+    /// * `layout`: folders of each addon location inside the destination
+    /// * `standalone`:
+    ///                 Some(folder) - The destination should be it's own mod, named `folder`
+    ///                 None - The destination is part of a larger mod
+    pub fn layout_parent<P: Into<PathBuf>>(
+        &self,
+        destination_root: P,
+        layout: &Layout,
+        standalone: Option<&str>,
+    ) -> PathBuf {
+        let mut r = layout.location(destination_root.into(), self.location);
 
         // Individual Mod
-        if let Some(modname) = standalone {
+        if let Some(folder) = standalone {
             if self.location == AddonLocation::Addons {
                 warn!("Standalone addons should be in optionals or compats");
             }
-            r.push(&format!("@{}_{}", modname, self.name));
+            r.push(folder);
             r.push("addons");
         }
 
@@ -107,10 +130,65 @@ impl Addon {
         prefix: Option<&str>,
         standalone: Option<&str>,
     ) -> PathBuf {
-        let mut r = self.destination_parent(destination_root, standalone);
+        let folder = standalone.map(|modname| self.standalone_folder(modname));
+        self.layout_destination(
+            destination_root,
+            &Layout::default(),
+            prefix,
+            folder.as_deref(),
+        )
+    }
+
+    /// File path of the released addon, placed by a release layout
+    ///
+    /// Arguments:
+    /// * `standalone`:
+    ///                 Some(folder) - The destination should be it's own mod, named `folder`
+    ///                 None - The destination is part of a larger mod
+    pub fn layout_destination<P: Into<PathBuf>>(
+        &self,
+        destination_root: P,
+        layout: &Layout,
+        prefix: Option<&str>,
+        standalone: Option<&str>,
+    ) -> PathBuf {
+        let mut r = self.layout_parent(destination_root, layout, standalone);
         r.push(self.pbo(prefix));
         r
     }
+
+    /// Problems with the prefix of the addon, expected to be `{mainprefix}\{prefix}\addons\{addon}`
+    /// Ex: "z\hemtt\addons\main"
+    pub fn prefix_problems(&self, prefix: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+        if prefix.contains('/') {
+            problems.push(String::from("uses `/` instead of `\\`"));
+        }
+        let parts: Vec<&str> = prefix.split('\\').collect();
+        if parts.iter().any(|part| part.is_empty()) {
+            problems.push(String::from("contains an empty folder"));
+        }
+        if parts.len() != 4 {
+            problems.push(String::from(
+                "does not follow the `x\\{prefix}\\addons\\{addon}` convention",
+            ));
+            return problems;
+        }
+        let location = parts[2].to_lowercase();
+        if location != "addons" && location != self.location.to_string() {
+            problems.push(format!(
+                "has `{}` instead of `addons` or `{}` as its third folder",
+                parts[2], self.location
+            ));
+        }
+        if parts[3].to_lowercase() != self.name.to_lowercase() {
+            problems.push(format!(
+                "ends with `{}` instead of the addon name `{}`",
+                parts[3], self.name
+            ));
+        }
+        problems
+    }
 }
 
 impl From<&Addon> for hemtt_handlebars::Variables {
@@ -259,6 +337,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn layout_parent_named_standalone() {
+        let root = PathBuf::from("root");
+        assert_eq!(
+            get_optional().layout_parent(
+                &root,
+                &crate::Layout::default(),
+                Some("@Prefix - My Addon")
+            ),
+            PathBuf::from("root/optionals/@Prefix - My Addon/addons")
+        );
+    }
+
+    #[test]
+    fn prefix_problems() {
+        assert!(get_addon()
+            .prefix_problems("z\\hemtt\\addons\\my_addon")
+            .is_empty());
+        assert!(get_optional()
+            .prefix_problems("z\\hemtt\\optionals\\my_addon")
+            .is_empty());
+        assert_eq!(
+            get_addon().prefix_problems("z\\hemtt\\addons\\other").len(),
+            1
+        );
+        assert_eq!(get_addon().prefix_problems("hemtt\\my_addon").len(), 1);
+        assert_eq!(
+            get_addon()
+                .prefix_problems("z\\hemtt/addons\\my_addon")
+                .len(),
+            2
+        );
+    }
+
     #[test]
     fn destination_no_prefix_no_standalone() {
         let addons = vec![get_addon(), get_optional(), get_compat()]; //, get_custom()];
@@ -316,6 +428,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn destination_campaign() {
+        let campaign =
+            super::Addon::new("my_campaign".to_string(), super::AddonLocation::Campaigns).unwrap();
+        assert_eq!(campaign.source(), "campaigns/my_campaign");
+        assert_eq!(
+            campaign.destination("root", Some("prefix"), None),
+            PathBuf::from("root/addons/prefix_my_campaign.pbo")
+        );
+    }
+
     #[test]
     fn destination_with_prefix_with_standalone() {
         let addons = vec![get_addon(), get_optional(), get_compat()]; //, get_custom()];
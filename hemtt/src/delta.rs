@@ -0,0 +1,366 @@
+//! Binary delta patches between two versions of a file
+//!
+//! Blocks of the old file are found in the new file with a rolling checksum,
+//! like rsync, so data that moved because an earlier file in a PBO changed
+//! size is still copied from the old file instead of stored in the patch.
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Header of a patch file
+pub const MAGIC: &[u8; 8] = b"HEMTTDLT";
+/// Name of the manifest in a patch folder
+pub const MANIFEST: &str = "manifest.json";
+/// Extension of the patch of a file
+pub const EXTENSION: &str = "delta";
+
+/// Bytes of the old file that are matched at a time
+const BLOCK: usize = 1024;
+/// Blocks with the same checksum that are compared
+const CANDIDATES: usize = 4;
+
+const COPY: u8 = 0;
+const INSERT: u8 = 1;
+
+/// Changes between two releases, written next to the patches
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Manifest {
+    /// Version being patched
+    pub from: String,
+    /// Version after the patch is applied
+    pub to: String,
+    pub files: Vec<Change>,
+}
+
+/// A file that differs between the releases
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Change {
+    /// Path inside the release, separated by `/`
+    pub path: String,
+    pub action: Action,
+    /// SHA-1 of the file being patched or removed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_hash: Option<String>,
+    /// SHA-1 of the file after the patch is applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_hash: Option<String>,
+}
+
+impl Change {
+    /// Path inside the release, `None` if it could point outside of it
+    pub fn relative(&self) -> Option<PathBuf> {
+        let path = PathBuf::from(&self.path);
+        if path.as_os_str().is_empty()
+            || !path
+                .components()
+                .all(|c| matches!(c, std::path::Component::Normal(_)))
+        {
+            return None;
+        }
+        Some(path)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    /// `{path}.delta` is applied to the file
+    Patch,
+    /// The file is copied from the patch folder
+    Add,
+    /// The file is deleted
+    Remove,
+}
+
+/// Creates the patch that turns `old` into `new`
+pub fn diff(old: &[u8], new: &[u8]) -> Vec<u8> {
+    let mut patch = Vec::with_capacity(BLOCK);
+    patch.extend_from_slice(MAGIC);
+    patch.extend_from_slice(&(old.len() as u64).to_le_bytes());
+    patch.extend_from_slice(&(new.len() as u64).to_le_bytes());
+
+    let index = index(old);
+    // Start of the bytes of `new` that were not found in `old` yet
+    let mut pending = 0;
+    let mut pos = 0;
+    let mut rolling: Option<Rolling> = None;
+    while pos + BLOCK <= new.len() {
+        let mut checksum = rolling.unwrap_or_else(|| Rolling::new(&new[pos..pos + BLOCK]));
+        match find(&index, old, checksum.digest(), &new[pos..pos + BLOCK]) {
+            Some(offset) => {
+                let (mut start, mut from) = (pos, offset);
+                while start > pending && from > 0 && new[start - 1] == old[from - 1] {
+                    start -= 1;
+                    from -= 1;
+                }
+                let (mut end, mut to) = (pos + BLOCK, offset + BLOCK);
+                while end < new.len() && to < old.len() && new[end] == old[to] {
+                    end += 1;
+                    to += 1;
+                }
+                insert(&mut patch, &new[pending..start]);
+                copy(&mut patch, from, end - start);
+                pos = end;
+                pending = end;
+                rolling = None;
+            }
+            None => {
+                if pos + BLOCK < new.len() {
+                    checksum.roll(new[pos], new[pos + BLOCK]);
+                }
+                rolling = Some(checksum);
+                pos += 1;
+            }
+        }
+    }
+    insert(&mut patch, &new[pending..]);
+    patch
+}
+
+/// Location of the patch of a file in a patch folder
+/// Ex: "addons/main.pbo" => "addons/main.pbo.delta"
+pub fn patch_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(".");
+    name.push(EXTENSION);
+    path.with_file_name(name)
+}
+
+/// Applies a patch created by [`diff`] to `old`
+pub fn apply(old: &[u8], patch: &[u8]) -> Result<Vec<u8>, String> {
+    let mut reader = Reader(patch);
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(String::from("not a delta patch"));
+    }
+    let old_len = reader.u64()?;
+    if old_len != old.len() as u64 {
+        return Err(format!(
+            "the patch is for a file of {} bytes, not {} bytes",
+            old_len,
+            old.len()
+        ));
+    }
+    let new_len = reader.u64()?;
+    // The length is only checked after the patch is applied, don't reserve more than the
+    // inputs for a corrupt header
+    let mut new = Vec::with_capacity((new_len as usize).min(old.len() + patch.len()));
+    while !reader.0.is_empty() {
+        match reader.take(1)?[0] {
+            COPY => {
+                let offset = reader.u64()? as usize;
+                let len = reader.u64()? as usize;
+                let end = offset
+                    .checked_add(len)
+                    .filter(|end| *end <= old.len())
+                    .ok_or_else(|| String::from("the patch copies past the end of the file"))?;
+                new.extend_from_slice(&old[offset..end]);
+            }
+            INSERT => {
+                let len = reader.u64()? as usize;
+                new.extend_from_slice(reader.take(len)?);
+            }
+            op => return Err(format!("unknown operation {}", op)),
+        }
+    }
+    if new.len() as u64 != new_len {
+        return Err(format!(
+            "the patch created {} bytes instead of {} bytes",
+            new.len(),
+            new_len
+        ));
+    }
+    Ok(new)
+}
+
+/// Offsets of the blocks of the old file by their checksum
+fn index(old: &[u8]) -> HashMap<u32, Vec<usize>> {
+    let mut index: HashMap<u32, Vec<usize>> = HashMap::new();
+    for offset in (0..old.len() / BLOCK).map(|block| block * BLOCK) {
+        let offsets = index
+            .entry(Rolling::new(&old[offset..offset + BLOCK]).digest())
+            .or_default();
+        if offsets.len() < CANDIDATES {
+            offsets.push(offset);
+        }
+    }
+    index
+}
+
+fn find(index: &HashMap<u32, Vec<usize>>, old: &[u8], digest: u32, block: &[u8]) -> Option<usize> {
+    index
+        .get(&digest)?
+        .iter()
+        .copied()
+        .find(|offset| &old[*offset..*offset + BLOCK] == block)
+}
+
+fn copy(patch: &mut Vec<u8>, offset: usize, len: usize) {
+    patch.push(COPY);
+    patch.extend_from_slice(&(offset as u64).to_le_bytes());
+    patch.extend_from_slice(&(len as u64).to_le_bytes());
+}
+
+fn insert(patch: &mut Vec<u8>, data: &[u8]) {
+    if data.is_empty() {
+        return;
+    }
+    patch.push(INSERT);
+    patch.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    patch.extend_from_slice(data);
+}
+
+/// Checksum of a window of bytes that can be moved forward one byte at a time
+#[derive(Clone, Copy)]
+struct Rolling {
+    a: u32,
+    b: u32,
+    len: u32,
+}
+impl Rolling {
+    fn new(data: &[u8]) -> Self {
+        let len = data.len() as u32;
+        let (mut a, mut b) = (0u32, 0u32);
+        for (i, byte) in data.iter().enumerate() {
+            a = a.wrapping_add(u32::from(*byte));
+            b = b.wrapping_add((len - i as u32).wrapping_mul(u32::from(*byte)));
+        }
+        Self { a, b, len }
+    }
+
+    fn roll(&mut self, out: u8, input: u8) {
+        self.a = self
+            .a
+            .wrapping_sub(u32::from(out))
+            .wrapping_add(u32::from(input));
+        self.b = self
+            .b
+            .wrapping_sub(self.len.wrapping_mul(u32::from(out)))
+            .wrapping_add(self.a);
+    }
+
+    fn digest(&self) -> u32 {
+        (self.a & 0xffff) | (self.b << 16)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if len > self.0.len() {
+            return Err(String::from("the patch is truncated"));
+        }
+        let (data, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(data)
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn data(len: usize, seed: u32) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (state >> 16) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let old = data(20_000, 1);
+        let mut new = old.clone();
+        new.splice(5000..5000, data(300, 2));
+        new.drain(15_000..16_000);
+        new[100] ^= 0xff;
+        let patch = super::diff(&old, &new);
+        assert_eq!(super::apply(&old, &patch).unwrap(), new);
+        assert!(patch.len() < 1000);
+    }
+
+    #[test]
+    fn unrelated() {
+        let old = data(3000, 1);
+        let new = data(5000, 2);
+        assert_eq!(super::apply(&old, &super::diff(&old, &new)).unwrap(), new);
+        assert_eq!(super::apply(&[], &super::diff(&[], &new)).unwrap(), new);
+        assert!(super::apply(&old, &super::diff(&old, &[]))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn identical() {
+        let old = data(10_000, 3);
+        let patch = super::diff(&old, &old);
+        assert_eq!(patch.len(), 8 + 16 + 17);
+        assert_eq!(super::apply(&old, &patch).unwrap(), old);
+    }
+
+    #[test]
+    fn wrong_file() {
+        let old = data(4000, 1);
+        let patch = super::diff(&old, &data(4000, 2));
+        assert!(super::apply(&old[1..], &patch).is_err());
+        assert!(super::apply(&old, &patch[..patch.len() - 1]).is_err());
+        assert!(super::apply(&old, b"not a patch").is_err());
+        let mut corrupt = patch.clone();
+        corrupt[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(super::apply(&old, &corrupt).is_err());
+    }
+
+    #[test]
+    fn patch_path() {
+        assert_eq!(
+            super::patch_path(std::path::Path::new("addons/main.pbo")),
+            std::path::PathBuf::from("addons/main.pbo.delta")
+        );
+    }
+
+    #[test]
+    fn relative() {
+        let change = |path: &str| super::Change {
+            path: path.to_string(),
+            action: super::Action::Add,
+            from_hash: None,
+            to_hash: None,
+        };
+        assert_eq!(
+            change("addons/main.pbo").relative(),
+            Some(std::path::PathBuf::from("addons/main.pbo"))
+        );
+        assert_eq!(change("../main.pbo").relative(), None);
+        assert_eq!(change("addons/../../main.pbo").relative(), None);
+        assert_eq!(change("/etc/passwd").relative(), None);
+        assert_eq!(change("./main.pbo").relative(), None);
+        assert_eq!(change("").relative(), None);
+    }
+
+    #[test]
+    fn manifest() {
+        let manifest = super::Manifest {
+            from: String::from("1.0.0"),
+            to: String::from("1.1.0"),
+            files: vec![super::Change {
+                path: String::from("addons/main.pbo"),
+                action: super::Action::Patch,
+                from_hash: Some(String::from("ab")),
+                to_hash: Some(String::from("cd")),
+            }],
+        };
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert!(json.contains("\"action\":\"patch\""));
+        assert_eq!(
+            serde_json::from_str::<super::Manifest>(&json).unwrap(),
+            manifest
+        );
+    }
+}
@@ -0,0 +1,174 @@
+/// A location in a source file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub path: String,
+    /// Line of the location, starting at 1
+    pub line: usize,
+    /// Column of the location, starting at 1
+    pub column: usize,
+    /// Number of characters covered
+    pub length: usize,
+}
+
+impl Span {
+    pub fn new<S: Into<String>>(path: S, line: usize, column: usize, length: usize) -> Self {
+        Self {
+            path: path.into(),
+            line,
+            column,
+            length,
+        }
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.path, self.line, self.column)
+    }
+}
+
+/// An error at a location in a source file
+#[derive(Debug)]
+pub struct SourceError {
+    pub message: String,
+    pub span: Span,
+    /// Text of the line the error is on
+    pub line: Option<String>,
+    /// Includes that led to the file of the error, innermost first
+    pub included_from: Vec<Span>,
+}
+
+impl SourceError {
+    pub fn new<S: Into<String>>(message: S, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+            line: None,
+            included_from: Vec::new(),
+        }
+    }
+
+    pub fn with_line<S: Into<String>>(mut self, line: S) -> Self {
+        self.line = Some(line.into());
+        self
+    }
+}
+
+impl std::fmt::Display for SourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let number = self.span.line.to_string();
+        let gutter = " ".repeat(number.len());
+        write!(f, "{}\n{}--> {}", self.message, gutter, self.span)?;
+        if let Some(line) = &self.line {
+            let line = line.trim_end();
+            write!(
+                f,
+                "\n{} |\n{} | {}\n{} | {}{}",
+                gutter,
+                number,
+                line,
+                gutter,
+                " ".repeat(self.span.column.saturating_sub(1)),
+                "^".repeat(self.span.length.max(1))
+            )?;
+        }
+        for span in &self.included_from {
+            write!(f, "\n{} = included from {}", gutter, span)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+        }
+    }
+}
+
+/// A problem found in the project, for reporting to other tools
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    /// Error code or name of the check that found the problem
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+    /// Location of the problem, a line of 0 covers the whole file
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    pub fn new<R: Into<String>, M: Into<String>>(
+        rule: R,
+        severity: Severity,
+        message: M,
+        span: Option<Span>,
+    ) -> Self {
+        Self {
+            rule: rule.into(),
+            severity,
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+impl From<&crate::HEMTTError> for Diagnostic {
+    fn from(err: &crate::HEMTTError) -> Self {
+        match err {
+            crate::HEMTTError::Source(e) => Self::new(
+                err.code(),
+                Severity::Error,
+                e.message.clone(),
+                Some(e.span.clone()),
+            ),
+            _ => Self::new(err.code(), Severity::Error, err.to_string(), None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagnostic, Severity, SourceError, Span};
+
+    #[test]
+    fn display() {
+        let mut err = SourceError::new(
+            "Unable to resolve include `macros.hpp`",
+            Span::new("addons/main/script.hpp", 3, 1, 8),
+        )
+        .with_line("#include \"macros.hpp\"");
+        err.included_from
+            .push(Span::new("addons/main/config.cpp", 1, 1, 8));
+        assert_eq!(
+            err.to_string(),
+            "Unable to resolve include `macros.hpp`
+ --> addons/main/script.hpp:3:1
+  |
+3 | #include \"macros.hpp\"
+  | ^^^^^^^^
+  = included from addons/main/config.cpp:1:1"
+        );
+    }
+
+    #[test]
+    fn from_error() {
+        let err: crate::HEMTTError =
+            SourceError::new("define without name", Span::new("config.cpp", 2, 1, 1)).into();
+        let diagnostic = Diagnostic::from(&err);
+        assert_eq!(diagnostic.rule, "E0202");
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.message, "define without name");
+        assert_eq!(diagnostic.span.unwrap().line, 2);
+    }
+}
@@ -0,0 +1,257 @@
+//! Text format of Enfusion metadata, used by Arma Reforger's `*.conf` and `addon.gproj` files
+//!
+//! ```text
+//! GameProject {
+//!  ID "MyMod"
+//!  GUID "59A3A7D8E0F2B3C1"
+//!  Dependencies {
+//!   "58D0FB3206B6F859"
+//!  }
+//! }
+//! ```
+
+/// Project file at the root of every Enfusion mod
+pub const GPROJ: &str = "addon.gproj";
+
+/// A line of values, and the block that follows it
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Node {
+    pub line: usize,
+    pub values: Vec<String>,
+    pub children: Option<Vec<Node>>,
+}
+
+impl Node {
+    /// The first value, the class or property name
+    pub fn name(&self) -> &str {
+        self.values.first().map_or("", String::as_str)
+    }
+
+    /// The second value, the value of a property
+    pub fn value(&self) -> Option<&str> {
+        self.values.get(1).map(String::as_str)
+    }
+
+    /// The first child with a name
+    pub fn get(&self, name: &str) -> Option<&Node> {
+        self.children
+            .as_ref()
+            .and_then(|children| children.iter().find(|child| child.name() == name))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Value(String),
+    Open,
+    Close,
+    Newline,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, String> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                tokens.push((line, Token::Newline));
+                line += 1;
+            }
+            '{' => tokens.push((line, Token::Open)),
+            '}' => tokens.push((line, Token::Close)),
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.peek().map_or(false, |c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '"' => {
+                let start = line;
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => {
+                            if let Some(c) = chars.next() {
+                                value.push(c);
+                            }
+                        }
+                        Some('\n') | None => {
+                            return Err(format!("line {}: unterminated string", start))
+                        }
+                        Some(c) => value.push(c),
+                    }
+                }
+                tokens.push((line, Token::Value(value)));
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut value = c.to_string();
+                while let Some(c) = chars.peek() {
+                    if c.is_whitespace() || ['{', '}', '"'].contains(c) {
+                        break;
+                    }
+                    value.push(*c);
+                    chars.next();
+                }
+                tokens.push((line, Token::Value(value)));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parse the nodes of an Enfusion text file
+///
+/// ```
+/// let nodes = hemtt::enfusion::parse("GameProject {\n ID \"test\"\n}\n").unwrap();
+/// assert_eq!(nodes[0].get("ID").unwrap().value(), Some("test"));
+/// ```
+pub fn parse(source: &str) -> Result<Vec<Node>, String> {
+    let mut stack: Vec<Vec<Node>> = vec![Vec::new()];
+    let mut current: Option<Node> = None;
+    for (line, token) in tokenize(source)? {
+        match token {
+            Token::Value(value) => current
+                .get_or_insert_with(|| Node {
+                    line,
+                    ..Node::default()
+                })
+                .values
+                .push(value),
+            Token::Newline => {
+                if let Some(node) = current.take() {
+                    stack.last_mut().unwrap().push(node);
+                }
+            }
+            Token::Open => {
+                stack
+                    .last_mut()
+                    .unwrap()
+                    .push(current.take().unwrap_or(Node {
+                        line,
+                        ..Node::default()
+                    }));
+                stack.push(Vec::new());
+            }
+            Token::Close => {
+                if let Some(node) = current.take() {
+                    stack.last_mut().unwrap().push(node);
+                }
+                if stack.len() == 1 {
+                    return Err(format!("line {}: unexpected `}}`", line));
+                }
+                let children = stack.pop().unwrap();
+                stack.last_mut().unwrap().last_mut().unwrap().children = Some(children);
+            }
+        }
+    }
+    if stack.len() > 1 {
+        return Err(String::from("missing `}` at the end of the file"));
+    }
+    let mut nodes = stack.pop().unwrap();
+    nodes.extend(current);
+    Ok(nodes)
+}
+
+/// Whether a value is a resource GUID, 16 hexadecimal digits
+pub fn is_guid(value: &str) -> bool {
+    value.len() == 16 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Problems with the `GameProject` of an `addon.gproj`
+pub fn validate_gproj(nodes: &[Node]) -> Vec<String> {
+    let project = match nodes.iter().find(|node| node.name() == "GameProject") {
+        Some(project) => project,
+        None => return vec![String::from("`GameProject` is missing")],
+    };
+    let mut problems = Vec::new();
+    for key in &["ID", "GUID", "TITLE"] {
+        match project.get(key).and_then(Node::value) {
+            Some(value) if !value.is_empty() => {}
+            _ => problems.push(format!("`{}` is missing", key)),
+        }
+    }
+    if let Some(guid) = project.get("GUID").and_then(Node::value) {
+        if !guid.is_empty() && !is_guid(guid) {
+            problems.push(format!("`GUID` {} is not 16 hexadecimal digits", guid));
+        }
+    }
+    if let Some(dependencies) = project
+        .get("Dependencies")
+        .and_then(|d| d.children.as_ref())
+    {
+        for dependency in dependencies {
+            if !is_guid(dependency.name()) {
+                problems.push(format!(
+                    "line {}: dependency {} is not a GUID",
+                    dependency.line,
+                    dependency.name()
+                ));
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    const GPROJ: &str = r#"GameProject {
+ ID "test"
+ GUID "59A3A7D8E0F2B3C1"
+ TITLE "Test"
+ // Arma Reforger
+ Dependencies {
+  "58D0FB3206B6F859"
+ }
+}
+"#;
+
+    #[test]
+    fn parse() {
+        let nodes = super::parse(GPROJ).unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].name(), "GameProject");
+        assert_eq!(nodes[0].get("TITLE").unwrap().value(), Some("Test"));
+        assert_eq!(
+            nodes[0]
+                .get("Dependencies")
+                .unwrap()
+                .children
+                .as_ref()
+                .unwrap()[0]
+                .line,
+            7
+        );
+        assert!(super::validate_gproj(&nodes).is_empty());
+    }
+
+    #[test]
+    fn inheritance() {
+        let nodes =
+            super::parse("SCR_Test : \"{58D0FB3206B6F859}Configs/Base.conf\" {\n m_Value 1 2 3\n}")
+                .unwrap();
+        assert_eq!(nodes[0].values.len(), 3);
+        assert_eq!(nodes[0].get("m_Value").unwrap().values.len(), 4);
+    }
+
+    #[test]
+    fn unbalanced() {
+        assert!(super::parse("GameProject {\n").is_err());
+        assert!(super::parse("}\n").is_err());
+        assert!(super::parse("ID \"test\n").is_err());
+    }
+
+    #[test]
+    fn invalid_gproj() {
+        let nodes = super::parse("GameProject {\n ID \"test\"\n GUID \"xyz\"\n}").unwrap();
+        assert_eq!(
+            super::validate_gproj(&nodes),
+            vec![
+                String::from("`TITLE` is missing"),
+                String::from("`GUID` xyz is not 16 hexadecimal digits"),
+            ]
+        );
+        assert_eq!(super::validate_gproj(&[]).len(), 1);
+    }
+}
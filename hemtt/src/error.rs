@@ -19,7 +19,10 @@ pub enum HEMTTError {
     User(String),
     UserHint(String, String),
     Generic(String),
+    Interrupted,
+    Network(String),
     Preprocess(PreprocessError),
+    Source(Box<crate::SourceError>),
     IO(std::io::Error),
     IOPath(IOPathError),
     SemVer(semver::SemVerError),
@@ -29,15 +32,281 @@ pub enum HEMTTError {
     AddonConflict(String, crate::AddonLocation, crate::AddonLocation),
     AddonInvalidName(String),
     AddonInvalidLocation(String),
+    AddonPrefixConflict(String, String, String),
+    AddonsFailed(usize),
+    AddonsFailedChecks(usize),
+
+    // Mission
+    MissionInvalidName(String),
 
     // Project
     NoProjectFound,
+    InvalidConfig(String),
 
     // Templates
     TemplateUnknown(String),
+
+    // Signing
+    Signing(String),
+}
+
+/// A stable code identifying a kind of error
+pub struct ErrorCode {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub explanation: &'static str,
+}
+
+pub const ERROR_CODES: &[ErrorCode] = &[
+    ErrorCode {
+        code: "E0001",
+        name: "User",
+        explanation: "The command was used incorrectly. The message describes what was wrong with the input.",
+    },
+    ErrorCode {
+        code: "E0002",
+        name: "UserHint",
+        explanation: "The command was used incorrectly. The message is followed by a suggestion of what to try instead.",
+    },
+    ErrorCode {
+        code: "E0003",
+        name: "Generic",
+        explanation: "An unexpected error occurred. If the message does not make the cause clear, please submit a bug report.",
+    },
+    ErrorCode {
+        code: "E0004",
+        name: "Interrupted",
+        explanation: "HEMTT was stopped by Ctrl-C. The steps that were running finished, files that were not complete were removed.",
+    },
+    ErrorCode {
+        code: "E0101",
+        name: "IO",
+        explanation: "A file or folder could not be read or written. Check that it exists, is not open in another program and that you have permission to access it.",
+    },
+    ErrorCode {
+        code: "E0102",
+        name: "IOPath",
+        explanation: "The file or folder shown could not be read or written. Check that it exists, is not open in another program and that you have permission to access it.",
+    },
+    ErrorCode {
+        code: "E0103",
+        name: "Vfs",
+        explanation: "A file could not be accessed in the virtual filesystem HEMTT builds from. This usually means a file was removed or renamed during the build.",
+    },
+    ErrorCode {
+        code: "E0104",
+        name: "Network",
+        explanation: "A server could not be reached or answered with an error. Check the connection and the proxy in the user config, then try again.",
+    },
+    ErrorCode {
+        code: "E0201",
+        name: "Preprocess",
+        explanation: "A config could not be preprocessed. Check the file shown for unbalanced macros, missing `#endif`s and `#include`s that cannot be found.",
+    },
+    ErrorCode {
+        code: "E0202",
+        name: "Source",
+        explanation: "A config or script contains an error at the location shown. If the file was included by another file, the chain of includes is listed below the error.",
+    },
+    ErrorCode {
+        code: "E0301",
+        name: "AddonConflict",
+        explanation: "An addon with the same name exists in more than one of `addons`, `optionals`, `compats` and `campaigns`. Addon names must be unique across all locations, rename or remove one of them.",
+    },
+    ErrorCode {
+        code: "E0302",
+        name: "AddonInvalidName",
+        explanation: "Addon names may only contain lowercase letters, numbers and underscores. Rename the addon's folder.",
+    },
+    ErrorCode {
+        code: "E0303",
+        name: "AddonInvalidLocation",
+        explanation: "Addons can only be placed in `addons`, `optionals`, `compats` or `campaigns`.",
+    },
+    ErrorCode {
+        code: "E0304",
+        name: "AddonPrefixConflict",
+        explanation: "Two addons resolve to the same prefix, either from their `$PBOPREFIX$` files or the prefix generated from the project. Arma would silently load the files of only one of them, give each addon a unique prefix.",
+    },
+    ErrorCode {
+        code: "E0305",
+        name: "AddonsFailed",
+        explanation: "One or more addons could not be built. The errors of every addon are shown above the summary.",
+    },
+    ErrorCode {
+        code: "E0306",
+        name: "AddonsFailedChecks",
+        explanation: "One or more addons failed the checks, so nothing was built from them. The problems are shown above the summary, `hemtt check` shows them without building.",
+    },
+    ErrorCode {
+        code: "E0401",
+        name: "MissionInvalidName",
+        explanation: "Mission folders must be named `{name}.{map}`, for example `my_mission.Altis`, so the world the mission is made for is known.",
+    },
+    ErrorCode {
+        code: "E0501",
+        name: "NoProjectFound",
+        explanation: "No `hemtt.toml` or `.hemtt` folder was found in the current folder or any of its parents. Run the command inside a project folder.",
+    },
+    ErrorCode {
+        code: "E0502",
+        name: "SemVer",
+        explanation: "The version of the project is not a valid semantic version. Versions must be in the format `major.minor.patch`, for example `1.0.0`.",
+    },
+    ErrorCode {
+        code: "E0503",
+        name: "InvalidConfig",
+        explanation: "The project config, the lock file or the user config could not be read. The message shows the file and what is wrong with it.",
+    },
+    ErrorCode {
+        code: "E0601",
+        name: "TemplateUnknown",
+        explanation: "The template set in the project does not exist. The message lists the available templates.",
+    },
+    ErrorCode {
+        code: "E0701",
+        name: "Signing",
+        explanation: "A key could not be read or created, or a PBO could not be signed. Check that the private key is valid and was made for the same authority.",
+    },
+];
+
+/// An exit code of HEMTT, so scripts and CI can tell kinds of failure apart
+/// without reading the output
+pub struct ExitCode {
+    pub code: i32,
+    pub name: &'static str,
+    pub explanation: &'static str,
+}
+
+pub const EXIT_CODES: &[ExitCode] = &[
+    ExitCode {
+        code: 0,
+        name: "Success",
+        explanation: "The command completed.",
+    },
+    ExitCode {
+        code: 1,
+        name: "Error",
+        explanation: "An error without a more specific exit code, such as most mistakes in how a command was used. The error code in the output tells what went wrong.",
+    },
+    ExitCode {
+        code: 2,
+        name: "Config",
+        explanation: "No project was found, or the project, the user config, an addon or a mission is invalid.",
+    },
+    ExitCode {
+        code: 3,
+        name: "Build",
+        explanation: "One or more addons could not be built.",
+    },
+    ExitCode {
+        code: 4,
+        name: "Lint",
+        explanation: "One or more addons failed the checks, nothing was built from them.",
+    },
+    ExitCode {
+        code: 5,
+        name: "Signing",
+        explanation: "A key could not be read or created, or a PBO could not be signed.",
+    },
+    ExitCode {
+        code: 6,
+        name: "Network",
+        explanation: "A server could not be reached or answered with an error, or steamcmd, curl or rsync failed to transfer files.",
+    },
+    ExitCode {
+        code: 130,
+        name: "Interrupted",
+        explanation: "HEMTT was stopped by Ctrl-C.",
+    },
+];
+
+/// Finds the explanation of an error code, ignoring case
+pub fn explain(code: &str) -> Option<&'static ErrorCode> {
+    ERROR_CODES
+        .iter()
+        .find(|c| c.code.eq_ignore_ascii_case(code))
+}
+
+/// Finds the explanation of an exit code
+pub fn explain_exit(code: i32) -> Option<&'static ExitCode> {
+    EXIT_CODES.iter().find(|c| c.code == code)
 }
 
 impl HEMTTError {
+    /// The stable code of the error, explained by `hemtt explain`
+    pub fn code(&self) -> &'static str {
+        match *self {
+            Self::User(_) => "E0001",
+            Self::UserHint(_, _) => "E0002",
+            Self::Generic(_) => "E0003",
+            Self::Interrupted => "E0004",
+            Self::IO(_) => "E0101",
+            Self::IOPath(_) => "E0102",
+            Self::Vfs(_) => "E0103",
+            Self::Network(_) => "E0104",
+            Self::Preprocess(_) => "E0201",
+            Self::Source(_) => "E0202",
+
+            // Addon
+            Self::AddonConflict(_, _, _) => "E0301",
+            Self::AddonInvalidName(_) => "E0302",
+            Self::AddonInvalidLocation(_) => "E0303",
+            Self::AddonPrefixConflict(_, _, _) => "E0304",
+            Self::AddonsFailed(_) => "E0305",
+            Self::AddonsFailedChecks(_) => "E0306",
+
+            // Mission
+            Self::MissionInvalidName(_) => "E0401",
+
+            // Project
+            Self::NoProjectFound => "E0501",
+            Self::SemVer(_) => "E0502",
+            Self::InvalidConfig(_) => "E0503",
+
+            // Templates
+            Self::TemplateUnknown(_) => "E0601",
+
+            // Signing
+            Self::Signing(_) => "E0701",
+        }
+    }
+
+    /// The exit code of HEMTT when it stops with the error, one of `EXIT_CODES`
+    ///
+    /// `User` and `UserHint` errors are raised for many kinds of mistakes, not only
+    /// in the config, so they exit with 1 unless the error has a variant of its own
+    pub fn exit_code(&self) -> i32 {
+        match *self {
+            Self::AddonConflict(_, _, _)
+            | Self::AddonInvalidName(_)
+            | Self::AddonInvalidLocation(_)
+            | Self::AddonPrefixConflict(_, _, _)
+            | Self::MissionInvalidName(_)
+            | Self::NoProjectFound
+            | Self::SemVer(_)
+            | Self::InvalidConfig(_)
+            | Self::TemplateUnknown(_) => 2,
+            Self::AddonsFailed(_) => 3,
+            Self::AddonsFailedChecks(_) => 4,
+            Self::Signing(_) => 5,
+            Self::Network(_) => 6,
+            Self::Interrupted => 130,
+            _ => 1,
+        }
+    }
+
+    /// Adds the location of an include to the chain of a source error
+    pub fn included_from(self, span: crate::Span) -> Self {
+        match self {
+            Self::Source(mut e) => {
+                e.included_from.push(span);
+                Self::Source(e)
+            }
+            _ => self,
+        }
+    }
+
     pub fn can_submit_bug(&self) -> bool {
         !matches!(
             *self,
@@ -46,7 +315,15 @@ impl HEMTTError {
                 | Self::AddonConflict(_, _, _)
                 | Self::AddonInvalidName(_)
                 | Self::AddonInvalidLocation(_)
+                | Self::AddonPrefixConflict(_, _, _)
+                | Self::AddonsFailed(_)
+                | Self::AddonsFailedChecks(_)
+                | Self::MissionInvalidName(_)
                 | Self::NoProjectFound
+                | Self::InvalidConfig(_)
+                | Self::Interrupted
+                | Self::Network(_)
+                | Self::Source(_)
                 | Self::TemplateUnknown(_)
         )
     }
@@ -68,7 +345,10 @@ impl std::fmt::Display for HEMTTError {
             Self::User(ref s) => write!(f, "{}", s),
             Self::UserHint(ref s, ref h) => write!(f, "{}\ntry: {}", s, h),
             Self::Generic(ref s) => write!(f, "{}", s),
+            Self::Interrupted => write!(f, "Stopped by Ctrl-C"),
+            Self::Network(ref s) => write!(f, "{}", s),
             Self::Preprocess(ref e) => write!(f, "Preprocessor: {}", e.message),
+            Self::Source(ref e) => write!(f, "{}", e),
             Self::IO(ref e) => write!(f, "IO error: {}", e),
             Self::IOPath(ref e) => write!(f, "IO error: `{:#?}`\n{}", e.path, e.source),
             Self::SemVer(ref e) => write!(f, "SemVer error: `{}`", e),
@@ -89,9 +369,34 @@ impl std::fmt::Display for HEMTTError {
                 loc,
                 crate::AddonLocation::options()
             ),
+            Self::AddonPrefixConflict(ref prefix, ref addon, ref other) => write!(
+                f,
+                "Prefix conflict. `{}` cannot use the prefix `{}`, it is used by `{}`",
+                addon, prefix, other
+            ),
+            Self::AddonsFailed(count) => write!(
+                f,
+                "Unable to build {} addon{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+            Self::AddonsFailedChecks(count) => write!(
+                f,
+                "{} addon{} failed the checks",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+
+            // Mission
+            Self::MissionInvalidName(ref mission) => write!(
+                f,
+                "Invalid mission folder `{}`, expected `{{name}}.{{map}}`",
+                mission
+            ),
 
             // Project
             Self::NoProjectFound => write!(f, "No HEMTT Project found"),
+            Self::InvalidConfig(ref s) => write!(f, "{}", s),
 
             // Template
             Self::TemplateUnknown(ref template) => write!(
@@ -100,6 +405,9 @@ impl std::fmt::Display for HEMTTError {
                 template,
                 Templates::options()
             ),
+
+            // Signing
+            Self::Signing(ref s) => write!(f, "Signing error: {}", s),
         }
     }
 }
@@ -110,7 +418,10 @@ impl std::error::Error for HEMTTError {
             Self::User(_) => Some(self),
             Self::UserHint(_, ref _h) => Some(self),
             Self::Generic(_) => Some(self),
+            Self::Interrupted => Some(self),
+            Self::Network(_) => Some(self),
             Self::Preprocess(ref e) => Some(&e.source),
+            Self::Source(_) => Some(self),
             Self::IO(ref e) => Some(e),
             Self::IOPath(ref e) => Some(&e.source),
             Self::SemVer(ref e) => Some(e),
@@ -120,12 +431,22 @@ impl std::error::Error for HEMTTError {
             Self::AddonConflict(_, _, _) => Some(self),
             Self::AddonInvalidName(_) => Some(self),
             Self::AddonInvalidLocation(_) => Some(self),
+            Self::AddonPrefixConflict(_, _, _) => Some(self),
+            Self::AddonsFailed(_) => Some(self),
+            Self::AddonsFailedChecks(_) => Some(self),
+
+            // Mission
+            Self::MissionInvalidName(_) => Some(self),
 
             // Project
             Self::NoProjectFound => Some(self),
+            Self::InvalidConfig(_) => Some(self),
 
             // Template
             Self::TemplateUnknown(_) => Some(self),
+
+            // Signing
+            Self::Signing(_) => Some(self),
         }
     }
 }
@@ -148,8 +469,61 @@ impl From<vfs::VfsError> for HEMTTError {
     }
 }
 
+impl From<crate::SourceError> for HEMTTError {
+    fn from(err: crate::SourceError) -> Self {
+        Self::Source(Box::new(err))
+    }
+}
+
 impl From<String> for HEMTTError {
     fn from(err: String) -> Self {
         Self::Generic(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ERROR_CODES, EXIT_CODES};
+
+    #[test]
+    fn unique_codes() {
+        for (i, code) in ERROR_CODES.iter().enumerate() {
+            assert!(
+                ERROR_CODES[i + 1..].iter().all(|c| c.code != code.code),
+                "duplicate error code {}",
+                code.code
+            );
+        }
+    }
+
+    #[test]
+    fn explained() {
+        let err = super::HEMTTError::NoProjectFound;
+        assert_eq!(super::explain(err.code()).unwrap().name, "NoProjectFound");
+        assert!(super::explain("e0501").is_some());
+        assert!(super::explain("E9999").is_none());
+    }
+
+    #[test]
+    fn exit_codes() {
+        for (i, code) in EXIT_CODES.iter().enumerate() {
+            assert!(
+                EXIT_CODES[i + 1..].iter().all(|c| c.code != code.code),
+                "duplicate exit code {}",
+                code.code
+            );
+        }
+        for err in &[
+            super::HEMTTError::Generic(String::new()),
+            super::HEMTTError::NoProjectFound,
+            super::HEMTTError::AddonsFailed(1),
+            super::HEMTTError::AddonsFailedChecks(1),
+            super::HEMTTError::Signing(String::new()),
+            super::HEMTTError::Network(String::new()),
+            super::HEMTTError::Interrupted,
+        ] {
+            assert!(super::explain_exit(err.exit_code()).is_some());
+        }
+        assert_eq!(super::HEMTTError::AddonsFailedChecks(2).exit_code(), 4);
+    }
+}
@@ -5,14 +5,24 @@ extern crate log;
 extern crate hemtt_macros;
 
 mod addon;
+pub mod delta;
+mod diagnostic;
+pub use diagnostic::{Diagnostic, Severity, SourceError, Span};
+pub mod enfusion;
 mod error;
 pub use error::*;
+mod mission;
+mod path;
 pub mod project;
 pub mod templates;
 pub mod tools;
+mod user;
 
 pub use ::config::Config;
 pub use addon::{Addon, AddonLocation};
 pub use error::HEMTTError;
+pub use mission::{get_missions, Mission, MISSIONS_DIR};
+pub use path::{file_name, long_path, strip_prefix_no_case};
 pub use project::*;
 pub use templates::Template;
+pub use user::{UpdatePreferences, UserConfig};
@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use crate::HEMTTError;
+
+/// Folder containing the mission sources
+pub const MISSIONS_DIR: &str = "missions";
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Mission {
+    name: String,
+    map: String,
+    source: String,
+}
+impl Mission {
+    /// Create a mission from its folder name
+    ///
+    /// Arguments:
+    /// * `folder`: Name of the mission folder, in the format `{name}.{map}`
+    pub fn new<S: Into<String>>(folder: S) -> Result<Self, HEMTTError> {
+        let folder = folder.into();
+        let (name, map) = match folder.rfind('.') {
+            Some(index) if index != 0 && index != folder.len() - 1 => {
+                (folder[..index].to_string(), folder[index + 1..].to_string())
+            }
+            _ => return Err(HEMTTError::MissionInvalidName(folder)),
+        };
+        Ok(Self {
+            source: format!("{}/{}", MISSIONS_DIR, folder),
+            name,
+            map,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The world the mission is made for
+    pub fn map(&self) -> &str {
+        &self.map
+    }
+
+    /// Path to the mission folder
+    /// Ex: "missions/my_mission.Altis"
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Filename of the PBO
+    /// Ex: "my_mission.Altis.pbo"
+    pub fn pbo(&self) -> String {
+        format!("{}.{}.pbo", self.name, self.map)
+    }
+
+    /// File path of the released mission
+    ///
+    /// Arguments:
+    /// * `destination_root`: root folder of the destination
+    pub fn destination<P: Into<PathBuf>>(&self, destination_root: P) -> PathBuf {
+        let mut r = destination_root.into();
+        r.push("mpmissions");
+        r.push(self.pbo());
+        r
+    }
+}
+
+/// Find all missions in the project
+pub fn get_missions() -> Result<Vec<Mission>, HEMTTError> {
+    if !PathBuf::from(MISSIONS_DIR).exists() {
+        return Ok(Vec::new());
+    }
+    let mut missions = Vec::new();
+    for entry in std::fs::read_dir(MISSIONS_DIR)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            missions.push(Mission::new(crate::file_name(&path)?)?);
+        }
+    }
+    Ok(missions)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    #[test]
+    fn parse() {
+        let mission = super::Mission::new("my_mission.Altis").unwrap();
+        assert_eq!(mission.name(), "my_mission");
+        assert_eq!(mission.map(), "Altis");
+        assert_eq!(mission.source(), "missions/my_mission.Altis");
+        assert_eq!(mission.pbo(), "my_mission.Altis.pbo");
+    }
+
+    #[test]
+    fn parse_dotted_name() {
+        let mission = super::Mission::new("co10.escape.Tanoa").unwrap();
+        assert_eq!(mission.name(), "co10.escape");
+        assert_eq!(mission.map(), "Tanoa");
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(super::Mission::new("my_mission").is_err());
+        assert!(super::Mission::new("my_mission.").is_err());
+        assert!(super::Mission::new(".Altis").is_err());
+    }
+
+    #[test]
+    fn destination() {
+        let mission = super::Mission::new("my_mission.Altis").unwrap();
+        assert_eq!(
+            mission.destination("root"),
+            PathBuf::from("root/mpmissions/my_mission.Altis.pbo")
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Component, Path, PathBuf, Prefix},
+};
+
+use crate::HEMTTError;
+
+/// Longest path Windows opens without the extended-length prefix, leaving room
+/// for the 8.3 file name Windows requires folders to have space for
+#[cfg(windows)]
+const MAX_PATH: usize = 248;
+
+/// Path that is not limited to `MAX_PATH` on Windows, paths on other platforms
+/// and short paths are unchanged
+/// Ex: `C:\mod\releases\1.0.0\@mod\optionals\...` => `\\?\C:\mod\releases\1.0.0\@mod\optionals\...`
+pub fn long_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = path.as_ref();
+    if cfg!(windows) && path.as_os_str().len() >= max_path() {
+        extended(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Name of a file or folder that becomes part of the project, such as an addon or mission
+///
+/// Names outside of ASCII are fine, but they must be valid unicode to be packed
+pub fn file_name(path: &Path) -> Result<String, HEMTTError> {
+    let name = path.file_name().unwrap_or_else(|| path.as_os_str());
+    name.to_str().map(str::to_owned).ok_or_else(|| {
+        HEMTTError::UserHint(
+            format!("{:?} is not a valid unicode name", name),
+            String::from("rename it using only valid unicode characters"),
+        )
+    })
+}
+
+/// Strips a prefix from a path without case, as Arma resolves prefixes
+///
+/// The folders of both are compared one by one, so the rest of the path keeps
+/// its case and characters that change length in lowercase are not split
+/// Ex: `("Z\Mod\Addons\Main\data\icon.paa", "z/mod/addons/main")` => `Some("data\icon.paa")`
+pub fn strip_prefix_no_case<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    let is_separator = |c: char| c == '/' || c == '\\';
+    let mut rest = path.trim_start_matches(is_separator);
+    for folder in prefix
+        .split(is_separator)
+        .filter(|folder| !folder.is_empty())
+    {
+        let end = rest.find(is_separator).unwrap_or(rest.len());
+        if rest[..end].to_lowercase() != folder.to_lowercase() {
+            return None;
+        }
+        rest = rest[end..].trim_start_matches(is_separator);
+    }
+    Some(rest)
+}
+
+#[cfg(windows)]
+fn max_path() -> usize {
+    MAX_PATH
+}
+
+#[cfg(not(windows))]
+fn max_path() -> usize {
+    usize::MAX
+}
+
+/// Adds the `\\?\` prefix to an absolute, normalized path
+///
+/// Windows does not normalize extended-length paths, so `/`, `.` and `..` are
+/// resolved first
+fn extended(path: &Path) -> PathBuf {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        match std::env::current_dir() {
+            Ok(dir) => dir.join(path),
+            Err(_) => return path.to_path_buf(),
+        }
+    };
+    let mut long = OsString::new();
+    let mut parts: Vec<&OsStr> = Vec::new();
+    for component in absolute.components() {
+        match component {
+            Component::Prefix(p) => match p.kind() {
+                // Already extended or a device, used as is
+                Prefix::Verbatim(_)
+                | Prefix::VerbatimUNC(_, _)
+                | Prefix::VerbatimDisk(_)
+                | Prefix::DeviceNS(_) => return path.to_path_buf(),
+                // Ex: `\\server\share` => `\\?\UNC\server\share`
+                Prefix::UNC(server, share) => {
+                    long.push(r"\\?\UNC\");
+                    long.push(server);
+                    long.push(r"\");
+                    long.push(share);
+                }
+                Prefix::Disk(_) => {
+                    long.push(r"\\?\");
+                    long.push(p.as_os_str());
+                }
+            },
+            Component::RootDir | Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop();
+            }
+            Component::Normal(part) => parts.push(part),
+        }
+    }
+    // Names are joined as they are, so names that are not valid unicode are kept
+    for part in parts {
+        long.push(r"\");
+        long.push(part);
+    }
+    PathBuf::from(long)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{file_name, strip_prefix_no_case};
+
+    #[test]
+    fn non_ascii_name() {
+        assert_eq!(
+            file_name(Path::new("Mödpack 日本/addons/äddon")).unwrap(),
+            "äddon"
+        );
+    }
+
+    #[test]
+    fn strip_prefix() {
+        assert_eq!(
+            strip_prefix_no_case(r"Z\Mod\Addons\Main\data\Icon.paa", "z/mod/addons/main"),
+            Some(r"data\Icon.paa")
+        );
+        assert_eq!(
+            strip_prefix_no_case("/z/mod/addons/mainly/icon.paa", "z/mod/addons/main"),
+            None
+        );
+        // `İ` is longer in lowercase
+        assert_eq!(
+            strip_prefix_no_case("İtem/Äddon/Data.paa", &"İtem/äddon".to_lowercase()),
+            Some("Data.paa")
+        );
+        assert_eq!(
+            strip_prefix_no_case("/data/icon.paa", ""),
+            Some("data/icon.paa")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn invalid_name() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        assert!(file_name(Path::new(OsStr::from_bytes(b"addons/\xFFmain"))).is_err());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn long() {
+        use std::path::PathBuf;
+
+        use super::{extended, long_path};
+
+        assert_eq!(
+            extended(Path::new(r"C:\mod\releases\..\addons/main.pbo")),
+            PathBuf::from(r"\\?\C:\mod\addons\main.pbo")
+        );
+        assert_eq!(
+            extended(Path::new(r"\\server\share\mod\main.pbo")),
+            PathBuf::from(r"\\?\UNC\server\share\mod\main.pbo")
+        );
+        assert_eq!(
+            long_path(r"C:\mod\main.pbo"),
+            PathBuf::from(r"C:\mod\main.pbo")
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// Files stored compressed inside the built PBOs, matched by glob patterns
+/// against their path inside the addon
+/// Ex: `compress = ["*.sqf", "*.bin"]`, `raw = ["*.paa", "*.wss"]`
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Compression {
+    /// Files stored compressed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub compress: Vec<String>,
+
+    /// Files stored raw even when they match `compress`, such as assets that are already compressed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub raw: Vec<String>,
+}
+
+impl Compression {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether a file is stored compressed, by its path inside the addon
+    /// Ex: "functions/fnc_init.sqf"
+    pub fn compressed(&self, path: &str) -> bool {
+        let path = path.replace('\\', "/");
+        let path = path.trim_start_matches('/');
+        let matches = |patterns: &[String]| {
+            patterns.iter().any(|pattern| {
+                glob::Pattern::new(pattern).map_or(false, |pattern| {
+                    pattern.matches_with(
+                        path,
+                        glob::MatchOptions {
+                            case_sensitive: false,
+                            ..glob::MatchOptions::new()
+                        },
+                    )
+                })
+            })
+        };
+        matches(&self.compress) && !matches(&self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Compression;
+
+    #[test]
+    fn compressed() {
+        let compression = Compression {
+            compress: vec![String::from("*"), String::from("data/*.bin")],
+            raw: vec![String::from("*.paa"), String::from("*.wss")],
+        };
+        assert!(compression.compressed("config.bin"));
+        assert!(compression.compressed("functions\\fnc_init.sqf"));
+        assert!(!compression.compressed("data/icon_ca.PAA"));
+        assert!(!Compression::default().compressed("config.bin"));
+    }
+}
@@ -1,5 +1,8 @@
 use std::path::PathBuf;
 
+pub const DEFAULT_RELEASE_FOLDER: &str = "{{version}}/@{{modname}}";
+pub const DEFAULT_STANDALONE_NAME: &str = "@{{modname}}_{{addon.name}}";
+
 pub fn default_include() -> Vec<PathBuf> {
     let mut includes = vec![];
 
@@ -22,10 +25,18 @@ pub const fn default_reuse_private_key() -> Option<bool> {
     None
 }
 
+pub const fn default_version_defines() -> Option<bool> {
+    Some(true)
+}
+
 pub const fn default_folder_optionals() -> Option<bool> {
     Some(true)
 }
 
-pub const fn default_sig_version() -> u8 {
-    3
+pub const fn default_link_duplicates() -> Option<bool> {
+    None
+}
+
+pub const fn default_sig_version() -> Option<u8> {
+    None
 }
@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Folder that fetched dependencies are stored in
+pub const DEPS_DIR: &str = "deps";
+
+/// Arma 3 Steam App ID, used for Workshop downloads
+pub const ARMA3_APPID: u32 = 107_410;
+
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Dependency {
+    /// Steam Workshop ID of the mod
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub workshop: Option<u64>,
+
+    /// URL to a zip archive of the mod
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl Dependency {
+    /// Folder the dependency is fetched into
+    /// Ex: "deps/@cba"
+    pub fn folder(name: &str) -> PathBuf {
+        let mut path = PathBuf::from(DEPS_DIR);
+        path.push(format!("@{}", name));
+        path
+    }
+}
+
+/// Folder containing the headers extracted from the fetched dependencies
+/// Ex: "deps/include"
+pub fn deps_include() -> PathBuf {
+    let mut path = PathBuf::from(DEPS_DIR);
+    path.push("include");
+    path
+}
@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::Platform;
+
+/// A native extension built during releases and copied into the mod's root
+/// Ex: `[extensions.my_mod]` with `path = "extension"`
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Extension {
+    /// Folder of the extension, relative to the project root
+    pub path: String,
+
+    /// Program and arguments that build the extension, run in its folder,
+    /// defaults to `cargo build --release`
+    /// Ex: `["cmake", "--build", "build", "--config", "Release"]`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    pub command: Vec<String>,
+
+    /// File that is built, relative to the extension's folder, defaults to
+    /// the library Cargo builds for an extension of the same name
+    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default = "String::new")]
+    pub artifact: String,
+
+    /// Files built for each platform, relative to the extension's folder,
+    /// replaces `artifact` when the command builds for more than one platform
+    /// Ex: `windows = "target/x86_64-pc-windows-gnu/release/my_mod.dll"`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default = "BTreeMap::new")]
+    pub artifacts: BTreeMap<Platform, String>,
+}
+
+impl Extension {
+    pub fn command(&self) -> Vec<String> {
+        if self.command.is_empty() {
+            vec![
+                String::from("cargo"),
+                String::from("build"),
+                String::from("--release"),
+            ]
+        } else {
+            self.command.clone()
+        }
+    }
+
+    /// Files that are built, with the platform each is for
+    /// Ex: [(Windows, "extension/target/release/my_mod.dll")]
+    pub fn artifacts(&self, name: &str) -> Vec<(Platform, PathBuf)> {
+        let folder = Path::new(&self.path);
+        if !self.artifacts.is_empty() {
+            return self
+                .artifacts
+                .iter()
+                .map(|(platform, artifact)| (*platform, folder.join(artifact)))
+                .collect();
+        }
+        if !self.artifact.is_empty() {
+            return vec![(Platform::current(), folder.join(&self.artifact))];
+        }
+        let file = match Platform::current() {
+            Platform::Windows => format!("{}.dll", name),
+            Platform::Linux => format!("lib{}.so", name),
+        };
+        vec![(
+            Platform::current(),
+            folder.join("target").join("release").join(file),
+        )]
+    }
+
+    /// Artifacts that are set for more than one platform, with those platforms,
+    /// a file is only built for one platform so one of the entries is wrong
+    /// Ex: [("build/my_mod.dll", [Windows, Linux])]
+    pub fn conflicts(&self) -> Vec<(String, Vec<Platform>)> {
+        let mut platforms: BTreeMap<String, Vec<Platform>> = BTreeMap::new();
+        for (platform, artifact) in &self.artifacts {
+            platforms
+                .entry(artifact.replace('\\', "/"))
+                .or_default()
+                .push(*platform);
+        }
+        platforms
+            .into_iter()
+            .filter(|(_, platforms)| platforms.len() > 1)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use super::{Extension, Platform};
+
+    #[test]
+    fn artifacts() {
+        let mut extension = Extension {
+            path: String::from("extension"),
+            command: Vec::new(),
+            artifact: String::new(),
+            artifacts: BTreeMap::new(),
+        };
+        let built = match Platform::current() {
+            Platform::Windows => "extension/target/release/my_mod.dll",
+            Platform::Linux => "extension/target/release/libmy_mod.so",
+        };
+        assert_eq!(
+            extension.artifacts("my_mod"),
+            vec![(Platform::current(), PathBuf::from(built))]
+        );
+        extension.artifact = String::from("build/my_mod.so");
+        assert_eq!(
+            extension.artifacts("my_mod"),
+            vec![(
+                Platform::current(),
+                PathBuf::from("extension/build/my_mod.so")
+            )]
+        );
+        extension
+            .artifacts
+            .insert(Platform::Windows, String::from("win/my_mod.dll"));
+        extension
+            .artifacts
+            .insert(Platform::Linux, String::from("linux/libmy_mod.so"));
+        assert_eq!(
+            extension.artifacts("my_mod"),
+            vec![
+                (Platform::Windows, PathBuf::from("extension/win/my_mod.dll")),
+                (
+                    Platform::Linux,
+                    PathBuf::from("extension/linux/libmy_mod.so")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn conflicts() {
+        let mut extension = Extension {
+            path: String::from("extension"),
+            command: Vec::new(),
+            artifact: String::new(),
+            artifacts: BTreeMap::new(),
+        };
+        extension
+            .artifacts
+            .insert(Platform::Windows, String::from("build\\my_mod.dll"));
+        extension
+            .artifacts
+            .insert(Platform::Linux, String::from("build/libmy_mod.so"));
+        assert!(extension.conflicts().is_empty());
+        extension
+            .artifacts
+            .insert(Platform::Linux, String::from("build/my_mod.dll"));
+        assert_eq!(
+            extension.conflicts(),
+            vec![(
+                String::from("build/my_mod.dll"),
+                vec![Platform::Windows, Platform::Linux]
+            )]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::HEMTTError;
+
+/// Extra files included in releases, such as the logo, readme and license
+///
+/// Either a list of paths or glob patterns, each copied by file name into the
+/// release, or a mapping of sources to their destination inside the release.
+///
+/// A mapped source can be a file, a folder or a glob pattern. Files are copied
+/// to the destination, allowing them to be renamed, while folders and every
+/// match of a pattern are copied into the destination folder. A destination
+/// ending in `/` is always treated as a folder.
+/// Ex: `"docs/README.md" = "README.md"`
+/// Ex: `"userconfig" = "userconfig/my_mod"`
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ReleaseFiles {
+    Globs(Vec<String>),
+    Mapped(BTreeMap<String, String>),
+}
+
+impl Default for ReleaseFiles {
+    fn default() -> Self {
+        Self::Globs(Vec::new())
+    }
+}
+
+impl ReleaseFiles {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Globs(globs) => globs.is_empty(),
+            Self::Mapped(map) => map.is_empty(),
+        }
+    }
+
+    /// Every file to release, with its destination relative to the release's files folder
+    pub fn entries(&self) -> Result<Vec<(PathBuf, PathBuf)>, HEMTTError> {
+        let mut entries = Vec::new();
+        match self {
+            Self::Globs(globs) => {
+                for pattern in globs {
+                    for file in matches(pattern)? {
+                        if file.is_file() {
+                            let name = PathBuf::from(file.file_name().unwrap());
+                            entries.push((file, name));
+                        }
+                    }
+                }
+            }
+            Self::Mapped(map) => {
+                for (source, destination) in map {
+                    let folder = destination.ends_with('/');
+                    let destination = relative(destination)?;
+                    if Path::new(source).is_file() {
+                        let target = if folder || destination.as_os_str().is_empty() {
+                            destination.join(Path::new(source).file_name().unwrap())
+                        } else {
+                            destination
+                        };
+                        entries.push((PathBuf::from(source), target));
+                    } else if Path::new(source).is_dir() {
+                        entries.extend(folder_entries(Path::new(source), &destination));
+                    } else {
+                        for file in matches(source)? {
+                            let name = file.file_name().unwrap().to_owned();
+                            if file.is_dir() {
+                                entries.extend(folder_entries(&file, &destination.join(name)));
+                            } else {
+                                entries.push((file, destination.join(name)));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(entries)
+    }
+}
+
+fn matches(pattern: &str) -> Result<Vec<PathBuf>, HEMTTError> {
+    let paths = glob::glob(pattern)
+        .map_err(|e| HEMTTError::User(format!("Invalid file pattern `{}`: {}", pattern, e)))?;
+    let paths: Vec<PathBuf> = paths.filter_map(Result::ok).collect();
+    if paths.is_empty() {
+        warn!("Release file `{}` did not match any files", pattern);
+    }
+    Ok(paths)
+}
+
+fn folder_entries(folder: &Path, destination: &Path) -> Vec<(PathBuf, PathBuf)> {
+    walkdir::WalkDir::new(folder)
+        .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let target = destination.join(entry.path().strip_prefix(folder).unwrap());
+            (entry.into_path(), target)
+        })
+        .collect()
+}
+
+/// Destination inside the release, parts that could leave the release are not allowed
+fn relative(destination: &str) -> Result<PathBuf, HEMTTError> {
+    let mut path = PathBuf::new();
+    for part in destination.split(|c| c == '/' || c == '\\') {
+        if part.is_empty() {
+            continue;
+        }
+        let mut components = Path::new(part).components();
+        match (components.next(), components.next()) {
+            (Some(std::path::Component::Normal(_)), None) => path.push(part),
+            _ => {
+                return Err(HEMTTError::User(format!(
+                    "The release file destination `{}` must stay inside the release, `{}` is not allowed",
+                    destination, part
+                )))
+            }
+        }
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use super::ReleaseFiles;
+
+    #[test]
+    fn globs() {
+        let files = ReleaseFiles::Globs(vec![String::from("Cargo.*")]);
+        assert_eq!(
+            files.entries().unwrap(),
+            vec![(PathBuf::from("Cargo.toml"), PathBuf::from("Cargo.toml"))]
+        );
+    }
+
+    #[test]
+    fn mapped_rename() {
+        let mut map = BTreeMap::new();
+        map.insert(String::from("Cargo.toml"), String::from("meta/info.toml"));
+        assert_eq!(
+            ReleaseFiles::Mapped(map).entries().unwrap(),
+            vec![(PathBuf::from("Cargo.toml"), PathBuf::from("meta/info.toml"))]
+        );
+    }
+
+    #[test]
+    fn mapped_into_folder() {
+        let mut map = BTreeMap::new();
+        map.insert(String::from("Cargo.toml"), String::from("meta/"));
+        assert_eq!(
+            ReleaseFiles::Mapped(map).entries().unwrap(),
+            vec![(
+                PathBuf::from("Cargo.toml"),
+                PathBuf::from("meta/Cargo.toml")
+            )]
+        );
+    }
+
+    #[test]
+    fn mapped_folder() {
+        let mut map = BTreeMap::new();
+        map.insert(String::from("src/addon"), String::from("userconfig/addon"));
+        let entries = ReleaseFiles::Mapped(map).entries().unwrap();
+        assert!(entries.contains(&(
+            PathBuf::from("src/addon/mod.rs"),
+            PathBuf::from("userconfig/addon/mod.rs")
+        )));
+        assert!(entries.contains(&(
+            PathBuf::from("src/addon/location.rs"),
+            PathBuf::from("userconfig/addon/location.rs")
+        )));
+    }
+
+    #[test]
+    fn mapped_outside() {
+        for destination in &["../README.md", "meta/../../README.md", "./README.md"] {
+            let mut map = BTreeMap::new();
+            map.insert(String::from("Cargo.toml"), destination.to_string());
+            assert!(ReleaseFiles::Mapped(map).entries().is_err());
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+/// Style used by `hemtt fmt`
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Formatting {
+    /// Spaces per level of indentation
+    pub indent: usize,
+
+    /// Indent with tabs instead of spaces
+    pub tabs: bool,
+
+    /// Put a space on both sides of binary operators
+    pub operator_spacing: bool,
+
+    /// Where opening braces of blocks are placed
+    pub brace_style: BraceStyle,
+
+    /// Consecutive blank lines that are kept
+    pub blank_lines: usize,
+
+    /// Arrays in configs on lines longer than this are wrapped, one element per line
+    pub line_width: usize,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BraceStyle {
+    /// `if (_x) then {`
+    Kr,
+    /// The opening brace is on its own line
+    Allman,
+}
+
+impl Default for Formatting {
+    fn default() -> Self {
+        Self {
+            indent: 4,
+            tabs: false,
+            operator_spacing: true,
+            brace_style: BraceStyle::Kr,
+            blank_lines: 1,
+            line_width: 120,
+        }
+    }
+}
+
+impl Formatting {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Indentation for a level
+    pub fn indentation(&self, level: usize) -> String {
+        if self.tabs {
+            "\t".repeat(level)
+        } else {
+            " ".repeat(level * self.indent)
+        }
+    }
+}
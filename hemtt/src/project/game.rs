@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Layout, ARMA3_APPID};
+
+/// Steam app id of DayZ, its workshop items are downloaded with it
+pub const DAYZ_APPID: u32 = 221_100;
+
+/// Game the project is a mod for
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Game {
+    Arma3,
+    DayZ,
+    /// Experimental, Enfusion mods are released as a folder instead of PBOs
+    Reforger,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::Arma3
+    }
+}
+
+impl Game {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Steam app id of the game, used for the workshop
+    ///
+    /// Arma Reforger uses the Bohemia Workshop instead of the Steam Workshop
+    pub fn appid(self) -> Option<u32> {
+        match self {
+            Self::Arma3 => Some(ARMA3_APPID),
+            Self::DayZ => Some(DAYZ_APPID),
+            Self::Reforger => None,
+        }
+    }
+
+    /// Whether the game is built on the Enfusion engine, the project is a
+    /// single Enfusion addon instead of a folder of PBOs
+    pub fn is_enfusion(self) -> bool {
+        self == Self::Reforger
+    }
+
+    /// Signature version used when the project does not set one
+    ///
+    /// Version 3 only covers the script and config files of Arma 3, the `.c`
+    /// scripts of DayZ are only covered by version 2
+    pub fn sig_version(self) -> u8 {
+        match self {
+            Self::Arma3 | Self::Reforger => 3,
+            Self::DayZ => 2,
+        }
+    }
+
+    /// Release layout used when the project does not set one, DayZ servers on
+    /// Linux expect the capitalized folders of the official mods
+    pub fn layout(self) -> Layout {
+        match self {
+            Self::Arma3 | Self::Reforger => Layout::default(),
+            Self::DayZ => Layout {
+                addons: String::from("Addons"),
+                keys: String::from("Keys"),
+                ..Layout::default()
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Arma3 => "Arma 3",
+                Self::DayZ => "DayZ",
+                Self::Reforger => "Arma Reforger",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Game;
+
+    #[test]
+    fn defaults() {
+        assert_eq!(Game::default(), Game::Arma3);
+        assert_eq!(Game::Arma3.sig_version(), 3);
+        assert_eq!(Game::DayZ.sig_version(), 2);
+        assert_eq!(Game::DayZ.layout().addons, "Addons");
+        assert_eq!(Game::DayZ.layout().optionals, "optionals");
+        assert_eq!(Game::Reforger.appid(), None);
+    }
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Addon, AddonLocation, ReleaseFiles};
+
+/// A named subset of the project that is released as its own mod
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Kit {
+    /// Name of the released mod folder, defaults to `{modname}_{kit}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub modname: Option<String>,
+
+    /// Patterns of the addons included in the kit
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    pub addons: Vec<String>,
+
+    /// Patterns of the optionals included in the kit
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    pub optionals: Vec<String>,
+
+    /// Patterns of the compats included in the kit
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    pub compats: Vec<String>,
+
+    /// Extra files included in the kit's release, in addition to the project's files
+    #[serde(skip_serializing_if = "ReleaseFiles::is_empty")]
+    #[serde(default)]
+    pub files: ReleaseFiles,
+
+    /// Released alongside the mod by `hemtt build --release` when no kits are
+    /// selected with `--kit`, so kits such as a server mod are always released with it
+    #[serde(default)]
+    pub default: bool,
+
+    /// Workshop item the kit is published to with `hemtt publish workshop --kit`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub workshop: Option<u64>,
+}
+
+impl Kit {
+    /// The addon is part of the kit
+    pub fn contains(&self, addon: &Addon) -> bool {
+        let patterns = match addon.location() {
+            AddonLocation::Addons | AddonLocation::Campaigns => &self.addons,
+            AddonLocation::Optionals => &self.optionals,
+            AddonLocation::Compats => &self.compats,
+        };
+        patterns
+            .iter()
+            .any(|pattern| super::addon_matches(addon.name(), pattern))
+    }
+
+    /// Name of the released mod folder
+    ///
+    /// Arguments:
+    /// * `modname`: modname of the project
+    /// * `kit`: name of the kit
+    pub fn modname(&self, modname: &str, kit: &str) -> String {
+        self.modname
+            .clone()
+            .unwrap_or_else(|| format!("{}_{}", modname, kit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Kit;
+    use crate::{Addon, AddonLocation, ReleaseFiles};
+
+    fn kit() -> Kit {
+        Kit {
+            modname: None,
+            addons: vec![String::from("*")],
+            optionals: vec![String::from("server_*")],
+            compats: Vec::new(),
+            files: ReleaseFiles::default(),
+            default: false,
+            workshop: None,
+        }
+    }
+
+    #[test]
+    fn contains() {
+        let kit = kit();
+        assert!(kit.contains(&Addon::new("main", AddonLocation::Addons).unwrap()));
+        assert!(kit.contains(&Addon::new("server_logs", AddonLocation::Optionals).unwrap()));
+        assert!(!kit.contains(&Addon::new("client_ui", AddonLocation::Optionals).unwrap()));
+        assert!(!kit.contains(&Addon::new("ace", AddonLocation::Compats).unwrap()));
+    }
+
+    #[test]
+    fn modname() {
+        let mut kit = kit();
+        assert_eq!(kit.modname("mod", "server"), "mod_server");
+        kit.modname = Some(String::from("mod_dedicated"));
+        assert_eq!(kit.modname("mod", "server"), "mod_dedicated");
+    }
+}
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::AddonLocation;
+
+/// Where each kind of content is placed inside a release folder
+///
+/// Every entry is a path relative to the root of the release, an empty path
+/// places the content directly in the root.
+/// Ex: `compats = "optionals"` releases compats next to the optionals
+/// Ex: `optionals = ""` releases standalone optionals next to `addons/`
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Layout {
+    pub addons: String,
+    pub optionals: String,
+    pub compats: String,
+    pub keys: String,
+    pub missions: String,
+    pub files: String,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            addons: String::from("addons"),
+            optionals: String::from("optionals"),
+            compats: String::from("compats"),
+            keys: String::from("keys"),
+            missions: String::from("mpmissions"),
+            files: String::new(),
+        }
+    }
+}
+
+impl Layout {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Folder of an addon location inside the release, campaigns are released with the addons
+    pub fn location<P: AsRef<Path>>(&self, root: P, location: AddonLocation) -> PathBuf {
+        join(
+            root,
+            match location {
+                AddonLocation::Addons | AddonLocation::Campaigns => &self.addons,
+                AddonLocation::Optionals => &self.optionals,
+                AddonLocation::Compats => &self.compats,
+            },
+        )
+    }
+
+    /// Folder containing the public keys inside the release
+    pub fn keys<P: AsRef<Path>>(&self, root: P) -> PathBuf {
+        join(root, &self.keys)
+    }
+
+    /// Folder containing the missions inside the release
+    pub fn missions<P: AsRef<Path>>(&self, root: P) -> PathBuf {
+        join(root, &self.missions)
+    }
+
+    /// Folder containing the extra files inside the release
+    pub fn files<P: AsRef<Path>>(&self, root: P) -> PathBuf {
+        join(root, &self.files)
+    }
+}
+
+fn join<P: AsRef<Path>>(root: P, folder: &str) -> PathBuf {
+    let mut r = root.as_ref().to_path_buf();
+    for part in folder.split('/').filter(|part| !part.is_empty()) {
+        r.push(part);
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Layout;
+    use crate::AddonLocation;
+
+    #[test]
+    fn default() {
+        let layout = Layout::default();
+        assert_eq!(
+            layout.location("root", AddonLocation::Compats),
+            PathBuf::from("root/compats")
+        );
+        assert_eq!(
+            layout.location("root", AddonLocation::Campaigns),
+            PathBuf::from("root/addons")
+        );
+        assert_eq!(layout.keys("root"), PathBuf::from("root/keys"));
+        assert_eq!(layout.missions("root"), PathBuf::from("root/mpmissions"));
+        assert_eq!(layout.files("root"), PathBuf::from("root"));
+    }
+
+    #[test]
+    fn custom() {
+        let layout = Layout {
+            compats: String::from("optionals/compats"),
+            optionals: String::new(),
+            ..Layout::default()
+        };
+        assert_eq!(
+            layout.location("root", AddonLocation::Compats),
+            PathBuf::from("root/optionals/compats")
+        );
+        assert_eq!(
+            layout.location("root", AddonLocation::Optionals),
+            PathBuf::from("root")
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Checks of the addons' content, each can be turned off
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Lints {
+    /// CfgPatches declare the project's `version`, `versionStr`, `author` and `url`
+    pub patches: bool,
+
+    /// CfgPatches require the project's `required_version` of the game, no newer
+    pub required_version: bool,
+
+    /// CfgPatches `units[]` only lists vehicles the addon defines, and lists every
+    /// vehicle with `scope = 2`
+    pub units: bool,
+
+    /// CfgPatches `weapons[]` only lists weapons the addon defines
+    pub weapons: bool,
+
+    /// Display properties use stringtable entries instead of literal text
+    pub strings: bool,
+
+    /// Literal text allowed in display properties, supports glob patterns
+    /// Ex: `["AK-12", "*Mk.II*"]`
+    pub strings_allowed: Vec<String>,
+
+    /// Text files are UTF-8 without a byte order mark
+    pub encoding: bool,
+
+    /// Lines of text files end the same way within each file
+    pub line_endings: bool,
+
+    /// Line ending required for files with an extension
+    /// Ex: `sqf = "lf"`, `bat = "crlf"`
+    pub line_ending_exts: BTreeMap<String, LineEnding>,
+
+    /// Includes match the case of the file on disk
+    pub include_case: bool,
+
+    /// Includes and file paths in configs use backslashes
+    pub separators: bool,
+
+    /// Stringtables only use languages supported by Arma 3
+    pub languages: bool,
+
+    /// Textures have a power of two width and height, and PAAs use a format
+    /// supported by Arma 3
+    pub textures: bool,
+
+    /// Textures, materials and proxies of unbinarized models are in the project
+    /// or its dependencies
+    pub models: bool,
+
+    /// Size in megabytes a PBO should not exceed, large PBOs make joining slow
+    /// and sometimes fail to load, `0` turns it off
+    pub pbo_size: u64,
+
+    /// Number of files a PBO should not exceed, `0` turns it off
+    pub pbo_files: usize,
+
+    /// Size in megabytes a single file in a PBO should not exceed, `0` turns it off
+    pub file_size: u64,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::Crlf => "\r\n",
+        }
+    }
+}
+
+impl std::fmt::Display for LineEnding {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Lf => "LF",
+                Self::Crlf => "CRLF",
+            }
+        )
+    }
+}
+
+impl Default for Lints {
+    fn default() -> Self {
+        Self {
+            patches: true,
+            required_version: true,
+            units: true,
+            weapons: true,
+            strings: true,
+            strings_allowed: Vec::new(),
+            encoding: true,
+            line_endings: true,
+            line_ending_exts: BTreeMap::new(),
+            include_case: true,
+            separators: true,
+            languages: true,
+            textures: true,
+            models: true,
+            pbo_size: 1000,
+            pbo_files: 5000,
+            file_size: 500,
+        }
+    }
+}
+
+impl Lints {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Literal text that is allowed in display properties
+    pub fn string_allowed(&self, text: &str) -> bool {
+        self.strings_allowed.iter().any(|allowed| {
+            allowed == text
+                || glob::Pattern::new(allowed)
+                    .map(|pattern| pattern.matches(text))
+                    .unwrap_or(false)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lints;
+
+    #[test]
+    fn string_allowed() {
+        let lints = Lints {
+            strings_allowed: vec![String::from("AK-12"), String::from("*Mk.II*")],
+            ..Lints::default()
+        };
+        assert!(lints.string_allowed("AK-12"));
+        assert!(lints.string_allowed("Rifle Mk.II (Black)"));
+        assert!(!lints.string_allowed("My Rifle"));
+    }
+}
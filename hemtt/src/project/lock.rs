@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate as hemtt;
+use crate::HEMTTError;
+
+/// File that pins the exact versions of the dependencies
+pub const LOCK_FILE: &str = "hemtt.lock";
+
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Lock {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default = "BTreeMap::new")]
+    pub dependencies: BTreeMap<String, LockedDependency>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct LockedDependency {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub workshop: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Last update of the Workshop item when it was locked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub time_updated: Option<u64>,
+
+    /// SHA-1 of the fetched files
+    pub hash: String,
+}
+
+impl LockedDependency {
+    /// The locked entry was created from the same source as the dependency
+    pub fn matches(&self, dep: &super::Dependency) -> bool {
+        self.workshop == dep.workshop && self.url == dep.url
+    }
+}
+
+impl Lock {
+    /// Read the lockfile, an empty lock is returned if it does not exist
+    pub fn read() -> Result<Self, HEMTTError> {
+        if !Path::new(LOCK_FILE).exists() {
+            return Ok(Self::default());
+        }
+        let mut data = String::new();
+        open_file!(LOCK_FILE)?.read_to_string(&mut data)?;
+        toml::from_str(&data)
+            .map_err(|e| HEMTTError::InvalidConfig(format!("{}: {}", LOCK_FILE, e)))
+    }
+
+    pub fn write(&self) -> Result<(), HEMTTError> {
+        let mut out = create_file!(LOCK_FILE)?;
+        out.write_all(b"# This file is generated by HEMTT, do not edit it by hand\n")?;
+        out.write_all(
+            toml::to_string(self)
+                .map_err(|e| HEMTTError::Generic(e.to_string()))?
+                .as_bytes(),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Lock, LockedDependency};
+    use crate::Dependency;
+
+    #[test]
+    fn roundtrip() {
+        let mut lock = Lock::default();
+        lock.dependencies.insert(
+            String::from("cba"),
+            LockedDependency {
+                workshop: Some(450_814_997),
+                url: None,
+                time_updated: Some(1_600_000_000),
+                hash: String::from("abc"),
+            },
+        );
+        let text = toml::to_string(&lock).unwrap();
+        assert_eq!(lock, toml::from_str(&text).unwrap());
+    }
+
+    #[test]
+    fn matches() {
+        let locked = LockedDependency {
+            workshop: Some(450_814_997),
+            url: None,
+            time_updated: None,
+            hash: String::new(),
+        };
+        assert!(locked.matches(&Dependency {
+            workshop: Some(450_814_997),
+            url: None,
+        }));
+        assert!(!locked.matches(&Dependency {
+            workshop: None,
+            url: Some(String::from("https://example.com/mod.zip")),
+        }));
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+/// A server the release is uploaded to with `hemtt publish mirror`
+/// Ex: `url = "sftp://deploy@mirror.example.com/mods/@mod"`
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Mirror {
+    /// Folder on the server that contains the mod
+    ///
+    /// `ftp://`, `ftps://` and `sftp://` URLs are uploaded with curl, `rsync://` URLs
+    /// and `user@host:path` destinations with rsync
+    pub url: String,
+
+    /// Account used to log in to ftp and sftp mirrors, rsync reads it from the url
+    ///
+    /// The password is read from the `HEMTT_MIRROR_{NAME}_PASSWORD` environment
+    /// variable, sftp falls back to the ssh keys of the user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub user: Option<String>,
+
+    /// Delete files from rsync mirrors that are not in the release, off by default
+    #[serde(default)]
+    pub delete: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MirrorProtocol {
+    Ftp,
+    Sftp,
+    Rsync,
+}
+
+impl Mirror {
+    pub fn protocol(&self) -> Result<MirrorProtocol, String> {
+        match self.url.find("://").map(|end| &self.url[..end]) {
+            Some("ftp") | Some("ftps") => Ok(MirrorProtocol::Ftp),
+            Some("sftp") => Ok(MirrorProtocol::Sftp),
+            Some("rsync") => Ok(MirrorProtocol::Rsync),
+            Some(scheme) => Err(format!(
+                "`{}` is not supported, use ftp, ftps, sftp or rsync",
+                scheme
+            )),
+            None if self.url.contains(':') => Ok(MirrorProtocol::Rsync),
+            None => Err(String::from(
+                "the url needs a scheme, such as `sftp://`, or to be an rsync destination such as `user@host:path`",
+            )),
+        }
+    }
+
+    /// Location of a file in the mirror's folder
+    /// Ex: "addons/main.pbo"
+    pub fn file_url(&self, path: &str) -> String {
+        format!("{}/{}", self.url.trim_end_matches('/'), path)
+    }
+
+    /// Environment variable with the password of the mirror
+    /// Ex: "HEMTT_MIRROR_COMMUNITY_PASSWORD"
+    pub fn password_var(name: &str) -> String {
+        format!(
+            "HEMTT_MIRROR_{}_PASSWORD",
+            name.to_uppercase()
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mirror, MirrorProtocol};
+
+    fn mirror(url: &str) -> Mirror {
+        Mirror {
+            url: url.to_string(),
+            user: None,
+            delete: false,
+        }
+    }
+
+    #[test]
+    fn protocol() {
+        assert_eq!(
+            mirror("sftp://deploy@example.com/mods").protocol(),
+            Ok(MirrorProtocol::Sftp)
+        );
+        assert_eq!(
+            mirror("ftps://example.com/mods").protocol(),
+            Ok(MirrorProtocol::Ftp)
+        );
+        assert_eq!(
+            mirror("deploy@example.com:/srv/mods").protocol(),
+            Ok(MirrorProtocol::Rsync)
+        );
+        assert!(mirror("https://example.com/mods").protocol().is_err());
+        assert!(mirror("mods").protocol().is_err());
+    }
+
+    #[test]
+    fn file_url() {
+        assert_eq!(
+            mirror("ftp://example.com/mods/").file_url("addons/main.pbo"),
+            "ftp://example.com/mods/addons/main.pbo"
+        );
+    }
+
+    #[test]
+    fn password_var() {
+        assert_eq!(
+            Mirror::password_var("eu-west"),
+            "HEMTT_MIRROR_EU_WEST_PASSWORD"
+        );
+    }
+}
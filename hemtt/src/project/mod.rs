@@ -1,14 +1,65 @@
 use config::{Config, Environment, File};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use vfs::PhysicalFS;
 
 use semver::Version;
 use serde::{Deserialize, Serialize};
 
+mod compression;
+pub use compression::Compression;
+
 mod defaults;
 use defaults::*;
 
+mod dependency;
+pub use dependency::{deps_include, Dependency, ARMA3_APPID, DEPS_DIR};
+
+mod extension;
+pub use extension::Extension;
+
+mod files;
+pub use files::ReleaseFiles;
+
+mod formatting;
+pub use formatting::{BraceStyle, Formatting};
+
+mod game;
+pub use game::{Game, DAYZ_APPID};
+
+mod kit;
+pub use kit::Kit;
+
+mod layout;
+pub use layout::Layout;
+
+mod lints;
+pub use lints::{LineEnding, Lints};
+
+mod lock;
+pub use lock::{Lock, LockedDependency, LOCK_FILE};
+
+mod mirror;
+pub use mirror::{Mirror, MirrorProtocol};
+
+mod notifications;
+pub use notifications::{changelog_excerpt, Hook, Notifications, EVENTS};
+
+mod photoshoot;
+pub use photoshoot::Photoshoot;
+
+mod platform;
+pub use platform::{Platform, Platforms};
+
+mod prebuilt;
+pub use prebuilt::Prebuilt;
+
+mod terrain;
+pub use terrain::Terrain;
+
+mod translations;
+pub use translations::Translations;
+
 use crate::{Addon, AddonLocation, HEMTTError};
 
 pub fn addon_matches(name: &str, pattern: &str) -> bool {
@@ -38,31 +89,69 @@ pub fn get_addon_from_locations(locations: &[AddonLocation]) -> Result<Vec<Addon
 }
 
 pub fn get_addon_from_location(location: &AddonLocation) -> Result<Vec<Addon>, HEMTTError> {
-    std::fs::read_dir(location.to_string())?
-        .map(|file| file.unwrap().path())
-        .filter(|file_or_dir| file_or_dir.is_dir())
-        .map(|file| {
-            Addon::new(
-                file.file_name().unwrap().to_str().unwrap().to_owned(),
-                *location,
-            )
-        })
-        .collect()
+    let mut addons = Vec::new();
+    for entry in std::fs::read_dir(location.to_string())? {
+        let path = entry?.path();
+        if path.is_dir() {
+            addons.push(Addon::new(crate::file_name(&path)?, *location)?);
+        }
+    }
+    Ok(addons)
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
 pub struct Project {
     name: String,
     prefix: String,
+
+    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default = "String::new")]
     author: String,
 
+    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default = "String::new")]
+    pub url: String,
+
+    /// Steam Workshop ID of the released mod
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub workshop: Option<u64>,
+
+    /// Steam Workshop tags of the released mod, checked before it is published
+    /// Ex: `["Mod", "Equipment"]`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    pub workshop_tags: Vec<String>,
+
+    /// Servers the release is uploaded to with `hemtt publish mirror {name}`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default = "BTreeMap::new")]
+    pub mirrors: BTreeMap<String, Mirror>,
+
+    #[serde(skip_serializing_if = "Notifications::is_default")]
+    #[serde(default)]
+    pub notifications: Notifications,
+
     #[serde(skip_serializing_if = "String::is_empty")]
     #[serde(default = "String::new")]
     template: String,
 
+    /// Game the project is a mod for, which sets the defaults of the release
+    /// layout and signature version
+    #[serde(skip_serializing_if = "Game::is_default")]
+    #[serde(default)]
+    pub game: Game,
+
     #[serde(default = "default_version")]
     version: Version,
 
+    /// Oldest version of the game the mod supports, every CfgPatches requires it
+    /// with `requiredVersion`
+    /// Ex: `required_version = "2.06"`
+    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default = "String::new")]
+    pub required_version: String,
+
     #[serde(skip_serializing_if = "String::is_empty")]
     #[serde(default = "String::new")]
     modname: String,
@@ -70,6 +159,14 @@ pub struct Project {
     #[serde(default = "default_mainprefix")]
     mainprefix: String,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default = "default_version_defines")]
+    pub version_defines: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub git_hash: Option<usize>,
+
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     #[serde(default = "HashMap::new")]
     #[serde(rename(deserialize = "headerexts"))] // DEPRECATED
@@ -81,18 +178,37 @@ pub struct Project {
     #[serde(default = "default_include")]
     pub include: Vec<PathBuf>,
 
+    /// Folders mounted on the virtual P-drive by their prefix, for files of
+    /// other mods that are not fetched as dependencies
+    /// Ex: `"a3" = "../unpacked/a3"`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default = "BTreeMap::new")]
+    pub mounts: BTreeMap<String, PathBuf>,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[serde(default = "Vec::new")]
     pub exclude: Vec<String>,
 
+    #[serde(skip_serializing_if = "ReleaseFiles::is_empty")]
+    #[serde(default)]
+    pub files: ReleaseFiles,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[serde(default = "Vec::new")]
-    pub files: Vec<String>,
+    pub render: Vec<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default = "default_folder_optionals")]
     pub folder_optionals: Option<bool>,
 
+    #[serde(skip_serializing_if = "Compression::is_default")]
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[serde(skip_serializing_if = "Terrain::is_default")]
+    #[serde(default)]
+    pub terrain: Terrain,
+
     // Signing
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default = "default_reuse_private_key")]
@@ -111,10 +227,66 @@ pub struct Project {
     #[serde(rename(deserialize = "authority"))]
     pub authority: String,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default = "default_sig_version")]
     #[serde(rename(deserialize = "sigversion"))] // DEPRECATED
     #[serde(rename(deserialize = "sig_version"))]
-    pub sig_version: u8,
+    sig_version: Option<u8>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    pub extra_keys: Vec<PathBuf>,
+
+    // Releases
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default = "BTreeMap::new")]
+    pub kits: BTreeMap<String, Kit>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    pub prebuilt: Vec<Prebuilt>,
+
+    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default = "String::new")]
+    release_folder: String,
+
+    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default = "String::new")]
+    release_archive: String,
+
+    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default = "String::new")]
+    standalone_name: String,
+
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default = "BTreeMap::new")]
+    pub titles: BTreeMap<String, String>,
+
+    #[serde(skip_serializing_if = "Layout::is_default")]
+    #[serde(default)]
+    pub layout: Layout,
+
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default = "BTreeMap::new")]
+    pub extensions: BTreeMap<String, Extension>,
+
+    #[serde(skip_serializing_if = "Platforms::is_default")]
+    #[serde(default)]
+    pub platforms: Platforms,
+
+    /// Identical files in the release folders are hard linked to each other, off by default
+    ///
+    /// Writing to a linked file changes every copy, only enable it when the release
+    /// folders are not edited after they are built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default = "default_link_duplicates")]
+    pub link_duplicates: Option<bool>,
+
+    /// An Arma 3 Launcher preset of the mod and its workshop dependencies is
+    /// written into the release folders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub launcher_preset: Option<bool>,
 
     // Scripts
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -132,11 +304,38 @@ pub struct Project {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[serde(default = "Vec::new")]
     pub releasebuild: Vec<String>,
+
+    // Dependencies
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default = "BTreeMap::new")]
+    pub dependencies: BTreeMap<String, Dependency>,
+
+    // Checks
+    #[serde(skip_serializing_if = "Lints::is_default")]
+    #[serde(default)]
+    pub lints: Lints,
+
+    #[serde(skip_serializing_if = "Formatting::is_default")]
+    #[serde(default)]
+    pub format: Formatting,
+
+    // Utilities
+    #[serde(skip_serializing_if = "Photoshoot::is_empty")]
+    #[serde(default)]
+    pub photoshoot: Photoshoot,
+
+    #[serde(skip_serializing_if = "Translations::is_empty")]
+    #[serde(default)]
+    pub translations: Translations,
     // #[serde(skip_serializing_if = "HashMap::is_empty")]
     // #[serde(default = "HashMap::new")]
     // pub scripts: HashMap<String, crate::BuildScript>,
 }
 
+/// Folder the addons and missions are built into, laid out as a mod that can
+/// be loaded while developing
+pub const OUTPUT_DIR: &str = ".hemttout";
+
 impl Project {
     /// Finds the root of the project
     pub fn find_root() -> Result<PathBuf, HEMTTError> {
@@ -163,6 +362,11 @@ impl Project {
         }
     }
 
+    /// Folder the addons and missions are built into
+    pub fn output_dir() -> Result<PathBuf, HEMTTError> {
+        Ok(Self::find_root()?.join(OUTPUT_DIR))
+    }
+
     pub fn fs() -> Result<PhysicalFS, HEMTTError> {
         Ok(PhysicalFS::new(Self::find_root()?))
     }
@@ -172,33 +376,67 @@ impl Project {
             name,
             prefix,
             author,
+            url: String::new(),
+            workshop: None,
+            workshop_tags: Vec::new(),
+            mirrors: BTreeMap::new(),
+            notifications: Notifications::default(),
             template,
+            game: Game::default(),
 
             version: default_version(),
+            required_version: String::new(),
 
             modname: String::new(),
             mainprefix: default_mainprefix(),
 
+            version_defines: default_version_defines(),
+            git_hash: None,
             header_exts: HashMap::new(),
 
             include: default_include(),
+            mounts: BTreeMap::new(),
             exclude: Vec::new(),
-            files: if std::path::Path::new("mod.cpp").exists() {
+            files: ReleaseFiles::Globs(if std::path::Path::new("mod.cpp").exists() {
                 vec!["mod.cpp".to_owned()]
             } else {
                 Vec::new()
-            },
+            }),
+            render: Vec::new(),
             folder_optionals: default_folder_optionals(),
+            compression: Compression::default(),
+            terrain: Terrain::default(),
 
             reuse_private_key: default_reuse_private_key(),
             key_name: String::new(),
             authority: String::new(),
             sig_version: default_sig_version(),
+            extra_keys: Vec::new(),
+
+            kits: BTreeMap::new(),
+            prebuilt: Vec::new(),
+            release_folder: String::new(),
+            release_archive: String::new(),
+            standalone_name: String::new(),
+            titles: BTreeMap::new(),
+            layout: Layout::default(),
+            extensions: BTreeMap::new(),
+            platforms: Platforms::default(),
+            link_duplicates: default_link_duplicates(),
+            launcher_preset: None,
 
             check: Vec::new(),
             postbuild: Vec::new(),
             prebuild: Vec::new(),
             releasebuild: Vec::new(),
+
+            dependencies: BTreeMap::new(),
+
+            lints: Lints::default(),
+            format: Formatting::default(),
+
+            photoshoot: Photoshoot::default(),
+            translations: Translations::default(),
             // scripts: HashMap::new(),
         }
     }
@@ -212,23 +450,38 @@ impl Project {
         if PathBuf::from("hemtt.toml").exists() {
             // Single file (toml)
             p.merge(File::with_name("hemtt.toml").required(true))
-                .map_err(|e| HEMTTError::Generic(e.to_string()))?;
+                .map_err(|e| HEMTTError::InvalidConfig(e.to_string()))?;
+            // Machine specific settings, kept out of version control
+            // Ex: hemtt.local.json, hemtt.local.toml
+            p.merge(File::with_name("hemtt.local").required(false))
+                .map_err(|e| HEMTTError::InvalidConfig(e.to_string()))?;
         } else {
             // Project folder
             if !PathBuf::from(".hemtt/").exists() {
                 return Err(HEMTTError::NoProjectFound);
             }
             p.merge(File::with_name(".hemtt/base").required(true))
-                .map_err(|e| HEMTTError::Generic(e.to_string()))?;
-            // p.merge(File::with_name(&format!(".hemtt/{}", "base")).required(false)).map_err(|e| HEMTTError::Generic(e.to_string()));
+                .map_err(|e| HEMTTError::InvalidConfig(e.to_string()))?;
+            // p.merge(File::with_name(&format!(".hemtt/{}", "base")).required(false)).map_err(|e| HEMTTError::InvalidConfig(e.to_string()));
             p.merge(File::with_name(".hemtt/local").required(false))
-                .map_err(|e| HEMTTError::Generic(e.to_string()))?;
+                .map_err(|e| HEMTTError::InvalidConfig(e.to_string()))?;
         }
 
         p.merge(Environment::with_prefix("app"))
-            .map_err(|e| HEMTTError::Generic(e.to_string()))?;
-
-        p.try_into().map_err(|e| HEMTTError::Generic(e.to_string()))
+            .map_err(|e| HEMTTError::InvalidConfig(e.to_string()))?;
+
+        let mut project: Self = p
+            .try_into()
+            .map_err(|e| HEMTTError::InvalidConfig(e.to_string()))?;
+        if project.author.is_empty() {
+            if let Some(author) = crate::UserConfig::read()?.author {
+                project.author = author;
+            }
+        }
+        if project.layout.is_default() {
+            project.layout = project.game.layout();
+        }
+        Ok(project)
     }
 
     /// The name of the project
@@ -241,6 +494,15 @@ impl Project {
         &self.prefix
     }
 
+    /// The name of the released mod folder, defaults to the prefix
+    pub fn modname(&self) -> &str {
+        if self.modname.is_empty() {
+            &self.prefix
+        } else {
+            &self.modname
+        }
+    }
+
     /// The root prefix
     pub fn mainprefix(&self) -> &str {
         &self.mainprefix
@@ -251,6 +513,11 @@ impl Project {
         &self.author
     }
 
+    /// Version of the signatures, the game's default when it is not set
+    pub fn sig_version(&self) -> u8 {
+        self.sig_version.unwrap_or_else(|| self.game.sig_version())
+    }
+
     /// The project template, used for file generation
     pub fn template(&self) -> &str {
         &self.template
@@ -261,6 +528,146 @@ impl Project {
         &self.version
     }
 
+    /// Name of the private key file, includes the version unless the key is reused
+    pub fn key_name(&self) -> String {
+        if !self.key_name.is_empty() {
+            self.key_name.clone()
+        } else if self.reuse_private_key.unwrap_or(false) {
+            self.prefix.clone()
+        } else {
+            format!("{}_{}", self.prefix, self.version)
+        }
+    }
+
+    /// Name of the authority used in keys and signatures, defaults to the key name
+    pub fn signing_authority(&self) -> String {
+        if self.authority.is_empty() {
+            self.key_name()
+        } else {
+            self.authority.clone()
+        }
+    }
+
+    /// Template of the release folder, relative to `releases/`
+    /// Ex: "{{version}}/@{{modname}}"
+    pub fn release_folder(&self) -> &str {
+        if self.release_folder.is_empty() {
+            DEFAULT_RELEASE_FOLDER
+        } else {
+            &self.release_folder
+        }
+    }
+
+    /// Template of the release archive name, no archive is created when empty
+    /// Ex: "{{prefix}}-{{version}}-{{channel}}"
+    pub fn release_archive(&self) -> &str {
+        &self.release_archive
+    }
+
+    /// Template of the folder name of optionals released as their own mod
+    /// Ex: "@{{prefix}} - {{title}}"
+    pub fn standalone_name(&self) -> &str {
+        if self.standalone_name.is_empty() {
+            DEFAULT_STANDALONE_NAME
+        } else {
+            &self.standalone_name
+        }
+    }
+
+    /// Display name of an addon, defaults to the addon name
+    pub fn title<'a>(&'a self, addon: &'a Addon) -> &'a str {
+        self.titles
+            .get(addon.name())
+            .map_or_else(|| addon.name(), String::as_str)
+    }
+
+    /// Files rendered through handlebars in place, matched by the `render` glob patterns
+    /// Ex: "addons/*/script_version.hpp"
+    pub fn is_rendered(&self, path: &str) -> bool {
+        let path = path.trim_start_matches('/');
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..glob::MatchOptions::new()
+        };
+        self.render.iter().any(|pattern| {
+            glob::Pattern::new(pattern).map_or(false, |pat| pat.matches_with(path, options))
+        })
+    }
+
+    /// Macros describing the version, defined for every preprocessed file unless disabled
+    /// Ex: `VERSION` => "1.2.3.0", `VERSION_AR` => "1,2,3,0"
+    pub fn version_macros(&self) -> Vec<(String, String)> {
+        if !self.version_defines.unwrap_or(true) {
+            return Vec::new();
+        }
+        let build = self
+            .version
+            .pre
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<String>>()
+            .join(".")
+            .parse::<u64>()
+            .unwrap_or(0);
+        let parts = [
+            self.version.major,
+            self.version.minor,
+            self.version.patch,
+            build,
+        ];
+        let join = |sep: &str| {
+            parts
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<String>>()
+                .join(sep)
+        };
+        vec![
+            (String::from("MAJOR"), parts[0].to_string()),
+            (String::from("MINOR"), parts[1].to_string()),
+            (String::from("PATCHLVL"), parts[2].to_string()),
+            (String::from("BUILD"), parts[3].to_string()),
+            (String::from("VERSION"), join(".")),
+            (String::from("VERSION_STR"), join("_")),
+            (String::from("VERSION_AR"), join(",")),
+        ]
+    }
+
+    /// Value of a key in the resolved configuration, nested keys are separated by `.`
+    /// Ex: "version", "kits.server.addons"
+    pub fn get(&self, key: &str) -> Option<serde_json::Value> {
+        let value = self.to_json()?;
+        key.split('.').try_fold(value, |value, part| match value {
+            serde_json::Value::Object(mut map) => map.remove(part),
+            serde_json::Value::Array(mut array) => part
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index < array.len())
+                .map(|index| array.swap_remove(index)),
+            _ => None,
+        })
+    }
+
+    /// The project with its resolved values
+    pub fn to_json(&self) -> Option<serde_json::Value> {
+        let mut value = serde_json::to_value(self).ok()?;
+        if let Some(map) = value.as_object_mut() {
+            map.insert(String::from("modname"), self.modname().into());
+            map.insert(String::from("key_name"), self.key_name().into());
+            map.insert(String::from("authority"), self.signing_authority().into());
+            map.insert(String::from("release_folder"), self.release_folder().into());
+            map.insert(
+                String::from("standalone_name"),
+                self.standalone_name().into(),
+            );
+            map.insert(
+                String::from("layout"),
+                serde_json::to_value(&self.layout).ok()?,
+            );
+        }
+        Some(value)
+    }
+
     /// Mutable version of the project
     pub fn version_mut(&mut self) -> &mut Version {
         &mut self.version
@@ -270,7 +677,6 @@ impl Project {
 impl From<&Project> for hemtt_handlebars::Variables {
     fn from(project: &Project) -> Self {
         use serde_json::{Map, Value};
-        use std::collections::BTreeMap;
         Self::from({
             let mut map = BTreeMap::new();
             map.insert(
@@ -338,3 +744,24 @@ impl From<&Project> for hemtt_handlebars::Variables {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Project;
+
+    #[test]
+    fn get() {
+        let p = Project::new(
+            String::from("Test"),
+            String::from("test"),
+            String::from("Author"),
+            String::new(),
+        );
+        assert_eq!(p.get("name"), Some(serde_json::Value::from("Test")));
+        assert_eq!(p.get("version"), Some(serde_json::Value::from("0.1.0")));
+        assert_eq!(p.get("modname"), Some(serde_json::Value::from("test")));
+        assert_eq!(p.get("layout.keys"), Some(serde_json::Value::from("keys")));
+        assert_eq!(p.get("missing"), None);
+        assert_eq!(p.get("name.missing"), None);
+    }
+}
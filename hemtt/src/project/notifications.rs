@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Longest changelog excerpt included in a notification
+const EXCERPT_LENGTH: usize = 1000;
+
+/// Events that can trigger a hook
+pub const EVENTS: [&str; 8] = [
+    "build.started",
+    "build.completed",
+    "build.failed",
+    "release.completed",
+    "release.failed",
+    "publish.completed",
+    "publish.failed",
+    "dev.rebuilt",
+];
+
+/// Where to announce releases and publishes, after they complete or fail
+/// Ex: `discord = "https://discord.com/api/webhooks/..."`
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Notifications {
+    /// Discord webhook, the `HEMTT_DISCORD_WEBHOOK` environment variable takes
+    /// precedence so the webhook can be kept out of the repository
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discord: Option<String>,
+
+    /// Links to the released files by name, templates with the project's variables
+    /// Ex: `Download = "https://example.com/mods/{{modname}}-{{version}}.zip"`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, String>,
+
+    /// Requests and commands triggered by events, with the event as JSON
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<Hook>,
+}
+
+/// An HTTP POST or a command triggered by events
+/// Ex: `events = ["release.*"]`, `url = "https://example.com/hemtt"`
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Hook {
+    /// Events that trigger the hook, `build.*` matches every build event,
+    /// every event when empty
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+
+    /// URL the event is posted to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Command run from the project's folder, the event is written to its input
+    /// Ex: `["python", "tools/announce.py"]`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub command: Vec<String>,
+}
+
+impl Hook {
+    /// Whether the event triggers the hook
+    pub fn handles(&self, event: &str) -> bool {
+        self.events.is_empty()
+            || self
+                .events
+                .iter()
+                .any(|pattern| event_matches(pattern, event))
+    }
+
+    /// Events of the hook that do not exist
+    pub fn unknown_events(&self) -> Vec<&str> {
+        self.events
+            .iter()
+            .filter(|pattern| !EVENTS.iter().any(|event| event_matches(pattern, event)))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Ex: "build.*" matches "build.started"
+fn event_matches(pattern: &str, event: &str) -> bool {
+    pattern == event
+        || pattern
+            .strip_suffix(".*")
+            .map_or(false, |prefix| event.starts_with(&format!("{}.", prefix)))
+}
+
+impl Notifications {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn discord_webhook(&self) -> Option<String> {
+        std::env::var("HEMTT_DISCORD_WEBHOOK")
+            .ok()
+            .filter(|webhook| !webhook.is_empty())
+            .or_else(|| self.discord.clone())
+    }
+}
+
+/// The section of a markdown changelog for a version, shortened to fit in a
+/// notification
+///
+/// The section starts at the first heading that contains the version and ends
+/// at the next heading of the same level
+pub fn changelog_excerpt(changelog: &str, version: &str) -> Option<String> {
+    let mut lines = changelog.lines();
+    let level = lines.by_ref().find_map(|line| {
+        let level = line.chars().take_while(|c| *c == '#').count();
+        if level > 0 && line.contains(version) {
+            Some(level)
+        } else {
+            None
+        }
+    })?;
+    let excerpt = lines
+        .take_while(|line| {
+            let heading = line.chars().take_while(|c| *c == '#').count();
+            heading == 0 || heading > level
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let excerpt = excerpt.trim();
+    if excerpt.is_empty() {
+        return None;
+    }
+    if excerpt.chars().count() <= EXCERPT_LENGTH {
+        return Some(excerpt.to_string());
+    }
+    let mut short: String = excerpt.chars().take(EXCERPT_LENGTH).collect();
+    if let Some(end) = short.rfind('\n') {
+        short.truncate(end);
+    }
+    short.push_str("\n…");
+    Some(short)
+}
+
+#[cfg(test)]
+mod tests {
+    const CHANGELOG: &str = "# Changelog
+
+## [1.1.0] - 2021-08-01
+### Added
+- Night vision
+
+### Fixed
+- Reloading
+
+## [1.0.0] - 2021-07-01
+- First release
+";
+
+    #[test]
+    fn hook_events() {
+        let hook = super::Hook {
+            events: vec![String::from("build.*"), String::from("publish.completed")],
+            ..super::Hook::default()
+        };
+        assert!(hook.handles("build.started"));
+        assert!(hook.handles("publish.completed"));
+        assert!(!hook.handles("publish.failed"));
+        assert!(!hook.handles("buildx.started"));
+        assert!(super::Hook::default().handles("release.failed"));
+        assert!(hook.unknown_events().is_empty());
+        let hook = super::Hook {
+            events: vec![String::from("release.published"), String::from("deploy.*")],
+            ..super::Hook::default()
+        };
+        assert_eq!(hook.unknown_events(), vec!["release.published", "deploy.*"]);
+    }
+
+    #[test]
+    fn excerpt() {
+        assert_eq!(
+            super::changelog_excerpt(CHANGELOG, "1.1.0").unwrap(),
+            "### Added\n- Night vision\n\n### Fixed\n- Reloading"
+        );
+        assert_eq!(
+            super::changelog_excerpt(CHANGELOG, "1.0.0").unwrap(),
+            "- First release"
+        );
+        assert!(super::changelog_excerpt(CHANGELOG, "2.0.0").is_none());
+    }
+
+    #[test]
+    fn long_excerpt() {
+        let changelog = format!("## 1.0.0\n{}", "- A change\n".repeat(200));
+        let excerpt = super::changelog_excerpt(&changelog, "1.0.0").unwrap();
+        assert!(excerpt.chars().count() <= super::EXCERPT_LENGTH + 2);
+        assert!(excerpt.ends_with("- A change\n…"));
+    }
+}
@@ -0,0 +1,17 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Photoshoot {
+    /// Classes to generate editor previews for, grouped by the addon they belong to
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default = "BTreeMap::new")]
+    pub previews: BTreeMap<String, Vec<String>>,
+}
+
+impl Photoshoot {
+    pub fn is_empty(&self) -> bool {
+        self.previews.is_empty()
+    }
+}
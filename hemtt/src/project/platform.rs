@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Operating system that a release or a file is for
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    Windows,
+    Linux,
+}
+
+impl Platform {
+    pub const ALL: [Self; 2] = [Self::Windows, Self::Linux];
+
+    /// Platform HEMTT is running on
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else {
+            Self::Linux
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Windows => "windows",
+            Self::Linux => "linux",
+        }
+    }
+
+    /// Name of an extension's file that Arma loads on the platform
+    /// Ex: "my_mod_x64.dll"
+    pub fn extension_file(self, name: &str) -> String {
+        match self {
+            Self::Windows => format!("{}_x64.dll", name),
+            Self::Linux => format!("{}_x64.so", name),
+        }
+    }
+}
+
+impl std::fmt::Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// How releases with files for specific platforms are made
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Platforms {
+    /// Make a release for each platform, in a folder named after the platform,
+    /// instead of one release with the files of every platform
+    pub separate: bool,
+
+    /// Release files only included in the release of a platform, glob patterns
+    /// matched against their destination in the release
+    /// Ex: `"*.so" = "linux"`, `"bin/win/*" = "windows"`
+    pub files: BTreeMap<String, Platform>,
+}
+
+impl Platforms {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Platforms released separately, `None` is a release for every platform
+    pub fn releases(&self) -> Vec<Option<Platform>> {
+        if self.separate {
+            Platform::ALL.iter().copied().map(Some).collect()
+        } else {
+            vec![None]
+        }
+    }
+
+    /// The file at a destination in the release belongs in the release of a platform
+    pub fn includes(&self, destination: &str, platform: Option<Platform>) -> bool {
+        let platform = match platform {
+            Some(platform) => platform,
+            None => return true,
+        };
+        let destination = destination.replace('\\', "/");
+        self.files
+            .iter()
+            .find(|(pattern, _)| {
+                glob::Pattern::new(pattern).map_or(false, |p| p.matches(&destination))
+            })
+            .map_or(true, |(_, tagged)| *tagged == platform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Platform, Platforms};
+
+    #[test]
+    fn includes() {
+        let mut platforms = Platforms::default();
+        platforms
+            .files
+            .insert(String::from("*.so"), Platform::Linux);
+        platforms
+            .files
+            .insert(String::from("bin/win/*"), Platform::Windows);
+        assert!(platforms.includes("lib.so", None));
+        assert!(platforms.includes("lib.so", Some(Platform::Linux)));
+        assert!(!platforms.includes("lib.so", Some(Platform::Windows)));
+        assert!(!platforms.includes("bin\\win\\tool.exe", Some(Platform::Linux)));
+        assert!(platforms.includes("mod.cpp", Some(Platform::Windows)));
+    }
+}
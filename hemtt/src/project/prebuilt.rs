@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::HEMTTError;
+
+/// PBOs built outside of the project that are included in releases
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Prebuilt {
+    /// Path or glob pattern of the PBOs, relative to the project root
+    pub path: String,
+
+    /// Sign the PBOs with the project key instead of copying their signatures
+    #[serde(default)]
+    pub resign: bool,
+}
+
+impl Prebuilt {
+    /// PBOs matched by the path
+    pub fn pbos(&self) -> Result<Vec<PathBuf>, HEMTTError> {
+        let paths = glob::glob(&self.path).map_err(|e| {
+            HEMTTError::User(format!("Invalid prebuilt path `{}`: {}", self.path, e))
+        })?;
+        let pbos: Vec<PathBuf> = paths
+            .filter_map(Result::ok)
+            .filter(|path| path.extension().map_or(false, |ext| ext == "pbo"))
+            .collect();
+        if pbos.is_empty() {
+            return Err(HEMTTError::User(format!(
+                "Prebuilt path `{}` did not match any PBOs",
+                self.path
+            )));
+        }
+        Ok(pbos)
+    }
+
+    /// Signatures shipped next to a PBO
+    /// Ex: "extension.pbo" => ["extension.pbo.authority.bisign"]
+    pub fn signatures(pbo: &Path) -> Result<Vec<PathBuf>, HEMTTError> {
+        let name = crate::file_name(pbo)?;
+        let parent = match pbo.parent() {
+            Some(parent) if parent != Path::new("") => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let mut signatures = Vec::new();
+        for file in std::fs::read_dir(parent)? {
+            let file = file?.path();
+            let signature = file.file_name().map_or(false, |file_name| {
+                let file_name = file_name.to_string_lossy();
+                file_name.starts_with(&format!("{}.", name)) && file_name.ends_with(".bisign")
+            });
+            if signature {
+                signatures.push(file);
+            }
+        }
+        Ok(signatures)
+    }
+}
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::Compression;
+
+/// Seconds a terrain may take to binarize when no timeout is set
+const DEFAULT_TIMEOUT: u64 = 2 * 60 * 60;
+
+/// Terrain addons, their `.wrp` is binarized and the layers generated by Terrain
+/// Builder are packed without being checked
+/// Ex: `addons = ["map_*"]`, `layers = "data/layers"`, `timeout = 7200`
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Terrain {
+    /// Addons that are terrains, supports glob patterns
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub addons: Vec<String>,
+
+    /// Folder of the generated layers inside the terrain addons, defaults to `data/layers`
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub layers: String,
+
+    /// Seconds the binarization of a terrain may take, defaults to 2 hours
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+impl Terrain {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether an addon is a terrain
+    pub fn is_terrain(&self, addon: &str) -> bool {
+        self.addons
+            .iter()
+            .any(|pattern| super::addon_matches(addon, pattern))
+    }
+
+    /// Folder of the generated layers inside the terrain addons
+    pub fn layers(&self) -> &str {
+        if self.layers.is_empty() {
+            "data/layers"
+        } else {
+            self.layers.trim_matches('/')
+        }
+    }
+
+    /// Whether a file of an addon was generated by Terrain Builder, by its path inside the addon
+    /// Ex: "data/layers/s_000_000_lco.paa"
+    pub fn is_layer(&self, addon: &str, path: &str) -> bool {
+        if !self.is_terrain(addon) {
+            return false;
+        }
+        let path = path.replace('\\', "/").to_lowercase();
+        path.trim_start_matches('/')
+            .starts_with(&format!("{}/", self.layers().to_lowercase()))
+    }
+
+    /// Time the binarization of a terrain may take
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_TIMEOUT))
+    }
+
+    /// Whether a file of an addon is stored compressed, by its path inside the addon
+    ///
+    /// The `.wrp` and the textures of terrains are always stored raw, the game
+    /// streams them and the textures are already compressed
+    pub fn compressed(&self, compression: &Compression, addon: &str, path: &str) -> bool {
+        if self.is_terrain(addon) {
+            let path = path.to_lowercase();
+            if path.ends_with(".wrp") || path.ends_with(".paa") {
+                return false;
+            }
+        }
+        compression.compressed(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Compression, Terrain};
+
+    #[test]
+    fn layers() {
+        let terrain = Terrain {
+            addons: vec![String::from("map_*")],
+            ..Terrain::default()
+        };
+        assert!(terrain.is_layer("map_altis", "Data\\Layers\\s_000_000_lco.paa"));
+        assert!(!terrain.is_layer("map_altis", "data/layers.cfg"));
+        assert!(!terrain.is_layer("main", "data/layers/s_000_000_lco.paa"));
+    }
+
+    #[test]
+    fn compressed() {
+        let terrain = Terrain {
+            addons: vec![String::from("map_altis")],
+            ..Terrain::default()
+        };
+        let compression = Compression {
+            compress: vec![String::from("*")],
+            ..Compression::default()
+        };
+        assert!(!terrain.compressed(&compression, "map_altis", "altis.wrp"));
+        assert!(!terrain.compressed(&compression, "map_altis", "data/layers/m_000_000_lca.paa"));
+        assert!(terrain.compressed(&compression, "map_altis", "data/layers/p_000-000_l00.rvmat"));
+        assert!(terrain.compressed(&compression, "main", "data/icon.paa"));
+    }
+}
@@ -0,0 +1,19 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Translations {
+    /// Lowest percentage of keys translated into a language, checked by
+    /// `hemtt run translation coverage`
+    /// Ex: `minimum = { German = 90, French = 75 }`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default = "BTreeMap::new")]
+    pub minimum: BTreeMap<String, u8>,
+}
+
+impl Translations {
+    pub fn is_empty(&self) -> bool {
+        self.minimum.is_empty()
+    }
+}
@@ -0,0 +1,9 @@
+class CfgVehicles {
+    // Objects are listed in `units[]` of `CfgPatches` as well
+    // class House_F;
+    // class MyObject: House_F {
+    //     scope = 2;
+    //     displayName = "My Object";
+    //     model = "\z\test\addons\main\data\my_object.p3d";
+    // };
+};
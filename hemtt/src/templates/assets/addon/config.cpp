@@ -0,0 +1,13 @@
+#include "script_component.hpp"
+
+class CfgPatches {
+    class ADDON {
+        units[] = {};
+        weapons[] = {};
+        requiredVersion = 2.0;
+        requiredAddons[] = {"A3_Data_F"};
+        author = "AUTHOR";
+    };
+};
+
+#include "CfgVehicles.hpp"
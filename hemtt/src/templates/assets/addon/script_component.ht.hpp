@@ -0,0 +1 @@
+#define ADDON {{project.prefix}}_%%addon%%
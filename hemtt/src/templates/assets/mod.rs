@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use crate::{Addon, HEMTTError, Template};
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Assets {
+    path: PathBuf,
+}
+
+impl Assets {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path<P: Into<PathBuf>>(&self, path: P) -> PathBuf {
+        let mut new = self.path.clone();
+        new.push(path.into());
+        new
+    }
+}
+
+impl Template for Assets {
+    fn init(&self) -> Result<(), HEMTTError> {
+        super::write_init::<InitAssets>(&self.path)
+    }
+    fn new_addon(&self, addon: &Addon) -> Result<(), HEMTTError> {
+        super::write_addon::<AddonAssets>(&self.path, addon)
+    }
+    fn new_function(&self, _addon: &Addon, _name: &str) -> Result<PathBuf, HEMTTError> {
+        Err(HEMTTError::User(String::from(
+            "The assets template has no functions, use the `minimal` or `cba` template for scripts",
+        )))
+    }
+}
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "src/templates/assets/init/"]
+struct InitAssets;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "src/templates/assets/addon/"]
+struct AddonAssets;
+
+#[cfg(test)]
+mod test {
+    use super::Template;
+    use crate::{Addon, AddonLocation};
+
+    #[test]
+    fn function() {
+        let template = super::Assets::new(std::env::temp_dir());
+        assert!(template
+            .new_function(&Addon::new("test", AddonLocation::Addons).unwrap(), "test")
+            .is_err());
+    }
+
+    #[test]
+    fn render() {
+        crate::templates::render_all::<super::InitAssets>();
+        crate::templates::render_all::<super::AddonAssets>();
+    }
+}
@@ -1,3 +1,3 @@
-name = "{{name}}";
-dir = "@{{prefix}}";
-author = "{{author}}";
+name = "{{project.name}}";
+dir = "@{{project.prefix}}";
+author = "{{project.author}}";
@@ -23,47 +23,10 @@ impl CBA {
 
 impl Template for CBA {
     fn init(&self) -> Result<(), HEMTTError> {
-        if !self.path.exists() {
-            std::fs::create_dir_all(&self.path)?;
-        }
-        for file in InitAssets::iter() {
-            let mut f = create_file!({
-                let mut path = self.path.clone();
-                path.push(file.as_ref());
-                trace!("Writing init file: {:?}", path);
-                std::fs::create_dir_all(path.parent().unwrap())?;
-                path
-            })?;
-            f.write_all(&InitAssets::get(file.as_ref()).unwrap())?;
-        }
-        Ok(())
+        super::write_init::<InitAssets>(&self.path)
     }
     fn new_addon(&self, addon: &Addon) -> Result<(), HEMTTError> {
-        let source = addon.source();
-        if !self.path.join(source).exists() {
-            std::fs::create_dir_all(&source)?;
-        }
-        for file in AddonAssets::iter() {
-            let mut f = create_file!({
-                let mut path = self.path.clone();
-                path.push(source);
-                path.push(file.as_ref());
-                trace!("Writing addon file: {:?}", path);
-                std::fs::create_dir_all(path.parent().unwrap())?;
-                path
-            })?;
-            let content = AddonAssets::get(file.as_ref()).unwrap();
-            f.write_all(
-                super::replace(
-                    &super::Vars {
-                        addon: &addon.name(),
-                    },
-                    String::from_utf8(content.to_vec()).unwrap(),
-                )
-                .as_bytes(),
-            )?;
-        }
-        Ok(())
+        super::write_addon::<AddonAssets>(&self.path, addon)
     }
     fn new_function(&self, addon: &Addon, name: &str) -> Result<PathBuf, HEMTTError> {
         let function_file = {
@@ -102,40 +65,10 @@ struct AddonAssets;
 mod test {
     use super::Template;
     use crate::{Addon, AddonLocation};
-    #[test]
-    fn init() {
-        let folder = {
-            let mut tmp = std::env::temp_dir();
-            tmp.push(uuid::Uuid::new_v4().to_string());
-            tmp
-        };
-        let template = super::CBA::new(folder.clone());
-        template.init().unwrap();
-        std::fs::remove_dir_all(folder).unwrap();
-    }
-
-    #[test]
-    fn addon() {
-        let folder = {
-            let mut tmp = std::env::temp_dir();
-            tmp.push(uuid::Uuid::new_v4().to_string());
-            tmp
-        };
-        let template = super::CBA::new(folder.clone());
-        template.init().unwrap();
-        template
-            .new_addon(&Addon::new("test", AddonLocation::Addons).unwrap())
-            .unwrap();
-        std::fs::remove_dir_all(folder).unwrap();
-    }
 
     #[test]
     fn function() {
-        let folder = {
-            let mut tmp = std::env::temp_dir();
-            tmp.push(uuid::Uuid::new_v4().to_string());
-            tmp
-        };
+        let folder = crate::templates::test::folder("");
         let template = super::CBA::new(folder.clone());
         template.init().unwrap();
         template
@@ -146,4 +79,10 @@ mod test {
             .unwrap();
         std::fs::remove_dir_all(folder).unwrap();
     }
+
+    #[test]
+    fn render() {
+        crate::templates::render_all::<super::InitAssets>();
+        crate::templates::render_all::<super::AddonAssets>();
+    }
 }
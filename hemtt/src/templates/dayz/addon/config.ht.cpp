@@ -0,0 +1,21 @@
+class CfgPatches {
+    class {{project.prefix}}_%%addon%% {
+        units[] = {};
+        weapons[] = {};
+        requiredVersion = 0.1;
+        requiredAddons[] = {"{{project.prefix}}_main"};
+    };
+};
+
+class CfgMods {
+    class {{project.prefix}}_%%addon%% {
+        dir = "@{{project.prefix}}";
+        type = "mod";
+        dependencies[] = {"World"};
+        class defs {
+            class worldScriptModule {
+                files[] = {"{{project.mainprefix}}/{{project.prefix}}/addons/%%addon%%/scripts/4_World"};
+            };
+        };
+    };
+};
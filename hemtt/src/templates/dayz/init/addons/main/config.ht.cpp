@@ -0,0 +1,28 @@
+class CfgPatches {
+    class {{project.prefix}}_main {
+        units[] = {};
+        weapons[] = {};
+        requiredVersion = 0.1;
+        requiredAddons[] = {"DZ_Data"};
+    };
+};
+
+class CfgMods {
+    class {{project.prefix}} {
+        name = "{{project.name}}";
+        dir = "@{{project.prefix}}";
+        type = "mod";
+        dependencies[] = {"Game", "World", "Mission"};
+        class defs {
+            class gameScriptModule {
+                files[] = {"{{project.mainprefix}}/{{project.prefix}}/addons/main/scripts/3_Game"};
+            };
+            class worldScriptModule {
+                files[] = {"{{project.mainprefix}}/{{project.prefix}}/addons/main/scripts/4_World"};
+            };
+            class missionScriptModule {
+                files[] = {"{{project.mainprefix}}/{{project.prefix}}/addons/main/scripts/5_Mission"};
+            };
+        };
+    };
+};
@@ -0,0 +1,3 @@
+name = "{{project.name}}";
+dir = "@{{project.prefix}}";
+author = "{{project.author}}";
@@ -0,0 +1,11 @@
+#include "script_component.hpp"
+
+class CfgPatches {
+    class ADDON {
+        units[] = {};
+        weapons[] = {};
+        requiredVersion = 2.0;
+        requiredAddons[] = {};
+        author = "AUTHOR";
+    };
+};
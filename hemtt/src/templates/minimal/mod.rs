@@ -0,0 +1,83 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate as hemtt;
+use crate::{Addon, HEMTTError, Template};
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Minimal {
+    path: PathBuf,
+}
+
+impl Minimal {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path<P: Into<PathBuf>>(&self, path: P) -> PathBuf {
+        let mut new = self.path.clone();
+        new.push(path.into());
+        new
+    }
+}
+
+impl Template for Minimal {
+    fn init(&self) -> Result<(), HEMTTError> {
+        super::write_init::<InitAssets>(&self.path)
+    }
+    fn new_addon(&self, addon: &Addon) -> Result<(), HEMTTError> {
+        super::write_addon::<AddonAssets>(&self.path, addon)
+    }
+    fn new_function(&self, addon: &Addon, name: &str) -> Result<PathBuf, HEMTTError> {
+        let function_file = {
+            let mut path = self.path(addon.source());
+            path.push("functions");
+            path.push(format!("fn_{}.sqf", name));
+            path
+        };
+        if function_file.exists() {
+            return Err(HEMTTError::User("The function already exists".to_string()));
+        }
+        trace!("function file: {:?}", function_file);
+        create_dir!(function_file.parent().unwrap())?;
+        let mut f = create_file!(&function_file)?;
+        // The minimal template has no function library, functions are added to `CfgFunctions`
+        f.write_all(format!("// Add `{}` to `CfgFunctions` to compile it\n", name).as_bytes())?;
+        f.flush()?;
+        Ok(function_file)
+    }
+}
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "src/templates/minimal/init/"]
+struct InitAssets;
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "src/templates/minimal/addon/"]
+struct AddonAssets;
+
+#[cfg(test)]
+mod test {
+    use super::Template;
+    use crate::{Addon, AddonLocation};
+
+    #[test]
+    fn function() {
+        let folder = crate::templates::test::folder("");
+        let template = super::Minimal::new(folder.clone());
+        template.init().unwrap();
+        template
+            .new_addon(&Addon::new("test", AddonLocation::Addons).unwrap())
+            .unwrap();
+        template
+            .new_function(&Addon::new("test", AddonLocation::Addons).unwrap(), "test")
+            .unwrap();
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn render() {
+        crate::templates::render_all::<super::InitAssets>();
+        crate::templates::render_all::<super::AddonAssets>();
+    }
+}
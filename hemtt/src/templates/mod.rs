@@ -1,22 +1,35 @@
 // mod ace;
+pub mod assets;
 pub mod cba;
-// mod vanilla;
+pub mod dayz;
+pub mod minimal;
+pub mod reforger;
 
 mod replace;
-use std::{convert::TryFrom, fmt::Display, path::PathBuf};
+use std::io::Write;
+use std::{
+    convert::TryFrom,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
 pub use replace::{replace, Vars};
 
 mod template;
 pub use template::Template;
 
-use crate::HEMTTError;
+use crate as hemtt;
+use crate::{Addon, HEMTTError};
 
 use strum::{EnumIter, IntoEnumIterator};
 
 #[derive(Debug, Clone, Copy, EnumIter, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Templates {
+    Minimal,
     CBA,
+    Assets,
+    DayZ,
+    Reforger,
 }
 impl Templates {
     pub fn validate(template: String) -> Result<(), String> {
@@ -27,6 +40,17 @@ impl Templates {
         }
     }
 
+    /// What the template creates, for choosing a template
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Minimal => "an addon with a plain config, for small mods",
+            Self::CBA => "CBA macros, a function library and a GitHub workflow",
+            Self::Assets => "configs for models and textures, without scripts",
+            Self::DayZ => "a DayZ mod with script modules",
+            Self::Reforger => "an Arma Reforger project",
+        }
+    }
+
     /// CLI - Valid options for CLI interfaces
     pub fn options() -> String {
         format!(
@@ -42,7 +66,11 @@ impl TryFrom<String> for Templates {
     type Error = ();
     fn try_from(value: String) -> Result<Self, Self::Error> {
         match value.as_str() {
+            "minimal" => Ok(Self::Minimal),
             "cba" => Ok(Self::CBA),
+            "assets" => Ok(Self::Assets),
+            "dayz" => Ok(Self::DayZ),
+            "reforger" => Ok(Self::Reforger),
             _ => Err(()),
         }
     }
@@ -50,13 +78,133 @@ impl TryFrom<String> for Templates {
 impl Display for Templates {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
+            Self::Minimal => "minimal",
             Self::CBA => "cba",
+            Self::Assets => "assets",
+            Self::DayZ => "dayz",
+            Self::Reforger => "reforger",
         })
     }
 }
 
 pub fn init(template: Templates, path: PathBuf) -> Result<(), HEMTTError> {
     match template {
+        Templates::Minimal => minimal::Minimal::new(path).init(),
         Templates::CBA => cba::CBA::new(path).init(),
+        Templates::Assets => assets::Assets::new(path).init(),
+        Templates::DayZ => dayz::DayZ::new(path).init(),
+        Templates::Reforger => reforger::Reforger::new(path).init(),
+    }
+}
+
+/// Writes the files of a template's `init` folder into the project
+pub(crate) fn write_init<E: rust_embed::RustEmbed>(root: &Path) -> Result<(), HEMTTError> {
+    create_dir!(root)?;
+    for file in E::iter() {
+        let path = root.join(file.as_ref());
+        trace!("Writing init file: {:?}", path);
+        create_dir!(path.parent().unwrap())?;
+        create_file!(&path)?.write_all(&E::get(file.as_ref()).unwrap())?;
+    }
+    Ok(())
+}
+
+/// Writes the files of a template's `addon` folder into a new addon of the project,
+/// with the addon's name filled in
+pub(crate) fn write_addon<E: rust_embed::RustEmbed>(
+    root: &Path,
+    addon: &Addon,
+) -> Result<(), HEMTTError> {
+    let source = root.join(addon.source());
+    create_dir!(source)?;
+    for file in E::iter() {
+        let path = source.join(file.as_ref());
+        trace!("Writing addon file: {:?}", path);
+        create_dir!(path.parent().unwrap())?;
+        let content = E::get(file.as_ref()).unwrap();
+        create_file!(&path)?.write_all(
+            replace(
+                &Vars {
+                    addon: &addon.name(),
+                },
+                String::from_utf8(content.to_vec()).unwrap(),
+            )
+            .as_bytes(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Renders every file of a template that is populated when building,
+/// the way the build renders them, panicking on the first that fails
+#[cfg(test)]
+pub(crate) fn render_all<E: rust_embed::RustEmbed>() {
+    let project = crate::Project::new(
+        String::from("Test"),
+        String::from("test"),
+        String::from("Tester"),
+        String::new(),
+    );
+    let addon = crate::Addon::new("test", crate::AddonLocation::Addons).unwrap();
+    let mut vars = hemtt_handlebars::Variables::from(&project);
+    vars.append((&addon).into());
+    for file in E::iter() {
+        let name = file.rsplit('/').next().unwrap_or_default();
+        if !(name.contains(".ht.") || name.ends_with(".ht") || name == "$PBOPREFIX$") {
+            continue;
+        }
+        let content = replace(
+            &Vars { addon: "test" },
+            String::from_utf8(E::get(file.as_ref()).unwrap().to_vec()).unwrap(),
+        );
+        if let Err(e) = hemtt_handlebars::render(&content.replace("\\{", "\\\\{"), &vars) {
+            panic!("Unable to render `{}`: {}", file, e);
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::path::PathBuf;
+
+    use super::Template;
+    use crate::{Addon, AddonLocation};
+
+    /// A folder in the temp dir for a project that does not exist yet, its name starts with `name`
+    pub(crate) fn folder(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{}{}", name, uuid::Uuid::new_v4()))
+    }
+
+    /// The templates that have addons, each in its own folder
+    fn templates(name: &str) -> Vec<(PathBuf, Box<dyn Template>)> {
+        let templates: [fn(PathBuf) -> Box<dyn Template>; 4] = [
+            |path| Box::new(super::minimal::Minimal::new(path)),
+            |path| Box::new(super::cba::CBA::new(path)),
+            |path| Box::new(super::assets::Assets::new(path)),
+            |path| Box::new(super::dayz::DayZ::new(path)),
+        ];
+        templates
+            .iter()
+            .map(|new| {
+                let root = folder(name);
+                (root.clone(), new(root))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn addon() {
+        for name in &["", "Mödpack 日本 "] {
+            for (root, template) in templates(name) {
+                template.init().unwrap();
+                template
+                    .new_addon(&Addon::new("test", AddonLocation::Addons).unwrap())
+                    .unwrap();
+                assert!(root.join("addons").join("test").is_dir());
+                // The addon is created in the project, not the working directory
+                assert!(!std::path::Path::new("addons").join("test").exists());
+                std::fs::remove_dir_all(root).unwrap();
+            }
+        }
     }
 }
@@ -0,0 +1,113 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate as hemtt;
+use crate::{Addon, HEMTTError, Template};
+
+/// Enfusion mods are a single addon, scripts are added to the game module
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Reforger {
+    path: PathBuf,
+}
+
+impl Reforger {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path<P: Into<PathBuf>>(&self, path: P) -> PathBuf {
+        let mut new = self.path.clone();
+        new.push(path.into());
+        new
+    }
+
+    /// A new resource GUID for the project
+    fn guid(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.path.hash(&mut hasher);
+        std::time::SystemTime::now().hash(&mut hasher);
+        format!("{:016X}", hasher.finish())
+    }
+}
+
+impl Template for Reforger {
+    fn init(&self) -> Result<(), HEMTTError> {
+        if !self.path.exists() {
+            std::fs::create_dir_all(&self.path)?;
+        }
+        let guid = self.guid();
+        for file in InitAssets::iter() {
+            let mut f = create_file!({
+                let mut path = self.path.clone();
+                path.push(file.as_ref());
+                trace!("Writing init file: {:?}", path);
+                std::fs::create_dir_all(path.parent().unwrap())?;
+                path
+            })?;
+            let content = InitAssets::get(file.as_ref()).unwrap();
+            f.write_all(
+                String::from_utf8(content.to_vec())
+                    .unwrap()
+                    .replace("%%guid%%", &guid)
+                    .as_bytes(),
+            )?;
+        }
+        Ok(())
+    }
+    fn new_addon(&self, _: &Addon) -> Result<(), HEMTTError> {
+        Err(HEMTTError::User(String::from(
+            "Enfusion projects are a single addon, new addons can not be created",
+        )))
+    }
+    fn new_function(&self, _: &Addon, name: &str) -> Result<PathBuf, HEMTTError> {
+        let function_file = {
+            let mut path = self.path("Scripts");
+            path.push("Game");
+            path.push(format!("{}.c", name));
+            path
+        };
+        if function_file.exists() {
+            return Err(HEMTTError::User("The function already exists".to_string()));
+        }
+        trace!("function file: {:?}", function_file);
+        create_dir!(function_file.parent().unwrap())?;
+        let mut f = create_file!(&function_file)?;
+        f.write_all(format!("class {}\n{{\n}};\n", name).as_bytes())?;
+        f.flush()?;
+        Ok(function_file)
+    }
+}
+
+#[derive(rust_embed::RustEmbed)]
+#[folder = "src/templates/reforger/init/"]
+struct InitAssets;
+
+#[cfg(test)]
+mod test {
+    use super::Template;
+    use crate::{Addon, AddonLocation};
+    #[test]
+    fn init() {
+        let folder = crate::templates::test::folder("");
+        let template = super::Reforger::new(folder.clone());
+        template.init().unwrap();
+        let gproj = std::fs::read_to_string(folder.join(crate::enfusion::GPROJ)).unwrap();
+        assert!(
+            crate::enfusion::validate_gproj(&crate::enfusion::parse(&gproj).unwrap()).is_empty()
+        );
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn function() {
+        let folder = crate::templates::test::folder("");
+        let template = super::Reforger::new(folder.clone());
+        template.init().unwrap();
+        template
+            .new_function(&Addon::new("test", AddonLocation::Addons).unwrap(), "test")
+            .unwrap();
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+}
@@ -23,3 +23,32 @@ pub fn find_bi_tool(tool: &str) -> Result<PathBuf, HEMTTError> {
 pub fn find_bi_tool(_tool: &str) -> Result<PathBuf, HEMTTError> {
     unreachable!();
 }
+
+/// Locate the Arma 3 installation folder, `arma_path` in the user config takes precedence
+///
+/// ```no_run
+/// let arma3 = hemtt::tools::find_arma3();
+/// ```
+pub fn find_arma3() -> Result<PathBuf, HEMTTError> {
+    if let Some(path) = crate::UserConfig::read()?.arma_path {
+        return Ok(path);
+    }
+    find_arma3_installed()
+}
+
+#[cfg(windows)]
+fn find_arma3_installed() -> Result<PathBuf, HEMTTError> {
+    let hklm = winreg::RegKey::predef(winreg::enums::HKEY_LOCAL_MACHINE);
+    let arma3 = hklm.open_subkey("SOFTWARE\\WOW6432Node\\Bohemia Interactive\\arma 3")?;
+    let value: String = arma3.get_value("main")?;
+
+    Ok(PathBuf::from(value))
+}
+
+#[cfg(not(windows))]
+fn find_arma3_installed() -> Result<PathBuf, HEMTTError> {
+    Err(HEMTTError::UserHint(
+        String::from("Arma 3 can only be located on Windows"),
+        String::from("set `arma_path` in the user config"),
+    ))
+}
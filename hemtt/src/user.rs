@@ -0,0 +1,138 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::HEMTTError;
+
+/// Settings of the current user, shared by all projects
+///
+/// Read from `config.toml` in the `hemtt` folder of the user's config directory,
+/// `%APPDATA%` on Windows and `$XDG_CONFIG_HOME` or `~/.config` elsewhere.
+/// Project settings take precedence over these.
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UserConfig {
+    /// Author used when the project does not set one
+    pub author: Option<String>,
+
+    /// Arma 3 installation folder, used instead of searching for it
+    pub arma_path: Option<PathBuf>,
+
+    /// Token used for requests to GitHub
+    pub github_token: Option<String>,
+
+    /// Steam account used by steamcmd, its credentials are cached by steamcmd
+    pub steam_username: Option<String>,
+
+    /// Proxy for network requests, overrides the `HTTPS_PROXY` environment variables
+    /// Ex: "http://proxy.example.com:8080"
+    pub proxy: Option<String>,
+
+    /// PEM file of additional certificate authorities to trust
+    pub ca_bundle: Option<PathBuf>,
+
+    pub update: UpdatePreferences,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UpdatePreferences {
+    /// Release channel to update from
+    pub channel: String,
+
+    /// Check for a new version when running a command, at most once a day
+    pub check: bool,
+}
+
+impl Default for UpdatePreferences {
+    fn default() -> Self {
+        Self {
+            channel: String::from("stable"),
+            check: true,
+        }
+    }
+}
+
+impl UserConfig {
+    /// Location of the user config file
+    pub fn path() -> Option<PathBuf> {
+        let dir = if cfg!(windows) {
+            std::env::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| {
+                    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config"))
+                })
+        };
+        dir.map(|dir| dir.join("hemtt").join("config.toml"))
+    }
+
+    /// Reads the user config, the defaults are used if it does not exist
+    pub fn read() -> Result<Self, HEMTTError> {
+        match Self::path() {
+            Some(path) if path.exists() => {
+                debug!("Reading user config {:?}", path);
+                let source = std::fs::read_to_string(&path)?;
+                toml::from_str(&source).map_err(|e| {
+                    HEMTTError::InvalidConfig(format!("Invalid user config {:?}: {}", path, e))
+                })
+            }
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Writes the user config, creating its folder if needed
+    pub fn write(&self) -> Result<(), HEMTTError> {
+        let path = Self::path().ok_or_else(|| {
+            HEMTTError::User(String::from(
+                "Unable to find the user config directory, set HOME or APPDATA",
+            ))
+        })?;
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        let source = toml::to_string(self)
+            .map_err(|e| HEMTTError::Generic(format!("Unable to write the user config: {}", e)))?;
+        std::fs::write(&path, source)?;
+        Ok(())
+    }
+
+    /// Steam account from the `HEMTT_STEAM_USERNAME` environment variable or the user config
+    pub fn steam_username(&self) -> Option<String> {
+        std::env::var("HEMTT_STEAM_USERNAME")
+            .ok()
+            .filter(|username| !username.is_empty())
+            .or_else(|| self.steam_username.clone())
+    }
+
+    /// GitHub token from the `GITHUB_TOKEN` environment variable or the user config
+    pub fn github_token(&self) -> Option<String> {
+        std::env::var("GITHUB_TOKEN")
+            .ok()
+            .or_else(|| self.github_token.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UserConfig;
+
+    #[test]
+    fn parse() {
+        let config: UserConfig = toml::from_str(
+            r#"
+author = "Brett"
+arma_path = "C:/Arma 3"
+
+[update]
+channel = "beta"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.author.as_deref(), Some("Brett"));
+        assert_eq!(config.steam_username, None);
+        assert_eq!(config.update.channel, "beta");
+        let written: UserConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(written, config);
+        assert!(config.update.check);
+    }
+}